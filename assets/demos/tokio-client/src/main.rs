@@ -46,6 +46,11 @@ async fn main() -> Result<(), Error> {
         Event::Response(response) => {
             return Err(Error::msg(format!("Expected greeting, got `{response:?}`")));
         }
+        Event::ContinuationRequest(continuation_request) => {
+            return Err(Error::msg(format!(
+                "Expected greeting, got `{continuation_request:?}`"
+            )));
+        }
     };
 
     // Then, we send a login command to the server ...
@@ -122,6 +127,9 @@ async fn main() -> Result<(), Error> {
                     println!("[!] unexpected response");
                 }
             },
+            Event::ContinuationRequest(_) => {
+                println!("[!] unexpected continuation request");
+            }
         }
     }
 
@@ -0,0 +1,45 @@
+use bytes::BytesMut;
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use tokio_support::server::{BorrowedEvent, Event, ImapServerCodec};
+use tokio_util::codec::Decoder;
+
+const NOOP: &[u8] = b"a NOOP\r\n";
+const COMMANDS: usize = 1024;
+
+fn criterion_benchmark(c: &mut Criterion) {
+    // # Setup
+    let input = NOOP.repeat(COMMANDS);
+
+    let mut group = c.benchmark_group("decode_noop");
+    group.throughput(Throughput::Elements(COMMANDS as u64));
+
+    // Before: Every command is converted via `into_static()`.
+    group.bench_function("decode", |b| {
+        b.iter(|| {
+            let mut src = BytesMut::from(input.as_slice());
+            let mut codec = ImapServerCodec::new(1024);
+
+            while let Some(event) = codec.decode(&mut src).unwrap() {
+                assert!(matches!(black_box(event), Event::Command(_)));
+            }
+        })
+    });
+
+    // After: Every command borrows from the buffer.
+    group.bench_function("decode_borrowed", |b| {
+        b.iter(|| {
+            let mut src = BytesMut::from(input.as_slice());
+            let mut codec = ImapServerCodec::new(1024);
+
+            while let Some(event) = codec.decode_borrowed(&mut src).unwrap() {
+                assert!(matches!(black_box(event), BorrowedEvent::Command(_)));
+            }
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark);
+
+criterion_main!(benches);
@@ -1,13 +1,17 @@
-use std::io::{Error as IoError, Write};
+use std::{
+    collections::VecDeque,
+    io::{Error as IoError, ErrorKind, Write},
+};
 
 use bounded_static::IntoBoundedStatic;
 use bytes::{Buf, BufMut, BytesMut};
 use imap_codec::{
     decode::{Decoder, GreetingDecodeError, ResponseDecodeError},
-    encode::Encoder,
+    encode::{Encoder, Fragment},
     imap_types::{
         command::Command,
-        response::{Greeting, Response},
+        core::{LiteralMode, Tag},
+        response::{CommandContinuationRequest, Greeting, Response, Status, Tagged},
         state::{State as ImapState, State},
     },
     CommandCodec, GreetingCodec, ResponseCodec,
@@ -22,6 +26,14 @@ pub struct ImapClientCodec {
     state: FramingState,
     imap_state: ImapState<'static>,
     max_literal_length: u32,
+    pending: Option<PendingCommand>,
+}
+
+/// A command that waits for a continuation request before its next literal can be sent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PendingCommand {
+    tag: Tag<'static>,
+    fragments: VecDeque<Fragment>,
 }
 
 impl ImapClientCodec {
@@ -30,6 +42,30 @@ impl ImapClientCodec {
             state: FramingState::ReadLine { to_consume_acc: 0 },
             imap_state: ImapState::Greeting,
             max_literal_length,
+            pending: None,
+        }
+    }
+
+    /// Returns `true` when a command waits for a continuation request.
+    pub fn is_literal_pending(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    fn on_response(&mut self, response: Response<'static>) -> Event {
+        let Some(pending) = &self.pending else {
+            return Event::Response(response);
+        };
+
+        match response {
+            Response::CommandContinuationRequest(continuation_request) => {
+                Event::ContinuationRequest(continuation_request)
+            }
+            Response::Status(Status::Tagged(Tagged { ref tag, .. })) if *tag == pending.tag => {
+                // The server rejected the literal (or the command).
+                self.pending = None;
+                Event::Response(response)
+            }
+            response => Event::Response(response),
         }
     }
 }
@@ -59,8 +95,16 @@ impl PartialEq for ImapClientCodecError {
 pub enum Event {
     Greeting(Greeting<'static>),
     Response(Response<'static>),
+    /// The server is ready to receive the next literal of a pending command.
+    ///
+    /// Send [`Continue`] to proceed.
+    ContinuationRequest(CommandContinuationRequest<'static>),
 }
 
+/// Send the next literal (and the rest of the command up to the following literal).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Continue;
+
 impl TokioDecoder for ImapClientCodec {
     type Item = Event;
     type Error = ImapClientCodecError;
@@ -106,7 +150,7 @@ impl TokioDecoder for ImapClientCodec {
                                 let parser = |input| {
                                     ResponseCodec::default()
                                         .decode(input)
-                                        .map(|(rem, rsp)| (rem, Event::Response(rsp.into_static())))
+                                        .map(|(rem, rsp)| (rem, rsp.into_static()))
                                 };
 
                                 match parser(line) {
@@ -121,7 +165,7 @@ impl TokioDecoder for ImapClientCodec {
                                             self.imap_state = ImapState::NotAuthenticated;
                                         }
 
-                                        return Ok(Some(outcome));
+                                        return Ok(Some(self.on_response(outcome)));
                                     }
                                     Err(error) => match error {
                                         // We supposedly need more data ...
@@ -141,7 +185,8 @@ impl TokioDecoder for ImapClientCodec {
                                                     length,
                                                 };
 
-                                                return Ok(None);
+                                                // The literal might already be in `src`.
+                                                continue;
                                             } else {
                                                 src.advance(*to_consume_acc);
 
@@ -156,7 +201,8 @@ impl TokioDecoder for ImapClientCodec {
                                                 ));
                                             }
                                         }
-                                        ResponseDecodeError::Failed => {
+                                        ResponseDecodeError::InvalidCode { .. }
+                                        | ResponseDecodeError::Failed => {
                                             let consumed = src.split_to(*to_consume_acc);
                                             self.state =
                                                 FramingState::ReadLine { to_consume_acc: 0 };
@@ -203,15 +249,97 @@ impl TokioDecoder for ImapClientCodec {
     }
 }
 
+/// Write `fragments` to `dst` until a synchronizing literal is found.
+///
+/// The synchronizing literal (and everything after it) remains in `fragments`.
+fn write_until_sync_literal(
+    fragments: &mut VecDeque<Fragment>,
+    dst: &mut BytesMut,
+) -> Result<(), IoError> {
+    dst.reserve(
+        fragments
+            .iter()
+            .map_while(|fragment| match fragment {
+                Fragment::Line { data }
+                | Fragment::Literal {
+                    data,
+                    mode: LiteralMode::NonSync,
+                } => Some(data.len()),
+                Fragment::Literal {
+                    mode: LiteralMode::Sync,
+                    ..
+                } => None,
+            })
+            .sum(),
+    );
+
+    let mut writer = dst.writer();
+
+    while let Some(fragment) = fragments.pop_front() {
+        match fragment {
+            Fragment::Line { data }
+            | Fragment::Literal {
+                data,
+                mode: LiteralMode::NonSync,
+            } => writer.write_all(&data)?,
+            fragment @ Fragment::Literal {
+                mode: LiteralMode::Sync,
+                ..
+            } => {
+                fragments.push_front(fragment);
+                break;
+            }
+        }
+    }
+
+    Ok(())
+}
+
 impl<'a> TokioEncoder<&Command<'a>> for ImapClientCodec {
     type Error = IoError;
 
     fn encode(&mut self, item: &Command, dst: &mut BytesMut) -> Result<(), Self::Error> {
-        //dst.reserve(item.len());
-        let mut writer = dst.writer();
-        // TODO(225): Don't use `dump` here.
-        let data = CommandCodec::default().encode(item).dump();
-        writer.write_all(&data)?;
+        if self.pending.is_some() {
+            return Err(IoError::new(
+                ErrorKind::Other,
+                "Previous command waits for a continuation request",
+            ));
+        }
+
+        let mut fragments: VecDeque<Fragment> = CommandCodec::default().encode(item).collect();
+
+        write_until_sync_literal(&mut fragments, dst)?;
+
+        if !fragments.is_empty() {
+            self.pending = Some(PendingCommand {
+                tag: item.tag.clone().into_static(),
+                fragments,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+impl TokioEncoder<Continue> for ImapClientCodec {
+    type Error = IoError;
+
+    fn encode(&mut self, _: Continue, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        let Some(mut pending) = self.pending.take() else {
+            return Err(IoError::new(ErrorKind::Other, "No literal pending"));
+        };
+
+        // Note: A pending command always starts with a synchronizing literal.
+        if let Some(Fragment::Literal { data, .. }) = pending.fragments.pop_front() {
+            dst.writer().write_all(&data)?;
+        }
+
+        write_until_sync_literal(&mut pending.fragments, dst)?;
+
+        if !pending.fragments.is_empty() {
+            self.pending = Some(pending);
+        }
+
         Ok(())
     }
 }
@@ -223,11 +351,12 @@ mod tests {
 
     use bytes::BytesMut;
     use imap_codec::imap_types::{
+        command::CommandBody,
         core::{Literal, NString},
         fetch::{MessageDataItem, Section},
-        response::{Data, GreetingKind},
+        response::{Data, GreetingKind, StatusKind},
     };
-    use tokio_util::codec::Decoder;
+    use tokio_util::codec::{Decoder, Encoder};
 
     use super::*;
 
@@ -358,4 +487,187 @@ mod tests {
             assert_eq!(expected, got);
         }
     }
+
+    #[test]
+    fn test_decoder_fetch_multiple_literals() {
+        let mut src = BytesMut::from(
+            b"* OK ...\r\n\
+* 1 FETCH (RFC822.HEADER {6}\r\nab\r\n\r\n BODY[TEXT] {3}\r\nxyz)\r\n\
+* 2 FETCH (BODY[TEXT] {0}\r\n)\r\n\
+* 3 FETCH (RFC822.SIZE 42 BODY[HEADER] {3}\r\n"
+                .as_ref(),
+        );
+        let mut codec = ImapClientCodec::new(1024);
+
+        assert_eq!(
+            codec.decode(&mut src),
+            Ok(Some(Event::Greeting(
+                Greeting::new(GreetingKind::Ok, None, "...").unwrap(),
+            )))
+        );
+
+        assert_eq!(
+            codec.decode(&mut src),
+            Ok(Some(Event::Response(Response::Data(
+                Data::fetch(
+                    1,
+                    vec![
+                        MessageDataItem::Rfc822Header(NString(Some(
+                            Literal::try_from("ab\r\n\r\n").unwrap().into()
+                        ))),
+                        MessageDataItem::BodyExt {
+                            section: Some(Section::Text(None)),
+                            origin: None,
+                            data: NString(Some(Literal::try_from("xyz").unwrap().into())),
+                        },
+                    ],
+                )
+                .unwrap(),
+            ))))
+        );
+
+        assert_eq!(
+            codec.decode(&mut src),
+            Ok(Some(Event::Response(Response::Data(
+                Data::fetch(
+                    2,
+                    vec![MessageDataItem::BodyExt {
+                        section: Some(Section::Text(None)),
+                        origin: None,
+                        data: NString(Some(Literal::try_from("").unwrap().into())),
+                    }],
+                )
+                .unwrap(),
+            ))))
+        );
+
+        // The third response is incomplete ...
+        assert_eq!(codec.decode(&mut src), Ok(None));
+
+        // ... until the literal and the rest of the line arrive.
+        src.extend_from_slice(b"abc)\r\n");
+        assert_eq!(
+            codec.decode(&mut src),
+            Ok(Some(Event::Response(Response::Data(
+                Data::fetch(
+                    3,
+                    vec![
+                        MessageDataItem::Rfc822Size(42),
+                        MessageDataItem::BodyExt {
+                            section: Some(Section::Header(None)),
+                            origin: None,
+                            data: NString(Some(Literal::try_from("abc").unwrap().into())),
+                        },
+                    ],
+                )
+                .unwrap(),
+            ))))
+        );
+        assert!(src.is_empty());
+    }
+
+    #[test]
+    fn test_encoder_literal_handshake() {
+        let mut codec = ImapClientCodec::new(1024);
+        let mut src = BytesMut::from(b"* OK ...\r\n".as_ref());
+        assert!(matches!(
+            codec.decode(&mut src),
+            Ok(Some(Event::Greeting(_)))
+        ));
+
+        let command = CommandBody::append("INBOX", vec![], None, "Hello")
+            .unwrap()
+            .tag("A")
+            .unwrap();
+
+        // Only the line announcing the literal is sent ...
+        let mut dst = BytesMut::new();
+        codec.encode(&command, &mut dst).unwrap();
+        assert_eq!(dst.as_ref(), b"A APPEND INBOX {5}\r\n");
+        assert!(codec.is_literal_pending());
+
+        // ... and we can't send another command in between.
+        assert!(codec.encode(&command, &mut dst).is_err());
+
+        // The continuation request is surfaced to the caller ...
+        src.extend_from_slice(b"+ Ready\r\n");
+        assert_eq!(
+            codec.decode(&mut src),
+            Ok(Some(Event::ContinuationRequest(
+                CommandContinuationRequest::basic(None, "Ready").unwrap()
+            )))
+        );
+
+        // ... who sends the literal.
+        dst.clear();
+        codec.encode(Continue, &mut dst).unwrap();
+        assert_eq!(dst.as_ref(), b"Hello\r\n");
+        assert!(!codec.is_literal_pending());
+        assert!(codec.encode(Continue, &mut dst).is_err());
+
+        // Without a pending literal, a continuation request is a normal response.
+        src.extend_from_slice(b"+ Ready\r\n");
+        assert_eq!(
+            codec.decode(&mut src),
+            Ok(Some(Event::Response(Response::CommandContinuationRequest(
+                CommandContinuationRequest::basic(None, "Ready").unwrap()
+            ))))
+        );
+    }
+
+    #[test]
+    fn test_encoder_literal_non_sync() {
+        let mut codec = ImapClientCodec::new(1024);
+
+        let command = CommandBody::append_non_sync("INBOX", vec![], None, "Hello")
+            .unwrap()
+            .tag("A")
+            .unwrap();
+
+        let mut dst = BytesMut::new();
+        codec.encode(&command, &mut dst).unwrap();
+        assert_eq!(dst.as_ref(), b"A APPEND INBOX {5+}\r\nHello\r\n");
+        assert!(!codec.is_literal_pending());
+    }
+
+    #[test]
+    fn test_encoder_literal_rejected() {
+        let mut codec = ImapClientCodec::new(1024);
+        let mut src = BytesMut::from(b"* OK ...\r\n".as_ref());
+        assert!(matches!(
+            codec.decode(&mut src),
+            Ok(Some(Event::Greeting(_)))
+        ));
+
+        let command = CommandBody::append("INBOX", vec![], None, "Hello")
+            .unwrap()
+            .tag("A")
+            .unwrap();
+
+        let mut dst = BytesMut::new();
+        codec.encode(&command, &mut dst).unwrap();
+        assert!(codec.is_literal_pending());
+
+        // A status response of another command doesn't affect the pending literal ...
+        src.extend_from_slice(b"B OK done\r\n");
+        assert!(matches!(
+            codec.decode(&mut src),
+            Ok(Some(Event::Response(Response::Status(_))))
+        ));
+        assert!(codec.is_literal_pending());
+
+        // ... but the rejection of the pending command does.
+        src.extend_from_slice(b"A NO too large\r\n");
+        match codec.decode(&mut src) {
+            Ok(Some(Event::Response(Response::Status(Status::Tagged(tagged))))) => {
+                assert_eq!(tagged.body.kind, StatusKind::No);
+            }
+            got => panic!("Unexpected result: {got:?}"),
+        }
+        assert!(!codec.is_literal_pending());
+
+        dst.clear();
+        codec.encode(&command, &mut dst).unwrap();
+        assert_eq!(dst.as_ref(), b"A APPEND INBOX {5}\r\n");
+    }
 }
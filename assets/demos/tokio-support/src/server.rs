@@ -1,12 +1,13 @@
-use std::io::{Error as IoError, Write};
+use std::io::Error as IoError;
 
-use bounded_static::IntoBoundedStatic;
+use bounded_static::{IntoBoundedStatic, ToBoundedStatic};
 use bytes::{Buf, BufMut, BytesMut};
 use imap_codec::{
     decode::{CommandDecodeError, Decoder},
     encode::Encoder,
     imap_types::{
         command::Command,
+        core::{Atom, Tag},
         response::{Greeting, Response},
     },
     CommandCodec, GreetingCodec, ResponseCodec,
@@ -20,6 +21,10 @@ use super::{find_crlf_inclusive, FramingError, FramingState};
 pub struct ImapServerCodec {
     state: FramingState,
     max_literal_size: usize,
+    /// Maximum size of a command, i.e., its lines and literals (see [`Self::with_max_command_size`]).
+    max_command_size: Option<usize>,
+    /// Number of bytes that were decoded but are still in the buffer (see `decode_borrowed`).
+    to_advance: usize,
 }
 
 impl ImapServerCodec {
@@ -27,49 +32,86 @@ impl ImapServerCodec {
         Self {
             state: FramingState::ReadLine { to_consume_acc: 0 },
             max_literal_size,
+            max_command_size: None,
+            to_advance: 0,
         }
     }
-}
 
-#[derive(Debug, Error)]
-pub enum ImapServerCodecError {
-    #[error(transparent)]
-    Io(#[from] IoError),
-    #[error(transparent)]
-    Framing(#[from] FramingError),
-    #[error("Parsing failed")]
-    ParsingFailed(BytesMut),
-}
+    /// Limit the total size of a command, i.e., its lines and all of its literals.
+    ///
+    /// Without this limit, a client can send an arbitrarily large command by using many literals
+    /// that are all below the literal limit. A literal that would exceed the limit is rejected
+    /// right away. A command that exceeds the limit after its last literal is rejected with
+    /// [`ImapServerCodecError::CommandTooLarge`].
+    pub fn with_max_command_size(mut self, max_command_size: usize) -> Self {
+        self.max_command_size = Some(max_command_size);
+        self
+    }
 
-impl PartialEq for ImapServerCodecError {
-    fn eq(&self, other: &Self) -> bool {
-        match (self, other) {
-            (Self::Io(error1), Self::Io(error2)) => error1.kind() == error2.kind(),
-            (Self::Framing(kind1), Self::Framing(kind2)) => kind1 == kind2,
-            (Self::ParsingFailed(x), Self::ParsingFailed(y)) => x == y,
-            _ => false,
+    /// Check that `command`, which was decoded from `consumed` bytes, doesn't exceed the maximum
+    /// command size.
+    ///
+    /// `consumed` must include all lines and literals of the command.
+    pub fn check_command_size(
+        &self,
+        command: &Command,
+        consumed: usize,
+    ) -> Result<(), ImapServerCodecError> {
+        match self.max_command_size {
+            Some(max_command_size) if consumed > max_command_size => {
+                Err(ImapServerCodecError::CommandTooLarge {
+                    tag: command.tag.to_static(),
+                    size: consumed,
+                    max_command_size,
+                })
+            }
+            _ => Ok(()),
         }
     }
-}
 
-#[derive(Debug, PartialEq, Eq)]
-pub enum Event {
-    Command(Command<'static>),
-    ActionRequired(Action),
-    // More might be require.
-}
+    /// Decode all complete events in `src` at once.
+    ///
+    /// This is useful for pipelining clients. Errors are returned in place, so a malformed command
+    /// doesn't hide the ones following it. Decoding stops when more data is needed.
+    pub fn decode_all(&mut self, src: &mut BytesMut) -> Vec<Result<Event, ImapServerCodecError>> {
+        let mut outcomes = Vec::new();
 
-#[derive(Debug, PartialEq, Eq)]
-pub enum Action {
-    SendLiteralAck(u32),
-    SendLiteralReject(u32),
-}
+        loop {
+            match self.decode(src) {
+                Ok(Some(event)) => outcomes.push(Ok(event)),
+                Ok(None) => break,
+                // Note: Every error consumes input, so this loop terminates.
+                Err(error) => outcomes.push(Err(error)),
+            }
+        }
 
-impl TokioDecoder for ImapServerCodec {
-    type Item = Event;
-    type Error = ImapServerCodecError;
+        outcomes
+    }
+
+    /// Decode the next event without converting the command via `into_static()`.
+    ///
+    /// The returned command borrows from `src`. Thus, decoding a command only allocates when the
+    /// command itself requires it, e.g., for a quoted string containing escapes.
+    ///
+    /// Because of the borrow, the decoded bytes can't be removed from `src` right away. They are
+    /// removed with the next call to this method, [`TokioDecoder::decode`], or [`Self::consume`].
+    pub fn decode_borrowed<'b>(
+        &mut self,
+        src: &'b mut BytesMut,
+    ) -> Result<Option<BorrowedEvent<'b>>, ImapServerCodecError> {
+        self.consume(src);
+
+        if let FramingState::ReadLiteral {
+            to_consume_acc,
+            length,
+        } = self.state
+        {
+            src.reserve((to_consume_acc + length as usize).saturating_sub(src.len()));
+        }
+
+        // From here on, `src` is only read.
+        let src: &'b BytesMut = src;
 
-    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
         loop {
             match self.state {
                 FramingState::ReadLine {
@@ -87,12 +129,14 @@ impl TokioDecoder for ImapServerCodec {
                                 // We got a complete message.
                                 Ok((rem, cmd)) => {
                                     assert!(rem.is_empty());
-                                    let cmd = cmd.into_static();
 
-                                    src.advance(*to_consume_acc);
+                                    let consumed = *to_consume_acc;
+                                    self.to_advance = consumed;
                                     self.state = FramingState::ReadLine { to_consume_acc: 0 };
 
-                                    return Ok(Some(Event::Command(cmd)));
+                                    self.check_command_size(&cmd, consumed)?;
+
+                                    return Ok(Some(BorrowedEvent::Command(cmd)));
                                 }
                                 Err(error) => match error {
                                     // We supposedly need more data ...
@@ -104,30 +148,47 @@ impl TokioDecoder for ImapServerCodec {
                                     }
                                     // We found a literal.
                                     CommandDecodeError::LiteralFound { length, .. } => {
-                                        if length as usize <= self.max_literal_size {
-                                            src.reserve(length as usize);
+                                        let command_size = *to_consume_acc + length as usize;
 
+                                        if length as usize <= self.max_literal_size
+                                            && self
+                                                .max_command_size
+                                                .map_or(true, |max| command_size <= max)
+                                        {
                                             self.state = FramingState::ReadLiteral {
                                                 to_consume_acc: *to_consume_acc,
                                                 length,
                                             };
 
-                                            return Ok(Some(Event::ActionRequired(
+                                            return Ok(Some(BorrowedEvent::ActionRequired(
                                                 Action::SendLiteralAck(length),
                                             )));
                                         } else {
-                                            src.advance(*to_consume_acc);
-
+                                            self.to_advance = *to_consume_acc;
                                             self.state =
                                                 FramingState::ReadLine { to_consume_acc: 0 };
 
-                                            return Ok(Some(Event::ActionRequired(
+                                            return Ok(Some(BorrowedEvent::ActionRequired(
                                                 Action::SendLiteralReject(length),
                                             )));
                                         }
                                     }
-                                    CommandDecodeError::Failed => {
-                                        let consumed = src.split_to(*to_consume_acc);
+                                    // A server should reply with `<tag> BAD unknown command`.
+                                    CommandDecodeError::UnknownCommand { tag, name } => {
+                                        let discarded = BytesMut::from(line);
+                                        self.to_advance = *to_consume_acc;
+                                        self.state = FramingState::ReadLine { to_consume_acc: 0 };
+
+                                        return Err(ImapServerCodecError::UnknownCommand {
+                                            tag: tag.into_static(),
+                                            name: name.into_static(),
+                                            discarded,
+                                        });
+                                    }
+                                    CommandDecodeError::InvalidSequenceSet { .. }
+                                    | CommandDecodeError::Failed => {
+                                        let consumed = BytesMut::from(line);
+                                        self.to_advance = *to_consume_acc;
                                         self.state = FramingState::ReadLine { to_consume_acc: 0 };
 
                                         return Err(ImapServerCodecError::ParsingFailed(consumed));
@@ -140,7 +201,7 @@ impl TokioDecoder for ImapServerCodec {
                         //
                         // Note: This line is missing the `\r\n` and should be discarded.
                         Err(to_discard) => {
-                            src.advance(*to_consume_acc + to_discard);
+                            self.to_advance = *to_consume_acc + to_discard;
                             self.state = FramingState::ReadLine { to_consume_acc: 0 };
 
                             return Err(ImapServerCodecError::Framing(FramingError::NotCrLf));
@@ -166,18 +227,121 @@ impl TokioDecoder for ImapServerCodec {
             }
         }
     }
+
+    /// Remove the bytes of the last event returned by [`Self::decode_borrowed`] from `src`.
+    ///
+    /// Calling this method more than once (or without a previous event) does nothing.
+    pub fn consume(&mut self, src: &mut BytesMut) {
+        src.advance(std::mem::take(&mut self.to_advance));
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ImapServerCodecError {
+    #[error(transparent)]
+    Io(#[from] IoError),
+    #[error(transparent)]
+    Framing(#[from] FramingError),
+    #[error("Parsing failed")]
+    ParsingFailed(BytesMut),
+    #[error("Unknown command {name:?}")]
+    UnknownCommand {
+        tag: Tag<'static>,
+        name: Atom<'static>,
+        discarded: BytesMut,
+    },
+    #[error("Expected a maximum command size of {max_command_size} bytes, got {size} bytes")]
+    CommandTooLarge {
+        tag: Tag<'static>,
+        size: usize,
+        max_command_size: usize,
+    },
+}
+
+impl PartialEq for ImapServerCodecError {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Io(error1), Self::Io(error2)) => error1.kind() == error2.kind(),
+            (Self::Framing(kind1), Self::Framing(kind2)) => kind1 == kind2,
+            (Self::ParsingFailed(x), Self::ParsingFailed(y)) => x == y,
+            (
+                Self::UnknownCommand {
+                    tag: tag1,
+                    name: name1,
+                    discarded: discarded1,
+                },
+                Self::UnknownCommand {
+                    tag: tag2,
+                    name: name2,
+                    discarded: discarded2,
+                },
+            ) => tag1 == tag2 && name1 == name2 && discarded1 == discarded2,
+            (
+                Self::CommandTooLarge {
+                    tag: tag1,
+                    size: size1,
+                    max_command_size: max1,
+                },
+                Self::CommandTooLarge {
+                    tag: tag2,
+                    size: size2,
+                    max_command_size: max2,
+                },
+            ) => tag1 == tag2 && size1 == size2 && max1 == max2,
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Event {
+    Command(Command<'static>),
+    ActionRequired(Action),
+    // More might be require.
+}
+
+/// Like [`Event`], but the command borrows from the input buffer (see [`ImapServerCodec::decode_borrowed`]).
+#[derive(Debug, PartialEq, Eq)]
+pub enum BorrowedEvent<'a> {
+    Command(Command<'a>),
+    ActionRequired(Action),
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Action {
+    SendLiteralAck(u32),
+    SendLiteralReject(u32),
+}
+
+impl TokioDecoder for ImapServerCodec {
+    type Item = Event;
+    type Error = ImapServerCodecError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        let event = match self.decode_borrowed(src) {
+            Ok(Some(BorrowedEvent::Command(cmd))) => Ok(Some(Event::Command(cmd.into_static()))),
+            Ok(Some(BorrowedEvent::ActionRequired(action))) => {
+                Ok(Some(Event::ActionRequired(action)))
+            }
+            Ok(None) => Ok(None),
+            Err(error) => Err(error),
+        };
+
+        // The command is owned, so we can remove it from `src` right away.
+        self.consume(src);
+
+        event
+    }
 }
 
 impl TokioEncoder<&Greeting<'_>> for ImapServerCodec {
     type Error = IoError;
 
     fn encode(&mut self, item: &Greeting, dst: &mut BytesMut) -> Result<(), Self::Error> {
-        //dst.reserve(item.len());
-        let mut writer = dst.writer();
-        // TODO(225): Don't use `dump` here.
-        let data = GreetingCodec::default().encode(item).dump();
-        writer.write_all(&data)?;
-        Ok(())
+        let codec = GreetingCodec::default();
+        // Note: `dst` grows while the encoder writes its (buffered) chunks.
+        // The server doesn't need to wait for a continuation request before sending a literal.
+        codec.encode_to(item, &mut dst.writer(), |_, _| Ok(()))
     }
 }
 
@@ -185,12 +349,10 @@ impl TokioEncoder<&Response<'_>> for ImapServerCodec {
     type Error = IoError;
 
     fn encode(&mut self, item: &Response, dst: &mut BytesMut) -> Result<(), Self::Error> {
-        //dst.reserve(item.len());
-        let mut writer = dst.writer();
-        // TODO(225): Don't use `dump` here.
-        let data = ResponseCodec::default().encode(item).dump();
-        writer.write_all(&data)?;
-        Ok(())
+        let codec = ResponseCodec::default();
+        // Note: `dst` grows while the encoder writes its (buffered) chunks.
+        // The server doesn't need to wait for a continuation request before sending a literal.
+        codec.encode_to(item, &mut dst.writer(), |_, _| Ok(()))
     }
 }
 
@@ -199,12 +361,12 @@ mod tests {
     use bytes::BytesMut;
     use imap_codec::imap_types::{
         command::{Command, CommandBody},
-        core::{AString, AtomExt, IString, Literal},
+        core::{AString, AtomExt, IString, Literal, NString},
+        fetch::MessageDataItem,
+        response::{Data, GreetingKind},
         secret::Secret,
     };
-    #[cfg(feature = "quirk_crlf_relaxed")]
-    use imap_types::core::Tag;
-    use tokio_util::codec::Decoder;
+    use tokio_util::codec::{Decoder, Encoder};
 
     use super::*;
 
@@ -243,6 +405,29 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_decoder_unknown_command() {
+        let mut src = BytesMut::from(b"a xfoo bar\r\nb noop\r\n".as_ref());
+        let mut codec = ImapServerCodec::new(1024);
+
+        assert_eq!(
+            codec.decode(&mut src),
+            Err(ImapServerCodecError::UnknownCommand {
+                tag: Tag::try_from("a").unwrap(),
+                name: Atom::try_from("xfoo").unwrap(),
+                discarded: BytesMut::from(b"a xfoo bar\r\n".as_ref()),
+            })
+        );
+
+        // The unknown command doesn't affect the next one.
+        assert_eq!(
+            codec.decode(&mut src),
+            Ok(Some(Event::Command(
+                Command::new("b", CommandBody::Noop).unwrap()
+            )))
+        );
+    }
+
     #[test]
     fn test_decoder_literal() {
         let tests = [
@@ -292,6 +477,82 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_decode_all() {
+        let mut src = BytesMut::from(b"a NOOP\r\nb NOOP\r\nc NOOP\r\nd NO".as_ref());
+        let mut codec = ImapServerCodec::new(1024);
+
+        let expected: Vec<_> = ["a", "b", "c"]
+            .into_iter()
+            .map(|tag| {
+                Ok(Event::Command(
+                    Command::new(tag, CommandBody::Noop).unwrap(),
+                ))
+            })
+            .collect();
+        assert_eq!(codec.decode_all(&mut src), expected);
+        assert_eq!(src, b"d NO".as_ref());
+
+        // Errors don't stop decoding.
+        src.extend_from_slice(b"OP\r\nxxx\r\ne NOOP\r\n");
+        assert_eq!(
+            codec.decode_all(&mut src),
+            vec![
+                Ok(Event::Command(
+                    Command::new("d", CommandBody::Noop).unwrap()
+                )),
+                Err(ImapServerCodecError::ParsingFailed(BytesMut::from(
+                    b"xxx\r\n".as_ref()
+                ))),
+                Ok(Event::Command(
+                    Command::new("e", CommandBody::Noop).unwrap()
+                )),
+            ]
+        );
+        assert!(src.is_empty());
+    }
+
+    #[test]
+    fn test_decode_borrowed() {
+        let mut src = BytesMut::from(b"a NOOP\r\nb LOGIN alice {5}\r\n".as_ref());
+        let mut codec = ImapServerCodec::new(1024);
+
+        match codec.decode_borrowed(&mut src) {
+            Ok(Some(BorrowedEvent::Command(cmd))) => {
+                assert_eq!(cmd.tag.inner(), "a");
+                assert_eq!(cmd.body, CommandBody::Noop);
+            }
+            got => panic!("Unexpected {got:?}"),
+        }
+        // The command is still in the buffer ...
+        assert_eq!(src, b"a NOOP\r\nb LOGIN alice {5}\r\n".as_ref());
+
+        assert_eq!(
+            codec.decode_borrowed(&mut src),
+            Ok(Some(BorrowedEvent::ActionRequired(Action::SendLiteralAck(
+                5
+            ))))
+        );
+        // ... until the next call.
+        assert_eq!(src, b"b LOGIN alice {5}\r\n".as_ref());
+
+        src.extend_from_slice(b"hello\r\n");
+        assert_eq!(
+            codec.decode_borrowed(&mut src),
+            Ok(Some(BorrowedEvent::Command(
+                Command::new(
+                    "b",
+                    CommandBody::login("alice", Literal::try_from("hello").unwrap()).unwrap()
+                )
+                .unwrap()
+            )))
+        );
+
+        codec.consume(&mut src);
+        assert!(src.is_empty());
+        assert_eq!(codec.decode_borrowed(&mut src), Ok(None));
+    }
+
     #[test]
     fn test_decoder_error() {
         let tests = [
@@ -357,4 +618,114 @@ mod tests {
             assert_eq!(expected, got);
         }
     }
+
+    #[test]
+    fn test_decoder_max_command_size() {
+        let tests = [
+            (
+                b"a SEARCH SUBJECT {3}\r\n".as_ref(),
+                Ok(Some(Event::ActionRequired(Action::SendLiteralAck(3)))),
+            ),
+            (
+                b"foo SUBJECT {3}\r\n",
+                Ok(Some(Event::ActionRequired(Action::SendLiteralAck(3)))),
+            ),
+            (
+                b"foo SUBJECT {3}\r\n",
+                Ok(Some(Event::ActionRequired(Action::SendLiteralAck(3)))),
+            ),
+            // Every literal is below the literal limit, but the command would exceed 64 bytes.
+            (
+                b"foo SUBJECT {3}\r\n",
+                Ok(Some(Event::ActionRequired(Action::SendLiteralReject(3)))),
+            ),
+            (
+                b"b NOOP\r\n",
+                Ok(Some(Event::Command(
+                    Command::new("b", CommandBody::Noop).unwrap(),
+                ))),
+            ),
+            (
+                b"c SEARCH SUBJECT {3}\r\n",
+                Ok(Some(Event::ActionRequired(Action::SendLiteralAck(3)))),
+            ),
+            // The line following the last literal exceeds the limit.
+            (
+                b"foo SUBJECT aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa\r\n",
+                Err(ImapServerCodecError::CommandTooLarge {
+                    tag: Tag::try_from("c").unwrap(),
+                    size: 80,
+                    max_command_size: 64,
+                }),
+            ),
+            (
+                b"d NOOP\r\n",
+                Ok(Some(Event::Command(
+                    Command::new("d", CommandBody::Noop).unwrap(),
+                ))),
+            ),
+        ];
+
+        let mut src = BytesMut::new();
+        let mut codec = ImapServerCodec::new(16).with_max_command_size(64);
+
+        for (test, expected) in tests {
+            src.extend_from_slice(test);
+            let got = codec.decode(&mut src);
+
+            dbg!((std::str::from_utf8(test).unwrap(), &expected, &got));
+
+            assert_eq!(expected, got);
+        }
+
+        assert!(src.is_empty());
+    }
+
+    #[test]
+    fn test_check_command_size() {
+        let command = Command::new("a", CommandBody::Noop).unwrap();
+
+        let codec = ImapServerCodec::new(16);
+        assert_eq!(codec.check_command_size(&command, usize::MAX), Ok(()));
+
+        let codec = ImapServerCodec::new(16).with_max_command_size(8);
+        assert_eq!(codec.check_command_size(&command, 8), Ok(()));
+        assert_eq!(
+            codec.check_command_size(&command, 9),
+            Err(ImapServerCodecError::CommandTooLarge {
+                tag: Tag::try_from("a").unwrap(),
+                size: 9,
+                max_command_size: 8,
+            })
+        );
+    }
+
+    #[test]
+    fn test_encoder() {
+        let mut codec = ImapServerCodec::new(1024);
+        let mut dst = BytesMut::new();
+
+        codec
+            .encode(
+                &Greeting::new(GreetingKind::Ok, None, "Hello").unwrap(),
+                &mut dst,
+            )
+            .unwrap();
+        assert_eq!(dst.as_ref(), b"* OK Hello\r\n");
+        dst.clear();
+
+        let response = Response::Data(
+            Data::fetch(
+                1,
+                vec![MessageDataItem::BodyExt {
+                    section: None,
+                    origin: None,
+                    data: NString(Some(Literal::try_from("ABCDE").unwrap().into())),
+                }],
+            )
+            .unwrap(),
+        );
+        codec.encode(&response, &mut dst).unwrap();
+        assert_eq!(dst.as_ref(), b"* 1 FETCH (BODY[] {5}\r\nABCDE)\r\n");
+    }
 }
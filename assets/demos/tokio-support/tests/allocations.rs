@@ -0,0 +1,63 @@
+//! Checks that `ImapServerCodec::decode_borrowed` doesn't allocate on the common path.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+};
+
+use bytes::BytesMut;
+use tokio_support::server::{BorrowedEvent, ImapServerCodec};
+use tokio_util::codec::Decoder;
+
+/// Allocator that counts the allocations of the current thread.
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn allocations<F: FnOnce()>(f: F) -> usize {
+    let before = ALLOCATIONS.with(Cell::get);
+    f();
+    ALLOCATIONS.with(Cell::get) - before
+}
+
+#[test]
+fn test_decode_noop_allocations() {
+    let mut codec = ImapServerCodec::new(1024);
+    let mut src = BytesMut::with_capacity(1024);
+
+    src.extend_from_slice(b"a NOOP\r\nb NOOP\r\n");
+    let got = allocations(|| {
+        for _ in 0..2 {
+            match codec.decode_borrowed(&mut src) {
+                Ok(Some(BorrowedEvent::Command(_))) => {}
+                got => panic!("Unexpected {got:?}"),
+            }
+        }
+        codec.consume(&mut src);
+    });
+    assert_eq!(got, 0);
+    assert!(src.is_empty());
+
+    // The owned path allocates (at least) for the tag.
+    src.extend_from_slice(b"c NOOP\r\n");
+    let got = allocations(|| {
+        codec.decode(&mut src).unwrap().unwrap();
+    });
+    assert!(got > 0);
+}
@@ -0,0 +1,102 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use imap_codec::{
+    decode::Decoder,
+    encode::Encoder,
+    imap_types::response::{Data, Response},
+    ResponseCodec,
+};
+
+/// Allocator that counts all allocations.
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn allocations<F: FnOnce()>(f: F) -> usize {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    f();
+    ALLOCATIONS.load(Ordering::Relaxed) - before
+}
+
+const PARTS: usize = 256;
+
+fn criterion_benchmark(c: &mut Criterion) {
+    // # Setup
+    //
+    // Create a FETCH response with a large multipart BODYSTRUCTURE ...
+    let part = b"(\"TEXT\" \"plain\" (\"CHARSET\" \"utf-8\") NIL NIL \"7BIT\" 1234 56)";
+    let input = [
+        b"* 1 FETCH (BODYSTRUCTURE (".as_ref(),
+        &part.repeat(PARTS),
+        b" \"mixed\" (\"BOUNDARY\" \"xyz\") NIL NIL NIL))\r\n",
+    ]
+    .concat();
+    let (_, rsp) = ResponseCodec::default().decode(&input).unwrap();
+    assert!(matches!(rsp, Response::Data(Data::Fetch { .. })));
+
+    // ... and preallocate some memory to serialize the `Response` into.
+    let mut out = Vec::with_capacity(input.len());
+
+    let dump = allocations(|| {
+        out.extend_from_slice(&ResponseCodec::default().encode(&rsp).dump());
+    });
+    assert_eq!(out, input);
+    out.clear();
+
+    let encode_to = allocations(|| {
+        ResponseCodec::default()
+            .encode_to(&rsp, &mut out, |_, _| Ok(()))
+            .unwrap();
+    });
+    assert_eq!(out, input);
+    out.clear();
+
+    println!("allocations (encode + dump): {dump}");
+    println!("allocations (encode_to):     {encode_to}");
+
+    let mut group = c.benchmark_group("encode_body_structure");
+
+    // Before: Build `Fragment`s and copy them into `out`.
+    group.bench_function("encode_dump", |b| {
+        b.iter(|| {
+            let tmp = ResponseCodec::default().encode(&rsp).dump();
+            out.extend_from_slice(black_box(&tmp));
+            out.clear();
+        })
+    });
+
+    // After: Write into `out` directly.
+    group.bench_function("encode_to", |b| {
+        b.iter(|| {
+            ResponseCodec::default()
+                .encode_to(&rsp, &mut out, |_, _| Ok(()))
+                .unwrap();
+            black_box(&out);
+            out.clear();
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark);
+
+criterion_main!(benches);
@@ -0,0 +1,63 @@
+use std::num::NonZeroU32;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use imap_codec::{
+    encode::Encoder,
+    imap_types::{
+        core::{Literal, NString, Vec1},
+        fetch::MessageDataItem,
+        response::{Data, Response},
+    },
+    ResponseCodec,
+};
+
+const LITERALS: usize = 4096;
+
+fn criterion_benchmark(c: &mut Criterion) {
+    // # Setup
+    //
+    // Create a FETCH response with many literals, i.e., many `Fragment`s.
+    let rsp = Response::Data(Data::Fetch {
+        seq: NonZeroU32::new(1).unwrap(),
+        items: Vec1::try_from(
+            (0..LITERALS)
+                .map(|_| MessageDataItem::BodyExt {
+                    section: None,
+                    origin: None,
+                    data: NString::from(Literal::unvalidated(b"ABCDE".as_ref())),
+                })
+                .collect::<Vec<_>>(),
+        )
+        .unwrap(),
+    });
+    let encoded = ResponseCodec::default().encode(&rsp);
+
+    let mut group = c.benchmark_group("iterate_fragments");
+    group.throughput(Throughput::Elements(encoded.len() as u64));
+
+    group.bench_function("consume", |b| {
+        b.iter_batched(
+            || encoded.clone(),
+            |encoded| {
+                for fragment in encoded {
+                    black_box(fragment);
+                }
+            },
+            criterion::BatchSize::LargeInput,
+        )
+    });
+
+    group.bench_function("borrow", |b| {
+        b.iter(|| {
+            for fragment in &encoded {
+                black_box(fragment);
+            }
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark);
+
+criterion_main!(benches);
@@ -1,7 +1,7 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 use imap_codec::{decode::Decoder, imap_types::command::Command, CommandCodec};
 
-fn parse_command(input: &[u8]) -> Command {
+fn parse_command(input: &[u8]) -> Command<'_> {
     let (_remaining, cmd) = CommandCodec::default().decode(input).unwrap();
 
     cmd
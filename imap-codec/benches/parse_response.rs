@@ -1,7 +1,7 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 use imap_codec::{decode::Decoder, imap_types::response::Response, ResponseCodec};
 
-fn parse_response(input: &[u8]) -> Response {
+fn parse_response(input: &[u8]) -> Response<'_> {
     let (_remaining, rsp) = ResponseCodec::default().decode(input).unwrap();
 
     rsp
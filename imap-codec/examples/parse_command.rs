@@ -52,6 +52,26 @@ fn main() {
                 // ... and read more data.
                 read_more(&mut buffer, Role::Client);
             }
+            // Parser recognized a well-formed, but unknown command.
+            Err(CommandDecodeError::UnknownCommand { tag, name }) => {
+                println!(
+                    "S: {COLOR_SERVER}{} BAD unknown command {:?}{RESET}",
+                    tag.inner(),
+                    name.inner()
+                );
+                println!("Clearing buffer.");
+
+                // Clear the buffer and proceed with loop.
+                buffer.clear();
+            }
+            // Parser recognized an invalid sequence set, e.g., one that is too large.
+            Err(CommandDecodeError::InvalidSequenceSet { tag, error }) => {
+                println!("S: {COLOR_SERVER}{} BAD {error}{RESET}", tag.inner());
+                println!("Clearing buffer.");
+
+                // Clear the buffer and proceed with loop.
+                buffer.clear();
+            }
             // Parser failed.
             Err(CommandDecodeError::Failed) => {
                 println!("Error parsing command.");
@@ -20,7 +20,7 @@ use crate::{
 /// `auth-type = atom`
 ///
 /// Note: Defined by \[SASL\]
-pub(crate) fn auth_type(input: &[u8]) -> IMAPResult<&[u8], AuthMechanism> {
+pub(crate) fn auth_type(input: &[u8]) -> IMAPResult<'_, &[u8], AuthMechanism<'_>> {
     let (rem, atom) = atom(input)?;
 
     Ok((rem, AuthMechanism::from(atom)))
@@ -36,7 +36,7 @@ pub(crate) fn auth_type(input: &[u8]) -> IMAPResult<&[u8], AuthMechanism> {
 ///                CRLF is additionally parsed in this parser.
 ///                FIXME: Multiline base64 currently does not work.
 /// ```
-pub(crate) fn authenticate_data(input: &[u8]) -> IMAPResult<&[u8], AuthenticateData> {
+pub(crate) fn authenticate_data(input: &[u8]) -> IMAPResult<'_, &[u8], AuthenticateData<'_>> {
     alt((
         map(terminated(base64, crlf), AuthenticateData::r#continue),
         value(AuthenticateData::Cancel, tuple((tag("*"), crlf))),
@@ -45,6 +45,8 @@ pub(crate) fn authenticate_data(input: &[u8]) -> IMAPResult<&[u8], AuthenticateD
 
 #[cfg(test)]
 mod tests {
+    use imap_types::secret::Secret;
+
     use super::*;
     use crate::testing::{known_answer_test_encode, known_answer_test_parse};
 
@@ -111,4 +113,12 @@ mod tests {
             known_answer_test_parse(test, authenticate_data);
         }
     }
+
+    #[test]
+    fn test_encode_authenticate_data_plain() {
+        known_answer_test_encode((
+            AuthenticateData::plain(None, "alice", Secret::new("pass")).unwrap(),
+            b"AGFsaWNlAHBhc3M=\r\n",
+        ));
+    }
 }
@@ -33,7 +33,7 @@ pub(crate) fn body(
 fn body_limited<'a>(
     input: &'a [u8],
     remaining_recursions: usize,
-) -> IMAPResult<&'a [u8], BodyStructure> {
+) -> IMAPResult<'a, &'a [u8], BodyStructure<'a>> {
     if remaining_recursions == 0 {
         return Err(nom::Err::Failure(IMAPParseError {
             input,
@@ -67,7 +67,7 @@ fn body_limited<'a>(
 fn body_type_1part_limited<'a>(
     input: &'a [u8],
     remaining_recursions: usize,
-) -> IMAPResult<&'a [u8], BodyStructure> {
+) -> IMAPResult<'a, &'a [u8], BodyStructure<'a>> {
     if remaining_recursions == 0 {
         return Err(nom::Err::Failure(IMAPParseError {
             input,
@@ -96,7 +96,9 @@ fn body_type_1part_limited<'a>(
 /// `body-type-basic = media-basic SP body-fields`
 ///
 /// MESSAGE subtype MUST NOT be "RFC822"
-pub(crate) fn body_type_basic(input: &[u8]) -> IMAPResult<&[u8], (BasicFields, SpecificFields)> {
+pub(crate) fn body_type_basic(
+    input: &[u8],
+) -> IMAPResult<'_, &[u8], (BasicFields<'_>, SpecificFields<'_>)> {
     let mut parser = tuple((media_basic, sp, body_fields));
 
     let (remaining, ((type_, subtype), _, basic)) = parser(input)?;
@@ -124,7 +126,7 @@ pub(crate) fn body_type_basic(input: &[u8]) -> IMAPResult<&[u8], (BasicFields, S
 fn body_type_msg_limited<'a>(
     input: &'a [u8],
     remaining_recursions: usize,
-) -> IMAPResult<&'a [u8], (BasicFields, SpecificFields)> {
+) -> IMAPResult<'a, &'a [u8], (BasicFields<'a>, SpecificFields<'a>)> {
     if remaining_recursions == 0 {
         return Err(nom::Err::Failure(IMAPParseError {
             input,
@@ -165,7 +167,9 @@ fn body_type_msg_limited<'a>(
 /// `body-type-text = media-text SP
 ///                   body-fields SP
 ///                   body-fld-lines`
-pub(crate) fn body_type_text(input: &[u8]) -> IMAPResult<&[u8], (BasicFields, SpecificFields)> {
+pub(crate) fn body_type_text(
+    input: &[u8],
+) -> IMAPResult<'_, &[u8], (BasicFields<'_>, SpecificFields<'_>)> {
     let mut parser = tuple((media_text, sp, body_fields, sp, body_fld_lines));
 
     let (remaining, (subtype, _, basic, _, number_of_lines)) = parser(input)?;
@@ -187,7 +191,7 @@ pub(crate) fn body_type_text(input: &[u8]) -> IMAPResult<&[u8], (BasicFields, Sp
 ///                body-fld-desc SP
 ///                body-fld-enc SP
 ///                body-fld-octets`
-pub(crate) fn body_fields(input: &[u8]) -> IMAPResult<&[u8], BasicFields> {
+pub(crate) fn body_fields(input: &[u8]) -> IMAPResult<'_, &[u8], BasicFields<'_>> {
     let mut parser = tuple((
         body_fld_param,
         sp,
@@ -221,7 +225,9 @@ pub(crate) fn body_fields(input: &[u8]) -> IMAPResult<&[u8], BasicFields> {
 ///                    *(SP string SP string)
 ///                  ")" / nil
 /// ```
-pub(crate) fn body_fld_param(input: &[u8]) -> IMAPResult<&[u8], Vec<(IString, IString)>> {
+pub(crate) fn body_fld_param(
+    input: &[u8],
+) -> IMAPResult<'_, &[u8], Vec<(IString<'_>, IString<'_>)>> {
     let mut parser = alt((
         delimited(
             tag(b"("),
@@ -242,13 +248,13 @@ pub(crate) fn body_fld_param(input: &[u8]) -> IMAPResult<&[u8], Vec<(IString, IS
 
 #[inline]
 /// `body-fld-id = nstring`
-pub(crate) fn body_fld_id(input: &[u8]) -> IMAPResult<&[u8], NString> {
+pub(crate) fn body_fld_id(input: &[u8]) -> IMAPResult<'_, &[u8], NString<'_>> {
     nstring(input)
 }
 
 #[inline]
 /// `body-fld-desc = nstring`
-pub(crate) fn body_fld_desc(input: &[u8]) -> IMAPResult<&[u8], NString> {
+pub(crate) fn body_fld_desc(input: &[u8]) -> IMAPResult<'_, &[u8], NString<'_>> {
     nstring(input)
 }
 
@@ -268,7 +274,7 @@ pub(crate) fn body_fld_desc(input: &[u8]) -> IMAPResult<&[u8], NString> {
 /// `body-fld-enc = string`
 ///
 /// TODO: why the special case?
-pub(crate) fn body_fld_enc(input: &[u8]) -> IMAPResult<&[u8], IString> {
+pub(crate) fn body_fld_enc(input: &[u8]) -> IMAPResult<'_, &[u8], IString<'_>> {
     string(input)
 }
 
@@ -281,7 +287,7 @@ pub(crate) fn body_fld_enc(input: &[u8]) -> IMAPResult<&[u8], IString> {
 ///
 /// * A negative number, specifically `-1`, in Dovecot.
 #[allow(clippy::needless_return)]
-pub(crate) fn body_fld_octets(input: &[u8]) -> IMAPResult<&[u8], u32> {
+pub(crate) fn body_fld_octets(input: &[u8]) -> IMAPResult<'_, &[u8], u32> {
     #[cfg(not(feature = "quirk_rectify_numbers"))]
     return number(input);
 
@@ -299,7 +305,7 @@ pub(crate) fn body_fld_octets(input: &[u8]) -> IMAPResult<&[u8], u32> {
 
 #[inline]
 /// `body-fld-lines = number`
-pub(crate) fn body_fld_lines(input: &[u8]) -> IMAPResult<&[u8], u32> {
+pub(crate) fn body_fld_lines(input: &[u8]) -> IMAPResult<'_, &[u8], u32> {
     number(input)
 }
 
@@ -313,7 +319,7 @@ pub(crate) fn body_fld_lines(input: &[u8]) -> IMAPResult<&[u8], u32> {
 /// ```
 ///
 /// Note: MUST NOT be returned on non-extensible "BODY" fetch.
-pub(crate) fn body_ext_1part(input: &[u8]) -> IMAPResult<&[u8], SinglePartExtensionData> {
+pub(crate) fn body_ext_1part(input: &[u8]) -> IMAPResult<'_, &[u8], SinglePartExtensionData<'_>> {
     map(
         tuple((
             body_fld_md5,
@@ -346,7 +352,7 @@ pub(crate) fn body_ext_1part(input: &[u8]) -> IMAPResult<&[u8], SinglePartExtens
 
 #[inline]
 /// `body-fld-md5 = nstring`
-pub(crate) fn body_fld_md5(input: &[u8]) -> IMAPResult<&[u8], NString> {
+pub(crate) fn body_fld_md5(input: &[u8]) -> IMAPResult<'_, &[u8], NString<'_>> {
     nstring(input)
 }
 
@@ -354,7 +360,7 @@ pub(crate) fn body_fld_md5(input: &[u8]) -> IMAPResult<&[u8], NString> {
 #[allow(clippy::type_complexity)]
 pub(crate) fn body_fld_dsp(
     input: &[u8],
-) -> IMAPResult<&[u8], Option<(IString, Vec<(IString, IString)>)>> {
+) -> IMAPResult<'_, &[u8], Option<(IString<'_>, Vec<(IString<'_>, IString<'_>)>)>> {
     alt((
         delimited(
             tag(b"("),
@@ -369,7 +375,7 @@ pub(crate) fn body_fld_dsp(
 }
 
 /// `body-fld-lang = nstring / "(" string *(SP string) ")"`
-pub(crate) fn body_fld_lang(input: &[u8]) -> IMAPResult<&[u8], Vec<IString>> {
+pub(crate) fn body_fld_lang(input: &[u8]) -> IMAPResult<'_, &[u8], Vec<IString<'_>>> {
     alt((
         map(nstring, |nstring| match nstring.0 {
             Some(item) => vec![item],
@@ -381,7 +387,7 @@ pub(crate) fn body_fld_lang(input: &[u8]) -> IMAPResult<&[u8], Vec<IString>> {
 
 #[inline]
 /// `body-fld-loc = nstring`
-pub(crate) fn body_fld_loc(input: &[u8]) -> IMAPResult<&[u8], NString> {
+pub(crate) fn body_fld_loc(input: &[u8]) -> IMAPResult<'_, &[u8], NString<'_>> {
     nstring(input)
 }
 
@@ -408,7 +414,7 @@ pub(crate) fn body_extension(
 fn body_extension_limited<'a>(
     input: &'a [u8],
     remaining_recursion: usize,
-) -> IMAPResult<&'a [u8], BodyExtension> {
+) -> IMAPResult<'a, &'a [u8], BodyExtension<'a>> {
     if remaining_recursion == 0 {
         return Err(nom::Err::Failure(IMAPParseError {
             input,
@@ -438,7 +444,7 @@ fn body_extension_limited<'a>(
 fn body_type_mpart_limited(
     input: &[u8],
     remaining_recursion: usize,
-) -> IMAPResult<&[u8], BodyStructure> {
+) -> IMAPResult<'_, &[u8], BodyStructure<'_>> {
     if remaining_recursion == 0 {
         return Err(nom::Err::Failure(IMAPParseError {
             input,
@@ -476,7 +482,7 @@ fn body_type_mpart_limited(
 /// ```
 ///
 /// Note: MUST NOT be returned on non-extensible "BODY" fetch.
-pub(crate) fn body_ext_mpart(input: &[u8]) -> IMAPResult<&[u8], MultiPartExtensionData> {
+pub(crate) fn body_ext_mpart(input: &[u8]) -> IMAPResult<'_, &[u8], MultiPartExtensionData<'_>> {
     map(
         tuple((
             body_fld_param,
@@ -531,7 +537,7 @@ pub(crate) fn body_ext_mpart(input: &[u8]) -> IMAPResult<&[u8], MultiPartExtensi
 /// TODO: Why the special case?
 ///
 /// Defined in [MIME-IMT]
-pub(crate) fn media_basic(input: &[u8]) -> IMAPResult<&[u8], (IString, IString)> {
+pub(crate) fn media_basic(input: &[u8]) -> IMAPResult<'_, &[u8], (IString<'_>, IString<'_>)> {
     let mut parser = tuple((string, sp, media_subtype));
 
     let (remaining, (type_, _, subtype)) = parser(input)?;
@@ -543,7 +549,7 @@ pub(crate) fn media_basic(input: &[u8]) -> IMAPResult<&[u8], (IString, IString)>
 /// `media-subtype = string`
 ///
 /// Defined in [MIME-IMT]
-pub(crate) fn media_subtype(input: &[u8]) -> IMAPResult<&[u8], IString> {
+pub(crate) fn media_subtype(input: &[u8]) -> IMAPResult<'_, &[u8], IString<'_>> {
     string(input)
 }
 
@@ -558,7 +564,7 @@ pub(crate) fn media_subtype(input: &[u8]) -> IMAPResult<&[u8], IString> {
 /// Defined in [MIME-IMT]
 ///
 /// "message" "rfc822" basic specific-for-message-rfc822 extension
-pub(crate) fn media_message(input: &[u8]) -> IMAPResult<&[u8], &[u8]> {
+pub(crate) fn media_message(input: &[u8]) -> IMAPResult<'_, &[u8], &[u8]> {
     tag_no_case(b"\"MESSAGE\" \"RFC822\"")(input)
 }
 
@@ -567,7 +573,7 @@ pub(crate) fn media_message(input: &[u8]) -> IMAPResult<&[u8], &[u8]> {
 /// Defined in [MIME-IMT]
 ///
 /// "text" "?????" basic specific-for-text extension
-pub(crate) fn media_text(input: &[u8]) -> IMAPResult<&[u8], IString> {
+pub(crate) fn media_text(input: &[u8]) -> IMAPResult<'_, &[u8], IString<'_>> {
     let mut parser = preceded(tag_no_case(b"\"TEXT\" "), media_subtype);
 
     let (remaining, media_subtype) = parser(input)?;
@@ -580,7 +586,7 @@ mod tests {
     use std::num::NonZeroU32;
 
     use imap_types::{
-        core::{Literal, Quoted},
+        core::{IString, Literal, Quoted},
         fetch::MessageDataItem,
         response::{Data, Response},
     };
@@ -631,6 +637,32 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_single_part_extension_data_predicates() {
+        let (_, data) = body_ext_1part(b"\"AABB\" NIL (\"german\")|xxx").unwrap();
+
+        assert!(data.has_md5());
+        assert_eq!(data.md5(), Some(b"AABB".as_ref()));
+        assert!(!data.has_disposition());
+        assert_eq!(data.disposition(), None);
+        assert_eq!(
+            data.language(),
+            Some([IString::try_from("german").unwrap()].as_ref())
+        );
+        assert_eq!(data.location(), None);
+
+        let (_, data) = body_ext_1part(b"NIL (\"inline\" NIL) NIL \"loc\"|xxx").unwrap();
+
+        assert!(!data.has_md5());
+        assert!(data.has_disposition());
+        assert_eq!(
+            data.disposition(),
+            Some((&IString::try_from("inline").unwrap(), [].as_ref()))
+        );
+        assert_eq!(data.language(), Some([].as_ref()));
+        assert_eq!(data.location(), Some(b"loc".as_ref()));
+    }
+
     #[test]
     fn test_body_rec() {
         let _ = body(8)(str::repeat("(", 1_000_000).as_bytes());
@@ -763,6 +795,51 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_encode_empty_parameter_list() {
+        // `()` is invalid in a BODYSTRUCTURE, so an empty parameter list must be encoded as `NIL`.
+        let body = BodyStructure::Single {
+            body: Body {
+                basic: BasicFields {
+                    parameter_list: vec![],
+                    id: NString(None),
+                    description: NString(None),
+                    content_transfer_encoding: IString::try_from("7BIT").unwrap(),
+                    size: 42,
+                },
+                specific: SpecificFields::Basic {
+                    r#type: IString::try_from("APPLICATION").unwrap(),
+                    subtype: IString::try_from("octet-stream").unwrap(),
+                },
+            },
+            extension_data: Some(SinglePartExtensionData {
+                md5: NString(None),
+                tail: Some(Disposition {
+                    disposition: Some((IString::try_from("attachment").unwrap(), vec![])),
+                    tail: None,
+                }),
+            }),
+        };
+
+        known_answer_test_encode((
+            body,
+            b"(\"APPLICATION\" \"octet-stream\" NIL NIL NIL \"7BIT\" 42 NIL (\"attachment\" NIL))"
+                .as_ref(),
+        ));
+
+        known_answer_test_encode((
+            MultiPartExtensionData {
+                parameter_list: vec![],
+                tail: None,
+            },
+            b"NIL".as_ref(),
+        ));
+
+        // Quirk: `()` is accepted when parsing but normalized to `NIL`.
+        let (_, parameter_list) = body_fld_param(b"() ").unwrap();
+        assert_eq!(parameter_list, vec![]);
+    }
+
     #[test]
     fn test_number_quirk() {
         assert_eq!(body_fld_octets(b"0)").unwrap().1, 0);
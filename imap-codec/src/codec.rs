@@ -1,6 +1,8 @@
 pub mod decode;
 pub mod encode;
 
+use crate::sequence::DEFAULT_SEQUENCE_SET_MAX_LEN;
+
 /// Codec for greetings.
 #[derive(Clone, Debug, Default, PartialEq)]
 // We use `#[non_exhaustive]` to prevent users from using struct literal syntax.
@@ -11,9 +13,34 @@ pub mod encode;
 pub struct GreetingCodec;
 
 /// Codec for commands.
-#[derive(Clone, Debug, Default, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 #[non_exhaustive]
-pub struct CommandCodec;
+pub struct CommandCodec {
+    max_sequence_set_len: usize,
+}
+
+impl Default for CommandCodec {
+    fn default() -> Self {
+        Self {
+            max_sequence_set_len: DEFAULT_SEQUENCE_SET_MAX_LEN,
+        }
+    }
+}
+
+impl CommandCodec {
+    /// Set the maximum number of sequences accepted in a single sequence set.
+    ///
+    /// Commands exceeding the limit are rejected with
+    /// [`CommandDecodeError::InvalidSequenceSet`](decode::CommandDecodeError::InvalidSequenceSet).
+    /// The default is `100_000`.
+    ///
+    /// Note: The limit only applies to commands. [`ResponseCodec`] doesn't limit sequence sets,
+    /// e.g., in `ESEARCH`'s `ALL` or the `COPYUID` response code.
+    pub fn with_max_sequence_set_len(mut self, max_sequence_set_len: usize) -> Self {
+        self.max_sequence_set_len = max_sequence_set_len;
+        self
+    }
+}
 
 /// Codec for authenticate data lines.
 #[derive(Clone, Debug, Default, PartialEq)]
@@ -16,15 +16,17 @@ use bounded_static::{IntoBoundedStatic, ToStatic};
 use imap_types::{
     auth::AuthenticateData,
     command::Command,
-    core::{LiteralMode, Tag},
+    core::{Atom, LiteralMode, Tag},
     extensions::idle::IdleDone,
     response::{Greeting, Response},
+    sequence::error::SequenceSetError,
 };
 use nom::error::{ErrorKind, FromExternalError, ParseError};
 
 use crate::{
     auth::authenticate_data,
-    command::command,
+    command::{command, command_unknown},
+    core::tag_imap,
     extensions::idle::idle_done,
     response::{greeting, response},
     AuthenticateDataCodec, CommandCodec, GreetingCodec, IdleDoneCodec, ResponseCodec,
@@ -54,7 +56,10 @@ pub(crate) enum IMAPErrorKind<'a> {
     BadDateTime,
     LiteralContainsNull,
     RecursionLimitExceeded,
-    Nom(ErrorKind),
+    SequenceSet(SequenceSetError),
+    InvalidCode(CodeArgumentError),
+    ZeroNotAllowed,
+    Nom(#[allow(dead_code)] ErrorKind),
 }
 
 impl<'a, I> ParseError<I> for IMAPParseError<'a, I> {
@@ -192,6 +197,39 @@ pub enum CommandDecodeError<'a> {
         mode: LiteralMode,
     },
 
+    /// The command is well-formed, but its name is unknown.
+    ///
+    /// Contrary to [`CommandDecodeError::Failed`], the decoder recognized a complete command line,
+    /// i.e., `tag SP name [SP ...] CRLF`. A server may use this to reply with a tagged `BAD`
+    /// (see RFC 3501, section 7.1.5) and continue after the line.
+    ///
+    /// Note: Only the first line is recognized. When it ends with a literal announcement, e.g.,
+    /// `A XFOO {3}\r\n`, the literal data is *not* part of the unknown command. This is fine for
+    /// synchronizing literals because the client won't send the data without a command
+    /// continuation request. However, the data of a non-synchronizing literal, e.g.,
+    /// `A XFOO {3+}\r\n`, follows right away and must be discarded by the server (or the
+    /// connection closed).
+    UnknownCommand {
+        /// The tag of the command.
+        tag: Tag<'a>,
+
+        /// The (unknown) command name.
+        name: Atom<'a>,
+    },
+
+    /// The command contains an invalid sequence set.
+    ///
+    /// This is reported when a sequence set contains a zero, e.g., `1:0`, or has more sequences
+    /// than allowed (see [`CommandCodec::with_max_sequence_set_len`]). A server may use this to reply with a tagged
+    /// `BAD` and continue after the line.
+    InvalidSequenceSet {
+        /// The tag of the command.
+        tag: Tag<'a>,
+
+        /// The reason why the sequence set is invalid.
+        error: SequenceSetError,
+    },
+
     /// Decoding failed.
     Failed,
 }
@@ -227,10 +265,29 @@ pub enum ResponseDecodeError {
         length: u32,
     },
 
+    /// The response contains a known response code with an invalid argument.
+    ///
+    /// This is reported for `UIDNEXT`, `UIDVALIDITY`, and `UNSEEN`, e.g., `[UIDVALIDITY 0]`.
+    InvalidCode {
+        /// The reason why the argument is invalid.
+        error: CodeArgumentError,
+    },
+
     /// Decoding failed.
     Failed,
 }
 
+/// Reason why the argument of a response code is invalid.
+#[cfg_attr(feature = "bounded-static", derive(ToStatic))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CodeArgumentError {
+    /// The argument is missing or not a (32-bit) number, e.g., `[UIDNEXT abc]` or `[UIDNEXT *]`.
+    BadNumber,
+
+    /// The argument is zero, e.g., `[UIDVALIDITY 0]`.
+    ZeroNotAllowed,
+}
+
 /// Error during idle done decoding.
 #[cfg_attr(feature = "bounded-static", derive(ToStatic))]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -268,7 +325,7 @@ impl Decoder for CommandCodec {
         &self,
         input: &'a [u8],
     ) -> Result<(&'a [u8], Self::Message<'a>), Self::Error<'a>> {
-        match command(input) {
+        match command(self.max_sequence_set_len)(input) {
             Ok((rem, cmd)) => Ok((rem, cmd)),
             Err(nom::Err::Incomplete(_)) => Err(CommandDecodeError::Incomplete),
             Err(nom::Err::Failure(error)) => match error {
@@ -281,13 +338,29 @@ impl Decoder for CommandCodec {
                     length,
                     mode,
                 }),
-                _ => Err(CommandDecodeError::Failed),
+                IMAPParseError {
+                    input: _,
+                    kind: IMAPErrorKind::SequenceSet(error),
+                } => Err(CommandDecodeError::InvalidSequenceSet {
+                    // Unwrap: A sequence set is always preceded by a tag.
+                    tag: tag_imap(input).unwrap().1,
+                    error,
+                }),
+                _ => Err(command_failed(input)),
             },
-            Err(nom::Err::Error(_)) => Err(CommandDecodeError::Failed),
+            Err(nom::Err::Error(_)) => Err(command_failed(input)),
         }
     }
 }
 
+/// Distinguish between an unknown (but well-formed) command and a malformed command.
+fn command_failed(input: &[u8]) -> CommandDecodeError<'_> {
+    match command_unknown(input) {
+        Ok((_, (tag, name))) => CommandDecodeError::UnknownCommand { tag, name },
+        Err(_) => CommandDecodeError::Failed,
+    }
+}
+
 impl Decoder for ResponseCodec {
     type Message<'a> = Response<'a>;
     type Error<'a> = ResponseDecodeError;
@@ -304,6 +377,10 @@ impl Decoder for ResponseCodec {
                     kind: IMAPErrorKind::Literal { length, .. },
                     ..
                 } => Err(ResponseDecodeError::LiteralFound { length }),
+                IMAPParseError {
+                    kind: IMAPErrorKind::InvalidCode(error),
+                    ..
+                } => Err(ResponseDecodeError::InvalidCode { error }),
                 _ => Err(ResponseDecodeError::Failed),
             },
         }
@@ -350,11 +427,12 @@ mod tests {
 
     use imap_types::{
         command::{Command, CommandBody},
-        core::{IString, Literal, NString, Vec1},
+        core::{IString, Literal, NString, Tag, Vec1},
         extensions::idle::IdleDone,
         fetch::MessageDataItem,
+        flag::{Flag, FlagPerm},
         mailbox::Mailbox,
-        response::{Data, Greeting, GreetingKind, Response},
+        response::{Code, Data, Greeting, GreetingKind, Response, Status},
     };
 
     use super::*;
@@ -476,6 +554,30 @@ mod tests {
             // Failed
             (b"* noop\r\n".as_ref(), Err(CommandDecodeError::Failed)),
             (b"A  noop\r\n".as_ref(), Err(CommandDecodeError::Failed)),
+            (b"A select\r\n".as_ref(), Err(CommandDecodeError::Failed)),
+            (b"A xfoo\r".as_ref(), Err(CommandDecodeError::Failed)),
+            // UnknownCommand
+            (
+                b"A xfoo bar (baz)\r\n".as_ref(),
+                Err(CommandDecodeError::UnknownCommand {
+                    tag: Tag::try_from("A").unwrap(),
+                    name: Atom::try_from("xfoo").unwrap(),
+                }),
+            ),
+            (
+                b"A noopx\r\n".as_ref(),
+                Err(CommandDecodeError::UnknownCommand {
+                    tag: Tag::try_from("A").unwrap(),
+                    name: Atom::try_from("noopx").unwrap(),
+                }),
+            ),
+            (
+                b"A xfoo {3}\r\n".as_ref(),
+                Err(CommandDecodeError::UnknownCommand {
+                    tag: Tag::try_from("A").unwrap(),
+                    name: Atom::try_from("xfoo").unwrap(),
+                }),
+            ),
         ];
 
         for (test, expected) in tests {
@@ -491,6 +593,101 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_decode_command_sequence_set_limit() {
+        let expected = Err(CommandDecodeError::InvalidSequenceSet {
+            tag: Tag::try_from("A").unwrap(),
+            error: SequenceSetError::TooLarge { max: 3 },
+        });
+
+        let codec = CommandCodec::default().with_max_sequence_set_len(3);
+        assert!(codec.decode(b"A FETCH 1,2,3 FLAGS\r\n").is_ok());
+        assert_eq!(codec.decode(b"A FETCH 1,2,3,4 FLAGS\r\n"), expected);
+        assert_eq!(codec.decode(b"A UID COPY 1,2,3,4 x\r\n"), expected);
+
+        // The limit only applies to the configured codec.
+        assert!(CommandCodec::default()
+            .decode(b"A FETCH 1,2,3,4 FLAGS\r\n")
+            .is_ok());
+
+        let line = format!("A FETCH {} FLAGS\r\n", vec!["1"; 100_001].join(","));
+        assert_eq!(
+            CommandCodec::default().decode(line.as_bytes()),
+            Err(CommandDecodeError::InvalidSequenceSet {
+                tag: Tag::try_from("A").unwrap(),
+                error: SequenceSetError::TooLarge { max: 100_000 },
+            })
+        );
+    }
+
+    #[test]
+    fn test_decode_command_sequence_set_zero() {
+        for (test, at) in [
+            (b"A FETCH 0 FLAGS\r\n".as_ref(), 0),
+            (b"A FETCH 1:0 FLAGS\r\n", 2),
+            (b"A FETCH 1,0 FLAGS\r\n", 2),
+            (b"A UID STORE 5,0:3 +FLAGS (x)\r\n", 2),
+            (b"A SEARCH 1,2:0\r\n", 4),
+        ] {
+            assert_eq!(
+                CommandCodec::default().decode(test),
+                Err(CommandDecodeError::InvalidSequenceSet {
+                    tag: Tag::try_from("A").unwrap(),
+                    error: SequenceSetError::ZeroNotAllowed { at },
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn test_decode_command_append_literal() {
+        let expected = Command::new(
+            "A",
+            CommandBody::append("INBOX", vec![], None, b"abc".as_ref()).unwrap(),
+        )
+        .unwrap();
+
+        let tests = [
+            // The server must send a continuation request ...
+            (
+                b"A APPEND INBOX {3}\r\n".as_ref(),
+                Err(CommandDecodeError::LiteralFound {
+                    tag: Tag::try_from("A").unwrap(),
+                    length: 3,
+                    mode: LiteralMode::Sync,
+                }),
+            ),
+            // ... then read the message ...
+            (
+                b"A APPEND INBOX {3}\r\nab".as_ref(),
+                Err(CommandDecodeError::Incomplete),
+            ),
+            (
+                b"A APPEND INBOX {3}\r\nabc".as_ref(),
+                Err(CommandDecodeError::Incomplete),
+            ),
+            // ... and the trailing CRLF, which is part of the command.
+            (
+                b"A APPEND INBOX {3}\r\nabc\r".as_ref(),
+                Err(CommandDecodeError::Incomplete),
+            ),
+            (
+                b"A APPEND INBOX {3}\r\nabc\r\n".as_ref(),
+                Ok((b"".as_ref(), expected.clone())),
+            ),
+            (
+                b"A APPEND INBOX {3}\r\nabc\r\nB NOOP\r\n".as_ref(),
+                Ok((b"B NOOP\r\n".as_ref(), expected)),
+            ),
+        ];
+
+        for (test, expected) in tests {
+            let got = CommandCodec::default().decode(test);
+            dbg!((std::str::from_utf8(test).unwrap(), &expected, &got));
+            assert_eq!(expected, got);
+        }
+    }
+
     #[test]
     fn test_decode_authenticate_data() {
         let tests = [
@@ -683,4 +880,69 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_decode_response_byte_by_byte() {
+        let tests = [
+            (
+                b"* OK [UIDVALIDITY 1] UIDs valid\r\n".as_ref(),
+                Response::Status(
+                    Status::ok(
+                        None,
+                        Some(Code::UidValidity(NonZeroU32::new(1).unwrap())),
+                        "UIDs valid",
+                    )
+                    .unwrap(),
+                ),
+            ),
+            (
+                b"* OK [PERMANENTFLAGS (\\Deleted \\Seen \\*)] Limited\r\n".as_ref(),
+                Response::Status(
+                    Status::ok(
+                        None,
+                        Some(Code::PermanentFlags(vec![
+                            FlagPerm::Flag(Flag::Deleted),
+                            FlagPerm::Flag(Flag::Seen),
+                            FlagPerm::Asterisk,
+                        ])),
+                        "Limited",
+                    )
+                    .unwrap(),
+                ),
+            ),
+            (
+                b"A1 OK [READ-WRITE] SELECT completed\r\n".as_ref(),
+                Response::Status(
+                    Status::ok(
+                        Some(Tag::try_from("A1").unwrap()),
+                        Some(Code::ReadWrite),
+                        "SELECT completed",
+                    )
+                    .unwrap(),
+                ),
+            ),
+        ];
+
+        for (line, expected) in tests {
+            // Feed the line one byte at a time, as if every byte arrived in a separate read.
+            let mut buffer = Vec::new();
+
+            for (index, byte) in line.iter().enumerate() {
+                buffer.push(*byte);
+
+                let got = ResponseCodec::default().decode(&buffer);
+
+                if index + 1 < line.len() {
+                    assert_eq!(
+                        got,
+                        Err(ResponseDecodeError::Incomplete),
+                        "{:?}",
+                        std::str::from_utf8(&buffer).unwrap()
+                    );
+                } else {
+                    assert_eq!(got, Ok((b"".as_ref(), expected.clone())));
+                }
+            }
+        }
+    }
 }
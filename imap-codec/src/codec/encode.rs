@@ -45,9 +45,13 @@
 //! C: Pa²²W0rD
 //! ```
 
-use std::{borrow::Borrow, io::Write, num::NonZeroU32};
+use std::{
+    collections::{vec_deque, VecDeque},
+    io::{self, Write},
+    num::NonZeroU32,
+};
 
-use base64::{engine::general_purpose::STANDARD as base64, Engine};
+use base64::engine::general_purpose::STANDARD as base64;
 use chrono::{DateTime as ChronoDateTime, FixedOffset};
 #[cfg(any(feature = "ext_binary", feature = "ext_metadata"))]
 use imap_types::core::NString8;
@@ -79,7 +83,7 @@ use imap_types::{
     status::{StatusDataItem, StatusDataItemName},
     utils::escape_quoted,
 };
-use utils::{join_serializable, List1AttributeValueOrNil, List1OrNil};
+use utils::{join_serializable, write_base64, List1AttributeValueOrNil, List1OrNil};
 
 use crate::{AuthenticateDataCodec, CommandCodec, GreetingCodec, IdleDoneCodec, ResponseCodec};
 
@@ -93,6 +97,159 @@ pub trait Encoder {
     ///
     /// This will return an [`Encoded`] message.
     fn encode(&self, message: &Self::Message<'_>) -> Encoded;
+
+    /// Encode this message using the given [`LineEnding`].
+    ///
+    /// Note: Only [`LineEnding::CrLf`] produces valid IMAP. Other line endings are meant for,
+    /// e.g., logging or comparing expected outputs in tests. Literal data is never modified.
+    fn encode_with(&self, message: &Self::Message<'_>, line_ending: LineEnding) -> Encoded {
+        let mut encoded = self.encode(message);
+
+        if line_ending == LineEnding::Lf {
+            for fragment in encoded.items.iter_mut() {
+                if let Fragment::Line { data } = fragment {
+                    if data.ends_with(b"\r\n") {
+                        data.remove(data.len() - 2);
+                    }
+                }
+            }
+        }
+
+        encoded
+    }
+
+    /// Encode this message directly into `writer`.
+    ///
+    /// In contrast to [`Encoder::encode`], this doesn't build a [`Fragment`] for every line and
+    /// literal. Instead, `on_literal` is called at every literal boundary, i.e., after the line
+    /// announcing the literal was written, and before the literal data is written. Use it to,
+    /// e.g., flush `writer` and wait for a continuation request on [`LiteralMode::Sync`].
+    ///
+    /// Note: Implementors must stream the message, i.e., must not go through [`Encoder::encode`].
+    ///
+    /// ```rust
+    /// use imap_codec::{
+    ///     encode::Encoder,
+    ///     imap_types::command::{Command, CommandBody},
+    ///     CommandCodec,
+    /// };
+    ///
+    /// let command = Command::new("A1", CommandBody::login("Alice", "Pa²²W0rD").unwrap()).unwrap();
+    ///
+    /// let mut out = Vec::new();
+    /// let mut literals = 0;
+    ///
+    /// CommandCodec::default()
+    ///     .encode_to(&command, &mut out, |_, _| {
+    ///         literals += 1;
+    ///         Ok(())
+    ///     })
+    ///     .unwrap();
+    ///
+    /// assert_eq!(out, "A1 LOGIN Alice {10}\r\nPa²²W0rD\r\n".as_bytes());
+    /// assert_eq!(literals, 1);
+    /// ```
+    fn encode_to<W, F>(
+        &self,
+        message: &Self::Message<'_>,
+        writer: &mut W,
+        on_literal: F,
+    ) -> io::Result<()>
+    where
+        W: Write,
+        F: FnMut(&mut W, LiteralMode) -> io::Result<()>;
+
+    /// Return the length of the encoded message in bytes.
+    ///
+    /// This includes all lines (with their `\r\n`), literal announcements, and literal data.
+    ///
+    /// Note: The message is streamed into a counting writer, i.e., the result is exact, but it
+    /// costs as much as encoding the message. Don't use it only to reserve a buffer before
+    /// [`Encoder::encode_to`]. Writers that grow on demand, e.g., `Vec<u8>`, are already written
+    /// to in chunks.
+    fn encoded_len(&self, message: &Self::Message<'_>) -> usize {
+        let mut counter = CountingWriter(0);
+
+        // Safety: `CountingWriter` never fails.
+        self.encode_to(message, &mut counter, |_, _| Ok(()))
+            .unwrap();
+
+        counter.0
+    }
+}
+
+/// A writer that only counts the written bytes.
+struct CountingWriter(usize);
+
+impl Write for CountingWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0 += buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Render a message (or a message part) in its wire form for logging and debugging.
+///
+/// This wraps the same machinery as [`Encoder::encode`]. Literals are inlined after their
+/// announcement, and non-UTF-8 data is replaced lossily. Thus, the result is not meant to be sent.
+///
+/// Note: `Display` can't be implemented here because the types are defined in `imap-types`.
+///
+/// ```rust
+/// use imap_codec::{
+///     encode::ToImapString,
+///     imap_types::command::{Command, CommandBody},
+/// };
+///
+/// let command = Command::new("A", CommandBody::login("alice", "pass").unwrap()).unwrap();
+///
+/// assert_eq!(command.to_imap_string(), "A LOGIN alice pass\r\n");
+/// ```
+pub trait ToImapString {
+    /// Return the wire form as a (lossy) UTF-8 string.
+    fn to_imap_string(&self) -> String;
+}
+
+macro_rules! impl_to_imap_string {
+    ($type:ty) => {
+        impl ToImapString for $type {
+            fn to_imap_string(&self) -> String {
+                let mut out = Vec::new();
+                let mut sink = WriterSink {
+                    writer: &mut out,
+                    on_literal: |_: &mut Vec<u8>, _| Ok(()),
+                };
+                let mut encode_context = EncodeContext::with_sink(&mut sink);
+
+                // Safety: Writing into a `Vec` never fails.
+                EncodeIntoContext::encode_ctx(self, &mut encode_context).unwrap();
+                encode_context.flush().unwrap();
+
+                String::from_utf8_lossy(&out).into_owned()
+            }
+        }
+    };
+}
+
+impl_to_imap_string!(Greeting<'_>);
+impl_to_imap_string!(Command<'_>);
+impl_to_imap_string!(Response<'_>);
+impl_to_imap_string!(Envelope<'_>);
+impl_to_imap_string!(BodyStructure<'_>);
+
+/// Line ending used by [`Encoder::encode_with`].
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum LineEnding {
+    /// `\r\n` as required by IMAP.
+    #[default]
+    CrLf,
+
+    /// `\n` only.
+    Lf,
 }
 
 /// An encoded message.
@@ -122,10 +279,15 @@ pub trait Encoder {
 /// ```
 #[derive(Clone, Debug)]
 pub struct Encoded {
-    items: Vec<Fragment>,
+    items: VecDeque<Fragment>,
 }
 
 impl Encoded {
+    /// Iterate over the (remaining) [`Fragment`]s without consuming them.
+    pub fn iter(&self) -> vec_deque::Iter<'_, Fragment> {
+        self.items.iter()
+    }
+
     /// Dump the (remaining) encoded data without being guided by [`Fragment`]s.
     pub fn dump(self) -> Vec<u8> {
         let mut out = Vec::new();
@@ -139,17 +301,50 @@ impl Encoded {
 
         out
     }
+
+    /// Split the (remaining) encoded data into lines and literals.
+    ///
+    /// Every literal is returned together with the index of the line it follows. The concatenation
+    /// of line 0, all literals following line 0, line 1, etc. equals the output of [`Self::dump`].
+    #[allow(clippy::type_complexity)]
+    pub fn split_literals(self) -> (Vec<Vec<u8>>, Vec<(usize, Vec<u8>, LiteralMode)>) {
+        let mut lines = Vec::new();
+        let mut literals = Vec::new();
+
+        for fragment in self.items {
+            match fragment {
+                Fragment::Line { data } => lines.push(data),
+                Fragment::Literal { data, mode } => {
+                    // Note: A literal is always announced in a preceding line.
+                    literals.push((lines.len().saturating_sub(1), data, mode))
+                }
+            }
+        }
+
+        (lines, literals)
+    }
 }
 
 impl Iterator for Encoded {
     type Item = Fragment;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if !self.items.is_empty() {
-            Some(self.items.remove(0))
-        } else {
-            None
-        }
+        self.items.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.items.len(), Some(self.items.len()))
+    }
+}
+
+impl ExactSizeIterator for Encoded {}
+
+impl<'a> IntoIterator for &'a Encoded {
+    type Item = &'a Fragment;
+    type IntoIter = vec_deque::Iter<'a, Fragment>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
     }
 }
 
@@ -165,34 +360,97 @@ pub enum Fragment {
 
 //--------------------------------------------------------------------------------------------------
 
-#[derive(Clone, Debug, Default, Eq, PartialEq)]
-pub(crate) struct EncodeContext {
+/// Size of the buffer used to batch small writes into a [`Sink`].
+const SINK_BUFFER_SIZE: usize = 4096;
+
+#[derive(Default)]
+pub(crate) struct EncodeContext<'w> {
     accumulator: Vec<u8>,
     items: Vec<Fragment>,
+    /// When set, the `accumulator` is flushed into the sink instead of being collected into `items`.
+    sink: Option<&'w mut dyn Sink>,
+}
+
+/// A writer that is notified about literal boundaries (see [`Encoder::encode_to`]).
+pub(crate) trait Sink: Write {
+    fn literal(&mut self, mode: LiteralMode) -> io::Result<()>;
+}
+
+struct WriterSink<'a, W, F> {
+    writer: &'a mut W,
+    on_literal: F,
+}
+
+impl<'a, W, F> Write for WriterSink<'a, W, F>
+where
+    W: Write,
+{
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.writer.write(buf)
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        self.writer.write_all(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+impl<'a, W, F> Sink for WriterSink<'a, W, F>
+where
+    W: Write,
+    F: FnMut(&mut W, LiteralMode) -> io::Result<()>,
+{
+    fn literal(&mut self, mode: LiteralMode) -> io::Result<()> {
+        (self.on_literal)(self.writer, mode)
+    }
 }
 
-impl EncodeContext {
+impl<'w> EncodeContext<'w> {
     pub fn new() -> Self {
         Self::default()
     }
 
-    pub fn push_line(&mut self) {
-        self.items.push(Fragment::Line {
-            data: std::mem::take(&mut self.accumulator),
-        })
+    pub fn with_sink(sink: &'w mut dyn Sink) -> Self {
+        Self {
+            accumulator: Vec::with_capacity(SINK_BUFFER_SIZE),
+            items: Vec::new(),
+            sink: Some(sink),
+        }
     }
 
-    pub fn push_literal(&mut self, mode: LiteralMode) {
-        self.items.push(Fragment::Literal {
-            data: std::mem::take(&mut self.accumulator),
-            mode,
-        })
+    /// Write the data of a literal.
+    ///
+    /// Note: The literal must already be announced, e.g., by `{<length>}\r\n`.
+    pub fn write_literal(&mut self, data: &[u8], mode: LiteralMode) -> io::Result<()> {
+        match self.sink {
+            Some(ref mut sink) => {
+                sink.write_all(&self.accumulator)?;
+                self.accumulator.clear();
+                sink.literal(mode)?;
+                sink.write_all(data)
+            }
+            None => {
+                self.items.push(Fragment::Line {
+                    data: std::mem::take(&mut self.accumulator),
+                });
+                self.items.push(Fragment::Literal {
+                    data: data.to_vec(),
+                    mode,
+                });
+
+                Ok(())
+            }
+        }
     }
 
     pub fn into_items(self) -> Vec<Fragment> {
         let Self {
             accumulator,
             mut items,
+            ..
         } = self;
 
         if !accumulator.is_empty() {
@@ -218,13 +476,28 @@ impl EncodeContext {
     }
 }
 
-impl Write for EncodeContext {
+impl<'w> Write for EncodeContext<'w> {
     fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
-        self.accumulator.extend_from_slice(buf);
+        self.write_all(buf)?;
         Ok(buf.len())
     }
 
+    fn write_all(&mut self, buf: &[u8]) -> std::io::Result<()> {
+        self.accumulator.extend_from_slice(buf);
+
+        if self.accumulator.len() >= SINK_BUFFER_SIZE && self.sink.is_some() {
+            self.flush()?;
+        }
+
+        Ok(())
+    }
+
     fn flush(&mut self) -> std::io::Result<()> {
+        if let Some(ref mut sink) = self.sink {
+            sink.write_all(&self.accumulator)?;
+            self.accumulator.clear();
+        }
+
         Ok(())
     }
 }
@@ -236,12 +509,28 @@ macro_rules! impl_encoder_for_codec {
 
             fn encode(&self, message: &Self::Message<'_>) -> Encoded {
                 let mut encode_context = EncodeContext::new();
-                EncodeIntoContext::encode_ctx(message.borrow(), &mut encode_context).unwrap();
+                EncodeIntoContext::encode_ctx(message, &mut encode_context).unwrap();
 
                 Encoded {
-                    items: encode_context.into_items(),
+                    items: encode_context.into_items().into(),
                 }
             }
+
+            fn encode_to<W, F>(
+                &self,
+                message: &Self::Message<'_>,
+                writer: &mut W,
+                on_literal: F,
+            ) -> io::Result<()>
+            where
+                W: Write,
+                F: FnMut(&mut W, LiteralMode) -> io::Result<()>,
+            {
+                let mut sink = WriterSink { writer, on_literal };
+                let mut encode_context = EncodeContext::with_sink(&mut sink);
+                EncodeIntoContext::encode_ctx(message, &mut encode_context)?;
+                encode_context.flush()
+            }
         }
     };
 }
@@ -252,12 +541,56 @@ impl_encoder_for_codec!(AuthenticateDataCodec, AuthenticateData<'a>);
 impl_encoder_for_codec!(ResponseCodec, Response<'a>);
 impl_encoder_for_codec!(IdleDoneCodec, IdleDone);
 
+/// A reference to an encoder is an encoder, too.
+///
+/// This allows to pass, e.g., `&CommandCodec` to functions that are generic over [`Encoder`].
+impl<E> Encoder for &E
+where
+    E: Encoder + ?Sized,
+{
+    type Message<'a> = E::Message<'a>;
+
+    fn encode(&self, message: &Self::Message<'_>) -> Encoded {
+        (**self).encode(message)
+    }
+
+    fn encode_with(&self, message: &Self::Message<'_>, line_ending: LineEnding) -> Encoded {
+        (**self).encode_with(message, line_ending)
+    }
+
+    fn encode_to<W, F>(
+        &self,
+        message: &Self::Message<'_>,
+        writer: &mut W,
+        on_literal: F,
+    ) -> io::Result<()>
+    where
+        W: Write,
+        F: FnMut(&mut W, LiteralMode) -> io::Result<()>,
+    {
+        (**self).encode_to(message, writer, on_literal)
+    }
+
+    fn encoded_len(&self, message: &Self::Message<'_>) -> usize {
+        (**self).encoded_len(message)
+    }
+}
+
 // -------------------------------------------------------------------------------------------------
 
 pub(crate) trait EncodeIntoContext {
     fn encode_ctx(&self, ctx: &mut EncodeContext) -> std::io::Result<()>;
 }
 
+impl<T> EncodeIntoContext for &T
+where
+    T: EncodeIntoContext + ?Sized,
+{
+    fn encode_ctx(&self, ctx: &mut EncodeContext) -> std::io::Result<()> {
+        (**self).encode_ctx(ctx)
+    }
+}
+
 // ----- Primitive ---------------------------------------------------------------------------------
 
 impl EncodeIntoContext for u32 {
@@ -314,7 +647,7 @@ impl<'a> EncodeIntoContext for CommandBody<'a> {
                     if ir.declassify().is_empty() {
                         ctx.write_all(b"=")?;
                     } else {
-                        ctx.write_all(base64.encode(ir.declassify()).as_bytes())?;
+                        write_base64(ir.declassify(), ctx)?;
                     };
                 };
 
@@ -343,6 +676,18 @@ impl<'a> EncodeIntoContext for CommandBody<'a> {
                 ctx.write_all(b" ")?;
                 mailbox.encode_ctx(ctx)
             }
+            #[cfg(feature = "ext_special_use")]
+            CommandBody::CreateSpecialUse {
+                mailbox,
+                use_attributes,
+            } => {
+                ctx.write_all(b"CREATE")?;
+                ctx.write_all(b" ")?;
+                mailbox.encode_ctx(ctx)?;
+                ctx.write_all(b" (USE (")?;
+                join_serializable(use_attributes, b" ", ctx)?;
+                ctx.write_all(b"))")
+            }
             CommandBody::Delete { mailbox } => {
                 ctx.write_all(b"DELETE")?;
                 ctx.write_all(b" ")?;
@@ -378,6 +723,30 @@ impl<'a> EncodeIntoContext for CommandBody<'a> {
                 ctx.write_all(b" ")?;
                 mailbox_wildcard.encode_ctx(ctx)
             }
+            #[cfg(feature = "ext_list_extended")]
+            CommandBody::ListExtended {
+                selection_options,
+                reference,
+                mailbox_patterns,
+                return_options,
+            } => {
+                ctx.write_all(b"LIST")?;
+                if !selection_options.is_empty() {
+                    ctx.write_all(b" (")?;
+                    join_serializable(selection_options, b" ", ctx)?;
+                    ctx.write_all(b")")?;
+                }
+                ctx.write_all(b" ")?;
+                reference.encode_ctx(ctx)?;
+                ctx.write_all(b" ")?;
+                mailbox_patterns.encode_ctx(ctx)?;
+                if !return_options.is_empty() {
+                    ctx.write_all(b" RETURN (")?;
+                    join_serializable(return_options, b" ", ctx)?;
+                    ctx.write_all(b")")?;
+                }
+                Ok(())
+            }
             CommandBody::Lsub {
                 reference,
                 mailbox_wildcard,
@@ -425,6 +794,19 @@ impl<'a> EncodeIntoContext for CommandBody<'a> {
                 ctx.write_all(b" ")?;
                 message.encode_ctx(ctx)
             }
+            #[cfg(feature = "ext_multiappend")]
+            CommandBody::MultiAppend { mailbox, messages } => {
+                ctx.write_all(b"APPEND")?;
+                ctx.write_all(b" ")?;
+                mailbox.encode_ctx(ctx)?;
+
+                for message in messages.as_ref() {
+                    ctx.write_all(b" ")?;
+                    message.encode_ctx(ctx)?;
+                }
+
+                Ok(())
+            }
             CommandBody::Check => ctx.write_all(b"CHECK"),
             CommandBody::Close => ctx.write_all(b"CLOSE"),
             CommandBody::Expunge => ctx.write_all(b"EXPUNGE"),
@@ -445,6 +827,28 @@ impl<'a> EncodeIntoContext for CommandBody<'a> {
                 ctx.write_all(b" ")?;
                 join_serializable(criteria.as_ref(), b" ", ctx)
             }
+            #[cfg(feature = "ext_esearch")]
+            CommandBody::SearchExtended {
+                return_options,
+                charset,
+                criteria,
+                uid,
+            } => {
+                if *uid {
+                    ctx.write_all(b"UID SEARCH")?;
+                } else {
+                    ctx.write_all(b"SEARCH")?;
+                }
+                ctx.write_all(b" RETURN (")?;
+                join_serializable(return_options.as_ref(), b" ", ctx)?;
+                ctx.write_all(b")")?;
+                if let Some(charset) = charset {
+                    ctx.write_all(b" CHARSET ")?;
+                    charset.encode_ctx(ctx)?;
+                }
+                ctx.write_all(b" ")?;
+                join_serializable(criteria.as_ref(), b" ", ctx)
+            }
             #[cfg(feature = "ext_sort_thread")]
             CommandBody::Sort {
                 sort_criteria,
@@ -496,6 +900,26 @@ impl<'a> EncodeIntoContext for CommandBody<'a> {
                 ctx.write_all(b" ")?;
                 macro_or_item_names.encode_ctx(ctx)
             }
+            #[cfg(feature = "ext_condstore_qresync")]
+            CommandBody::FetchWithModifiers {
+                sequence_set,
+                macro_or_item_names,
+                uid,
+                modifiers,
+            } => {
+                if *uid {
+                    ctx.write_all(b"UID FETCH ")?;
+                } else {
+                    ctx.write_all(b"FETCH ")?;
+                }
+
+                sequence_set.encode_ctx(ctx)?;
+                ctx.write_all(b" ")?;
+                macro_or_item_names.encode_ctx(ctx)?;
+                ctx.write_all(b" (")?;
+                join_serializable(modifiers.as_ref(), b" ", ctx)?;
+                ctx.write_all(b")")
+            }
             CommandBody::Store {
                 sequence_set,
                 kind,
@@ -529,6 +953,43 @@ impl<'a> EncodeIntoContext for CommandBody<'a> {
                 join_serializable(flags, b" ", ctx)?;
                 ctx.write_all(b")")
             }
+            #[cfg(feature = "ext_condstore_qresync")]
+            CommandBody::StoreWithModifiers {
+                sequence_set,
+                kind,
+                response,
+                flags,
+                uid,
+                modifiers,
+            } => {
+                if *uid {
+                    ctx.write_all(b"UID STORE ")?;
+                } else {
+                    ctx.write_all(b"STORE ")?;
+                }
+
+                sequence_set.encode_ctx(ctx)?;
+                ctx.write_all(b" (")?;
+                join_serializable(modifiers.as_ref(), b" ", ctx)?;
+                ctx.write_all(b") ")?;
+
+                match kind {
+                    StoreType::Add => ctx.write_all(b"+")?,
+                    StoreType::Remove => ctx.write_all(b"-")?,
+                    StoreType::Replace => {}
+                }
+
+                ctx.write_all(b"FLAGS")?;
+
+                match response {
+                    StoreResponse::Answer => {}
+                    StoreResponse::Silent => ctx.write_all(b".SILENT")?,
+                }
+
+                ctx.write_all(b" (")?;
+                join_serializable(flags, b" ", ctx)?;
+                ctx.write_all(b")")
+            }
             CommandBody::Copy {
                 sequence_set,
                 mailbox,
@@ -581,6 +1042,10 @@ impl<'a> EncodeIntoContext for CommandBody<'a> {
                 ctx.write_all(b" ")?;
                 mailbox.encode_ctx(ctx)
             }
+            #[cfg(feature = "ext_namespace")]
+            CommandBody::Namespace => ctx.write_all(b"NAMESPACE"),
+            #[cfg(feature = "ext_unauthenticate")]
+            CommandBody::Unauthenticate => ctx.write_all(b"UNAUTHENTICATE"),
             #[cfg(feature = "ext_id")]
             CommandBody::Id { parameters } => {
                 ctx.write_all(b"ID ")?;
@@ -654,6 +1119,49 @@ impl<'a> EncodeIntoContext for CommandBody<'a> {
                     ctx.write_all(b")")
                 }
             }
+            #[cfg(feature = "ext_acl")]
+            CommandBody::SetAcl {
+                mailbox,
+                identifier,
+                rights,
+            } => {
+                ctx.write_all(b"SETACL ")?;
+                mailbox.encode_ctx(ctx)?;
+                ctx.write_all(b" ")?;
+                identifier.encode_ctx(ctx)?;
+                ctx.write_all(b" ")?;
+                rights.encode_ctx(ctx)
+            }
+            #[cfg(feature = "ext_acl")]
+            CommandBody::DeleteAcl {
+                mailbox,
+                identifier,
+            } => {
+                ctx.write_all(b"DELETEACL ")?;
+                mailbox.encode_ctx(ctx)?;
+                ctx.write_all(b" ")?;
+                identifier.encode_ctx(ctx)
+            }
+            #[cfg(feature = "ext_acl")]
+            CommandBody::GetAcl { mailbox } => {
+                ctx.write_all(b"GETACL ")?;
+                mailbox.encode_ctx(ctx)
+            }
+            #[cfg(feature = "ext_acl")]
+            CommandBody::ListRights {
+                mailbox,
+                identifier,
+            } => {
+                ctx.write_all(b"LISTRIGHTS ")?;
+                mailbox.encode_ctx(ctx)?;
+                ctx.write_all(b" ")?;
+                identifier.encode_ctx(ctx)
+            }
+            #[cfg(feature = "ext_acl")]
+            CommandBody::MyRights { mailbox } => {
+                ctx.write_all(b"MYRIGHTS ")?;
+                mailbox.encode_ctx(ctx)
+            }
         }
     }
 }
@@ -668,8 +1176,7 @@ impl EncodeIntoContext for AuthenticateData<'_> {
     fn encode_ctx(&self, ctx: &mut EncodeContext) -> std::io::Result<()> {
         match self {
             Self::Continue(data) => {
-                let encoded = base64.encode(data.declassify());
-                ctx.write_all(encoded.as_bytes())?;
+                write_base64(data.declassify(), ctx)?;
                 ctx.write_all(b"\r\n")
             }
             Self::Cancel => ctx.write_all(b"*\r\n"),
@@ -714,11 +1221,7 @@ impl<'a> EncodeIntoContext for Literal<'a> {
             LiteralMode::NonSync => write!(ctx, "{{{}+}}\r\n", self.as_ref().len())?,
         }
 
-        ctx.push_line();
-        ctx.write_all(self.as_ref())?;
-        ctx.push_literal(self.mode());
-
-        Ok(())
+        ctx.write_literal(self.as_ref(), self.mode())
     }
 }
 
@@ -770,6 +1273,12 @@ impl EncodeIntoContext for StatusDataItemName {
             Self::DeletedStorage => ctx.write_all(b"DELETED-STORAGE"),
             #[cfg(feature = "ext_condstore_qresync")]
             Self::HighestModSeq => ctx.write_all(b"HIGHESTMODSEQ"),
+            #[cfg(feature = "ext_objectid")]
+            Self::MailboxId => ctx.write_all(b"MAILBOXID"),
+            #[cfg(feature = "ext_status_size")]
+            Self::Size => ctx.write_all(b"SIZE"),
+            #[cfg(feature = "ext_status_size")]
+            Self::AppendLimit => ctx.write_all(b"APPENDLIMIT"),
         }
     }
 }
@@ -912,6 +1421,40 @@ impl<'a> EncodeIntoContext for SearchKey<'a> {
                 sequence_set.encode_ctx(ctx)
             }
             SearchKey::Undraft => ctx.write_all(b"UNDRAFT"),
+            #[cfg(feature = "ext_annotate")]
+            SearchKey::Annotation {
+                entry,
+                attribute,
+                value,
+            } => {
+                ctx.write_all(b"ANNOTATION ")?;
+                entry.encode_ctx(ctx)?;
+                ctx.write_all(b" ")?;
+                attribute.encode_ctx(ctx)?;
+                ctx.write_all(b" ")?;
+                value.encode_ctx(ctx)
+            }
+            #[cfg(feature = "ext_within")]
+            SearchKey::Older(seconds) => write!(ctx, "OLDER {seconds}"),
+            #[cfg(feature = "ext_within")]
+            SearchKey::Younger(seconds) => write!(ctx, "YOUNGER {seconds}"),
+            #[cfg(feature = "ext_savedate")]
+            SearchKey::SaveDateSupported => ctx.write_all(b"SAVEDATESUPPORTED"),
+            #[cfg(feature = "ext_savedate")]
+            SearchKey::SavedBefore(date) => {
+                ctx.write_all(b"SAVEDBEFORE ")?;
+                date.encode_ctx(ctx)
+            }
+            #[cfg(feature = "ext_savedate")]
+            SearchKey::SavedOn(date) => {
+                ctx.write_all(b"SAVEDON ")?;
+                date.encode_ctx(ctx)
+            }
+            #[cfg(feature = "ext_savedate")]
+            SearchKey::SavedSince(date) => {
+                ctx.write_all(b"SAVEDSINCE ")?;
+                date.encode_ctx(ctx)
+            }
             SearchKey::SequenceSet(sequence_set) => sequence_set.encode_ctx(ctx),
             SearchKey::And(search_keys) => {
                 ctx.write_all(b"(")?;
@@ -937,6 +1480,8 @@ impl EncodeIntoContext for Sequence {
                 ctx.write_all(b":")?;
                 to.encode_ctx(ctx)
             }
+            #[cfg(feature = "ext_searchres")]
+            Sequence::SavedResult => ctx.write_all(b"$"),
         }
     }
 }
@@ -1045,6 +1590,22 @@ impl<'a> EncodeIntoContext for MessageDataItemName<'a> {
                 join_serializable(section, b".", ctx)?;
                 ctx.write_all(b"]")
             }
+            #[cfg(feature = "ext_condstore_qresync")]
+            MessageDataItemName::ModSeq => ctx.write_all(b"MODSEQ"),
+            #[cfg(feature = "ext_objectid")]
+            MessageDataItemName::EmailId => ctx.write_all(b"EMAILID"),
+            #[cfg(feature = "ext_objectid")]
+            MessageDataItemName::ThreadId => ctx.write_all(b"THREADID"),
+            #[cfg(feature = "ext_savedate")]
+            MessageDataItemName::SaveDate => ctx.write_all(b"SAVEDATE"),
+            #[cfg(feature = "ext_preview")]
+            MessageDataItemName::Preview { lazy } => {
+                if *lazy {
+                    ctx.write_all(b"PREVIEW (LAZY)")
+                } else {
+                    ctx.write_all(b"PREVIEW")
+                }
+            }
         }
     }
 }
@@ -1240,9 +1801,9 @@ impl<'a> EncodeIntoContext for Code<'a> {
             }
             // RFC 2221
             #[cfg(any(feature = "ext_login_referrals", feature = "ext_mailbox_referrals"))]
-            Code::Referral(url) => {
+            Code::Referral(urls) => {
                 ctx.write_all(b"REFERRAL ")?;
-                ctx.write_all(url.as_bytes())
+                join_serializable(urls.as_ref(), b" ", ctx)
             }
             Code::CompressionActive => ctx.write_all(b"COMPRESSIONACTIVE"),
             Code::OverQuota => ctx.write_all(b"OVERQUOTA"),
@@ -1254,7 +1815,54 @@ impl<'a> EncodeIntoContext for Code<'a> {
             }
             #[cfg(feature = "ext_binary")]
             Code::UnknownCte => ctx.write_all(b"UNKNOWN-CTE"),
+            #[cfg(feature = "ext_condstore_qresync")]
+            Code::HighestModSeq(modseq) => write!(ctx, "HIGHESTMODSEQ {modseq}"),
+            #[cfg(feature = "ext_condstore_qresync")]
+            Code::NoModSeq => ctx.write_all(b"NOMODSEQ"),
+            #[cfg(feature = "ext_condstore_qresync")]
+            Code::Modified(sequence_set) => {
+                ctx.write_all(b"MODIFIED ")?;
+                sequence_set.encode_ctx(ctx)
+            }
+            #[cfg(feature = "ext_uidplus")]
+            Code::AppendUid { uid_validity, uids } => {
+                ctx.write_all(b"APPENDUID ")?;
+                uid_validity.encode_ctx(ctx)?;
+                ctx.write_all(b" ")?;
+                uids.encode_ctx(ctx)
+            }
+            #[cfg(feature = "ext_uidplus")]
+            Code::CopyUid {
+                uid_validity,
+                source,
+                destination,
+            } => {
+                ctx.write_all(b"COPYUID ")?;
+                uid_validity.encode_ctx(ctx)?;
+                ctx.write_all(b" ")?;
+                source.encode_ctx(ctx)?;
+                ctx.write_all(b" ")?;
+                destination.encode_ctx(ctx)
+            }
+            #[cfg(feature = "ext_uidplus")]
+            Code::UidNotSticky => ctx.write_all(b"UIDNOTSTICKY"),
+            #[cfg(feature = "ext_objectid")]
+            Code::MailboxId(id) => {
+                ctx.write_all(b"MAILBOXID (")?;
+                id.encode_ctx(ctx)?;
+                ctx.write_all(b")")
+            }
             Code::Other(unknown) => unknown.encode_ctx(ctx),
+            Code::OtherParsed { name, value } => {
+                name.encode_ctx(ctx)?;
+
+                if let Some(value) = value {
+                    ctx.write_all(b" ")?;
+                    ctx.write_all(value.inner().as_bytes())?;
+                }
+
+                Ok(())
+            }
         }
     }
 }
@@ -1331,6 +1939,20 @@ impl<'a> EncodeIntoContext for Data<'a> {
                     join_serializable(seqs, b" ", ctx)?;
                 }
             }
+            #[cfg(feature = "ext_esearch")]
+            Data::ESearch { tag, uid, returns } => {
+                ctx.write_all(b"* ESEARCH")?;
+                if let Some(tag) = tag {
+                    write!(ctx, " (TAG \"{}\")", tag.as_ref())?;
+                }
+                if *uid {
+                    ctx.write_all(b" UID")?;
+                }
+                for data in returns {
+                    ctx.write_all(b" ")?;
+                    data.encode_ctx(ctx)?;
+                }
+            }
             #[cfg(feature = "ext_sort_thread")]
             Data::Sort(seqs) => {
                 if seqs.is_empty() {
@@ -1424,6 +2046,19 @@ impl<'a> EncodeIntoContext for Data<'a> {
                     }
                 }
             }
+            #[cfg(feature = "ext_namespace")]
+            Data::Namespace {
+                personal,
+                other,
+                shared,
+            } => {
+                ctx.write_all(b"* NAMESPACE ")?;
+                List1OrNil(personal, b"").encode_ctx(ctx)?;
+                ctx.write_all(b" ")?;
+                List1OrNil(other, b"").encode_ctx(ctx)?;
+                ctx.write_all(b" ")?;
+                List1OrNil(shared, b"").encode_ctx(ctx)?;
+            }
             #[cfg(feature = "ext_metadata")]
             Data::Metadata { mailbox, items } => {
                 ctx.write_all(b"* METADATA ")?;
@@ -1431,6 +2066,40 @@ impl<'a> EncodeIntoContext for Data<'a> {
                 ctx.write_all(b" ")?;
                 items.encode_ctx(ctx)?;
             }
+            #[cfg(feature = "ext_acl")]
+            Data::Acl { mailbox, entries } => {
+                ctx.write_all(b"* ACL ")?;
+                mailbox.encode_ctx(ctx)?;
+                for entry in entries {
+                    ctx.write_all(b" ")?;
+                    entry.encode_ctx(ctx)?;
+                }
+            }
+            #[cfg(feature = "ext_acl")]
+            Data::ListRights {
+                mailbox,
+                identifier,
+                required,
+                optional,
+            } => {
+                ctx.write_all(b"* LISTRIGHTS ")?;
+                mailbox.encode_ctx(ctx)?;
+                ctx.write_all(b" ")?;
+                identifier.encode_ctx(ctx)?;
+                ctx.write_all(b" ")?;
+                required.encode_ctx(ctx)?;
+                for rights in optional {
+                    ctx.write_all(b" ")?;
+                    rights.encode_ctx(ctx)?;
+                }
+            }
+            #[cfg(feature = "ext_acl")]
+            Data::MyRights { mailbox, rights } => {
+                ctx.write_all(b"* MYRIGHTS ")?;
+                mailbox.encode_ctx(ctx)?;
+                ctx.write_all(b" ")?;
+                rights.encode_ctx(ctx)?;
+            }
         }
 
         ctx.write_all(b"\r\n")
@@ -1484,6 +2153,27 @@ impl EncodeIntoContext for StatusDataItem {
                 ctx.write_all(b"DELETED-STORAGE ")?;
                 count.encode_ctx(ctx)
             }
+            #[cfg(feature = "ext_condstore_qresync")]
+            Self::HighestModSeq(value) => write!(ctx, "HIGHESTMODSEQ {value}"),
+            #[cfg(feature = "ext_objectid")]
+            Self::MailboxId(id) => {
+                ctx.write_all(b"MAILBOXID (")?;
+                id.encode_ctx(ctx)?;
+                ctx.write_all(b")")
+            }
+            #[cfg(feature = "ext_status_size")]
+            Self::Size(size) => {
+                ctx.write_all(b"SIZE ")?;
+                size.encode_ctx(ctx)
+            }
+            #[cfg(feature = "ext_status_size")]
+            Self::AppendLimit(limit) => {
+                ctx.write_all(b"APPENDLIMIT ")?;
+                match limit {
+                    Some(limit) => limit.encode_ctx(ctx),
+                    None => ctx.write_all(b"NIL"),
+                }
+            }
         }
     }
 }
@@ -1557,6 +2247,34 @@ impl<'a> EncodeIntoContext for MessageDataItem<'a> {
                 ctx.write_all(b"] ")?;
                 size.encode_ctx(ctx)
             }
+            #[cfg(feature = "ext_condstore_qresync")]
+            Self::ModSeq(modseq) => write!(ctx, "MODSEQ ({modseq})"),
+            #[cfg(feature = "ext_objectid")]
+            Self::EmailId(id) => {
+                ctx.write_all(b"EMAILID (")?;
+                id.encode_ctx(ctx)?;
+                ctx.write_all(b")")
+            }
+            #[cfg(feature = "ext_objectid")]
+            Self::ThreadId(Some(id)) => {
+                ctx.write_all(b"THREADID (")?;
+                id.encode_ctx(ctx)?;
+                ctx.write_all(b")")
+            }
+            #[cfg(feature = "ext_objectid")]
+            Self::ThreadId(None) => ctx.write_all(b"THREADID NIL"),
+            #[cfg(feature = "ext_savedate")]
+            Self::SaveDate(Some(datetime)) => {
+                ctx.write_all(b"SAVEDATE ")?;
+                datetime.encode_ctx(ctx)
+            }
+            #[cfg(feature = "ext_savedate")]
+            Self::SaveDate(None) => ctx.write_all(b"SAVEDATE NIL"),
+            #[cfg(feature = "ext_preview")]
+            Self::Preview(nstring) => {
+                ctx.write_all(b"PREVIEW ")?;
+                nstring.encode_ctx(ctx)
+            }
         }
     }
 }
@@ -1826,7 +2544,7 @@ impl<'a> EncodeIntoContext for CommandContinuationRequest<'a> {
             },
             Self::Base64(data) => {
                 ctx.write_all(b"+ ")?;
-                ctx.write_all(base64.encode(data).as_bytes())?;
+                write_base64(data, ctx)?;
                 ctx.write_all(b"\r\n")
             }
         }
@@ -1836,12 +2554,26 @@ impl<'a> EncodeIntoContext for CommandContinuationRequest<'a> {
 pub(crate) mod utils {
     use std::io::Write;
 
-    use super::{EncodeContext, EncodeIntoContext};
+    use base64::write::EncoderWriter;
+
+    use super::{base64, EncodeContext, EncodeIntoContext};
 
     pub struct List1OrNil<'a, T>(pub &'a Vec<T>, pub &'a [u8]);
 
     pub struct List1AttributeValueOrNil<'a, T>(pub &'a Vec<(T, T)>);
 
+    /// Write `data` base64-encoded into `ctx`.
+    ///
+    /// The data is encoded in chunks, so no intermediate string is allocated for large payloads
+    /// such as GSSAPI tokens.
+    pub(crate) fn write_base64(data: &[u8], ctx: &mut EncodeContext) -> std::io::Result<()> {
+        let mut writer = EncoderWriter::new(ctx, &base64);
+        writer.write_all(data)?;
+        writer.finish()?;
+
+        Ok(())
+    }
+
     pub(crate) fn join_serializable<I: EncodeIntoContext>(
         elements: &[I],
         sep: &[u8],
@@ -1924,6 +2656,58 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_to_imap_string() {
+        let cmd = Command::new("A", CommandBody::login("alice", "pass").unwrap()).unwrap();
+        assert_eq!(cmd.to_imap_string(), "A LOGIN alice pass\r\n");
+
+        // Literals are inlined.
+        let cmd = Command::new("A", CommandBody::login("alice", "pa\"ss\n").unwrap()).unwrap();
+        assert_eq!(cmd.to_imap_string(), "A LOGIN alice {6}\r\npa\"ss\n\r\n");
+
+        // Non-UTF-8 data is replaced.
+        let rsp = Response::Data(Data::Fetch {
+            seq: NonZeroU32::new(1).unwrap(),
+            items: Vec1::from(MessageDataItem::BodyExt {
+                section: None,
+                origin: None,
+                data: NString(Some(Literal::try_from(b"\xff".as_ref()).unwrap().into())),
+            }),
+        });
+        assert_eq!(
+            rsp.to_imap_string(),
+            "* 1 FETCH (BODY[] {1}\r\n\u{fffd})\r\n"
+        );
+    }
+
+    #[test]
+    fn test_encode_large_base64() {
+        use base64::Engine;
+
+        // Large enough to need many chunks.
+        let token: Vec<u8> = (0..=255u8).cycle().take(1024 * 1024 + 7).collect();
+        let expected = base64.encode(&token);
+
+        let got = AuthenticateDataCodec::default()
+            .encode(&AuthenticateData::r#continue(token.clone()))
+            .dump();
+        assert_eq!(got, format!("{expected}\r\n").into_bytes());
+
+        let command = Command::new(
+            "A",
+            CommandBody::authenticate_with_ir(
+                AuthMechanism::try_from("GSSAPI").unwrap(),
+                token.as_slice(),
+            ),
+        )
+        .unwrap();
+        let got = CommandCodec::default().encode(&command).dump();
+        assert_eq!(
+            got,
+            format!("A AUTHENTICATE GSSAPI {expected}\r\n").into_bytes()
+        );
+    }
+
     #[test]
     fn test_api_encoder_usage() {
         let cmd = Command::new(
@@ -2089,6 +2873,250 @@ mod tests {
         ])
     }
 
+    #[test]
+    fn test_split_literals() {
+        let cmd = Command::new(
+            "A",
+            CommandBody::login(
+                Literal::try_from(b"alice".as_ref()).unwrap(),
+                Literal::try_from(b"pass".as_ref()).unwrap().into_non_sync(),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+        let (lines, literals) = CommandCodec::default().encode(&cmd).split_literals();
+
+        assert_eq!(
+            lines,
+            vec![
+                b"A LOGIN {5}\r\n".to_vec(),
+                b" {4+}\r\n".to_vec(),
+                b"\r\n".to_vec(),
+            ]
+        );
+        assert_eq!(
+            literals,
+            vec![
+                (0, b"alice".to_vec(), LiteralMode::Sync),
+                (1, b"pass".to_vec(), LiteralMode::NonSync),
+            ]
+        );
+
+        let (lines, literals) = CommandCodec::default()
+            .encode(&Command::new("A", CommandBody::Noop).unwrap())
+            .split_literals();
+        assert_eq!(lines, vec![b"A NOOP\r\n".to_vec()]);
+        assert!(literals.is_empty());
+    }
+
+    #[test]
+    fn test_encoded_len() {
+        let commands = [
+            Command::new("A", CommandBody::Noop).unwrap(),
+            Command::new(
+                "A",
+                CommandBody::login(
+                    Literal::try_from(b"alice".as_ref()).unwrap(),
+                    Literal::try_from(b"pass".as_ref()).unwrap().into_non_sync(),
+                )
+                .unwrap(),
+            )
+            .unwrap(),
+            Command::new(
+                "A",
+                CommandBody::authenticate_with_ir(
+                    AuthMechanism::Plain,
+                    b"\x00alice\x00pass".as_ref(),
+                ),
+            )
+            .unwrap(),
+            Command::new(
+                "A",
+                CommandBody::append("INBOX", vec![], None, vec![b'x'; 10_000]).unwrap(),
+            )
+            .unwrap(),
+        ];
+
+        for command in commands {
+            let expected = CommandCodec::default().encode(&command).dump().len();
+            assert_eq!(CommandCodec::default().encoded_len(&command), expected);
+        }
+
+        let responses = [
+            Response::Data(Data::Exists(42)),
+            Response::Data(Data::Fetch {
+                seq: NonZeroU32::new(1).unwrap(),
+                items: Vec1::from(MessageDataItem::BodyExt {
+                    section: None,
+                    origin: None,
+                    data: NString::from(Literal::unvalidated(b"ABCDE".as_ref())),
+                }),
+            }),
+        ];
+
+        for response in responses {
+            let expected = ResponseCodec::default().encode(&response).dump().len();
+            assert_eq!(ResponseCodec::default().encoded_len(&response), expected);
+        }
+
+        assert_eq!(
+            AuthenticateDataCodec::default()
+                .encoded_len(&AuthenticateData::r#continue(b"alice".as_ref())),
+            b"YWxpY2U=\r\n".len()
+        );
+    }
+
+    #[test]
+    fn test_encoded_many_fragments() {
+        let rsp = Response::Data(Data::Fetch {
+            seq: NonZeroU32::new(1).unwrap(),
+            items: Vec1::try_from(
+                (0..500)
+                    .map(|_| MessageDataItem::BodyExt {
+                        section: None,
+                        origin: None,
+                        data: NString::from(Literal::unvalidated(b"ABCDE".as_ref())),
+                    })
+                    .collect::<Vec<_>>(),
+            )
+            .unwrap(),
+        });
+
+        let mut encoded = ResponseCodec::default().encode(&rsp);
+        // One line before every literal, the literals, and the final line.
+        assert_eq!(encoded.len(), 1001);
+
+        // Borrowed iteration doesn't consume the fragments ...
+        let borrowed: Vec<Fragment> = encoded.iter().cloned().collect();
+        assert_eq!(borrowed.len(), 1001);
+        assert_eq!((&encoded).into_iter().count(), 1001);
+
+        // ... and yields the same fragments as consuming iteration.
+        assert_eq!(encoded.next(), Some(borrowed[0].clone()));
+        assert_eq!(encoded.len(), 1000);
+        assert_eq!(encoded.iter().next(), Some(&borrowed[1]));
+
+        let mut expected = Vec::new();
+        for fragment in &borrowed[1..] {
+            match fragment {
+                Fragment::Line { data } | Fragment::Literal { data, .. } => {
+                    expected.extend_from_slice(data)
+                }
+            }
+        }
+        assert_eq!(encoded.clone().dump(), expected);
+        assert_eq!(encoded.collect::<Vec<_>>(), &borrowed[1..]);
+    }
+
+    #[test]
+    fn test_encode_with_line_ending() {
+        let cmd = Command::new("A", CommandBody::Noop).unwrap();
+
+        let got = CommandCodec::default()
+            .encode_with(&cmd, LineEnding::Lf)
+            .dump();
+        assert_eq!(got, b"A NOOP\n");
+
+        let got = CommandCodec::default()
+            .encode_with(&cmd, LineEnding::CrLf)
+            .dump();
+        assert_eq!(got, b"A NOOP\r\n");
+
+        let cmd =
+            Command::new("A", CommandBody::login("alice", b"\r\n".as_ref()).unwrap()).unwrap();
+
+        let got = CommandCodec::default()
+            .encode_with(&cmd, LineEnding::Lf)
+            .dump();
+        assert_eq!(got, b"A LOGIN alice {2}\n\r\n\n");
+    }
+
+    #[test]
+    fn test_encode_to() {
+        let cmd = Command::new(
+            "A",
+            CommandBody::login(
+                Literal::try_from(b"alice".as_ref()).unwrap(),
+                Literal::try_from(b"pass".as_ref()).unwrap().into_non_sync(),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+        let mut out = Vec::new();
+        let mut boundaries = Vec::new();
+
+        CommandCodec::default()
+            .encode_to(&cmd, &mut out, |writer, mode| {
+                // Everything up to the literal is already written.
+                boundaries.push((writer.len(), mode));
+                Ok(())
+            })
+            .unwrap();
+
+        assert_eq!(out, b"A LOGIN {5}\r\nalice {4+}\r\npass\r\n");
+        assert_eq!(out, CommandCodec::default().encode(&cmd).dump());
+        assert_eq!(
+            boundaries,
+            vec![(13, LiteralMode::Sync), (25, LiteralMode::NonSync)]
+        );
+
+        // Errors of the callback are propagated.
+        let mut out = Vec::new();
+        let got = CommandCodec::default().encode_to(&cmd, &mut out, |_, _| {
+            Err(io::Error::new(
+                io::ErrorKind::Other,
+                "no continuation request",
+            ))
+        });
+        assert!(got.is_err());
+        assert_eq!(out, b"A LOGIN {5}\r\n");
+
+        // Without literals, the callback is never called.
+        let rsp = Response::Data(Data::Exists(42));
+        let mut out = Vec::new();
+        ResponseCodec::default()
+            .encode_to(&rsp, &mut out, |_, _| unreachable!())
+            .unwrap();
+        assert_eq!(out, b"* 42 EXISTS\r\n");
+    }
+
+    #[test]
+    fn test_encode_through_reference() {
+        fn dump_all<'a, E>(encoder: E, messages: &[E::Message<'a>]) -> Vec<u8>
+        where
+            E: Encoder,
+        {
+            messages
+                .iter()
+                .flat_map(|message| encoder.encode(message).dump())
+                .collect()
+        }
+
+        let codec = CommandCodec::default();
+        let commands = vec![
+            Command::new("A", CommandBody::Noop).unwrap(),
+            Command::new("B", CommandBody::login("alice", "\r\n").unwrap()).unwrap(),
+        ];
+
+        // Encoder by value and by reference ...
+        let expected = b"A NOOP\r\nB LOGIN alice {2}\r\n\r\n\r\n".to_vec();
+        assert_eq!(dump_all(CommandCodec::default(), &commands), expected);
+        assert_eq!(dump_all(&codec, &commands), expected);
+        assert_eq!(dump_all(&&codec, &commands), expected);
+
+        // ... and message by reference.
+        let references: Vec<&Command> = commands.iter().collect();
+        for (command, reference) in commands.iter().zip(&references) {
+            assert_eq!(codec.encode(reference).dump(), codec.encode(command).dump());
+
+            let mut out = Vec::new();
+            codec.encode_to(reference, &mut out, |_, _| Ok(())).unwrap();
+            assert_eq!(out, codec.encode(command).dump());
+        }
+    }
+
     fn kat_encoder<'a, E, M, F>(tests: &'a [(M, F)])
     where
         E: Encoder<Message<'a> = M> + Default,
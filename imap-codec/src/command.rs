@@ -5,37 +5,60 @@ use abnf_core::streaming::crlf;
 #[cfg(feature = "quirk_crlf_relaxed")]
 use abnf_core::streaming::crlf_relaxed as crlf;
 use abnf_core::streaming::sp;
-#[cfg(feature = "ext_binary")]
+#[cfg(all(feature = "ext_binary", not(feature = "ext_multiappend")))]
 use imap_types::extensions::binary::LiteralOrLiteral8;
+#[cfg(feature = "ext_list_extended")]
+use imap_types::extensions::list_extended::MailboxPatterns;
 use imap_types::{
     auth::AuthMechanism,
     command::{Command, CommandBody},
-    core::AString,
+    core::{AString, Atom, Tag},
     fetch::{Macro, MacroOrMessageDataItemNames},
     flag::{Flag, StoreResponse, StoreType},
     secret::Secret,
+    utils::indicators::is_text_char,
 };
+#[cfg(feature = "ext_multiappend")]
+use imap_types::{core::Vec2, extensions::multiappend::AppendMessage};
+#[cfg(feature = "ext_multiappend")]
+use nom::multi::many1;
 use nom::{
     branch::alt,
-    bytes::streaming::{tag, tag_no_case},
+    bytes::streaming::{tag, tag_no_case, take_while},
     combinator::{map, opt, value},
+    error::ErrorKind,
     multi::{separated_list0, separated_list1},
     sequence::{delimited, preceded, terminated, tuple},
 };
 
-#[cfg(feature = "ext_binary")]
+#[cfg(feature = "ext_acl")]
+use crate::extensions::acl::acl;
+#[cfg(all(feature = "ext_binary", not(feature = "ext_multiappend")))]
 use crate::extensions::binary::literal8;
+#[cfg(feature = "ext_condstore_qresync")]
+use crate::extensions::condstore_qresync::{fetch_modifiers, store_modifiers};
 #[cfg(feature = "ext_id")]
 use crate::extensions::id::id;
+#[cfg(feature = "ext_list_extended")]
+use crate::extensions::list_extended::{list_return_opts, list_select_opts, mbox_or_pat};
 #[cfg(feature = "ext_metadata")]
 use crate::extensions::metadata::{getmetadata, setmetadata};
+#[cfg(feature = "ext_multiappend")]
+use crate::extensions::multiappend::append_message;
+#[cfg(feature = "ext_namespace")]
+use crate::extensions::namespace::namespace;
+#[cfg(feature = "ext_special_use")]
+use crate::extensions::special_use::create_params;
+#[cfg(feature = "ext_unauthenticate")]
+use crate::extensions::unauthenticate::unauthenticate;
+#[cfg(all(feature = "ext_utf8", not(feature = "ext_multiappend")))]
+use crate::extensions::utf8::append_data_utf8;
 #[cfg(feature = "ext_sort_thread")]
 use crate::extensions::{sort::sort, thread::thread};
 use crate::{
     auth::auth_type,
-    core::{astring, base64, literal, tag_imap},
-    datetime::date_time,
-    decode::{IMAPErrorKind, IMAPResult},
+    core::{astring, atom, base64, tag_imap},
+    decode::{IMAPErrorKind, IMAPParseError, IMAPResult},
     extensions::{
         compress::compress,
         enable::enable,
@@ -50,6 +73,8 @@ use crate::{
     sequence::sequence_set,
     status::status_att,
 };
+#[cfg(not(feature = "ext_multiappend"))]
+use crate::{core::literal, datetime::date_time};
 
 /// `command = tag SP (
 ///                     command-any /
@@ -57,38 +82,91 @@ use crate::{
 ///                     command-nonauth /
 ///                     command-select
 ///                   ) CRLF`
-pub(crate) fn command(input: &[u8]) -> IMAPResult<&[u8], Command> {
-    let mut parser_tag = terminated(tag_imap, sp);
-    let mut parser_body = terminated(
-        alt((command_any, command_auth, command_nonauth, command_select)),
-        crlf,
-    );
+pub(crate) fn command(
+    max_sequence_set_len: usize,
+) -> impl Fn(&[u8]) -> IMAPResult<'_, &[u8], Command<'_>> {
+    move |input: &[u8]| {
+        let mut parser_tag = terminated(tag_imap, sp);
+        let mut parser_body = terminated(
+            alt((
+                command_any,
+                command_auth,
+                command_nonauth,
+                command_select(max_sequence_set_len),
+            )),
+            crlf,
+        );
 
-    let (remaining, obtained_tag) = parser_tag(input)?;
+        let (remaining, obtained_tag) = parser_tag(input)?;
 
-    match parser_body(remaining) {
-        Ok((remaining, body)) => Ok((
-            remaining,
-            Command {
-                tag: obtained_tag,
-                body,
-            },
-        )),
-        Err(mut error) => {
-            // If we got an `IMAPErrorKind::Literal`, we fill in the missing `tag`.
-            if let nom::Err::Error(ref mut err) | nom::Err::Failure(ref mut err) = error {
-                if let IMAPErrorKind::Literal { ref mut tag, .. } = err.kind {
-                    *tag = Some(obtained_tag);
+        match parser_body(remaining) {
+            Ok((remaining, body)) => Ok((
+                remaining,
+                Command {
+                    tag: obtained_tag,
+                    body,
+                },
+            )),
+            Err(mut error) => {
+                // If we got an `IMAPErrorKind::Literal`, we fill in the missing `tag`.
+                if let nom::Err::Error(ref mut err) | nom::Err::Failure(ref mut err) = error {
+                    if let IMAPErrorKind::Literal { ref mut tag, .. } = err.kind {
+                        *tag = Some(obtained_tag);
+                    }
                 }
-            }
 
-            Err(error)
+                Err(error)
+            }
         }
     }
 }
 
+/// `tag SP atom *TEXT-CHAR CRLF`
+///
+/// Recognizes a well-formed command line whose command name is not supported by [`command`].
+///
+/// Note: This is used to distinguish unknown commands from malformed commands.
+pub(crate) fn command_unknown(input: &[u8]) -> IMAPResult<'_, &[u8], (Tag<'_>, Atom<'_>)> {
+    let mut parser = tuple((tag_imap, sp, atom, take_while(is_text_char), crlf));
+
+    let (remaining, (tag, _, name, _, _)) = parser(input)?;
+
+    if is_command_name(name.as_ref()) {
+        return Err(nom::Err::Error(IMAPParseError {
+            input,
+            kind: IMAPErrorKind::Nom(ErrorKind::Verify),
+        }));
+    }
+
+    Ok((remaining, (tag, name)))
+}
+
+/// Returns `true` when `name` is the name of a command supported by [`command`].
+///
+/// Note: Every `command_*` parser keeps its names right next to its alternatives.
+fn is_command_name(name: &str) -> bool {
+    [
+        COMMAND_ANY_NAMES,
+        COMMAND_AUTH_NAMES,
+        COMMAND_NONAUTH_NAMES,
+        COMMAND_SELECT_NAMES,
+    ]
+    .iter()
+    .flat_map(|names| names.iter())
+    .any(|known| known.eq_ignore_ascii_case(name))
+}
+
 // # Command Any
 
+/// Names of the commands recognized by [`command_any`] (see [`is_command_name`]).
+const COMMAND_ANY_NAMES: &[&str] = &[
+    "CAPABILITY",
+    "LOGOUT",
+    "NOOP",
+    #[cfg(feature = "ext_id")]
+    "ID",
+];
+
 /// ```abnf
 /// command-any = "CAPABILITY" /
 ///               "LOGOUT" /
@@ -98,7 +176,7 @@ pub(crate) fn command(input: &[u8]) -> IMAPResult<&[u8], Command> {
 /// ```
 ///
 /// Note: Valid in all states
-pub(crate) fn command_any(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
+pub(crate) fn command_any(input: &[u8]) -> IMAPResult<'_, &[u8], CommandBody<'_>> {
     alt((
         value(CommandBody::Capability, tag_no_case(b"CAPABILITY")),
         value(CommandBody::Logout, tag_no_case(b"LOGOUT")),
@@ -111,6 +189,45 @@ pub(crate) fn command_any(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
 
 // # Command Auth
 
+/// Names of the commands recognized by [`command_auth`] (see [`is_command_name`]).
+const COMMAND_AUTH_NAMES: &[&str] = &[
+    "APPEND",
+    "CREATE",
+    "DELETE",
+    "EXAMINE",
+    "LIST",
+    "LSUB",
+    "RENAME",
+    "SELECT",
+    "STATUS",
+    "SUBSCRIBE",
+    "UNSUBSCRIBE",
+    "IDLE",
+    "ENABLE",
+    "COMPRESS",
+    "GETQUOTA",
+    "GETQUOTAROOT",
+    "SETQUOTA",
+    #[cfg(feature = "ext_metadata")]
+    "SETMETADATA",
+    #[cfg(feature = "ext_metadata")]
+    "GETMETADATA",
+    #[cfg(feature = "ext_namespace")]
+    "NAMESPACE",
+    #[cfg(feature = "ext_acl")]
+    "SETACL",
+    #[cfg(feature = "ext_acl")]
+    "DELETEACL",
+    #[cfg(feature = "ext_acl")]
+    "GETACL",
+    #[cfg(feature = "ext_acl")]
+    "LISTRIGHTS",
+    #[cfg(feature = "ext_acl")]
+    "MYRIGHTS",
+    #[cfg(feature = "ext_unauthenticate")]
+    "UNAUTHENTICATE",
+];
+
 /// ```abnf
 /// command-auth = append /
 ///                create /
@@ -130,11 +247,18 @@ pub(crate) fn command_any(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
 ///                getquotaroot / ; RFC 9208
 ///                setquota /     ; RFC 9208
 ///                setmetadata /  ; RFC 5464
-///                getmetadata    ; RFC 5464
+///                getmetadata /  ; RFC 5464
+///                namespace /    ; RFC 2342
+///                setacl /       ; RFC 4314
+///                deleteacl /    ; RFC 4314
+///                getacl /       ; RFC 4314
+///                listrights /   ; RFC 4314
+///                myrights /     ; RFC 4314
+///                unauthenticate ; RFC 8437
 /// ```
 ///
 /// Note: Valid only in Authenticated or Selected state
-pub(crate) fn command_auth(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
+pub(crate) fn command_auth(input: &[u8]) -> IMAPResult<'_, &[u8], CommandBody<'_>> {
     alt((
         append,
         create,
@@ -150,18 +274,23 @@ pub(crate) fn command_auth(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
         idle,
         enable,
         compress,
-        getquota,
-        getquotaroot,
-        setquota,
+        alt((getquota, getquotaroot, setquota)),
         #[cfg(feature = "ext_metadata")]
         setmetadata,
         #[cfg(feature = "ext_metadata")]
         getmetadata,
+        #[cfg(feature = "ext_namespace")]
+        namespace,
+        #[cfg(feature = "ext_acl")]
+        acl,
+        #[cfg(feature = "ext_unauthenticate")]
+        unauthenticate,
     ))(input)
 }
 
 /// `append = "APPEND" SP mailbox [SP flag-list] [SP date-time] SP literal`
-pub(crate) fn append(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
+#[cfg(not(feature = "ext_multiappend"))]
+pub(crate) fn append(input: &[u8]) -> IMAPResult<'_, &[u8], CommandBody<'_>> {
     let mut parser = tuple((
         tag_no_case(b"APPEND"),
         sp,
@@ -175,6 +304,8 @@ pub(crate) fn append(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
         alt((
             map(literal, LiteralOrLiteral8::Literal),
             map(literal8, LiteralOrLiteral8::Literal8),
+            #[cfg(feature = "ext_utf8")]
+            map(append_data_utf8, LiteralOrLiteral8::Utf8),
         )),
     ));
 
@@ -191,21 +322,71 @@ pub(crate) fn append(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
     ))
 }
 
-/// `create = "CREATE" SP mailbox`
+/// ```abnf
+/// append = "APPEND" SP mailbox 1*append-message ; RFC 3502
+/// ```
+///
+/// Note: A single message is returned as [`CommandBody::Append`].
+#[cfg(feature = "ext_multiappend")]
+pub(crate) fn append(input: &[u8]) -> IMAPResult<'_, &[u8], CommandBody<'_>> {
+    let mut parser = tuple((tag_no_case(b"APPEND"), sp, mailbox, many1(append_message)));
+
+    let (remaining, (_, _, mailbox, mut messages)) = parser(input)?;
+
+    let body = if messages.len() == 1 {
+        // Unwrap: Safe because we checked the length above.
+        let AppendMessage {
+            flags,
+            date,
+            message,
+        } = messages.pop().unwrap();
+
+        CommandBody::Append {
+            mailbox,
+            flags,
+            date,
+            message,
+        }
+    } else {
+        CommandBody::MultiAppend {
+            mailbox,
+            // Unwrap: Safe because we use `many1` above and checked the length.
+            messages: Vec2::try_from(messages).unwrap(),
+        }
+    };
+
+    Ok((remaining, body))
+}
+
+/// ```abnf
+/// create = "CREATE" SP mailbox
+///          [create-params] ; RFC 6154
+/// ```
 ///
 /// Note: Use of INBOX gives a NO error
-pub(crate) fn create(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
+pub(crate) fn create(input: &[u8]) -> IMAPResult<'_, &[u8], CommandBody<'_>> {
     let mut parser = tuple((tag_no_case(b"CREATE"), sp, mailbox));
 
     let (remaining, (_, _, mailbox)) = parser(input)?;
 
+    #[cfg(feature = "ext_special_use")]
+    if let (remaining, Some(use_attributes)) = opt(create_params)(remaining)? {
+        return Ok((
+            remaining,
+            CommandBody::CreateSpecialUse {
+                mailbox,
+                use_attributes,
+            },
+        ));
+    }
+
     Ok((remaining, CommandBody::Create { mailbox }))
 }
 
 /// `delete = "DELETE" SP mailbox`
 ///
 /// Note: Use of INBOX gives a NO error
-pub(crate) fn delete(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
+pub(crate) fn delete(input: &[u8]) -> IMAPResult<'_, &[u8], CommandBody<'_>> {
     let mut parser = tuple((tag_no_case(b"DELETE"), sp, mailbox));
 
     let (remaining, (_, _, mailbox)) = parser(input)?;
@@ -214,7 +395,7 @@ pub(crate) fn delete(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
 }
 
 /// `examine = "EXAMINE" SP mailbox`
-pub(crate) fn examine(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
+pub(crate) fn examine(input: &[u8]) -> IMAPResult<'_, &[u8], CommandBody<'_>> {
     let mut parser = tuple((tag_no_case(b"EXAMINE"), sp, mailbox));
 
     let (remaining, (_, _, mailbox)) = parser(input)?;
@@ -223,7 +404,8 @@ pub(crate) fn examine(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
 }
 
 /// `list = "LIST" SP mailbox SP list-mailbox`
-pub(crate) fn list(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
+#[cfg(not(feature = "ext_list_extended"))]
+pub(crate) fn list(input: &[u8]) -> IMAPResult<'_, &[u8], CommandBody<'_>> {
     let mut parser = tuple((tag_no_case(b"LIST"), sp, mailbox, sp, list_mailbox));
 
     let (remaining, (_, _, reference, _, mailbox_wildcard)) = parser(input)?;
@@ -237,8 +419,55 @@ pub(crate) fn list(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
     ))
 }
 
+/// ```abnf
+/// list = "LIST" [SP list-select-opts] SP mailbox SP mbox-or-pat [SP list-return-opts]
+/// ```
+///
+/// Note: Extended by RFC 5258. A LIST command without extended syntax is returned as
+/// [`CommandBody::List`].
+#[cfg(feature = "ext_list_extended")]
+pub(crate) fn list(input: &[u8]) -> IMAPResult<'_, &[u8], CommandBody<'_>> {
+    let mut parser = tuple((
+        tag_no_case(b"LIST"),
+        map(
+            opt(preceded(sp, list_select_opts)),
+            Option::unwrap_or_default,
+        ),
+        sp,
+        mailbox,
+        sp,
+        mbox_or_pat,
+        map(
+            opt(preceded(sp, list_return_opts)),
+            Option::unwrap_or_default,
+        ),
+    ));
+
+    let (remaining, (_, selection_options, _, reference, _, mailbox_patterns, return_options)) =
+        parser(input)?;
+
+    let body = match mailbox_patterns {
+        MailboxPatterns::Single(mailbox_wildcard)
+            if selection_options.is_empty() && return_options.is_empty() =>
+        {
+            CommandBody::List {
+                reference,
+                mailbox_wildcard,
+            }
+        }
+        mailbox_patterns => CommandBody::ListExtended {
+            selection_options,
+            reference,
+            mailbox_patterns,
+            return_options,
+        },
+    };
+
+    Ok((remaining, body))
+}
+
 /// `lsub = "LSUB" SP mailbox SP list-mailbox`
-pub(crate) fn lsub(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
+pub(crate) fn lsub(input: &[u8]) -> IMAPResult<'_, &[u8], CommandBody<'_>> {
     let mut parser = tuple((tag_no_case(b"LSUB"), sp, mailbox, sp, list_mailbox));
 
     let (remaining, (_, _, reference, _, mailbox_wildcard)) = parser(input)?;
@@ -255,7 +484,7 @@ pub(crate) fn lsub(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
 /// `rename = "RENAME" SP mailbox SP mailbox`
 ///
 /// Note: Use of INBOX as a destination gives a NO error
-pub(crate) fn rename(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
+pub(crate) fn rename(input: &[u8]) -> IMAPResult<'_, &[u8], CommandBody<'_>> {
     let mut parser = tuple((tag_no_case(b"RENAME"), sp, mailbox, sp, mailbox));
 
     let (remaining, (_, _, mailbox, _, new_mailbox)) = parser(input)?;
@@ -270,7 +499,7 @@ pub(crate) fn rename(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
 }
 
 /// `select = "SELECT" SP mailbox`
-pub(crate) fn select(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
+pub(crate) fn select(input: &[u8]) -> IMAPResult<'_, &[u8], CommandBody<'_>> {
     let mut parser = tuple((tag_no_case(b"SELECT"), sp, mailbox));
 
     let (remaining, (_, _, mailbox)) = parser(input)?;
@@ -279,7 +508,7 @@ pub(crate) fn select(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
 }
 
 /// `status = "STATUS" SP mailbox SP "(" status-att *(SP status-att) ")"`
-pub(crate) fn status(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
+pub(crate) fn status(input: &[u8]) -> IMAPResult<'_, &[u8], CommandBody<'_>> {
     let mut parser = tuple((
         tag_no_case(b"STATUS"),
         sp,
@@ -300,7 +529,7 @@ pub(crate) fn status(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
 }
 
 /// `subscribe = "SUBSCRIBE" SP mailbox`
-pub(crate) fn subscribe(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
+pub(crate) fn subscribe(input: &[u8]) -> IMAPResult<'_, &[u8], CommandBody<'_>> {
     let mut parser = tuple((tag_no_case(b"SUBSCRIBE"), sp, mailbox));
 
     let (remaining, (_, _, mailbox)) = parser(input)?;
@@ -309,7 +538,7 @@ pub(crate) fn subscribe(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
 }
 
 /// `unsubscribe = "UNSUBSCRIBE" SP mailbox`
-pub(crate) fn unsubscribe(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
+pub(crate) fn unsubscribe(input: &[u8]) -> IMAPResult<'_, &[u8], CommandBody<'_>> {
     let mut parser = tuple((tag_no_case(b"UNSUBSCRIBE"), sp, mailbox));
 
     let (remaining, (_, _, mailbox)) = parser(input)?;
@@ -319,10 +548,18 @@ pub(crate) fn unsubscribe(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
 
 // # Command NonAuth
 
+/// Names of the commands recognized by [`command_nonauth`] (see [`is_command_name`]).
+const COMMAND_NONAUTH_NAMES: &[&str] = &[
+    "LOGIN",
+    "AUTHENTICATE",
+    #[cfg(feature = "starttls")]
+    "STARTTLS",
+];
+
 /// `command-nonauth = login / authenticate / "STARTTLS"`
 ///
 /// Note: Valid only when in Not Authenticated state
-pub(crate) fn command_nonauth(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
+pub(crate) fn command_nonauth(input: &[u8]) -> IMAPResult<'_, &[u8], CommandBody<'_>> {
     let mut parser = alt((
         login,
         map(authenticate, |(mechanism, initial_response)| {
@@ -341,7 +578,7 @@ pub(crate) fn command_nonauth(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
 }
 
 /// `login = "LOGIN" SP userid SP password`
-pub(crate) fn login(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
+pub(crate) fn login(input: &[u8]) -> IMAPResult<'_, &[u8], CommandBody<'_>> {
     let mut parser = tuple((tag_no_case(b"LOGIN"), sp, userid, sp, password));
 
     let (remaining, (_, _, username, _, password)) = parser(input)?;
@@ -357,13 +594,13 @@ pub(crate) fn login(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
 
 #[inline]
 /// `userid = astring`
-pub(crate) fn userid(input: &[u8]) -> IMAPResult<&[u8], AString> {
+pub(crate) fn userid(input: &[u8]) -> IMAPResult<'_, &[u8], AString<'_>> {
     astring(input)
 }
 
 #[inline]
 /// `password = astring`
-pub(crate) fn password(input: &[u8]) -> IMAPResult<&[u8], AString> {
+pub(crate) fn password(input: &[u8]) -> IMAPResult<'_, &[u8], AString<'_>> {
     astring(input)
 }
 
@@ -382,7 +619,7 @@ pub(crate) fn password(input: &[u8]) -> IMAPResult<&[u8], AString> {
 #[allow(clippy::type_complexity)]
 pub(crate) fn authenticate(
     input: &[u8],
-) -> IMAPResult<&[u8], (AuthMechanism, Option<Secret<Cow<[u8]>>>)> {
+) -> IMAPResult<'_, &[u8], (AuthMechanism<'_>, Option<Secret<Cow<'_, [u8]>>>)> {
     let mut parser = tuple((
         tag_no_case(b"AUTHENTICATE "),
         auth_type,
@@ -404,6 +641,26 @@ pub(crate) fn authenticate(
 
 // # Command Select
 
+/// Names of the commands recognized by [`command_select`] (see [`is_command_name`]).
+///
+/// Note: `UID` prefixes other commands, e.g., `UID FETCH`.
+const COMMAND_SELECT_NAMES: &[&str] = &[
+    "CHECK",
+    "CLOSE",
+    "EXPUNGE",
+    "COPY",
+    "FETCH",
+    "STORE",
+    "UID",
+    "SEARCH",
+    #[cfg(feature = "ext_sort_thread")]
+    "SORT",
+    #[cfg(feature = "ext_sort_thread")]
+    "THREAD",
+    "UNSELECT",
+    "MOVE",
+];
+
 /// `command-select = "CHECK" /
 ///                   "CLOSE" /
 ///                   "EXPUNGE" /
@@ -414,108 +671,172 @@ pub(crate) fn authenticate(
 ///                   search`
 ///
 /// Note: Valid only when in Selected state
-pub(crate) fn command_select(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
-    alt((
-        value(CommandBody::Check, tag_no_case(b"CHECK")),
-        value(CommandBody::Close, tag_no_case(b"CLOSE")),
-        value(CommandBody::Expunge, tag_no_case(b"EXPUNGE")),
-        copy,
-        fetch,
-        store,
-        uid,
-        search,
-        #[cfg(feature = "ext_sort_thread")]
-        sort,
-        #[cfg(feature = "ext_sort_thread")]
-        thread,
-        value(CommandBody::Unselect, tag_no_case(b"UNSELECT")),
-        r#move,
-    ))(input)
+pub(crate) fn command_select(
+    max_sequence_set_len: usize,
+) -> impl Fn(&[u8]) -> IMAPResult<'_, &[u8], CommandBody<'_>> {
+    move |input: &[u8]| {
+        alt((
+            value(CommandBody::Check, tag_no_case(b"CHECK")),
+            value(CommandBody::Close, tag_no_case(b"CLOSE")),
+            value(CommandBody::Expunge, tag_no_case(b"EXPUNGE")),
+            copy(max_sequence_set_len),
+            fetch(max_sequence_set_len),
+            store(max_sequence_set_len),
+            uid(max_sequence_set_len),
+            search(max_sequence_set_len),
+            #[cfg(feature = "ext_sort_thread")]
+            sort(max_sequence_set_len),
+            #[cfg(feature = "ext_sort_thread")]
+            thread(max_sequence_set_len),
+            value(CommandBody::Unselect, tag_no_case(b"UNSELECT")),
+            r#move(max_sequence_set_len),
+        ))(input)
+    }
 }
 
 /// `copy = "COPY" SP sequence-set SP mailbox`
-pub(crate) fn copy(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
-    let mut parser = tuple((tag_no_case(b"COPY"), sp, sequence_set, sp, mailbox));
+pub(crate) fn copy(
+    max_sequence_set_len: usize,
+) -> impl Fn(&[u8]) -> IMAPResult<'_, &[u8], CommandBody<'_>> {
+    move |input: &[u8]| {
+        let mut parser = tuple((
+            tag_no_case(b"COPY"),
+            sp,
+            sequence_set(max_sequence_set_len),
+            sp,
+            mailbox,
+        ));
 
-    let (remaining, (_, _, sequence_set, _, mailbox)) = parser(input)?;
+        let (remaining, (_, _, sequence_set, _, mailbox)) = parser(input)?;
 
-    Ok((
-        remaining,
-        CommandBody::Copy {
-            sequence_set,
-            mailbox,
-            uid: false,
-        },
-    ))
+        Ok((
+            remaining,
+            CommandBody::Copy {
+                sequence_set,
+                mailbox,
+                uid: false,
+            },
+        ))
+    }
 }
 
-/// `fetch = "FETCH" SP sequence-set SP ("ALL" /
-///                                      "FULL" /
-///                                      "FAST" /
-///                                      fetch-att / "(" fetch-att *(SP fetch-att) ")")`
-pub(crate) fn fetch(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
-    let mut parser = tuple((
-        tag_no_case(b"FETCH"),
-        sp,
-        sequence_set,
-        sp,
-        alt((
-            value(
-                MacroOrMessageDataItemNames::Macro(Macro::All),
-                tag_no_case(b"ALL"),
-            ),
-            value(
-                MacroOrMessageDataItemNames::Macro(Macro::Fast),
-                tag_no_case(b"FAST"),
-            ),
-            value(
-                MacroOrMessageDataItemNames::Macro(Macro::Full),
-                tag_no_case(b"FULL"),
-            ),
-            map(fetch_att, |fetch_att| {
-                MacroOrMessageDataItemNames::MessageDataItemNames(vec![fetch_att])
-            }),
-            map(
-                delimited(tag(b"("), separated_list0(sp, fetch_att), tag(b")")),
-                MacroOrMessageDataItemNames::MessageDataItemNames,
-            ),
-        )),
-    ));
-
-    let (remaining, (_, _, sequence_set, _, macro_or_item_names)) = parser(input)?;
+/// ```abnf
+/// fetch = "FETCH" SP sequence-set SP ("ALL" /
+///                                     "FULL" /
+///                                     "FAST" /
+///                                     fetch-att / "(" fetch-att *(SP fetch-att) ")")
+///         [fetch-modifiers] ; RFC 7162
+/// ```
+pub(crate) fn fetch(
+    max_sequence_set_len: usize,
+) -> impl Fn(&[u8]) -> IMAPResult<'_, &[u8], CommandBody<'_>> {
+    move |input: &[u8]| {
+        let mut parser = tuple((
+            tag_no_case(b"FETCH"),
+            sp,
+            sequence_set(max_sequence_set_len),
+            sp,
+            alt((
+                value(
+                    MacroOrMessageDataItemNames::Macro(Macro::All),
+                    tag_no_case(b"ALL"),
+                ),
+                value(
+                    MacroOrMessageDataItemNames::Macro(Macro::Fast),
+                    tag_no_case(b"FAST"),
+                ),
+                value(
+                    MacroOrMessageDataItemNames::Macro(Macro::Full),
+                    tag_no_case(b"FULL"),
+                ),
+                map(fetch_att, |fetch_att| {
+                    MacroOrMessageDataItemNames::MessageDataItemNames(vec![fetch_att])
+                }),
+                map(
+                    delimited(tag(b"("), separated_list0(sp, fetch_att), tag(b")")),
+                    MacroOrMessageDataItemNames::MessageDataItemNames,
+                ),
+            )),
+        ));
+
+        let (remaining, (_, _, sequence_set, _, macro_or_item_names)) = parser(input)?;
+
+        #[cfg(feature = "ext_condstore_qresync")]
+        if let (remaining, Some(modifiers)) = opt(fetch_modifiers)(remaining)? {
+            return Ok((
+                remaining,
+                CommandBody::FetchWithModifiers {
+                    sequence_set,
+                    macro_or_item_names,
+                    uid: false,
+                    modifiers,
+                },
+            ));
+        }
 
-    Ok((
-        remaining,
-        CommandBody::Fetch {
-            sequence_set,
-            macro_or_item_names,
-            uid: false,
-        },
-    ))
+        Ok((
+            remaining,
+            CommandBody::Fetch {
+                sequence_set,
+                macro_or_item_names,
+                uid: false,
+            },
+        ))
+    }
 }
 
-/// `store = "STORE" SP sequence-set SP store-att-flags`
-pub(crate) fn store(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
-    let mut parser = tuple((tag_no_case(b"STORE"), sp, sequence_set, sp, store_att_flags));
-
-    let (remaining, (_, _, sequence_set, _, (kind, response, flags))) = parser(input)?;
+/// ```abnf
+/// store = "STORE" SP sequence-set
+///         [store-modifiers] ; RFC 7162
+///         SP store-att-flags
+/// ```
+pub(crate) fn store(
+    max_sequence_set_len: usize,
+) -> impl Fn(&[u8]) -> IMAPResult<'_, &[u8], CommandBody<'_>> {
+    move |input: &[u8]| {
+        let (remaining, (_, _, sequence_set)) = tuple((
+            tag_no_case(b"STORE"),
+            sp,
+            sequence_set(max_sequence_set_len),
+        ))(input)?;
+
+        #[cfg(feature = "ext_condstore_qresync")]
+        let (remaining, modifiers) = opt(store_modifiers)(remaining)?;
+
+        let (remaining, (_, (kind, response, flags))) = tuple((sp, store_att_flags))(remaining)?;
+
+        #[cfg(feature = "ext_condstore_qresync")]
+        if let Some(modifiers) = modifiers {
+            return Ok((
+                remaining,
+                CommandBody::StoreWithModifiers {
+                    sequence_set,
+                    kind,
+                    response,
+                    flags,
+                    uid: false,
+                    modifiers,
+                },
+            ));
+        }
 
-    Ok((
-        remaining,
-        CommandBody::Store {
-            sequence_set,
-            kind,
-            response,
-            flags,
-            uid: false,
-        },
-    ))
+        Ok((
+            remaining,
+            CommandBody::Store {
+                sequence_set,
+                kind,
+                response,
+                flags,
+                uid: false,
+            },
+        ))
+    }
 }
 
 /// `store-att-flags = (["+" / "-"] "FLAGS" [".SILENT"]) SP (flag-list / (flag *(SP flag)))`
 pub(crate) fn store_att_flags(
     input: &[u8],
-) -> IMAPResult<&[u8], (StoreType, StoreResponse, Vec<Flag>)> {
+) -> IMAPResult<'_, &[u8], (StoreType, StoreResponse, Vec<Flag<'_>>)> {
     let mut parser = tuple((
         tuple((
             map(
@@ -546,25 +867,40 @@ pub(crate) fn store_att_flags(
 /// `uid = "UID" SP (copy / fetch / search / store)`
 ///
 /// Note: Unique identifiers used instead of message sequence numbers
-pub(crate) fn uid(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
-    let mut parser = tuple((
-        tag_no_case(b"UID"),
-        sp,
-        alt((copy, fetch, search, store, r#move)),
-    ));
-
-    let (remaining, (_, _, mut cmd)) = parser(input)?;
+pub(crate) fn uid(
+    max_sequence_set_len: usize,
+) -> impl Fn(&[u8]) -> IMAPResult<'_, &[u8], CommandBody<'_>> {
+    move |input: &[u8]| {
+        let mut parser = tuple((
+            tag_no_case(b"UID"),
+            sp,
+            alt((
+                copy(max_sequence_set_len),
+                fetch(max_sequence_set_len),
+                search(max_sequence_set_len),
+                store(max_sequence_set_len),
+                r#move(max_sequence_set_len),
+            )),
+        ));
+
+        let (remaining, (_, _, mut cmd)) = parser(input)?;
+
+        match cmd {
+            CommandBody::Copy { ref mut uid, .. }
+            | CommandBody::Fetch { ref mut uid, .. }
+            | CommandBody::Search { ref mut uid, .. }
+            | CommandBody::Store { ref mut uid, .. }
+            | CommandBody::Move { ref mut uid, .. } => *uid = true,
+            #[cfg(feature = "ext_esearch")]
+            CommandBody::SearchExtended { ref mut uid, .. } => *uid = true,
+            #[cfg(feature = "ext_condstore_qresync")]
+            CommandBody::FetchWithModifiers { ref mut uid, .. }
+            | CommandBody::StoreWithModifiers { ref mut uid, .. } => *uid = true,
+            _ => unreachable!(),
+        }
 
-    match cmd {
-        CommandBody::Copy { ref mut uid, .. }
-        | CommandBody::Fetch { ref mut uid, .. }
-        | CommandBody::Search { ref mut uid, .. }
-        | CommandBody::Store { ref mut uid, .. }
-        | CommandBody::Move { ref mut uid, .. } => *uid = true,
-        _ => unreachable!(),
+        Ok((remaining, cmd))
     }
-
-    Ok((remaining, cmd))
 }
 
 #[cfg(test)]
@@ -574,14 +910,21 @@ mod tests {
     use imap_types::{
         core::Tag,
         fetch::{MessageDataItemName, Section},
+        mailbox::Mailbox,
     };
 
     use super::*;
-    use crate::{encode::Encoder, CommandCodec};
+    use crate::{
+        decode::Decoder, encode::Encoder, sequence::DEFAULT_SEQUENCE_SET_MAX_LEN,
+        testing::kat_inverse_command, CommandCodec,
+    };
 
     #[test]
     fn test_parse_fetch() {
-        println!("{:#?}", fetch(b"fetch 1:1 (flags)???"));
+        println!(
+            "{:#?}",
+            fetch(DEFAULT_SEQUENCE_SET_MAX_LEN)(b"fetch 1:1 (flags)???")
+        );
     }
 
     #[test]
@@ -656,4 +999,261 @@ mod tests {
 
         assert_eq!(buffer, b"A AUTHENTICATE PLAIN =\r\n")
     }
+
+    #[test]
+    fn test_fetch_macro_or_item_names_normalize() {
+        use MessageDataItemName::*;
+
+        let tests = [
+            (
+                MacroOrMessageDataItemNames::from(vec![InternalDate, Flags, Rfc822Size]),
+                MacroOrMessageDataItemNames::Macro(Macro::Fast),
+            ),
+            (
+                vec![Flags, InternalDate, Rfc822Size, Envelope, Body, Flags].into(),
+                Macro::Full.into(),
+            ),
+            (vec![Flags].into(), vec![Flags].into()),
+            (vec![Flags, Uid].into(), vec![Flags, Uid].into()),
+            (Macro::All.into(), Macro::All.into()),
+        ];
+
+        for (test, expected) in tests {
+            assert_eq!(test.normalize(), expected);
+        }
+
+        assert_eq!(
+            MacroOrMessageDataItemNames::Macro(Macro::All).expand(),
+            vec![Flags, InternalDate, Rfc822Size, Envelope]
+        );
+    }
+
+    #[test]
+    fn test_fetch_single_item_without_parentheses() {
+        let tests = [
+            (
+                vec![MessageDataItemName::Flags],
+                b"A FETCH 1 FLAGS\r\n".as_ref(),
+            ),
+            (
+                vec![MessageDataItemName::Flags, MessageDataItemName::Uid],
+                b"A FETCH 1 (FLAGS UID)\r\n".as_ref(),
+            ),
+        ];
+
+        for (items, expected) in tests {
+            let command = CommandBody::fetch("1", items, false)
+                .unwrap()
+                .tag("A")
+                .unwrap();
+
+            let buffer = CommandCodec::default().encode(&command).dump();
+            assert_eq!(buffer, expected);
+        }
+    }
+
+    #[test]
+    fn test_append_non_sync() {
+        let command = CommandBody::append_non_sync("inbox", vec![], None, b"hello".as_ref())
+            .unwrap()
+            .tag("A")
+            .unwrap();
+
+        let buffer = CommandCodec::default().encode(&command).dump();
+        assert_eq!(buffer, b"A APPEND INBOX {5+}\r\nhello\r\n");
+
+        let (_, got) = CommandCodec::default().decode(&buffer).unwrap();
+        assert_eq!(command, got);
+    }
+
+    #[test]
+    fn test_mailbox_constructors() {
+        kat_inverse_command(&[
+            (
+                b"A SELECT INBOX\r\n".as_ref(),
+                b"".as_ref(),
+                CommandBody::select("inbox").unwrap().tag("A").unwrap(),
+            ),
+            (
+                b"A EXAMINE Archive\r\n",
+                b"",
+                CommandBody::examine("Archive").unwrap().tag("A").unwrap(),
+            ),
+            (
+                b"A CREATE \"Sent Items\"\r\n",
+                b"",
+                CommandBody::create("Sent Items").unwrap().tag("A").unwrap(),
+            ),
+            (
+                b"A DELETE Trash\r\n",
+                b"",
+                CommandBody::delete("Trash").unwrap().tag("A").unwrap(),
+            ),
+            (
+                b"A SUBSCRIBE news\r\n",
+                b"",
+                CommandBody::subscribe("news").unwrap().tag("A").unwrap(),
+            ),
+            (
+                b"A UNSUBSCRIBE news\r\n",
+                b"",
+                CommandBody::unsubscribe("news").unwrap().tag("A").unwrap(),
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_rename_inbox() {
+        let command = CommandBody::rename("inbox", "Archive")
+            .unwrap()
+            .tag("A")
+            .unwrap();
+
+        assert!(command.body.is_inbox_rename());
+        assert!(!CommandBody::rename("Archive", "inbox")
+            .unwrap()
+            .is_inbox_rename());
+
+        let buffer = CommandCodec::default().encode(&command).dump();
+        assert_eq!(buffer, b"A RENAME INBOX Archive\r\n");
+
+        let (_, got) = CommandCodec::default().decode(&buffer).unwrap();
+        assert_eq!(command, got);
+    }
+
+    #[test]
+    fn test_copy_move_inbox_destination() {
+        for (command, expected) in [
+            (
+                CommandBody::copy(1, "inbox", false).unwrap(),
+                b"A COPY 1 INBOX\r\n".as_ref(),
+            ),
+            (
+                CommandBody::copy("1:*", "InBoX", true).unwrap(),
+                b"A UID COPY 1:* INBOX\r\n",
+            ),
+            (
+                CommandBody::r#move(1, "inbox", false).unwrap(),
+                b"A MOVE 1 INBOX\r\n",
+            ),
+            (
+                CommandBody::r#move(1, "INBOX", true).unwrap(),
+                b"A UID MOVE 1 INBOX\r\n",
+            ),
+        ] {
+            match &command {
+                CommandBody::Copy { mailbox, .. } | CommandBody::Move { mailbox, .. } => {
+                    assert_eq!(*mailbox, Mailbox::Inbox)
+                }
+                _ => unreachable!(),
+            }
+
+            let command = command.tag("A").unwrap();
+            let buffer = CommandCodec::default().encode(&command).dump();
+            assert_eq!(buffer, expected);
+        }
+
+        // A quoted destination is recognized as INBOX, too.
+        let (_, got) = CommandCodec::default()
+            .decode(b"A COPY 1 \"inbox\"\r\n")
+            .unwrap();
+        assert_eq!(
+            got,
+            CommandBody::copy(1, Mailbox::Inbox, false)
+                .unwrap()
+                .tag("A")
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_is_command_name_covers_every_command() {
+        let tests = [
+            b"A CAPABILITY\r\n".as_ref(),
+            b"A NOOP\r\n",
+            b"A LOGOUT\r\n",
+            #[cfg(feature = "starttls")]
+            b"A STARTTLS\r\n",
+            b"A AUTHENTICATE PLAIN\r\n",
+            b"A LOGIN alice pass\r\n",
+            b"A SELECT INBOX\r\n",
+            #[cfg(feature = "ext_sort_thread")]
+            b"A SORT (ARRIVAL) UTF-8 ALL\r\n",
+            #[cfg(feature = "ext_sort_thread")]
+            b"A THREAD REFERENCES UTF-8 ALL\r\n",
+            b"A UNSELECT\r\n",
+            b"A EXAMINE INBOX\r\n",
+            b"A CREATE a\r\n",
+            b"A DELETE a\r\n",
+            b"A RENAME a b\r\n",
+            b"A SUBSCRIBE a\r\n",
+            b"A UNSUBSCRIBE a\r\n",
+            b"A LIST \"\" *\r\n",
+            b"A LSUB \"\" *\r\n",
+            b"A STATUS INBOX (MESSAGES)\r\n",
+            b"A APPEND INBOX {1+}\r\na\r\n",
+            b"A CHECK\r\n",
+            b"A CLOSE\r\n",
+            b"A EXPUNGE\r\n",
+            b"A SEARCH ALL\r\n",
+            b"A FETCH 1 FLAGS\r\n",
+            b"A STORE 1 +FLAGS (\\Seen)\r\n",
+            b"A COPY 1 a\r\n",
+            b"A UID FETCH 1 FLAGS\r\n",
+            b"A IDLE\r\n",
+            b"A ENABLE CONDSTORE\r\n",
+            b"A COMPRESS DEFLATE\r\n",
+            b"A GETQUOTA \"\"\r\n",
+            b"A GETQUOTAROOT INBOX\r\n",
+            b"A SETQUOTA \"\" (STORAGE 512)\r\n",
+            b"A MOVE 1 a\r\n",
+            #[cfg(feature = "ext_id")]
+            b"A ID NIL\r\n",
+            #[cfg(feature = "ext_namespace")]
+            b"A NAMESPACE\r\n",
+            #[cfg(feature = "ext_metadata")]
+            b"A SETMETADATA INBOX (/private/comment NIL)\r\n",
+            #[cfg(feature = "ext_metadata")]
+            b"A GETMETADATA INBOX /private/comment\r\n",
+            #[cfg(feature = "ext_acl")]
+            b"A SETACL INBOX fred lrswi\r\n",
+            #[cfg(feature = "ext_acl")]
+            b"A DELETEACL INBOX fred\r\n",
+            #[cfg(feature = "ext_acl")]
+            b"A GETACL INBOX\r\n",
+            #[cfg(feature = "ext_acl")]
+            b"A LISTRIGHTS INBOX fred\r\n",
+            #[cfg(feature = "ext_acl")]
+            b"A MYRIGHTS INBOX\r\n",
+            #[cfg(feature = "ext_unauthenticate")]
+            b"A UNAUTHENTICATE\r\n",
+        ];
+
+        let mut names = Vec::new();
+
+        for test in tests {
+            let (_, got) = command(DEFAULT_SEQUENCE_SET_MAX_LEN)(test).unwrap();
+            let name = test.split(|b| *b == b' ' || *b == b'\r').nth(1).unwrap();
+            let name = std::str::from_utf8(name).unwrap();
+
+            assert!(is_command_name(got.name()), "{}", got.name());
+            assert!(is_command_name(name), "{name}");
+            assert!(command_unknown(test).is_err(), "{name}");
+
+            names.push(name);
+        }
+
+        // Every listed name has a command.
+        for known in [
+            COMMAND_ANY_NAMES,
+            COMMAND_AUTH_NAMES,
+            COMMAND_NONAUTH_NAMES,
+            COMMAND_SELECT_NAMES,
+        ]
+        .iter()
+        .flat_map(|names| names.iter())
+        {
+            assert!(names.contains(known), "{known}");
+        }
+    }
 }
@@ -6,13 +6,19 @@ use abnf_core::streaming::crlf;
 use abnf_core::streaming::crlf_relaxed as crlf;
 use abnf_core::{is_alpha, is_digit, streaming::dquote};
 use base64::{engine::general_purpose::STANDARD as _base64, Engine};
+#[cfg(not(feature = "quirk_atom_char_relaxed"))]
+use imap_types::utils::indicators::{is_atom_char, is_tag_char};
+#[cfg(feature = "quirk_atom_char_relaxed")]
+use imap_types::utils::indicators::{
+    is_atom_char_relaxed as is_atom_char, is_tag_char_relaxed as is_tag_char,
+};
 use imap_types::{
     core::{
         AString, Atom, AtomExt, Charset, IString, Literal, LiteralMode, NString, Quoted,
         QuotedChar, Tag, Text,
     },
     utils::{
-        indicators::{is_astring_char, is_atom_char, is_quoted_specials, is_text_char},
+        indicators::{is_astring_char, is_quoted_specials, is_text_char},
         unescape_quoted,
     },
 };
@@ -31,7 +37,7 @@ use crate::decode::{IMAPErrorKind, IMAPParseError, IMAPResult};
 /// `number = 1*DIGIT`
 ///
 /// Unsigned 32-bit integer (0 <= n < 4,294,967,296)
-pub(crate) fn number(input: &[u8]) -> IMAPResult<&[u8], u32> {
+pub(crate) fn number(input: &[u8]) -> IMAPResult<'_, &[u8], u32> {
     map_res(
         // # Safety
         //
@@ -48,7 +54,7 @@ pub(crate) fn number(input: &[u8]) -> IMAPResult<&[u8], u32> {
 /// Unsigned 63-bit integer (0 <= n <= 9,223,372,036,854,775,807)
 ///
 /// Defined in RFC 9051
-pub(crate) fn number64(input: &[u8]) -> IMAPResult<&[u8], u64> {
+pub(crate) fn number64(input: &[u8]) -> IMAPResult<'_, &[u8], u64> {
     map_res(
         // # Safety
         //
@@ -61,14 +67,14 @@ pub(crate) fn number64(input: &[u8]) -> IMAPResult<&[u8], u64> {
 /// `nz-number = digit-nz *DIGIT`
 ///
 /// Non-zero unsigned 32-bit integer (0 < n < 4,294,967,296)
-pub(crate) fn nz_number(input: &[u8]) -> IMAPResult<&[u8], NonZeroU32> {
+pub(crate) fn nz_number(input: &[u8]) -> IMAPResult<'_, &[u8], NonZeroU32> {
     map_res(number, NonZeroU32::try_from)(input)
 }
 
 // ----- string -----
 
 /// `string = quoted / literal`
-pub(crate) fn string(input: &[u8]) -> IMAPResult<&[u8], IString> {
+pub(crate) fn string(input: &[u8]) -> IMAPResult<'_, &[u8], IString<'_>> {
     alt((map(quoted, IString::Quoted), map(literal, IString::Literal)))(input)
 }
 
@@ -76,7 +82,7 @@ pub(crate) fn string(input: &[u8]) -> IMAPResult<&[u8], IString> {
 ///
 /// This function only allocates a new String, when needed, i.e. when
 /// quoted chars need to be replaced.
-pub(crate) fn quoted(input: &[u8]) -> IMAPResult<&[u8], Quoted> {
+pub(crate) fn quoted(input: &[u8]) -> IMAPResult<'_, &[u8], Quoted<'_>> {
     let mut parser = tuple((
         dquote,
         map(
@@ -99,7 +105,7 @@ pub(crate) fn quoted(input: &[u8]) -> IMAPResult<&[u8], Quoted> {
 }
 
 /// `QUOTED-CHAR = <any TEXT-CHAR except quoted-specials> / "\" quoted-specials`
-pub(crate) fn quoted_char(input: &[u8]) -> IMAPResult<&[u8], QuotedChar> {
+pub(crate) fn quoted_char(input: &[u8]) -> IMAPResult<'_, &[u8], QuotedChar> {
     map(
         alt((
             map(
@@ -140,7 +146,7 @@ pub(crate) fn is_any_text_char_except_quoted_specials(byte: u8) -> bool {
 /// literal8 = <defined in RFC 4466>
 /// ```
 /// -- <https://datatracker.ietf.org/doc/html/rfc7888#section-8>
-pub(crate) fn literal(input: &[u8]) -> IMAPResult<&[u8], Literal> {
+pub(crate) fn literal(input: &[u8]) -> IMAPResult<'_, &[u8], Literal<'_>> {
     let (remaining, (length, mode)) = terminated(
         delimited(
             tag(b"{"),
@@ -187,7 +193,7 @@ pub(crate) fn literal(input: &[u8]) -> IMAPResult<&[u8], Literal> {
 // ----- astring ----- atom (roughly) or string
 
 /// `astring = 1*ASTRING-CHAR / string`
-pub(crate) fn astring(input: &[u8]) -> IMAPResult<&[u8], AString> {
+pub(crate) fn astring(input: &[u8]) -> IMAPResult<'_, &[u8], AString<'_>> {
     alt((
         map(take_while1(is_astring_char), |bytes: &[u8]| {
             // # Safety
@@ -204,7 +210,7 @@ pub(crate) fn astring(input: &[u8]) -> IMAPResult<&[u8], AString> {
 }
 
 /// `atom = 1*ATOM-CHAR`
-pub(crate) fn atom(input: &[u8]) -> IMAPResult<&[u8], Atom> {
+pub(crate) fn atom(input: &[u8]) -> IMAPResult<'_, &[u8], Atom<'_>> {
     let parser = take_while1(is_atom_char);
 
     let (remaining, parsed_atom) = parser(input)?;
@@ -223,7 +229,7 @@ pub(crate) fn atom(input: &[u8]) -> IMAPResult<&[u8], Atom> {
 // ----- nstring ----- nil or string
 
 /// `nstring = string / nil`
-pub(crate) fn nstring(input: &[u8]) -> IMAPResult<&[u8], NString> {
+pub(crate) fn nstring(input: &[u8]) -> IMAPResult<'_, &[u8], NString<'_>> {
     alt((
         map(string, |item| NString(Some(item))),
         map(nil, |_| NString(None)),
@@ -232,14 +238,22 @@ pub(crate) fn nstring(input: &[u8]) -> IMAPResult<&[u8], NString> {
 
 #[inline]
 /// `nil = "NIL"`
-pub(crate) fn nil(input: &[u8]) -> IMAPResult<&[u8], &[u8]> {
+pub(crate) fn nil(input: &[u8]) -> IMAPResult<'_, &[u8], &[u8]> {
     tag_no_case(b"NIL")(input)
 }
 
+// ----- sp -----
+
+/// Relaxed version of `SP` that accepts one or more spaces.
+#[cfg(feature = "quirk_spaces_relaxed")]
+pub(crate) fn sp_relaxed(input: &[u8]) -> IMAPResult<'_, &[u8], &[u8]> {
+    take_while1(|byte| byte == b' ')(input)
+}
+
 // ----- text -----
 
 /// `text = 1*TEXT-CHAR`
-pub(crate) fn text(input: &[u8]) -> IMAPResult<&[u8], Text> {
+pub(crate) fn text(input: &[u8]) -> IMAPResult<'_, &[u8], Text<'_>> {
     map(take_while1(is_text_char), |bytes|
         // # Safety
         // 
@@ -251,7 +265,7 @@ pub(crate) fn text(input: &[u8]) -> IMAPResult<&[u8], Text> {
 // ----- base64 -----
 
 /// `base64 = *(4base64-char) [base64-terminal]`
-pub(crate) fn base64(input: &[u8]) -> IMAPResult<&[u8], Vec<u8>> {
+pub(crate) fn base64(input: &[u8]) -> IMAPResult<'_, &[u8], Vec<u8>> {
     map_res(
         recognize(tuple((
             take_while(is_base64_char),
@@ -273,18 +287,18 @@ pub(crate) fn is_base64_char(i: u8) -> bool {
 /// `charset = atom / quoted`
 ///
 /// Note: see errata id: 261
-pub(crate) fn charset(input: &[u8]) -> IMAPResult<&[u8], Charset> {
+pub(crate) fn charset(input: &[u8]) -> IMAPResult<'_, &[u8], Charset<'_>> {
     alt((map(atom, Charset::Atom), map(quoted, Charset::Quoted)))(input)
 }
 
 // ----- tag -----
 
 /// `tag = 1*<any ASTRING-CHAR except "+">`
-pub(crate) fn tag_imap(input: &[u8]) -> IMAPResult<&[u8], Tag> {
-    map(take_while1(|b| is_astring_char(b) && b != b'+'), |val| {
+pub(crate) fn tag_imap(input: &[u8]) -> IMAPResult<'_, &[u8], Tag<'_>> {
+    map(take_while1(is_tag_char), |val| {
         // # Safety
         //
-        // `is_astring_char` ensures that `val` is UTF-8.
+        // `is_tag_char` ensures that `val` is UTF-8.
         Tag::unvalidated(from_utf8(val).unwrap())
     })(input)
 }
@@ -390,6 +404,27 @@ mod tests {
         assert_eq!(rem, b"xxx");
     }
 
+    #[test]
+    fn test_encode_astring_from_str_smart() {
+        let tests = [
+            ("hello", "hello"),
+            ("hello world", "\"hello world\""),
+            ("say \"hi\"", r#""say \"hi\"""#),
+            ("C:\\", r#""C:\\""#),
+            ("hello\r\n", "{7}\r\nhello\r\n"),
+        ];
+
+        for (from, expected) in tests {
+            let mut ctx = EncodeContext::new();
+            AString::from_str_smart(from)
+                .unwrap()
+                .encode_ctx(&mut ctx)
+                .unwrap();
+
+            assert_eq!(from_utf8(&ctx.dump()).unwrap(), expected);
+        }
+    }
+
     #[test]
     fn test_encode_charset() {
         let tests = [
@@ -433,4 +468,41 @@ mod tests {
         //_base64.decode(b"aa==").unwrap();
         _base64.decode(b"aQ==").unwrap();
     }
+
+    #[test]
+    fn test_atom_char_quirk() {
+        let (rem, val) = tag_imap(b"A1 NOOP").unwrap();
+        assert_eq!(val, Tag::try_from("A1").unwrap());
+        assert_eq!(rem, b" NOOP");
+
+        #[cfg(not(feature = "quirk_atom_char_relaxed"))]
+        {
+            let (rem, val) = tag_imap(b"A{1 NOOP").unwrap();
+            assert_eq!(val, Tag::try_from("A").unwrap());
+            assert_eq!(rem, b"{1 NOOP");
+
+            let (rem, val) = atom(b"x{y ").unwrap();
+            assert_eq!(val, Atom::try_from("x").unwrap());
+            assert_eq!(rem, b"{y ");
+        }
+
+        #[cfg(feature = "quirk_atom_char_relaxed")]
+        {
+            let (rem, val) = tag_imap(b"A{1 NOOP").unwrap();
+            assert_eq!(val.inner(), "A{1");
+            assert_eq!(rem, b" NOOP");
+
+            let (rem, val) = atom(b"x{y ").unwrap();
+            assert_eq!(val.inner(), "x{y");
+            assert_eq!(rem, b" ");
+
+            // Only the parser is relaxed.
+            assert!(Tag::try_from("A{1").is_err());
+            assert!(Atom::try_from("x{y").is_err());
+        }
+
+        // Still not allowed.
+        assert!(tag_imap(b"+").is_err());
+        assert!(atom(b"(").is_err());
+    }
 }
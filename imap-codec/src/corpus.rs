@@ -0,0 +1,271 @@
+//! # Fuzzing seed corpus
+//!
+//! A set of canonical messages, one per greeting, command, response, ... variant.
+//!
+//! Every seed is exactly what imap-codec would produce when encoding the decoded message. Thus,
+//! the seeds make a good starting point for fuzzing and double as documentation of the supported
+//! syntax. Seeds for feature-gated extensions are only included when the feature is enabled.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use imap_codec::corpus::write_corpus;
+//!
+//! // Writes `fuzz/corpus/command/noop`, `fuzz/corpus/response/exists`, etc.
+//! let count = write_corpus("fuzz/corpus").unwrap();
+//! println!("Wrote {count} seeds");
+//! ```
+
+use std::{fs, io, path::Path};
+
+/// A single seed of the corpus.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Seed {
+    /// Kind of message.
+    pub kind: SeedKind,
+    /// Name of the seed, unique per kind.
+    pub name: &'static str,
+    /// Encoded message.
+    pub data: &'static [u8],
+}
+
+/// Kind of message a [`Seed`] holds.
+///
+/// This determines the codec to use and the fuzz target the seed belongs to.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum SeedKind {
+    Greeting,
+    Command,
+    Response,
+    AuthenticateData,
+    IdleDone,
+}
+
+impl SeedKind {
+    /// Name of the (parsing) fuzz target.
+    pub fn target(&self) -> &'static str {
+        match self {
+            Self::Greeting => "greeting",
+            Self::Command => "command",
+            Self::Response => "response",
+            Self::AuthenticateData => "authenticate_data",
+            Self::IdleDone => "idle_done",
+        }
+    }
+}
+
+macro_rules! seed {
+    ($kind:ident, $name:literal, $data:literal) => {
+        Seed {
+            kind: SeedKind::$kind,
+            name: $name,
+            data: $data,
+        }
+    };
+}
+
+/// Returns all seeds.
+pub fn seeds() -> Vec<Seed> {
+    vec![
+        // ----- Greeting -----
+        seed!(Greeting, "ok", b"* OK IMAP4rev1 Service Ready\r\n"),
+        seed!(
+            Greeting,
+            "ok_code",
+            b"* OK [CAPABILITY IMAP4REV1 IDLE] Hello\r\n"
+        ),
+        seed!(Greeting, "preauth", b"* PREAUTH Logged in as alice\r\n"),
+        seed!(Greeting, "bye", b"* BYE Autologout\r\n"),
+        // ----- Command -----
+        seed!(Command, "capability", b"A CAPABILITY\r\n"),
+        seed!(Command, "noop", b"A NOOP\r\n"),
+        seed!(Command, "logout", b"A LOGOUT\r\n"),
+        #[cfg(feature = "starttls")]
+        seed!(Command, "starttls", b"A STARTTLS\r\n"),
+        seed!(Command, "authenticate", b"A AUTHENTICATE PLAIN\r\n"),
+        seed!(
+            Command,
+            "authenticate_ir",
+            b"A AUTHENTICATE PLAIN AGFsaWNlAHBhc3N3b3Jk\r\n"
+        ),
+        seed!(Command, "login", b"A LOGIN alice password\r\n"),
+        seed!(Command, "select", b"A SELECT INBOX\r\n"),
+        seed!(Command, "unselect", b"A UNSELECT\r\n"),
+        seed!(Command, "examine", b"A EXAMINE Archive\r\n"),
+        seed!(Command, "create", b"A CREATE Archive/2024\r\n"),
+        seed!(Command, "delete", b"A DELETE Archive/2024\r\n"),
+        seed!(Command, "rename", b"A RENAME Archive Old\r\n"),
+        seed!(Command, "subscribe", b"A SUBSCRIBE Archive\r\n"),
+        seed!(Command, "unsubscribe", b"A UNSUBSCRIBE Archive\r\n"),
+        seed!(Command, "list", b"A LIST \"\" *\r\n"),
+        seed!(Command, "lsub", b"A LSUB \"\" %\r\n"),
+        seed!(Command, "status", b"A STATUS INBOX (MESSAGES UNSEEN)\r\n"),
+        seed!(
+            Command,
+            "append",
+            b"A APPEND INBOX (\\Seen) {5+}\r\nHello\r\n"
+        ),
+        seed!(Command, "check", b"A CHECK\r\n"),
+        seed!(Command, "close", b"A CLOSE\r\n"),
+        seed!(Command, "expunge", b"A EXPUNGE\r\n"),
+        seed!(Command, "search", b"A SEARCH UNSEEN FROM alice\r\n"),
+        seed!(Command, "uid_search", b"A UID SEARCH ALL\r\n"),
+        seed!(Command, "fetch", b"A FETCH 1:* (FLAGS UID)\r\n"),
+        seed!(Command, "fetch_macro", b"A FETCH 1 FULL\r\n"),
+        seed!(
+            Command,
+            "uid_fetch",
+            b"A UID FETCH 1:5 BODY.PEEK[HEADER]\r\n"
+        ),
+        seed!(Command, "store", b"A STORE 1 +FLAGS (\\Seen)\r\n"),
+        seed!(
+            Command,
+            "uid_store",
+            b"A UID STORE 1 -FLAGS.SILENT (\\Deleted)\r\n"
+        ),
+        seed!(Command, "copy", b"A COPY 1:3 Archive\r\n"),
+        seed!(Command, "move", b"A MOVE 1:3 Archive\r\n"),
+        seed!(Command, "idle", b"A IDLE\r\n"),
+        seed!(Command, "enable", b"A ENABLE UTF8=ACCEPT\r\n"),
+        seed!(Command, "compress", b"A COMPRESS DEFLATE\r\n"),
+        seed!(Command, "getquota", b"A GETQUOTA \"\"\r\n"),
+        seed!(Command, "getquotaroot", b"A GETQUOTAROOT INBOX\r\n"),
+        seed!(Command, "setquota", b"A SETQUOTA \"\" (STORAGE 512)\r\n"),
+        #[cfg(feature = "ext_id")]
+        seed!(Command, "id", b"A ID (\"name\" \"imap-codec\")\r\n"),
+        #[cfg(feature = "ext_namespace")]
+        seed!(Command, "namespace", b"A NAMESPACE\r\n"),
+        #[cfg(feature = "ext_unauthenticate")]
+        seed!(Command, "unauthenticate", b"A UNAUTHENTICATE\r\n"),
+        // ----- Response -----
+        seed!(Response, "ok", b"A OK LOGIN completed\r\n"),
+        seed!(
+            Response,
+            "ok_code",
+            b"A OK [READ-WRITE] SELECT completed\r\n"
+        ),
+        seed!(Response, "no", b"A NO [TRYCREATE] No such mailbox\r\n"),
+        seed!(Response, "bad", b"A BAD Command unknown\r\n"),
+        seed!(
+            Response,
+            "untagged_ok",
+            b"* OK [UIDVALIDITY 3857529045] UIDs valid\r\n"
+        ),
+        seed!(Response, "bye", b"* BYE Logging out\r\n"),
+        seed!(Response, "continue", b"+ Ready for literal data\r\n"),
+        seed!(Response, "continue_base64", b"+ AGFsaWNl\r\n"),
+        seed!(
+            Response,
+            "capability",
+            b"* CAPABILITY IMAP4REV1 AUTH=PLAIN SASL-IR\r\n"
+        ),
+        seed!(Response, "list", b"* LIST (\\Noselect) \"/\" Archive\r\n"),
+        seed!(Response, "lsub", b"* LSUB () \".\" Archive\r\n"),
+        seed!(
+            Response,
+            "status",
+            b"* STATUS INBOX (MESSAGES 231 UIDNEXT 44292)\r\n"
+        ),
+        seed!(Response, "search", b"* SEARCH 2 84 882\r\n"),
+        seed!(
+            Response,
+            "flags",
+            b"* FLAGS (\\Answered \\Flagged \\Deleted \\Seen \\Draft)\r\n"
+        ),
+        seed!(Response, "exists", b"* 23 EXISTS\r\n"),
+        seed!(Response, "recent", b"* 5 RECENT\r\n"),
+        seed!(Response, "expunge", b"* 44 EXPUNGE\r\n"),
+        seed!(
+            Response,
+            "fetch",
+            b"* 12 FETCH (FLAGS (\\Seen) UID 4827313)\r\n"
+        ),
+        seed!(Response, "enabled", b"* ENABLED UTF8=ACCEPT\r\n"),
+        seed!(Response, "quota", b"* QUOTA \"\" (STORAGE 10 512)\r\n"),
+        seed!(Response, "quotaroot", b"* QUOTAROOT INBOX \"\"\r\n"),
+        // ----- AuthenticateData -----
+        seed!(AuthenticateData, "continue", b"AGFsaWNlAHBhc3N3b3Jk\r\n"),
+        seed!(AuthenticateData, "cancel", b"*\r\n"),
+        // ----- IdleDone -----
+        seed!(IdleDone, "done", b"DONE\r\n"),
+    ]
+}
+
+/// Writes all seeds into `dir`.
+///
+/// Every seed is written to `<dir>/<target>/<name>`, where `<target>` is the name of the fuzz
+/// target (see [`SeedKind::target`]). Missing directories are created and existing seeds are
+/// overwritten. Returns the number of written seeds.
+pub fn write_corpus<P: AsRef<Path>>(dir: P) -> io::Result<usize> {
+    let seeds = seeds();
+
+    for seed in &seeds {
+        let path = dir.as_ref().join(seed.kind.target());
+        fs::create_dir_all(&path)?;
+        fs::write(path.join(seed.name), seed.data)?;
+    }
+
+    Ok(seeds.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use imap_types::utils::escape_byte_string;
+
+    use super::*;
+    use crate::{
+        decode::Decoder, encode::Encoder, AuthenticateDataCodec, CommandCodec, GreetingCodec,
+        IdleDoneCodec, ResponseCodec,
+    };
+
+    macro_rules! roundtrip {
+        ($codec:ident, $data:expr) => {{
+            let (remaining, message) = $codec::default().decode($data).unwrap_or_else(|_| {
+                panic!("Seed does not decode: {}", escape_byte_string($data));
+            });
+            assert!(remaining.is_empty());
+            assert_eq!(
+                $codec::default().encode(&message).dump(),
+                $data,
+                "Seed is not canonical: {}",
+                escape_byte_string($data)
+            );
+        }};
+    }
+
+    #[test]
+    fn test_seeds() {
+        let seeds = seeds();
+        assert!(!seeds.is_empty());
+
+        let mut names = HashSet::new();
+
+        for seed in seeds {
+            assert!(names.insert((seed.kind, seed.name)), "{seed:?}");
+
+            match seed.kind {
+                SeedKind::Greeting => roundtrip!(GreetingCodec, seed.data),
+                SeedKind::Command => roundtrip!(CommandCodec, seed.data),
+                SeedKind::Response => roundtrip!(ResponseCodec, seed.data),
+                SeedKind::AuthenticateData => roundtrip!(AuthenticateDataCodec, seed.data),
+                SeedKind::IdleDone => roundtrip!(IdleDoneCodec, seed.data),
+            }
+        }
+    }
+
+    #[test]
+    fn test_write_corpus() {
+        let dir = std::env::temp_dir().join(format!("imap-codec-corpus-{}", std::process::id()));
+
+        let count = write_corpus(&dir).unwrap();
+        assert_eq!(count, seeds().len());
+        assert_eq!(
+            fs::read(dir.join("command").join("noop")).unwrap(),
+            b"A NOOP\r\n"
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -19,14 +19,14 @@ use crate::decode::{IMAPErrorKind, IMAPParseError, IMAPResult};
 /// ```abnf
 /// date = date-text / DQUOTE date-text DQUOTE
 /// ```
-pub(crate) fn date(input: &[u8]) -> IMAPResult<&[u8], Option<NaiveDate>> {
+pub(crate) fn date(input: &[u8]) -> IMAPResult<'_, &[u8], Option<NaiveDate>> {
     alt((date_text, delimited(dquote, date_text, dquote)))(input)
 }
 
 /// ```abnf
 /// date-text = date-day "-" date-month "-" date-year
 /// ```
-pub(crate) fn date_text(input: &[u8]) -> IMAPResult<&[u8], Option<NaiveDate>> {
+pub(crate) fn date_text(input: &[u8]) -> IMAPResult<'_, &[u8], Option<NaiveDate>> {
     let mut parser = tuple((date_day, tag(b"-"), date_month, tag(b"-"), date_year));
 
     let (remaining, (d, _, m, _, y)) = parser(input)?;
@@ -42,7 +42,7 @@ pub(crate) fn date_text(input: &[u8]) -> IMAPResult<&[u8], Option<NaiveDate>> {
 /// ```abnf
 /// date-day = 1*2DIGIT
 /// ```
-pub(crate) fn date_day(input: &[u8]) -> IMAPResult<&[u8], u8> {
+pub(crate) fn date_day(input: &[u8]) -> IMAPResult<'_, &[u8], u8> {
     digit_1_2(input)
 }
 
@@ -51,7 +51,7 @@ pub(crate) fn date_day(input: &[u8]) -> IMAPResult<&[u8], u8> {
 ///              "May" / "Jun" / "Jul" / "Aug" /
 ///              "Sep" / "Oct" / "Nov" / "Dec"
 /// ```
-pub(crate) fn date_month(input: &[u8]) -> IMAPResult<&[u8], u8> {
+pub(crate) fn date_month(input: &[u8]) -> IMAPResult<'_, &[u8], u8> {
     alt((
         value(1, tag_no_case(b"Jan")),
         value(2, tag_no_case(b"Feb")),
@@ -71,7 +71,7 @@ pub(crate) fn date_month(input: &[u8]) -> IMAPResult<&[u8], u8> {
 /// ```abnf
 /// date-year = 4DIGIT
 /// ```
-pub(crate) fn date_year(input: &[u8]) -> IMAPResult<&[u8], u16> {
+pub(crate) fn date_year(input: &[u8]) -> IMAPResult<'_, &[u8], u16> {
     digit_4(input)
 }
 
@@ -80,7 +80,7 @@ pub(crate) fn date_year(input: &[u8]) -> IMAPResult<&[u8], u16> {
 /// ```abnf
 /// time = 2DIGIT ":" 2DIGIT ":" 2DIGIT
 /// ```
-pub(crate) fn time(input: &[u8]) -> IMAPResult<&[u8], Option<NaiveTime>> {
+pub(crate) fn time(input: &[u8]) -> IMAPResult<'_, &[u8], Option<NaiveTime>> {
     let mut parser = tuple((digit_2, tag(b":"), digit_2, tag(b":"), digit_2));
 
     let (remaining, (h, _, m, _, s)) = parser(input)?;
@@ -98,7 +98,7 @@ pub(crate) fn time(input: &[u8]) -> IMAPResult<&[u8], Option<NaiveTime>> {
 ///              zone
 ///             DQUOTE
 /// ```
-pub(crate) fn date_time(input: &[u8]) -> IMAPResult<&[u8], DateTime> {
+pub(crate) fn date_time(input: &[u8]) -> IMAPResult<'_, &[u8], DateTime> {
     let mut parser = delimited(
         dquote,
         tuple((
@@ -144,7 +144,7 @@ pub(crate) fn date_time(input: &[u8]) -> IMAPResult<&[u8], DateTime> {
 /// ```abnf
 /// date-day-fixed = (SP DIGIT) / 2DIGIT
 /// ```
-pub(crate) fn date_day_fixed(input: &[u8]) -> IMAPResult<&[u8], u8> {
+pub(crate) fn date_day_fixed(input: &[u8]) -> IMAPResult<'_, &[u8], u8> {
     alt((
         map(
             preceded(sp, take_while_m_n(1, 1, is_digit)),
@@ -163,7 +163,7 @@ pub(crate) fn date_day_fixed(input: &[u8]) -> IMAPResult<&[u8], u8> {
 /// ```abnf
 /// zone = ("+" / "-") 4DIGIT
 /// ```
-pub(crate) fn zone(input: &[u8]) -> IMAPResult<&[u8], Option<FixedOffset>> {
+pub(crate) fn zone(input: &[u8]) -> IMAPResult<'_, &[u8], Option<FixedOffset>> {
     let mut parser = tuple((alt((char('+'), char('-'))), digit_2, digit_2));
 
     let (remaining, (sign, hh, mm)) = parser(input)?;
@@ -179,7 +179,7 @@ pub(crate) fn zone(input: &[u8]) -> IMAPResult<&[u8], Option<FixedOffset>> {
     Ok((remaining, zone))
 }
 
-fn digit_1_2(input: &[u8]) -> IMAPResult<&[u8], u8> {
+fn digit_1_2(input: &[u8]) -> IMAPResult<'_, &[u8], u8> {
     map_res(
         map(take_while_m_n(1, 2, is_digit), |bytes| {
             // # Safety
@@ -191,7 +191,7 @@ fn digit_1_2(input: &[u8]) -> IMAPResult<&[u8], u8> {
     )(input)
 }
 
-fn digit_2(input: &[u8]) -> IMAPResult<&[u8], u8> {
+fn digit_2(input: &[u8]) -> IMAPResult<'_, &[u8], u8> {
     map_res(
         map(take_while_m_n(2, 2, is_digit), |bytes| {
             // # Safety
@@ -203,7 +203,7 @@ fn digit_2(input: &[u8]) -> IMAPResult<&[u8], u8> {
     )(input)
 }
 
-fn digit_4(input: &[u8]) -> IMAPResult<&[u8], u16> {
+fn digit_4(input: &[u8]) -> IMAPResult<'_, &[u8], u16> {
     map_res(
         map(take_while_m_n(4, 4, is_digit), |bytes| {
             // # Safety
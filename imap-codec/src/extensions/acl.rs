@@ -0,0 +1,384 @@
+//! The IMAP4 Access Control List (ACL) Extension
+
+use std::io::Write;
+
+use abnf_core::streaming::sp;
+use imap_types::{
+    command::CommandBody,
+    core::AString,
+    extensions::acl::{AclEntry, AclRights},
+    response::Data,
+};
+use nom::{
+    branch::alt,
+    bytes::streaming::tag_no_case,
+    combinator::map,
+    error::ErrorKind,
+    multi::many0,
+    sequence::{preceded, separated_pair, tuple},
+};
+
+use crate::{
+    core::astring,
+    decode::{IMAPErrorKind, IMAPParseError, IMAPResult},
+    encode::{EncodeContext, EncodeIntoContext},
+    mailbox::mailbox,
+};
+
+// ----- Command -----
+
+/// ```abnf
+/// command-auth =/ setacl / deleteacl / getacl / listrights / myrights
+/// ```
+pub(crate) fn acl(input: &[u8]) -> IMAPResult<'_, &[u8], CommandBody<'_>> {
+    alt((setacl, deleteacl, getacl, listrights, myrights))(input)
+}
+
+/// ```abnf
+/// setacl = "SETACL" SP mailbox SP identifier SP mod-rights
+/// ```
+pub(crate) fn setacl(input: &[u8]) -> IMAPResult<'_, &[u8], CommandBody<'_>> {
+    let mut parser = tuple((
+        tag_no_case("SETACL"),
+        preceded(sp, mailbox),
+        preceded(sp, identifier),
+        preceded(sp, rights),
+    ));
+
+    let (remaining, (_, mailbox, identifier, rights)) = parser(input)?;
+
+    Ok((
+        remaining,
+        CommandBody::SetAcl {
+            mailbox,
+            identifier,
+            rights,
+        },
+    ))
+}
+
+/// ```abnf
+/// deleteacl = "DELETEACL" SP mailbox SP identifier
+/// ```
+pub(crate) fn deleteacl(input: &[u8]) -> IMAPResult<'_, &[u8], CommandBody<'_>> {
+    let mut parser = tuple((
+        tag_no_case("DELETEACL"),
+        preceded(sp, mailbox),
+        preceded(sp, identifier),
+    ));
+
+    let (remaining, (_, mailbox, identifier)) = parser(input)?;
+
+    Ok((
+        remaining,
+        CommandBody::DeleteAcl {
+            mailbox,
+            identifier,
+        },
+    ))
+}
+
+/// ```abnf
+/// getacl = "GETACL" SP mailbox
+/// ```
+pub(crate) fn getacl(input: &[u8]) -> IMAPResult<'_, &[u8], CommandBody<'_>> {
+    let mut parser = tuple((tag_no_case("GETACL"), preceded(sp, mailbox)));
+
+    let (remaining, (_, mailbox)) = parser(input)?;
+
+    Ok((remaining, CommandBody::GetAcl { mailbox }))
+}
+
+/// ```abnf
+/// listrights = "LISTRIGHTS" SP mailbox SP identifier
+/// ```
+pub(crate) fn listrights(input: &[u8]) -> IMAPResult<'_, &[u8], CommandBody<'_>> {
+    let mut parser = tuple((
+        tag_no_case("LISTRIGHTS"),
+        preceded(sp, mailbox),
+        preceded(sp, identifier),
+    ));
+
+    let (remaining, (_, mailbox, identifier)) = parser(input)?;
+
+    Ok((
+        remaining,
+        CommandBody::ListRights {
+            mailbox,
+            identifier,
+        },
+    ))
+}
+
+/// ```abnf
+/// myrights = "MYRIGHTS" SP mailbox
+/// ```
+pub(crate) fn myrights(input: &[u8]) -> IMAPResult<'_, &[u8], CommandBody<'_>> {
+    let mut parser = tuple((tag_no_case("MYRIGHTS"), preceded(sp, mailbox)));
+
+    let (remaining, (_, mailbox)) = parser(input)?;
+
+    Ok((remaining, CommandBody::MyRights { mailbox }))
+}
+
+/// ```abnf
+/// identifier = astring
+/// ```
+#[inline]
+pub(crate) fn identifier(input: &[u8]) -> IMAPResult<'_, &[u8], AString<'_>> {
+    astring(input)
+}
+
+/// ```abnf
+/// rights     = astring
+///              ; only lowercase ASCII letters and digits are allowed.
+///
+/// mod-rights = astring
+///              ; +rights to add, -rights to remove
+///              ; rights to replace
+/// ```
+///
+/// Note: `rights` and `mod-rights` share this parser. The modifier is only meaningful in SETACL.
+pub(crate) fn rights(input: &[u8]) -> IMAPResult<'_, &[u8], AclRights<'_>> {
+    let (remaining, astring) = astring(input)?;
+
+    match AclRights::try_from(astring) {
+        Ok(rights) => Ok((remaining, rights)),
+        Err(_) => Err(nom::Err::Failure(IMAPParseError {
+            input,
+            kind: IMAPErrorKind::Nom(ErrorKind::Verify),
+        })),
+    }
+}
+
+// ----- Response -----
+
+/// ```abnf
+/// mailbox-data =/ acl-data / listrights-data / myrights-data
+/// ```
+pub(crate) fn acl_data(input: &[u8]) -> IMAPResult<'_, &[u8], Data<'_>> {
+    alt((acl_response, listrights_response, myrights_response))(input)
+}
+
+/// ```abnf
+/// acl-data = "ACL" SP mailbox *(SP identifier SP rights)
+/// ```
+pub(crate) fn acl_response(input: &[u8]) -> IMAPResult<'_, &[u8], Data<'_>> {
+    let mut parser = tuple((
+        tag_no_case("ACL"),
+        preceded(sp, mailbox),
+        many0(preceded(
+            sp,
+            map(
+                separated_pair(identifier, sp, rights),
+                |(identifier, rights)| AclEntry { identifier, rights },
+            ),
+        )),
+    ));
+
+    let (remaining, (_, mailbox, entries)) = parser(input)?;
+
+    Ok((remaining, Data::Acl { mailbox, entries }))
+}
+
+/// ```abnf
+/// listrights-data = "LISTRIGHTS" SP mailbox SP identifier SP rights *(SP rights)
+/// ```
+pub(crate) fn listrights_response(input: &[u8]) -> IMAPResult<'_, &[u8], Data<'_>> {
+    let mut parser = tuple((
+        tag_no_case("LISTRIGHTS"),
+        preceded(sp, mailbox),
+        preceded(sp, identifier),
+        preceded(sp, rights),
+        many0(preceded(sp, rights)),
+    ));
+
+    let (remaining, (_, mailbox, identifier, required, optional)) = parser(input)?;
+
+    Ok((
+        remaining,
+        Data::ListRights {
+            mailbox,
+            identifier,
+            required,
+            optional,
+        },
+    ))
+}
+
+/// ```abnf
+/// myrights-data = "MYRIGHTS" SP mailbox SP rights
+/// ```
+pub(crate) fn myrights_response(input: &[u8]) -> IMAPResult<'_, &[u8], Data<'_>> {
+    let mut parser = tuple((
+        tag_no_case("MYRIGHTS"),
+        preceded(sp, mailbox),
+        preceded(sp, rights),
+    ));
+
+    let (remaining, (_, mailbox, rights)) = parser(input)?;
+
+    Ok((remaining, Data::MyRights { mailbox, rights }))
+}
+
+impl<'a> EncodeIntoContext for AclRights<'a> {
+    fn encode_ctx(&self, ctx: &mut EncodeContext) -> std::io::Result<()> {
+        self.inner().encode_ctx(ctx)
+    }
+}
+
+impl<'a> EncodeIntoContext for AclEntry<'a> {
+    fn encode_ctx(&self, ctx: &mut EncodeContext) -> std::io::Result<()> {
+        self.identifier.encode_ctx(ctx)?;
+        ctx.write_all(b" ")?;
+        self.rights.encode_ctx(ctx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use imap_types::{
+        command::{Command, CommandBody},
+        core::AString,
+        extensions::acl::{AclEntry, AclRights},
+        mailbox::Mailbox,
+        response::{Data, Response},
+    };
+
+    use super::*;
+    use crate::testing::{kat_inverse_command, kat_inverse_response, known_answer_test_encode};
+
+    #[test]
+    fn test_encode_setacl() {
+        known_answer_test_encode((
+            CommandBody::set_acl("INBOX", "Fred", "+rw")
+                .unwrap()
+                .tag("A")
+                .unwrap(),
+            b"A SETACL INBOX Fred +rw\r\n".as_ref(),
+        ));
+    }
+
+    #[test]
+    fn test_kat_inverse_command_acl() {
+        kat_inverse_command(&[
+            (
+                b"A SETACL INBOX/Drafts John lrswicda\r\n".as_ref(),
+                b"".as_ref(),
+                Command::new(
+                    "A",
+                    CommandBody::set_acl("INBOX/Drafts", "John", "lrswicda").unwrap(),
+                )
+                .unwrap(),
+            ),
+            (
+                b"A SETACL INBOX \"Fred Smith\" \"\"\r\n".as_ref(),
+                b"".as_ref(),
+                Command::new(
+                    "A",
+                    CommandBody::set_acl("INBOX", "Fred Smith", "").unwrap(),
+                )
+                .unwrap(),
+            ),
+            (
+                b"A DELETEACL INBOX -Fred\r\n".as_ref(),
+                b"".as_ref(),
+                Command::new("A", CommandBody::delete_acl("INBOX", "-Fred").unwrap()).unwrap(),
+            ),
+            (
+                b"A GETACL INBOX\r\n".as_ref(),
+                b"".as_ref(),
+                Command::new("A", CommandBody::get_acl("INBOX").unwrap()).unwrap(),
+            ),
+            (
+                b"A LISTRIGHTS ~/Mail/saved smith\r\n".as_ref(),
+                b"".as_ref(),
+                Command::new(
+                    "A",
+                    CommandBody::list_rights("~/Mail/saved", "smith").unwrap(),
+                )
+                .unwrap(),
+            ),
+            (
+                b"A MYRIGHTS INBOX\r\n".as_ref(),
+                b"".as_ref(),
+                Command::new("A", CommandBody::my_rights("INBOX").unwrap()).unwrap(),
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_parse_setacl_invalid_rights() {
+        assert!(setacl(b"SETACL INBOX Fred rwQ\r\n").is_err());
+        assert!(setacl(b"SETACL INBOX Fred r+w\r\n").is_err());
+    }
+
+    #[test]
+    fn test_kat_inverse_response_acl() {
+        kat_inverse_response(&[
+            (
+                b"* ACL INBOX Fred rwipsldexta -Fred wetd $team w\r\n".as_ref(),
+                b"".as_ref(),
+                Response::Data(Data::Acl {
+                    mailbox: Mailbox::Inbox,
+                    entries: vec![
+                        AclEntry::new(
+                            AString::try_from("Fred").unwrap(),
+                            AclRights::try_from("rwipsldexta").unwrap(),
+                        ),
+                        AclEntry::new(
+                            AString::try_from("-Fred").unwrap(),
+                            AclRights::try_from("wetd").unwrap(),
+                        ),
+                        AclEntry::new(
+                            AString::try_from("$team").unwrap(),
+                            AclRights::try_from("w").unwrap(),
+                        ),
+                    ],
+                }),
+            ),
+            (
+                b"* ACL INBOX\r\n".as_ref(),
+                b"".as_ref(),
+                Response::Data(Data::Acl {
+                    mailbox: Mailbox::Inbox,
+                    entries: vec![],
+                }),
+            ),
+            (
+                b"* LISTRIGHTS ~/Mail/saved smith la r swicdkxte\r\n".as_ref(),
+                b"".as_ref(),
+                Response::Data(Data::ListRights {
+                    mailbox: Mailbox::try_from("~/Mail/saved").unwrap(),
+                    identifier: AString::try_from("smith").unwrap(),
+                    required: AclRights::try_from("la").unwrap(),
+                    optional: vec![
+                        AclRights::try_from("r").unwrap(),
+                        AclRights::try_from("swicdkxte").unwrap(),
+                    ],
+                }),
+            ),
+            (
+                b"* LISTRIGHTS archive/imap anyone \"\" l r s 0 1\r\n".as_ref(),
+                b"".as_ref(),
+                Response::Data(Data::ListRights {
+                    mailbox: Mailbox::try_from("archive/imap").unwrap(),
+                    identifier: AString::try_from("anyone").unwrap(),
+                    required: AclRights::try_from("").unwrap(),
+                    optional: ["l", "r", "s", "0", "1"]
+                        .into_iter()
+                        .map(|rights| AclRights::try_from(rights).unwrap())
+                        .collect(),
+                }),
+            ),
+            (
+                b"* MYRIGHTS INBOX rwiptsldaex\r\n".as_ref(),
+                b"".as_ref(),
+                Response::Data(Data::MyRights {
+                    mailbox: Mailbox::Inbox,
+                    rights: AclRights::try_from("rwiptsldaex").unwrap(),
+                }),
+            ),
+        ]);
+    }
+}
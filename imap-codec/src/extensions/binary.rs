@@ -29,7 +29,7 @@ use crate::{
 /// ;; <number> represents the number of OCTETs in the response string.
 /// ;; The "+" is only allowed when both LITERAL+ and BINARY extensions are supported by the server.
 /// ```
-pub(crate) fn literal8(input: &[u8]) -> IMAPResult<&[u8], Literal8> {
+pub(crate) fn literal8(input: &[u8]) -> IMAPResult<'_, &[u8], Literal8<'_>> {
     let (remaining, (length, mode)) = terminated(
         delimited(
             tag(b"~{"),
@@ -74,6 +74,12 @@ impl<'a> EncodeIntoContext for LiteralOrLiteral8<'a> {
         match self {
             LiteralOrLiteral8::Literal(lit) => lit.encode_ctx(ctx),
             LiteralOrLiteral8::Literal8(lit8) => lit8.encode_ctx(ctx),
+            #[cfg(feature = "ext_utf8")]
+            LiteralOrLiteral8::Utf8(lit8) => {
+                ctx.write_all(b"UTF8 (")?;
+                lit8.encode_ctx(ctx)?;
+                ctx.write_all(b")")
+            }
         }
     }
 }
@@ -85,18 +91,14 @@ impl<'a> EncodeIntoContext for Literal8<'a> {
             LiteralMode::NonSync => write!(ctx, "~{{{}+}}\r\n", self.data.len())?,
         }
 
-        ctx.push_line();
-        ctx.write_all(&self.data)?;
-        ctx.push_literal(self.mode);
-
-        Ok(())
+        ctx.write_literal(&self.data, self.mode)
     }
 }
 
 /// ```abnf
 /// section-binary = "[" [section-part] "]"
 /// ```
-pub(crate) fn section_binary(input: &[u8]) -> IMAPResult<&[u8], Vec<NonZeroU32>> {
+pub(crate) fn section_binary(input: &[u8]) -> IMAPResult<'_, &[u8], Vec<NonZeroU32>> {
     delimited(
         tag("["),
         // We use `Vec<T>` instead of `Option<Vec1<T>>`.
@@ -110,10 +112,77 @@ pub(crate) fn section_binary(input: &[u8]) -> IMAPResult<&[u8], Vec<NonZeroU32>>
 /// ```abnf
 /// partial = "<" number "." nz-number ">"
 /// ```
-pub(crate) fn partial(input: &[u8]) -> IMAPResult<&[u8], (u32, NonZeroU32)> {
+pub(crate) fn partial(input: &[u8]) -> IMAPResult<'_, &[u8], (u32, NonZeroU32)> {
     delimited(
         tag(b"<"),
         separated_pair(number, tag(b"."), nz_number),
         tag(b">"),
     )(input)
 }
+
+#[cfg(test)]
+mod tests {
+    use imap_types::{
+        command::{Command, CommandBody},
+        core::NString8,
+        fetch::{MessageDataItem, MessageDataItemName},
+        response::{Data, Response},
+    };
+
+    use super::*;
+    use crate::testing::{kat_inverse_command, kat_inverse_response};
+
+    #[test]
+    fn test_kat_inverse_command_fetch_binary() {
+        kat_inverse_command(&[(
+            b"A FETCH 1 (BINARY[1.2]<0.5> BINARY.SIZE[1])\r\n".as_ref(),
+            b"".as_ref(),
+            Command::new(
+                "A",
+                CommandBody::fetch(
+                    "1",
+                    vec![
+                        MessageDataItemName::Binary {
+                            section: vec![NonZeroU32::new(1).unwrap(), NonZeroU32::new(2).unwrap()],
+                            partial: Some((0, NonZeroU32::new(5).unwrap())),
+                            peek: false,
+                        },
+                        MessageDataItemName::BinarySize {
+                            section: vec![NonZeroU32::new(1).unwrap()],
+                        },
+                    ],
+                    false,
+                )
+                .unwrap(),
+            )
+            .unwrap(),
+        )]);
+    }
+
+    #[test]
+    fn test_kat_inverse_response_fetch_binary_with_nul() {
+        kat_inverse_response(&[(
+            b"* 1 FETCH (BINARY[] ~{5}\r\n\x00ab\x00c BINARY.SIZE[] 5)\r\n".as_ref(),
+            b"".as_ref(),
+            Response::Data(
+                Data::fetch(
+                    1,
+                    vec![
+                        MessageDataItem::Binary {
+                            section: vec![],
+                            value: NString8::Literal8(Literal8 {
+                                data: Cow::Borrowed(b"\x00ab\x00c"),
+                                mode: LiteralMode::Sync,
+                            }),
+                        },
+                        MessageDataItem::BinarySize {
+                            section: vec![],
+                            size: 5,
+                        },
+                    ],
+                )
+                .unwrap(),
+            ),
+        )]);
+    }
+}
@@ -21,12 +21,12 @@ use crate::{
 };
 
 /// `algorithm = "DEFLATE"`
-pub(crate) fn algorithm(input: &[u8]) -> IMAPResult<&[u8], CompressionAlgorithm> {
+pub(crate) fn algorithm(input: &[u8]) -> IMAPResult<'_, &[u8], CompressionAlgorithm> {
     value(CompressionAlgorithm::Deflate, tag_no_case("DEFLATE"))(input)
 }
 
 /// `compress = "COMPRESS" SP algorithm`
-pub(crate) fn compress(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
+pub(crate) fn compress(input: &[u8]) -> IMAPResult<'_, &[u8], CommandBody<'_>> {
     map(preceded(tag_no_case("COMPRESS "), algorithm), |algorithm| {
         CommandBody::Compress { algorithm }
     })(input)
@@ -0,0 +1,379 @@
+use std::{io::Write, num::NonZeroU64};
+
+use abnf_core::streaming::sp;
+use imap_types::{
+    core::Vec1,
+    extensions::condstore_qresync::{FetchModifier, StoreModifier},
+    fetch::MessageDataItem,
+    response::Code,
+    status::StatusDataItem,
+};
+use nom::{
+    branch::alt,
+    bytes::streaming::{tag, tag_no_case},
+    combinator::{map, map_res, value, verify},
+    multi::separated_list1,
+    sequence::{delimited, preceded, tuple},
+};
+
+#[cfg(feature = "ext_partial")]
+use crate::extensions::partial::fetch_modifier_partial;
+use crate::{
+    core::number64,
+    decode::IMAPResult,
+    encode::{EncodeContext, EncodeIntoContext},
+    sequence::sequence_set,
+};
+
+/// ```abnf
+/// mod-sequence-value = 1*DIGIT
+///                      ;; Positive unsigned 63-bit integer
+///                      ;; (mod-sequence)
+///                      ;; (1 <= n <= 9,223,372,036,854,775,807).
+/// ```
+pub(crate) fn mod_sequence_value(input: &[u8]) -> IMAPResult<'_, &[u8], NonZeroU64> {
+    map_res(mod_sequence_valzer, NonZeroU64::try_from)(input)
+}
+
+/// ```abnf
+/// mod-sequence-valzer = "0" / mod-sequence-value
+/// ```
+pub(crate) fn mod_sequence_valzer(input: &[u8]) -> IMAPResult<'_, &[u8], u64> {
+    verify(number64, |value| *value <= i64::MAX as u64)(input)
+}
+
+/// ```abnf
+/// status-att-val =/ "HIGHESTMODSEQ" SP mod-sequence-value
+/// ```
+pub(crate) fn status_att_val_highestmodseq(input: &[u8]) -> IMAPResult<'_, &[u8], StatusDataItem> {
+    map(
+        preceded(tag_no_case(b"HIGHESTMODSEQ "), mod_sequence_value),
+        StatusDataItem::HighestModSeq,
+    )(input)
+}
+
+/// ```abnf
+/// fetch-modifiers = SP "(" fetch-modifier *(SP fetch-modifier) ")"
+///
+/// fetch-modifier = chgsince-fetch-mod /
+///                  "PARTIAL" SP partial-range ; RFC 9394
+///
+/// chgsince-fetch-mod = "CHANGEDSINCE" SP mod-sequence-value
+/// ```
+pub(crate) fn fetch_modifiers(input: &[u8]) -> IMAPResult<'_, &[u8], Vec1<FetchModifier>> {
+    preceded(
+        sp,
+        delimited(
+            tag(b"("),
+            map(
+                separated_list1(
+                    sp,
+                    alt((
+                        map(
+                            preceded(tag_no_case(b"CHANGEDSINCE "), mod_sequence_value),
+                            FetchModifier::ChangedSince,
+                        ),
+                        #[cfg(feature = "ext_partial")]
+                        map(fetch_modifier_partial, FetchModifier::Partial),
+                    )),
+                ),
+                // Safety: Safe because we use `separated_list1` above.
+                |modifiers| Vec1::try_from(modifiers).unwrap(),
+            ),
+            tag(b")"),
+        ),
+    )(input)
+}
+
+/// ```abnf
+/// store-modifiers = SP "(" store-modifier *(SP store-modifier) ")"
+///
+/// store-modifier = "UNCHANGEDSINCE" SP mod-sequence-valzer
+/// ```
+pub(crate) fn store_modifiers(input: &[u8]) -> IMAPResult<'_, &[u8], Vec1<StoreModifier>> {
+    preceded(
+        sp,
+        delimited(
+            tag(b"("),
+            map(
+                separated_list1(
+                    sp,
+                    map(
+                        preceded(tag_no_case(b"UNCHANGEDSINCE "), mod_sequence_valzer),
+                        StoreModifier::UnchangedSince,
+                    ),
+                ),
+                // Safety: Safe because we use `separated_list1` above.
+                |modifiers| Vec1::try_from(modifiers).unwrap(),
+            ),
+            tag(b")"),
+        ),
+    )(input)
+}
+
+/// ```abnf
+/// fetch-mod-resp = "MODSEQ" SP "(" permsg-modsequence ")"
+///
+/// permsg-modsequence = mod-sequence-value
+/// ```
+pub(crate) fn fetch_mod_resp(input: &[u8]) -> IMAPResult<'_, &[u8], MessageDataItem<'_>> {
+    map(
+        preceded(
+            tag_no_case(b"MODSEQ "),
+            delimited(tag(b"("), mod_sequence_value, tag(b")")),
+        ),
+        MessageDataItem::ModSeq,
+    )(input)
+}
+
+/// ```abnf
+/// resp-text-code =/ "HIGHESTMODSEQ" SP mod-sequence-value /
+///                   "NOMODSEQ" /
+///                   "MODIFIED" SP sequence-set
+/// ```
+pub(crate) fn resp_text_code_condstore(input: &[u8]) -> IMAPResult<'_, &[u8], Code<'_>> {
+    alt((
+        map(
+            tuple((tag_no_case(b"HIGHESTMODSEQ"), sp, mod_sequence_value)),
+            |(_, _, modseq)| Code::HighestModSeq(modseq),
+        ),
+        value(Code::NoModSeq, tag_no_case(b"NOMODSEQ")),
+        map(
+            tuple((tag_no_case(b"MODIFIED"), sp, sequence_set(usize::MAX))),
+            |(_, _, sequence_set)| Code::Modified(sequence_set),
+        ),
+    ))(input)
+}
+
+impl EncodeIntoContext for FetchModifier {
+    fn encode_ctx(&self, ctx: &mut EncodeContext) -> std::io::Result<()> {
+        match self {
+            FetchModifier::ChangedSince(modseq) => write!(ctx, "CHANGEDSINCE {modseq}"),
+            #[cfg(feature = "ext_partial")]
+            FetchModifier::Partial(range) => {
+                ctx.write_all(b"PARTIAL ")?;
+                range.encode_ctx(ctx)
+            }
+        }
+    }
+}
+
+impl EncodeIntoContext for StoreModifier {
+    fn encode_ctx(&self, ctx: &mut EncodeContext) -> std::io::Result<()> {
+        match self {
+            StoreModifier::UnchangedSince(modseq) => write!(ctx, "UNCHANGEDSINCE {modseq}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU32;
+
+    use imap_types::{
+        command::{Command, CommandBody},
+        core::Vec1,
+        fetch::{MacroOrMessageDataItemNames, MessageDataItemName},
+        flag::{Flag, FlagFetch, StoreResponse, StoreType},
+        mailbox::Mailbox,
+        response::{Data, Response, Status},
+        sequence::SequenceSet,
+    };
+
+    use super::*;
+    use crate::{
+        decode::Decoder,
+        encode::Encoder,
+        testing::{kat_inverse_command, kat_inverse_response},
+        CommandCodec, ResponseCodec,
+    };
+
+    #[test]
+    fn test_kat_inverse_command_condstore() {
+        kat_inverse_command(&[
+            (
+                b"A STORE 7,5,9 (UNCHANGEDSINCE 320162338) +FLAGS.SILENT (\\Deleted)\r\n".as_ref(),
+                b"".as_ref(),
+                Command::new(
+                    "A",
+                    CommandBody::StoreWithModifiers {
+                        sequence_set: SequenceSet::try_from("7,5,9").unwrap(),
+                        kind: StoreType::Add,
+                        response: StoreResponse::Silent,
+                        flags: vec![Flag::Deleted],
+                        uid: false,
+                        modifiers: Vec1::from(StoreModifier::UnchangedSince(320162338)),
+                    },
+                )
+                .unwrap(),
+            ),
+            (
+                b"A UID STORE 1 (UNCHANGEDSINCE 0) FLAGS (\\Seen)\r\n",
+                b"",
+                Command::new(
+                    "A",
+                    CommandBody::StoreWithModifiers {
+                        sequence_set: SequenceSet::try_from(1).unwrap(),
+                        kind: StoreType::Replace,
+                        response: StoreResponse::Answer,
+                        flags: vec![Flag::Seen],
+                        uid: true,
+                        modifiers: Vec1::from(StoreModifier::UnchangedSince(0)),
+                    },
+                )
+                .unwrap(),
+            ),
+            (
+                b"A FETCH 1:* (FLAGS MODSEQ) (CHANGEDSINCE 12345)\r\n",
+                b"",
+                Command::new(
+                    "A",
+                    CommandBody::FetchWithModifiers {
+                        sequence_set: SequenceSet::try_from("1:*").unwrap(),
+                        macro_or_item_names: MacroOrMessageDataItemNames::MessageDataItemNames(
+                            vec![MessageDataItemName::Flags, MessageDataItemName::ModSeq],
+                        ),
+                        uid: false,
+                        modifiers: Vec1::from(FetchModifier::ChangedSince(
+                            NonZeroU64::new(12345).unwrap(),
+                        )),
+                    },
+                )
+                .unwrap(),
+            ),
+            (
+                b"A FETCH 1 (FLAGS MODSEQ)\r\n",
+                b"",
+                CommandBody::fetch(
+                    1,
+                    vec![MessageDataItemName::Flags, MessageDataItemName::ModSeq],
+                    false,
+                )
+                .unwrap()
+                .tag("A")
+                .unwrap(),
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_kat_inverse_response_condstore() {
+        kat_inverse_response(&[
+            (
+                b"* 4 FETCH (MODSEQ (320162397))\r\n".as_ref(),
+                b"".as_ref(),
+                Response::Data(Data::Fetch {
+                    seq: NonZeroU32::new(4).unwrap(),
+                    items: Vec1::from(MessageDataItem::ModSeq(NonZeroU64::new(320162397).unwrap())),
+                }),
+            ),
+            (
+                b"* 7 FETCH (FLAGS (\\Seen) MODSEQ (320162342))\r\n",
+                b"",
+                Response::Data(Data::Fetch {
+                    seq: NonZeroU32::new(7).unwrap(),
+                    items: Vec1::try_from(vec![
+                        MessageDataItem::Flags(vec![FlagFetch::Flag(Flag::Seen)]),
+                        MessageDataItem::ModSeq(NonZeroU64::new(320162342).unwrap()),
+                    ])
+                    .unwrap(),
+                }),
+            ),
+            (
+                b"* OK [HIGHESTMODSEQ 715194045007] Highest\r\n",
+                b"",
+                Response::Status(
+                    Status::ok(
+                        None,
+                        Some(Code::HighestModSeq(NonZeroU64::new(715194045007).unwrap())),
+                        "Highest",
+                    )
+                    .unwrap(),
+                ),
+            ),
+            (
+                b"* STATUS blurdybloop (MESSAGES 231 UIDNEXT 44292 HIGHESTMODSEQ 7011231777)\r\n",
+                b"",
+                Response::Data(Data::Status {
+                    mailbox: Mailbox::try_from("blurdybloop").unwrap(),
+                    items: vec![
+                        StatusDataItem::Messages(231),
+                        StatusDataItem::UidNext(NonZeroU32::new(44292).unwrap()),
+                        StatusDataItem::HighestModSeq(NonZeroU64::new(7011231777).unwrap()),
+                    ]
+                    .into(),
+                }),
+            ),
+            (
+                b"* OK [NOMODSEQ] Sorry, this mailbox format doesn't support modsequences\r\n",
+                b"",
+                Response::Status(
+                    Status::ok(
+                        None,
+                        Some(Code::NoModSeq),
+                        "Sorry, this mailbox format doesn't support modsequences",
+                    )
+                    .unwrap(),
+                ),
+            ),
+            (
+                b"A OK [MODIFIED 7,9] Conditional STORE failed\r\n",
+                b"",
+                Response::Status(
+                    Status::ok(
+                        Some("A".try_into().unwrap()),
+                        Some(Code::Modified(SequenceSet::try_from("7,9").unwrap())),
+                        "Conditional STORE failed",
+                    )
+                    .unwrap(),
+                ),
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_refetch_modified() {
+        let (_, rsp) = ResponseCodec::default()
+            .decode(b"A OK [MODIFIED 7,9] Conditional STORE failed\r\n")
+            .unwrap();
+        let Response::Status(status) = rsp else {
+            panic!("expected status, got {rsp:?}");
+        };
+
+        let (_, store) = CommandCodec::default()
+            .decode(b"A STORE 7:9 (UNCHANGEDSINCE 12345) +FLAGS (\\Seen)\r\n")
+            .unwrap();
+        let fetch = Command::new("B", store.body.refetch_modified(&status).unwrap()).unwrap();
+
+        assert_eq!(
+            CommandCodec::default().encode(&fetch).dump(),
+            b"B FETCH 7,9 (FLAGS MODSEQ)\r\n"
+        );
+    }
+
+    #[test]
+    fn test_parse_mod_sequence_value() {
+        assert!(mod_sequence_value(b"0 ").is_err());
+        assert_eq!(
+            mod_sequence_value(b"9223372036854775807 ").unwrap().1,
+            NonZeroU64::new(9223372036854775807).unwrap()
+        );
+        assert!(mod_sequence_value(b"9223372036854775808 ").is_err());
+        assert!(mod_sequence_value(b"18446744073709551615 ").is_err());
+    }
+
+    #[test]
+    fn test_parse_store_modifiers() {
+        assert_eq!(
+            store_modifiers(b" (UNCHANGEDSINCE 0) ").unwrap().1,
+            Vec1::from(StoreModifier::UnchangedSince(0))
+        );
+        assert_eq!(
+            store_modifiers(b" (UNCHANGEDSINCE 9223372036854775807) ")
+                .unwrap()
+                .1,
+            Vec1::from(StoreModifier::UnchangedSince(9223372036854775807))
+        );
+        assert!(store_modifiers(b" (UNCHANGEDSINCE 9223372036854775808) ").is_err());
+    }
+}
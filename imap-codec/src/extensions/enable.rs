@@ -34,7 +34,7 @@ use crate::{
 ///
 /// command-any =/ enable
 /// ```
-pub(crate) fn enable(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
+pub(crate) fn enable(input: &[u8]) -> IMAPResult<'_, &[u8], CommandBody<'_>> {
     let mut parser = tuple((
         tag_no_case("ENABLE"),
         many1(preceded(sp, capability_enable)),
@@ -50,12 +50,12 @@ pub(crate) fn enable(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
     ))
 }
 
-pub(crate) fn capability_enable(input: &[u8]) -> IMAPResult<&[u8], CapabilityEnable> {
+pub(crate) fn capability_enable(input: &[u8]) -> IMAPResult<'_, &[u8], CapabilityEnable<'_>> {
     map(atom, CapabilityEnable::from)(input)
 }
 
 /// `enable-data = "ENABLED" *(SP capability)`
-pub(crate) fn enable_data(input: &[u8]) -> IMAPResult<&[u8], Data> {
+pub(crate) fn enable_data(input: &[u8]) -> IMAPResult<'_, &[u8], Data<'_>> {
     let mut parser = tuple((
         tag_no_case(b"ENABLED"),
         many0(preceded(sp, capability_enable)),
@@ -78,10 +78,11 @@ mod tests {
         command::Command,
         core::Atom,
         extensions::enable::{CapabilityEnable, Utf8Kind},
+        response::Response,
     };
 
     use super::*;
-    use crate::testing::kat_inverse_command;
+    use crate::{decode::Decoder, testing::kat_inverse_command, ResponseCodec};
 
     #[test]
     fn test_parse_enable() {
@@ -92,6 +93,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_data_is_enabled() {
+        let (_, rsp) = ResponseCodec::default()
+            .decode(b"* ENABLED CONDSTORE QRESYNC\r\n")
+            .unwrap();
+        let Response::Data(data) = rsp else {
+            panic!("expected data, got {rsp:?}");
+        };
+
+        for capability in ["CONDSTORE", "QRESYNC", "qresync"] {
+            assert!(data.is_enabled(&CapabilityEnable::try_from(capability).unwrap()));
+        }
+        assert!(!data.is_enabled(&CapabilityEnable::Utf8(Utf8Kind::Accept)));
+        assert!(!data.is_enabled(&CapabilityEnable::try_from("METADATA").unwrap()));
+        assert!(!Data::Exists(1).is_enabled(&CapabilityEnable::try_from("QRESYNC").unwrap()));
+    }
+
     #[test]
     fn test_kat_inverse_command_enable() {
         kat_inverse_command(&[
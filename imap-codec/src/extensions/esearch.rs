@@ -0,0 +1,295 @@
+//! IMAP4 Extension for Returning SEARCH Results in Extended Format
+
+// Additional changes:
+//
+// search = "SEARCH" [search-return-opts] SP search-program
+// mailbox-data =/ "ESEARCH" [search-correlator] [SP "UID"] *(SP search-return-data)
+
+use std::{borrow::Cow, io::Write};
+
+use abnf_core::streaming::sp;
+use imap_types::{
+    core::{Tag, Vec1},
+    extensions::esearch::{ESearchReturn, SearchReturnOption},
+    response::Data,
+};
+use nom::{
+    branch::alt,
+    bytes::streaming::{tag, tag_no_case},
+    combinator::{map, opt, value},
+    error::ErrorKind,
+    multi::{many0, separated_list0},
+    sequence::{delimited, preceded, tuple},
+};
+
+#[cfg(feature = "ext_partial")]
+use crate::extensions::partial::{search_return_data_partial, search_return_opt_partial};
+use crate::{
+    core::{number, nz_number, string},
+    decode::{IMAPErrorKind, IMAPParseError, IMAPResult},
+    encode::{EncodeContext, EncodeIntoContext},
+    sequence::sequence_set,
+};
+
+/// ```abnf
+/// search-return-opts = SP "RETURN" SP "(" [search-return-opt *(SP search-return-opt)] ")"
+/// ```
+///
+/// Note: `RETURN ()` is equivalent to `RETURN (ALL)` and parsed as such.
+pub(crate) fn search_return_opts(input: &[u8]) -> IMAPResult<'_, &[u8], Vec1<SearchReturnOption>> {
+    let mut parser = preceded(
+        tag_no_case(" RETURN "),
+        delimited(tag("("), separated_list0(sp, search_return_opt), tag(")")),
+    );
+
+    let (remaining, options) = parser(input)?;
+
+    match Vec1::try_from(options) {
+        Ok(options) => Ok((remaining, options)),
+        Err(_) => Ok((remaining, Vec1::from(SearchReturnOption::All))),
+    }
+}
+
+/// ```abnf
+/// search-return-opt = "MIN" / "MAX" / "ALL" / "COUNT" /
+///                     "SAVE" /                       ; RFC 5182
+///                     "PARTIAL" SP partial-range     ; RFC 9394
+/// ```
+pub(crate) fn search_return_opt(input: &[u8]) -> IMAPResult<'_, &[u8], SearchReturnOption> {
+    alt((
+        value(SearchReturnOption::Min, tag_no_case("MIN")),
+        value(SearchReturnOption::Max, tag_no_case("MAX")),
+        value(SearchReturnOption::All, tag_no_case("ALL")),
+        value(SearchReturnOption::Count, tag_no_case("COUNT")),
+        #[cfg(feature = "ext_searchres")]
+        value(SearchReturnOption::Save, tag_no_case("SAVE")),
+        #[cfg(feature = "ext_partial")]
+        map(search_return_opt_partial, SearchReturnOption::Partial),
+    ))(input)
+}
+
+/// ```abnf
+/// esearch-response = "ESEARCH" [search-correlator] [SP "UID"] *(SP search-return-data)
+/// ```
+pub(crate) fn esearch_response(input: &[u8]) -> IMAPResult<'_, &[u8], Data<'_>> {
+    let mut parser = tuple((
+        tag_no_case("ESEARCH"),
+        opt(search_correlator),
+        opt(tag_no_case(" UID")),
+        many0(preceded(sp, search_return_data)),
+    ));
+
+    let (remaining, (_, tag, uid, returns)) = parser(input)?;
+
+    Ok((
+        remaining,
+        Data::ESearch {
+            tag,
+            uid: uid.is_some(),
+            returns,
+        },
+    ))
+}
+
+/// ```abnf
+/// search-correlator = SP "(" "TAG" SP tag-string ")"
+///
+/// tag-string = string
+/// ```
+pub(crate) fn search_correlator(input: &[u8]) -> IMAPResult<'_, &[u8], Tag<'_>> {
+    let (remaining, tag_string) = delimited(tag_no_case(" (TAG "), string, tag(")"))(input)?;
+
+    let tag = match tag_string.into_inner() {
+        Cow::Borrowed(bytes) => Tag::try_from(bytes),
+        Cow::Owned(bytes) => Tag::try_from(bytes),
+    };
+
+    match tag {
+        Ok(tag) => Ok((remaining, tag)),
+        Err(_) => Err(nom::Err::Failure(IMAPParseError {
+            input,
+            kind: IMAPErrorKind::Nom(ErrorKind::Verify),
+        })),
+    }
+}
+
+/// ```abnf
+/// search-return-data = "MIN" SP nz-number /
+///                      "MAX" SP nz-number /
+///                      "ALL" SP sequence-set /
+///                      "COUNT" SP number /
+///                      "PARTIAL" SP "(" partial-range SP partial-results ")" ; RFC 9394
+/// ```
+///
+/// Note: `search-ret-data-ext` is not supported.
+pub(crate) fn search_return_data(input: &[u8]) -> IMAPResult<'_, &[u8], ESearchReturn> {
+    alt((
+        map(preceded(tag_no_case("MIN "), nz_number), ESearchReturn::Min),
+        map(preceded(tag_no_case("MAX "), nz_number), ESearchReturn::Max),
+        map(
+            preceded(tag_no_case("ALL "), sequence_set(usize::MAX)),
+            ESearchReturn::All,
+        ),
+        map(
+            preceded(tag_no_case("COUNT "), number),
+            ESearchReturn::Count,
+        ),
+        #[cfg(feature = "ext_partial")]
+        map(search_return_data_partial, |(range, results)| {
+            ESearchReturn::Partial { range, results }
+        }),
+    ))(input)
+}
+
+impl EncodeIntoContext for SearchReturnOption {
+    fn encode_ctx(&self, ctx: &mut EncodeContext) -> std::io::Result<()> {
+        match self {
+            Self::Min => ctx.write_all(b"MIN"),
+            Self::Max => ctx.write_all(b"MAX"),
+            Self::All => ctx.write_all(b"ALL"),
+            Self::Count => ctx.write_all(b"COUNT"),
+            #[cfg(feature = "ext_searchres")]
+            Self::Save => ctx.write_all(b"SAVE"),
+            #[cfg(feature = "ext_partial")]
+            Self::Partial(range) => {
+                ctx.write_all(b"PARTIAL ")?;
+                range.encode_ctx(ctx)
+            }
+        }
+    }
+}
+
+impl EncodeIntoContext for ESearchReturn {
+    fn encode_ctx(&self, ctx: &mut EncodeContext) -> std::io::Result<()> {
+        match self {
+            Self::Min(min) => write!(ctx, "MIN {min}"),
+            Self::Max(max) => write!(ctx, "MAX {max}"),
+            Self::All(sequence_set) => {
+                ctx.write_all(b"ALL ")?;
+                sequence_set.encode_ctx(ctx)
+            }
+            Self::Count(count) => write!(ctx, "COUNT {count}"),
+            #[cfg(feature = "ext_partial")]
+            Self::Partial { range, results } => {
+                ctx.write_all(b"PARTIAL (")?;
+                range.encode_ctx(ctx)?;
+                ctx.write_all(b" ")?;
+                match results {
+                    Some(sequence_set) => sequence_set.encode_ctx(ctx)?,
+                    None => ctx.write_all(b"NIL")?,
+                }
+                ctx.write_all(b")")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU32;
+
+    use imap_types::{
+        command::{Command, CommandBody},
+        core::Vec1,
+        response::Response,
+        search::SearchKey,
+        sequence::SequenceSet,
+    };
+
+    use super::*;
+    use crate::testing::{kat_inverse_command, kat_inverse_response, known_answer_test_encode};
+
+    #[test]
+    fn test_kat_inverse_command_search_return() {
+        kat_inverse_command(&[
+            (
+                b"A SEARCH RETURN (MIN MAX) ALL\r\n".as_ref(),
+                b"".as_ref(),
+                Command::new(
+                    "A",
+                    CommandBody::SearchExtended {
+                        charset: None,
+                        criteria: Vec1::from(SearchKey::All),
+                        uid: false,
+                        return_options: Vec1::try_from(vec![
+                            SearchReturnOption::Min,
+                            SearchReturnOption::Max,
+                        ])
+                        .unwrap(),
+                    },
+                )
+                .unwrap(),
+            ),
+            (
+                b"A UID SEARCH RETURN (COUNT) CHARSET UTF-8 SEEN\r\n".as_ref(),
+                b"".as_ref(),
+                Command::new(
+                    "A",
+                    CommandBody::SearchExtended {
+                        charset: Some("UTF-8".try_into().unwrap()),
+                        criteria: Vec1::from(SearchKey::Seen),
+                        uid: true,
+                        return_options: Vec1::from(SearchReturnOption::Count),
+                    },
+                )
+                .unwrap(),
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_parse_search_return_empty() {
+        let (rem, got) = search_return_opts(b" RETURN () ").unwrap();
+        assert_eq!(rem, b" ");
+        assert_eq!(got, Vec1::from(SearchReturnOption::All));
+    }
+
+    #[test]
+    fn test_kat_inverse_response_esearch() {
+        kat_inverse_response(&[
+            (
+                b"* ESEARCH (TAG \"A1\") UID MIN 1 MAX 999 COUNT 15\r\n".as_ref(),
+                b"".as_ref(),
+                Response::Data(Data::ESearch {
+                    tag: Some(Tag::try_from("A1").unwrap()),
+                    uid: true,
+                    returns: vec![
+                        ESearchReturn::Min(NonZeroU32::new(1).unwrap()),
+                        ESearchReturn::Max(NonZeroU32::new(999).unwrap()),
+                        ESearchReturn::Count(15),
+                    ],
+                }),
+            ),
+            (
+                b"* ESEARCH ALL 1:3,5\r\n".as_ref(),
+                b"".as_ref(),
+                Response::Data(Data::ESearch {
+                    tag: None,
+                    uid: false,
+                    returns: vec![ESearchReturn::All(SequenceSet::try_from("1:3,5").unwrap())],
+                }),
+            ),
+            (
+                b"* ESEARCH (TAG \"A2\")\r\n".as_ref(),
+                b"".as_ref(),
+                Response::Data(Data::ESearch {
+                    tag: Some(Tag::try_from("A2").unwrap()),
+                    uid: false,
+                    returns: vec![],
+                }),
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_encode_esearch_empty() {
+        known_answer_test_encode((
+            Response::Data(Data::ESearch {
+                tag: None,
+                uid: false,
+                returns: vec![],
+            }),
+            b"* ESEARCH\r\n".as_ref(),
+        ));
+    }
+}
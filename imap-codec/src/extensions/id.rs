@@ -20,12 +20,15 @@ use crate::{
     decode::IMAPResult,
 };
 
+/// Parsed `id_params_list`, i.e., `None` for `NIL`.
+type IdParams<'a> = Option<Vec<(IString<'a>, NString<'a>)>>;
+
 /// ```abnf
 /// id = "ID" SPACE id_params_list
 /// ```
 ///
 /// Note: Updated ABNF.
-pub(crate) fn id(input: &[u8]) -> IMAPResult<&[u8], Option<Vec<(IString, NString)>>> {
+pub(crate) fn id(input: &[u8]) -> IMAPResult<'_, &[u8], IdParams<'_>> {
     preceded(tag_no_case("ID "), id_params_list)(input)
 }
 
@@ -35,7 +38,7 @@ pub(crate) fn id(input: &[u8]) -> IMAPResult<&[u8], Option<Vec<(IString, NString
 ///
 /// Note: Updated ABNF.
 #[inline]
-pub(crate) fn id_response(input: &[u8]) -> IMAPResult<&[u8], Option<Vec<(IString, NString)>>> {
+pub(crate) fn id_response(input: &[u8]) -> IMAPResult<'_, &[u8], IdParams<'_>> {
     id(input)
 }
 
@@ -44,7 +47,7 @@ pub(crate) fn id_response(input: &[u8]) -> IMAPResult<&[u8], Option<Vec<(IString
 /// ```
 ///
 /// Note: Updated ABNF. (See https://github.com/modern-email/defects/issues/12)
-pub(crate) fn id_params_list(input: &[u8]) -> IMAPResult<&[u8], Option<Vec<(IString, NString)>>> {
+pub(crate) fn id_params_list(input: &[u8]) -> IMAPResult<'_, &[u8], IdParams<'_>> {
     alt((
         map(
             delimited(
@@ -139,10 +142,27 @@ mod tests {
 
     #[test]
     fn test_kat_inverse_response_id() {
-        kat_inverse_response(&[(
-            b"* ID nil\r\n".as_ref(),
-            b"".as_ref(),
-            Response::Data(Data::Id { parameters: None }),
-        )]);
+        kat_inverse_response(&[
+            (
+                b"* ID nil\r\n".as_ref(),
+                b"".as_ref(),
+                Response::Data(Data::Id { parameters: None }),
+            ),
+            (
+                b"* ID (\"name\" \"Cyrus\" \"version\" \"1.5\" \"os\" NIL)\r\n".as_ref(),
+                b"".as_ref(),
+                Response::Data(
+                    Data::id(Some(vec![
+                        NString::try_from("name").unwrap(),
+                        NString::try_from("Cyrus").unwrap(),
+                        NString::try_from("version").unwrap(),
+                        NString::try_from("1.5").unwrap(),
+                        NString::try_from("os").unwrap(),
+                        NString(None),
+                    ]))
+                    .unwrap(),
+                ),
+            ),
+        ]);
     }
 }
@@ -32,7 +32,7 @@ use crate::{
 /// ```
 ///
 /// Valid only in Authenticated or Selected state
-pub(crate) fn idle(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
+pub(crate) fn idle(input: &[u8]) -> IMAPResult<'_, &[u8], CommandBody<'_>> {
     value(CommandBody::Idle, tag_no_case("IDLE"))(input)
 }
 
@@ -50,7 +50,7 @@ pub(crate) fn idle(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
 ///
 /// Note: This parser must be executed *instead* of the command parser
 /// when the server is in the IDLE state.
-pub(crate) fn idle_done(input: &[u8]) -> IMAPResult<&[u8], IdleDone> {
+pub(crate) fn idle_done(input: &[u8]) -> IMAPResult<'_, &[u8], IdleDone> {
     value(IdleDone, tuple((tag_no_case("DONE"), crlf)))(input)
 }
 
@@ -0,0 +1,232 @@
+//! IMAP4 LIST Command Extensions
+
+// Additional changes:
+//
+// list = "LIST" [SP list-select-opts] SP mailbox SP mbox-or-pat [SP list-return-opts]
+// mbx-list-oflag =/ "\HasChildren" / "\HasNoChildren" / "\NonExistent"
+
+use std::io::Write;
+
+use abnf_core::streaming::sp;
+use imap_types::{
+    core::Vec1,
+    extensions::list_extended::{ListReturnOption, ListSelectionOption, MailboxPatterns},
+};
+use nom::{
+    branch::alt,
+    bytes::streaming::{tag, tag_no_case},
+    combinator::{map, value},
+    multi::{separated_list0, separated_list1},
+    sequence::{delimited, preceded},
+};
+
+#[cfg(feature = "ext_list_status")]
+use crate::extensions::list_status::return_option_status;
+use crate::{
+    decode::IMAPResult,
+    encode::{utils::join_serializable, EncodeContext, EncodeIntoContext},
+    mailbox::list_mailbox,
+};
+
+/// ```abnf
+/// list-select-opts = "(" [list-select-opt *(SP list-select-opt)] ")"
+/// ```
+///
+/// Note: `option-extension` is not supported.
+pub(crate) fn list_select_opts(input: &[u8]) -> IMAPResult<'_, &[u8], Vec<ListSelectionOption>> {
+    delimited(tag("("), separated_list0(sp, list_select_opt), tag(")"))(input)
+}
+
+/// ```abnf
+/// list-select-opt = "SUBSCRIBED" / "REMOTE" / "RECURSIVEMATCH"
+/// ```
+pub(crate) fn list_select_opt(input: &[u8]) -> IMAPResult<'_, &[u8], ListSelectionOption> {
+    alt((
+        value(ListSelectionOption::Subscribed, tag_no_case("SUBSCRIBED")),
+        value(ListSelectionOption::Remote, tag_no_case("REMOTE")),
+        value(
+            ListSelectionOption::RecursiveMatch,
+            tag_no_case("RECURSIVEMATCH"),
+        ),
+    ))(input)
+}
+
+/// ```abnf
+/// mbox-or-pat = list-mailbox / patterns
+///
+/// patterns = "(" list-mailbox *(SP list-mailbox) ")"
+/// ```
+pub(crate) fn mbox_or_pat(input: &[u8]) -> IMAPResult<'_, &[u8], MailboxPatterns<'_>> {
+    alt((
+        map(list_mailbox, MailboxPatterns::Single),
+        map(
+            delimited(tag("("), separated_list1(sp, list_mailbox), tag(")")),
+            // Safety: Safe because we use `separated_list1` above.
+            |patterns| MailboxPatterns::Multiple(Vec1::try_from(patterns).unwrap()),
+        ),
+    ))(input)
+}
+
+/// ```abnf
+/// list-return-opts = "RETURN" SP "(" [return-option *(SP return-option)] ")"
+/// ```
+pub(crate) fn list_return_opts(input: &[u8]) -> IMAPResult<'_, &[u8], Vec<ListReturnOption>> {
+    preceded(
+        tag_no_case("RETURN "),
+        delimited(tag("("), separated_list0(sp, return_option), tag(")")),
+    )(input)
+}
+
+/// ```abnf
+/// return-option = "SUBSCRIBED" / "CHILDREN" /
+///                 "STATUS" SP "(" status-att *(SP status-att) ")" ; RFC 5819
+/// ```
+///
+/// Note: `option-extension` is not supported.
+pub(crate) fn return_option(input: &[u8]) -> IMAPResult<'_, &[u8], ListReturnOption> {
+    alt((
+        value(ListReturnOption::Subscribed, tag_no_case("SUBSCRIBED")),
+        value(ListReturnOption::Children, tag_no_case("CHILDREN")),
+        #[cfg(feature = "ext_list_status")]
+        map(return_option_status, ListReturnOption::Status),
+    ))(input)
+}
+
+impl<'a> EncodeIntoContext for MailboxPatterns<'a> {
+    fn encode_ctx(&self, ctx: &mut EncodeContext) -> std::io::Result<()> {
+        match self {
+            Self::Single(pattern) => pattern.encode_ctx(ctx),
+            Self::Multiple(patterns) => {
+                ctx.write_all(b"(")?;
+                join_serializable(patterns.as_ref(), b" ", ctx)?;
+                ctx.write_all(b")")
+            }
+        }
+    }
+}
+
+impl EncodeIntoContext for ListSelectionOption {
+    fn encode_ctx(&self, ctx: &mut EncodeContext) -> std::io::Result<()> {
+        match self {
+            Self::Subscribed => ctx.write_all(b"SUBSCRIBED"),
+            Self::Remote => ctx.write_all(b"REMOTE"),
+            Self::RecursiveMatch => ctx.write_all(b"RECURSIVEMATCH"),
+        }
+    }
+}
+
+impl EncodeIntoContext for ListReturnOption {
+    fn encode_ctx(&self, ctx: &mut EncodeContext) -> std::io::Result<()> {
+        match self {
+            Self::Subscribed => ctx.write_all(b"SUBSCRIBED"),
+            Self::Children => ctx.write_all(b"CHILDREN"),
+            #[cfg(feature = "ext_list_status")]
+            Self::Status(item_names) => {
+                ctx.write_all(b"STATUS (")?;
+                join_serializable(item_names.as_ref(), b" ", ctx)?;
+                ctx.write_all(b")")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use imap_types::{
+        command::{Command, CommandBody},
+        core::IString,
+        flag::FlagNameAttribute,
+        mailbox::{ListMailbox, Mailbox},
+        response::{Data, Response},
+    };
+
+    use super::*;
+    use crate::testing::{kat_inverse_command, kat_inverse_response};
+
+    #[test]
+    fn test_kat_inverse_command_list_extended() {
+        kat_inverse_command(&[
+            (
+                b"A LIST (SUBSCRIBED) \"\" \"*\" RETURN (CHILDREN)\r\n".as_ref(),
+                b"".as_ref(),
+                Command::new(
+                    "A",
+                    CommandBody::ListExtended {
+                        reference: Mailbox::try_from("").unwrap(),
+                        selection_options: vec![ListSelectionOption::Subscribed],
+                        mailbox_patterns: MailboxPatterns::Single(ListMailbox::String(
+                            IString::Quoted("*".try_into().unwrap()),
+                        )),
+                        return_options: vec![ListReturnOption::Children],
+                    },
+                )
+                .unwrap(),
+            ),
+            (
+                b"A LIST (REMOTE SUBSCRIBED RECURSIVEMATCH) \"\" (INBOX Drafts %) RETURN (SUBSCRIBED CHILDREN)\r\n".as_ref(),
+                b"".as_ref(),
+                Command::new(
+                    "A",
+                    CommandBody::ListExtended {
+                        reference: Mailbox::try_from("").unwrap(),
+                        selection_options: vec![
+                            ListSelectionOption::Remote,
+                            ListSelectionOption::Subscribed,
+                            ListSelectionOption::RecursiveMatch,
+                        ],
+                        mailbox_patterns: MailboxPatterns::Multiple(
+                            Vec1::try_from(vec![
+                                ListMailbox::try_from("INBOX").unwrap(),
+                                ListMailbox::try_from("Drafts").unwrap(),
+                                ListMailbox::try_from("%").unwrap(),
+                            ])
+                            .unwrap(),
+                        ),
+                        return_options: vec![
+                            ListReturnOption::Subscribed,
+                            ListReturnOption::Children,
+                        ],
+                    },
+                )
+                .unwrap(),
+            ),
+            (
+                b"A LIST \"\" (INBOX)\r\n".as_ref(),
+                b"".as_ref(),
+                Command::new(
+                    "A",
+                    CommandBody::ListExtended {
+                        selection_options: vec![],
+                        reference: Mailbox::try_from("").unwrap(),
+                        mailbox_patterns: MailboxPatterns::Multiple(Vec1::from(
+                            ListMailbox::try_from("INBOX").unwrap(),
+                        )),
+                        return_options: vec![],
+                    },
+                )
+                .unwrap(),
+            ),
+            (
+                b"A LIST \"\" *\r\n".as_ref(),
+                b"".as_ref(),
+                Command::new("A", CommandBody::list("", "*").unwrap()).unwrap(),
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_kat_inverse_response_list_extended() {
+        kat_inverse_response(&[(
+            b"* LIST (\\HasChildren \\NonExistent) \"/\" Foo\r\n".as_ref(),
+            b"".as_ref(),
+            Response::Data(Data::List {
+                items: vec![
+                    FlagNameAttribute::HasChildren,
+                    FlagNameAttribute::NonExistent,
+                ],
+                delimiter: Some('/'.try_into().unwrap()),
+                mailbox: Mailbox::try_from("Foo").unwrap(),
+            }),
+        )]);
+    }
+}
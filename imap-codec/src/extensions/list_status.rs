@@ -0,0 +1,200 @@
+//! IMAP4 Extension for Returning STATUS Information in Extended LIST
+
+// Additional changes:
+//
+// capability    =/ "LIST-STATUS"
+// return-option =/ "STATUS" SP "(" status-att *(SP status-att) ")"
+
+use abnf_core::streaming::sp;
+use imap_types::{core::Vec1, status::StatusDataItemName};
+use nom::{
+    bytes::streaming::{tag, tag_no_case},
+    combinator::map,
+    multi::separated_list1,
+    sequence::{delimited, preceded},
+};
+
+use crate::{decode::IMAPResult, status::status_att};
+
+/// ```abnf
+/// return-option =/ "STATUS" SP "(" status-att *(SP status-att) ")"
+/// ```
+pub(crate) fn return_option_status(
+    input: &[u8],
+) -> IMAPResult<'_, &[u8], Vec1<StatusDataItemName>> {
+    preceded(
+        tag_no_case(b"STATUS "),
+        delimited(
+            tag(b"("),
+            map(
+                separated_list1(sp, status_att),
+                // Safety: Safe because we use `separated_list1` above.
+                |item_names| Vec1::try_from(item_names).unwrap(),
+            ),
+            tag(b")"),
+        ),
+    )(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use imap_types::{
+        command::{Command, CommandBody},
+        extensions::{
+            list_extended::{ListReturnOption, MailboxPatterns},
+            list_status::ListStatus,
+        },
+        flag::FlagNameAttribute,
+        mailbox::{ListMailbox, Mailbox},
+        response::{Capability, Data, Response},
+        status::StatusDataItem,
+    };
+
+    use super::*;
+    use crate::{
+        decode::Decoder,
+        testing::{kat_inverse_command, kat_inverse_response, known_answer_test_encode},
+        ResponseCodec,
+    };
+
+    #[test]
+    fn test_encode_list_return_option_status() {
+        let tests = [
+            (
+                ListReturnOption::Status(Vec1::from(StatusDataItemName::Messages)),
+                b"STATUS (MESSAGES)".as_ref(),
+            ),
+            (
+                ListReturnOption::Status(
+                    Vec1::try_from(vec![
+                        StatusDataItemName::Messages,
+                        StatusDataItemName::Unseen,
+                        StatusDataItemName::UidNext,
+                    ])
+                    .unwrap(),
+                ),
+                b"STATUS (MESSAGES UNSEEN UIDNEXT)",
+            ),
+        ];
+
+        for test in tests {
+            known_answer_test_encode(test);
+        }
+    }
+
+    #[test]
+    fn test_parse_list_return_option_status_failing() {
+        for test in [
+            b"STATUS ()".as_ref(),
+            b"STATUS (MESSAGES FOO)",
+            b"STATUS MESSAGES",
+        ] {
+            assert!(return_option_status(test).is_err());
+        }
+    }
+
+    #[test]
+    fn test_kat_inverse_command_list_status() {
+        kat_inverse_command(&[
+            (
+                b"A LIST \"\" * RETURN (STATUS (MESSAGES UNSEEN))\r\n".as_ref(),
+                b"".as_ref(),
+                Command::new(
+                    "A",
+                    CommandBody::ListExtended {
+                        reference: Mailbox::try_from("").unwrap(),
+                        selection_options: vec![],
+                        mailbox_patterns: MailboxPatterns::Single(
+                            ListMailbox::try_from("*").unwrap(),
+                        ),
+                        return_options: vec![ListReturnOption::Status(
+                            Vec1::try_from(vec![
+                                StatusDataItemName::Messages,
+                                StatusDataItemName::Unseen,
+                            ])
+                            .unwrap(),
+                        )],
+                    },
+                )
+                .unwrap(),
+            ),
+            (
+                b"A LIST \"\" % RETURN (CHILDREN STATUS (UIDNEXT))\r\n".as_ref(),
+                b"".as_ref(),
+                Command::new(
+                    "A",
+                    CommandBody::ListExtended {
+                        reference: Mailbox::try_from("").unwrap(),
+                        selection_options: vec![],
+                        mailbox_patterns: MailboxPatterns::Single(
+                            ListMailbox::try_from("%").unwrap(),
+                        ),
+                        return_options: vec![
+                            ListReturnOption::Children,
+                            ListReturnOption::Status(Vec1::from(StatusDataItemName::UidNext)),
+                        ],
+                    },
+                )
+                .unwrap(),
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_kat_inverse_response_list_status() {
+        kat_inverse_response(&[(
+            b"* CAPABILITY IMAP4REV1 LIST-EXTENDED LIST-STATUS\r\n".as_ref(),
+            b"".as_ref(),
+            Response::Data(Data::Capability(
+                Vec1::try_from(vec![
+                    Capability::Imap4Rev1,
+                    Capability::ListExtended,
+                    Capability::ListStatus,
+                ])
+                .unwrap(),
+            )),
+        )]);
+    }
+
+    #[test]
+    fn test_decode_and_associate_list_status() {
+        let mut input = b"* LIST () \".\" INBOX\r\n\
+* STATUS INBOX (MESSAGES 17 UNSEEN 16)\r\n\
+* LIST (\\Noselect) \".\" foo\r\n\
+* LIST () \".\" foo.bar\r\n\
+* STATUS foo.bar (MESSAGES 4 UNSEEN 2)\r\n"
+            .as_ref();
+
+        let mut data = Vec::new();
+
+        while !input.is_empty() {
+            let (remaining, response) = ResponseCodec::default().decode(input).unwrap();
+            match response {
+                Response::Data(item) => data.push(item),
+                other => panic!("Unexpected response: {other:?}"),
+            }
+            input = remaining;
+        }
+
+        let got = ListStatus::associate(data);
+
+        assert_eq!(
+            got.iter().map(|list| &list.mailbox).collect::<Vec<_>>(),
+            vec![
+                &Mailbox::Inbox,
+                &Mailbox::try_from("foo").unwrap(),
+                &Mailbox::try_from("foo.bar").unwrap(),
+            ]
+        );
+        assert_eq!(
+            got[0].status.as_deref(),
+            Some([StatusDataItem::Messages(17), StatusDataItem::Unseen(16),].as_ref())
+        );
+        assert_eq!(got[1].items, vec![FlagNameAttribute::Noselect]);
+        assert_eq!(got[1].status, None);
+        assert_eq!(
+            got[2].status.as_deref(),
+            Some([StatusDataItem::Messages(4), StatusDataItem::Unseen(2),].as_ref())
+        );
+    }
+}
@@ -34,7 +34,7 @@ use crate::{
 /// ; empty string for mailbox implies server annotation.
 /// setmetadata  = "SETMETADATA" SP mailbox SP entry-values
 /// ```
-pub(crate) fn setmetadata(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
+pub(crate) fn setmetadata(input: &[u8]) -> IMAPResult<'_, &[u8], CommandBody<'_>> {
     let mut parser = tuple((
         tag_no_case("SETMETADATA"),
         preceded(sp, mailbox),
@@ -55,7 +55,7 @@ pub(crate) fn setmetadata(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
 /// ```abnf
 /// entry-values = "(" entry-value *(SP entry-value) ")"
 /// ```
-pub(crate) fn entry_values(input: &[u8]) -> IMAPResult<&[u8], Vec1<EntryValue>> {
+pub(crate) fn entry_values(input: &[u8]) -> IMAPResult<'_, &[u8], Vec1<EntryValue<'_>>> {
     map(
         delimited(tag("("), separated_list1(sp, entry_value), tag(")")),
         Vec1::unvalidated,
@@ -66,7 +66,7 @@ pub(crate) fn entry_values(input: &[u8]) -> IMAPResult<&[u8], Vec1<EntryValue>>
 /// entry-value = entry SP value
 /// ```
 #[inline]
-pub(crate) fn entry_value(input: &[u8]) -> IMAPResult<&[u8], EntryValue> {
+pub(crate) fn entry_value(input: &[u8]) -> IMAPResult<'_, &[u8], EntryValue<'_>> {
     map(separated_pair(entry, sp, imap_value), |(entry, value)| {
         EntryValue { entry, value }
     })(input)
@@ -79,7 +79,7 @@ pub(crate) fn entry_value(input: &[u8]) -> IMAPResult<&[u8], EntryValue> {
 /// ```abnf
 /// entry = astring
 /// ```
-pub(crate) fn entry(input: &[u8]) -> IMAPResult<&[u8], Entry> {
+pub(crate) fn entry(input: &[u8]) -> IMAPResult<'_, &[u8], Entry<'_>> {
     let (rem, astring) = astring(input)?;
 
     if let Ok(entry) = Entry::try_from(astring) {
@@ -96,7 +96,7 @@ pub(crate) fn entry(input: &[u8]) -> IMAPResult<&[u8], Entry> {
 /// value = nstring / literal8
 /// ```
 #[inline]
-pub(crate) fn imap_value(input: &[u8]) -> IMAPResult<&[u8], NString8> {
+pub(crate) fn imap_value(input: &[u8]) -> IMAPResult<'_, &[u8], NString8<'_>> {
     alt((
         map(nstring, NString8::NString),
         map(literal8, NString8::Literal8),
@@ -108,7 +108,7 @@ pub(crate) fn imap_value(input: &[u8]) -> IMAPResult<&[u8], NString8> {
 /// ```
 ///
 /// Note: Empty string for mailbox implies server annotation.
-pub(crate) fn getmetadata(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
+pub(crate) fn getmetadata(input: &[u8]) -> IMAPResult<'_, &[u8], CommandBody<'_>> {
     let mut parser = tuple((
         tag_no_case("GETMETADATA"),
         opt(preceded(sp, getmetadata_options)),
@@ -131,7 +131,7 @@ pub(crate) fn getmetadata(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
 /// ```abnf
 /// getmetadata-options = "(" getmetadata-option *(SP getmetadata-option) ")"
 /// ```
-pub(crate) fn getmetadata_options(input: &[u8]) -> IMAPResult<&[u8], Vec1<GetMetadataOption>> {
+pub(crate) fn getmetadata_options(input: &[u8]) -> IMAPResult<'_, &[u8], Vec1<GetMetadataOption>> {
     map(
         delimited(tag("("), separated_list1(sp, getmetadata_option), tag(")")),
         Vec1::unvalidated,
@@ -148,7 +148,7 @@ pub(crate) fn getmetadata_options(input: &[u8]) -> IMAPResult<&[u8], Vec1<GetMet
 /// ; Used as a getmetadata-option
 /// scope-opt = "DEPTH" SP ("0" / "1" / "infinity")
 /// ```
-pub(crate) fn getmetadata_option(input: &[u8]) -> IMAPResult<&[u8], GetMetadataOption> {
+pub(crate) fn getmetadata_option(input: &[u8]) -> IMAPResult<'_, &[u8], GetMetadataOption> {
     // TODO: Generic parser required?
     alt((
         map(
@@ -172,7 +172,7 @@ pub(crate) fn getmetadata_option(input: &[u8]) -> IMAPResult<&[u8], GetMetadataO
 /// ```abnf
 /// entries = entry / "(" entry *(SP entry) ")"
 /// ```
-pub(crate) fn entries(input: &[u8]) -> IMAPResult<&[u8], Vec1<Entry>> {
+pub(crate) fn entries(input: &[u8]) -> IMAPResult<'_, &[u8], Vec1<Entry<'_>>> {
     alt((
         map(entry, Vec1::from),
         map(
@@ -192,7 +192,7 @@ pub(crate) fn entries(input: &[u8]) -> IMAPResult<&[u8], Vec1<Entry>> {
 ///
 /// entry-list = entry *(SP entry)
 /// ```
-pub(crate) fn metadata_resp(input: &[u8]) -> IMAPResult<&[u8], Data> {
+pub(crate) fn metadata_resp(input: &[u8]) -> IMAPResult<'_, &[u8], Data<'_>> {
     let mut parser = tuple((
         tag_no_case("METADATA"),
         preceded(sp, mailbox),
@@ -213,7 +213,7 @@ pub(crate) fn metadata_resp(input: &[u8]) -> IMAPResult<&[u8], Data> {
 /// ```abnf
 /// entry-list = entry *(SP entry)
 /// ```
-pub(crate) fn entry_list(input: &[u8]) -> IMAPResult<&[u8], Vec1<Entry>> {
+pub(crate) fn entry_list(input: &[u8]) -> IMAPResult<'_, &[u8], Vec1<Entry<'_>>> {
     map(separated_list1(sp, entry), Vec1::unvalidated)(input)
 }
 
@@ -236,7 +236,7 @@ impl<'a> EncodeIntoContext for MetadataResponse<'a> {
 /// "TOOMANY" /
 /// "NOPRIVATE"
 /// ```
-pub(crate) fn metadata_code(input: &[u8]) -> IMAPResult<&[u8], MetadataCode> {
+pub(crate) fn metadata_code(input: &[u8]) -> IMAPResult<'_, &[u8], MetadataCode> {
     alt((
         map(
             preceded(tag_no_case("LONGENTRIES "), number),
@@ -491,6 +491,29 @@ mod tests {
                 )
                 .unwrap(),
             ),
+            (
+                b"A GETMETADATA (MAXSIZE 1024 DEPTH 1) INBOX (/shared/comment /private/comment)\r\n"
+                    .as_ref(),
+                b"".as_ref(),
+                Command::new(
+                    "A",
+                    CommandBody::GetMetadata {
+                        options: vec![
+                            GetMetadataOption::MaxSize(1024),
+                            GetMetadataOption::Depth(Depth::One),
+                        ],
+                        mailbox: Mailbox::Inbox,
+                        entries: Vec1::try_from(vec![
+                            Entry::try_from(AString::try_from("/shared/comment").unwrap())
+                                .unwrap(),
+                            Entry::try_from(AString::try_from("/private/comment").unwrap())
+                                .unwrap(),
+                        ])
+                        .unwrap(),
+                    },
+                )
+                .unwrap(),
+            ),
         ]);
     }
 
@@ -9,19 +9,29 @@ use crate::{decode::IMAPResult, mailbox::mailbox, sequence::sequence_set};
 /// ```abnf
 /// move = "MOVE" SP sequence-set SP mailbox
 /// ```
-pub(crate) fn r#move(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
-    let mut parser = tuple((tag_no_case(b"MOVE"), sp, sequence_set, sp, mailbox));
-
-    let (remaining, (_, _, sequence_set, _, mailbox)) = parser(input)?;
-
-    Ok((
-        remaining,
-        CommandBody::Move {
-            sequence_set,
+pub(crate) fn r#move(
+    max_sequence_set_len: usize,
+) -> impl Fn(&[u8]) -> IMAPResult<'_, &[u8], CommandBody<'_>> {
+    move |input: &[u8]| {
+        let mut parser = tuple((
+            tag_no_case(b"MOVE"),
+            sp,
+            sequence_set(max_sequence_set_len),
+            sp,
             mailbox,
-            uid: false,
-        },
-    ))
+        ));
+
+        let (remaining, (_, _, sequence_set, _, mailbox)) = parser(input)?;
+
+        Ok((
+            remaining,
+            CommandBody::Move {
+                sequence_set,
+                mailbox,
+                uid: false,
+            },
+        ))
+    }
 }
 
 #[cfg(test)]
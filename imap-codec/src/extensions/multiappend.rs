@@ -0,0 +1,172 @@
+//! IMAP MULTIAPPEND Extension
+
+// Additional changes:
+//
+// append         = "APPEND" SP mailbox 1*append-message
+// append-message = append-opts SP append-data
+// append-opts    = [SP flag-list] [SP date-time]
+// append-data    = literal / literal8 ; literal8 only with BINARY
+//                  / "UTF8" SP "(" literal8 ")" ; RFC 6855
+
+use std::io::Write;
+
+use abnf_core::streaming::sp;
+#[cfg(feature = "ext_binary")]
+use imap_types::extensions::binary::LiteralOrLiteral8;
+use imap_types::extensions::multiappend::AppendMessage;
+#[cfg(feature = "ext_binary")]
+use nom::{branch::alt, combinator::map};
+use nom::{
+    combinator::opt,
+    sequence::{preceded, tuple},
+};
+
+#[cfg(feature = "ext_binary")]
+use crate::extensions::binary::literal8;
+#[cfg(feature = "ext_utf8")]
+use crate::extensions::utf8::append_data_utf8;
+use crate::{
+    core::literal,
+    datetime::date_time,
+    decode::IMAPResult,
+    encode::{utils::join_serializable, EncodeContext, EncodeIntoContext},
+    flag::flag_list,
+};
+
+/// ```abnf
+/// append-message = [SP flag-list] [SP date-time] SP append-data
+/// ```
+///
+/// Note: The leading `SP` is consumed by this parser.
+pub(crate) fn append_message(input: &[u8]) -> IMAPResult<'_, &[u8], AppendMessage<'_>> {
+    let mut parser = tuple((
+        opt(preceded(sp, flag_list)),
+        opt(preceded(sp, date_time)),
+        sp,
+        #[cfg(not(feature = "ext_binary"))]
+        literal,
+        #[cfg(feature = "ext_binary")]
+        alt((
+            map(literal, LiteralOrLiteral8::Literal),
+            map(literal8, LiteralOrLiteral8::Literal8),
+            #[cfg(feature = "ext_utf8")]
+            map(append_data_utf8, LiteralOrLiteral8::Utf8),
+        )),
+    ));
+
+    let (remaining, (flags, date, _, message)) = parser(input)?;
+
+    Ok((
+        remaining,
+        AppendMessage {
+            flags: flags.unwrap_or_default(),
+            date,
+            message,
+        },
+    ))
+}
+
+impl<'a> EncodeIntoContext for AppendMessage<'a> {
+    fn encode_ctx(&self, ctx: &mut EncodeContext) -> std::io::Result<()> {
+        if !self.flags.is_empty() {
+            ctx.write_all(b"(")?;
+            join_serializable(&self.flags, b" ", ctx)?;
+            ctx.write_all(b") ")?;
+        }
+
+        if let Some(date) = &self.date {
+            date.encode_ctx(ctx)?;
+            ctx.write_all(b" ")?;
+        }
+
+        self.message.encode_ctx(ctx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use imap_types::{
+        command::{Command, CommandBody},
+        core::{LiteralMode, Vec2},
+        flag::Flag,
+        mailbox::Mailbox,
+    };
+
+    use super::*;
+    use crate::{
+        encode::{Encoder, Fragment},
+        testing::kat_inverse_command,
+        CommandCodec,
+    };
+
+    #[test]
+    fn test_kat_inverse_command_multiappend() {
+        kat_inverse_command(&[
+            (
+                b"A APPEND saved-messages (\\Seen) {5}\r\nHello (\\Seen) {5}\r\nWorld\r\n".as_ref(),
+                b"".as_ref(),
+                Command::new(
+                    "A",
+                    CommandBody::multiappend(
+                        "saved-messages",
+                        vec![
+                            AppendMessage::new(vec![Flag::Seen], None, "Hello").unwrap(),
+                            AppendMessage::new(vec![Flag::Seen], None, "World").unwrap(),
+                        ],
+                    )
+                    .unwrap(),
+                )
+                .unwrap(),
+            ),
+            (
+                b"A APPEND INBOX {5}\r\nHello\r\n".as_ref(),
+                b"".as_ref(),
+                Command::new(
+                    "A",
+                    CommandBody::append(Mailbox::Inbox, vec![], None, "Hello").unwrap(),
+                )
+                .unwrap(),
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_encode_multiappend_fragments() {
+        let command = Command::new(
+            "A",
+            CommandBody::MultiAppend {
+                mailbox: Mailbox::Inbox,
+                messages: Vec2::from((
+                    AppendMessage::new(vec![Flag::Seen], None, "Hello").unwrap(),
+                    AppendMessage::new(vec![], None, "World").unwrap(),
+                )),
+            },
+        )
+        .unwrap();
+
+        let got: Vec<Fragment> = CommandCodec::default().encode(&command).collect();
+
+        assert_eq!(
+            got,
+            vec![
+                Fragment::Line {
+                    data: b"A APPEND INBOX (\\Seen) {5}\r\n".to_vec()
+                },
+                Fragment::Literal {
+                    data: b"Hello".to_vec(),
+                    mode: LiteralMode::Sync,
+                },
+                Fragment::Line {
+                    data: b" {5}\r\n".to_vec()
+                },
+                Fragment::Literal {
+                    data: b"World".to_vec(),
+                    mode: LiteralMode::Sync,
+                },
+                Fragment::Line {
+                    data: b"\r\n".to_vec()
+                },
+            ]
+        );
+    }
+}
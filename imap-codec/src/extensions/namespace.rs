@@ -0,0 +1,256 @@
+//! IMAP4 Namespace
+
+// Additional changes:
+//
+// command_auth ::= append / create / delete / examine / list / lsub / rename / select / status / subscribe / unsubscribe / namespace
+// response_data ::= "*" SP (resp_cond_state / resp_cond_bye / mailbox_data / message_data / capability_data / namespace_response)
+
+use std::io::Write;
+
+use abnf_core::streaming::{dquote, sp};
+use imap_types::{
+    command::CommandBody,
+    core::{IString, Vec1},
+    extensions::namespace::NamespaceDescriptor,
+    response::Data,
+};
+use nom::{
+    branch::alt,
+    bytes::streaming::{tag, tag_no_case},
+    combinator::{map, value},
+    multi::{many0, many1, separated_list1},
+    sequence::{delimited, preceded, tuple},
+};
+
+use crate::{
+    core::{nil, quoted_char, string},
+    decode::IMAPResult,
+    encode::{utils::join_serializable, EncodeContext, EncodeIntoContext},
+};
+
+/// ```abnf
+/// namespace = "NAMESPACE"
+/// ```
+#[inline]
+pub(crate) fn namespace(input: &[u8]) -> IMAPResult<'_, &[u8], CommandBody<'_>> {
+    value(CommandBody::Namespace, tag_no_case("NAMESPACE"))(input)
+}
+
+/// ```abnf
+/// namespace_response = "NAMESPACE" SP namespace SP namespace SP namespace
+/// ```
+///
+/// Note: The three namespaces are personal, other users', and shared namespaces.
+pub(crate) fn namespace_response(input: &[u8]) -> IMAPResult<'_, &[u8], Data<'_>> {
+    let mut parser = tuple((
+        tag_no_case("NAMESPACE "),
+        namespace_list,
+        sp,
+        namespace_list,
+        sp,
+        namespace_list,
+    ));
+
+    let (remaining, (_, personal, _, other, _, shared)) = parser(input)?;
+
+    Ok((
+        remaining,
+        Data::Namespace {
+            personal,
+            other,
+            shared,
+        },
+    ))
+}
+
+/// ```abnf
+/// namespace = nil / "(" 1*( "(" string SP (<"> QUOTED_CHAR <"> / nil) *(namespace_response_extension) ")" ) ")"
+/// ```
+///
+/// Note: `nil` is mapped to an empty `Vec`.
+pub(crate) fn namespace_list(input: &[u8]) -> IMAPResult<'_, &[u8], Vec<NamespaceDescriptor<'_>>> {
+    alt((
+        delimited(tag("("), many1(namespace_descriptor), tag(")")),
+        value(Vec::new(), nil),
+    ))(input)
+}
+
+/// ```abnf
+/// "(" string SP (<"> QUOTED_CHAR <"> / nil) *(namespace_response_extension) ")"
+/// ```
+pub(crate) fn namespace_descriptor(input: &[u8]) -> IMAPResult<'_, &[u8], NamespaceDescriptor<'_>> {
+    let mut parser = delimited(
+        tag("("),
+        tuple((
+            string,
+            sp,
+            alt((
+                map(delimited(dquote, quoted_char, dquote), Some),
+                value(None, nil),
+            )),
+            many0(namespace_response_extension),
+        )),
+        tag(")"),
+    );
+
+    let (remaining, (prefix, _, delimiter, extensions)) = parser(input)?;
+
+    Ok((
+        remaining,
+        NamespaceDescriptor {
+            prefix,
+            delimiter,
+            extensions,
+        },
+    ))
+}
+
+/// ```abnf
+/// namespace_response_extension = SP string SP "(" string *(SP string) ")"
+/// ```
+#[allow(clippy::type_complexity)]
+pub(crate) fn namespace_response_extension(
+    input: &[u8],
+) -> IMAPResult<'_, &[u8], (IString<'_>, Vec1<IString<'_>>)> {
+    let mut parser = tuple((
+        preceded(sp, string),
+        preceded(
+            sp,
+            delimited(tag("("), separated_list1(sp, string), tag(")")),
+        ),
+    ));
+
+    let (remaining, (name, values)) = parser(input)?;
+
+    // Safety: Safe because we use `separated_list1` above.
+    Ok((remaining, (name, Vec1::try_from(values).unwrap())))
+}
+
+impl<'a> EncodeIntoContext for NamespaceDescriptor<'a> {
+    fn encode_ctx(&self, ctx: &mut EncodeContext) -> std::io::Result<()> {
+        ctx.write_all(b"(")?;
+        self.prefix.encode_ctx(ctx)?;
+        ctx.write_all(b" ")?;
+
+        match &self.delimiter {
+            Some(delimiter) => {
+                ctx.write_all(b"\"")?;
+                delimiter.encode_ctx(ctx)?;
+                ctx.write_all(b"\"")?;
+            }
+            None => {
+                ctx.write_all(b"NIL")?;
+            }
+        }
+
+        for (name, values) in &self.extensions {
+            ctx.write_all(b" ")?;
+            name.encode_ctx(ctx)?;
+            ctx.write_all(b" ")?;
+            ctx.write_all(b"(")?;
+            join_serializable(values.as_ref(), b" ", ctx)?;
+            ctx.write_all(b")")?;
+        }
+
+        ctx.write_all(b")")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use imap_types::{
+        command::Command,
+        core::{QuotedChar, Vec1},
+        response::Response,
+    };
+
+    use super::*;
+    use crate::testing::{kat_inverse_command, kat_inverse_response};
+
+    #[test]
+    fn test_kat_inverse_command_namespace() {
+        kat_inverse_command(&[
+            (
+                b"A NAMESPACE\r\n".as_ref(),
+                b"".as_ref(),
+                Command::new("A", CommandBody::Namespace).unwrap(),
+            ),
+            (
+                b"A namespace\r\n?".as_ref(),
+                b"?".as_ref(),
+                Command::new("A", CommandBody::Namespace).unwrap(),
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_kat_inverse_response_namespace() {
+        kat_inverse_response(&[
+            (
+                b"* NAMESPACE ((\"\" \"/\")) NIL NIL\r\n".as_ref(),
+                b"".as_ref(),
+                Response::Data(Data::Namespace {
+                    personal: vec![NamespaceDescriptor::new(
+                        IString::try_from("").unwrap(),
+                        Some(QuotedChar::try_from('/').unwrap()),
+                    )],
+                    other: vec![],
+                    shared: vec![],
+                }),
+            ),
+            (
+                b"* NAMESPACE NIL NIL NIL\r\n".as_ref(),
+                b"".as_ref(),
+                Response::Data(Data::Namespace {
+                    personal: vec![],
+                    other: vec![],
+                    shared: vec![],
+                }),
+            ),
+            (
+                b"* NAMESPACE ((\"\" \"/\")) ((\"~\" \"/\")) ((\"#shared/\" \"/\")(\"#public/\" \"/\"))\r\n".as_ref(),
+                b"".as_ref(),
+                Response::Data(Data::Namespace {
+                    personal: vec![NamespaceDescriptor::new(
+                        IString::try_from("").unwrap(),
+                        Some(QuotedChar::try_from('/').unwrap()),
+                    )],
+                    other: vec![NamespaceDescriptor::new(
+                        IString::try_from("~").unwrap(),
+                        Some(QuotedChar::try_from('/').unwrap()),
+                    )],
+                    shared: vec![
+                        NamespaceDescriptor::new(
+                            IString::try_from("#shared/").unwrap(),
+                            Some(QuotedChar::try_from('/').unwrap()),
+                        ),
+                        NamespaceDescriptor::new(
+                            IString::try_from("#public/").unwrap(),
+                            Some(QuotedChar::try_from('/').unwrap()),
+                        ),
+                    ],
+                }),
+            ),
+            (
+                b"* NAMESPACE ((\"\" NIL \"X-PARAM\" (\"FLAG1\" \"FLAG2\"))) NIL NIL\r\n".as_ref(),
+                b"".as_ref(),
+                Response::Data(Data::Namespace {
+                    personal: vec![NamespaceDescriptor {
+                        prefix: IString::try_from("").unwrap(),
+                        delimiter: None,
+                        extensions: vec![(
+                            IString::try_from("X-PARAM").unwrap(),
+                            Vec1::try_from(vec![
+                                IString::try_from("FLAG1").unwrap(),
+                                IString::try_from("FLAG2").unwrap(),
+                            ])
+                            .unwrap(),
+                        )],
+                    }],
+                    other: vec![],
+                    shared: vec![],
+                }),
+            ),
+        ]);
+    }
+}
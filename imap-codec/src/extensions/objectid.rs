@@ -0,0 +1,205 @@
+//! IMAP Extension for Object Identifiers
+
+// Additional changes:
+//
+// capability =/ "OBJECTID"
+// fetch-att =/ "EMAILID" / "THREADID"
+// msg-att-static =/ fetch-emailid-resp / fetch-threadid-resp
+// resp-text-code =/ "MAILBOXID" SP "(" objectid ")"
+// status-att =/ "MAILBOXID"
+// status-att-val =/ "MAILBOXID" SP "(" objectid ")"
+
+use std::io::Write;
+
+use imap_types::{
+    extensions::objectid::ObjectId, fetch::MessageDataItem, response::Code, status::StatusDataItem,
+};
+use nom::{
+    branch::alt,
+    bytes::streaming::{tag, tag_no_case, take_while_m_n},
+    combinator::{map, value},
+    sequence::{delimited, preceded},
+};
+
+use crate::{
+    core::nil,
+    decode::IMAPResult,
+    encode::{EncodeContext, EncodeIntoContext},
+};
+
+/// ```abnf
+/// objectid = 1*255(ALPHA / DIGIT / "_" / "-")
+/// ```
+pub(crate) fn objectid(input: &[u8]) -> IMAPResult<'_, &[u8], ObjectId> {
+    map(
+        take_while_m_n(1, ObjectId::MAX_LEN, |b: u8| {
+            b.is_ascii_alphanumeric() || b == b'_' || b == b'-'
+        }),
+        // Safety: `unwrap` can't panic because of `take_while_m_n`.
+        |bytes: &[u8]| ObjectId::try_from(bytes).unwrap(),
+    )(input)
+}
+
+/// `"(" objectid ")"`
+fn objectid_paren(input: &[u8]) -> IMAPResult<'_, &[u8], ObjectId> {
+    delimited(tag(b"("), objectid, tag(b")"))(input)
+}
+
+/// ```abnf
+/// fetch-emailid-resp = "EMAILID" SP "(" objectid ")"
+///
+/// fetch-threadid-resp = "THREADID" SP ( "(" objectid ")" / nil )
+/// ```
+pub(crate) fn msg_att_objectid(input: &[u8]) -> IMAPResult<'_, &[u8], MessageDataItem<'_>> {
+    alt((
+        map(
+            preceded(tag_no_case(b"EMAILID "), objectid_paren),
+            MessageDataItem::EmailId,
+        ),
+        map(
+            preceded(
+                tag_no_case(b"THREADID "),
+                alt((map(objectid_paren, Some), value(None, nil))),
+            ),
+            MessageDataItem::ThreadId,
+        ),
+    ))(input)
+}
+
+/// ```abnf
+/// resp-text-code =/ "MAILBOXID" SP "(" objectid ")"
+/// ```
+pub(crate) fn resp_code_mailboxid(input: &[u8]) -> IMAPResult<'_, &[u8], Code<'_>> {
+    map(
+        preceded(tag_no_case(b"MAILBOXID "), objectid_paren),
+        Code::MailboxId,
+    )(input)
+}
+
+/// ```abnf
+/// status-att-val =/ "MAILBOXID" SP "(" objectid ")"
+/// ```
+pub(crate) fn status_att_val_mailboxid(input: &[u8]) -> IMAPResult<'_, &[u8], StatusDataItem> {
+    map(
+        preceded(tag_no_case(b"MAILBOXID "), objectid_paren),
+        StatusDataItem::MailboxId,
+    )(input)
+}
+
+impl EncodeIntoContext for ObjectId {
+    fn encode_ctx(&self, ctx: &mut EncodeContext) -> std::io::Result<()> {
+        ctx.write_all(self.inner().as_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU32;
+
+    use imap_types::{
+        command::{Command, CommandBody},
+        core::Vec1,
+        fetch::{MacroOrMessageDataItemNames, MessageDataItemName},
+        mailbox::Mailbox,
+        response::{Data, Response, Status},
+        status::StatusDataItemName,
+    };
+
+    use super::*;
+    use crate::testing::{kat_inverse_command, kat_inverse_response};
+
+    #[test]
+    fn test_parse_objectid() {
+        let (rem, got) = objectid(b"M6d99ac3275bb4e)").unwrap();
+        assert_eq!(rem, b")");
+        assert_eq!(got, ObjectId::try_from("M6d99ac3275bb4e").unwrap());
+
+        assert!(objectid(b" ").is_err());
+        assert!(objectid_paren(format!("({})", "a".repeat(256)).as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_kat_inverse_command_objectid() {
+        kat_inverse_command(&[
+            (
+                b"A FETCH 1 (EMAILID THREADID)\r\n".as_ref(),
+                b"".as_ref(),
+                Command::new(
+                    "A",
+                    CommandBody::fetch(
+                        1,
+                        MacroOrMessageDataItemNames::MessageDataItemNames(vec![
+                            MessageDataItemName::EmailId,
+                            MessageDataItemName::ThreadId,
+                        ]),
+                        false,
+                    )
+                    .unwrap(),
+                )
+                .unwrap(),
+            ),
+            (
+                b"A STATUS INBOX (MAILBOXID)\r\n".as_ref(),
+                b"".as_ref(),
+                Command::new(
+                    "A",
+                    CommandBody::status("INBOX", vec![StatusDataItemName::MailboxId]).unwrap(),
+                )
+                .unwrap(),
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_kat_inverse_response_objectid() {
+        kat_inverse_response(&[
+            (
+                b"* 1 FETCH (EMAILID (M00000001) THREADID (T64b478a75b7ea9))\r\n".as_ref(),
+                b"".as_ref(),
+                Response::Data(Data::Fetch {
+                    seq: NonZeroU32::new(1).unwrap(),
+                    items: Vec1::try_from(vec![
+                        MessageDataItem::EmailId(ObjectId::try_from("M00000001").unwrap()),
+                        MessageDataItem::ThreadId(Some(
+                            ObjectId::try_from("T64b478a75b7ea9").unwrap(),
+                        )),
+                    ])
+                    .unwrap(),
+                }),
+            ),
+            (
+                b"* 2 FETCH (THREADID NIL)\r\n".as_ref(),
+                b"".as_ref(),
+                Response::Data(Data::Fetch {
+                    seq: NonZeroU32::new(2).unwrap(),
+                    items: Vec1::from(MessageDataItem::ThreadId(None)),
+                }),
+            ),
+            (
+                b"* STATUS foo (MAILBOXID (F2212ea87-6097-4256-9d51-71338625))\r\n".as_ref(),
+                b"".as_ref(),
+                Response::Data(Data::Status {
+                    mailbox: Mailbox::try_from("foo").unwrap(),
+                    items: vec![StatusDataItem::MailboxId(
+                        ObjectId::try_from("F2212ea87-6097-4256-9d51-71338625").unwrap(),
+                    )]
+                    .into(),
+                }),
+            ),
+            (
+                b"A3 OK [MAILBOXID (F2212ea87-6097-4256-9d51-71338625)] Completed\r\n".as_ref(),
+                b"".as_ref(),
+                Response::Status(
+                    Status::ok(
+                        Some("A3".try_into().unwrap()),
+                        Some(Code::MailboxId(
+                            ObjectId::try_from("F2212ea87-6097-4256-9d51-71338625").unwrap(),
+                        )),
+                        "Completed",
+                    )
+                    .unwrap(),
+                ),
+            ),
+        ]);
+    }
+}
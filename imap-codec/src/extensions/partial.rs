@@ -0,0 +1,253 @@
+//! IMAP PARTIAL Extension for Paged SEARCH and FETCH
+
+// Additional changes:
+//
+// capability         =/ "PARTIAL"
+// search-return-opt  =/ "PARTIAL" SP partial-range
+// search-return-data =/ "PARTIAL" SP "(" partial-range SP partial-results ")"
+// fetch-modifier     =/ "PARTIAL" SP partial-range
+
+use std::io::Write;
+
+use abnf_core::streaming::sp;
+use imap_types::{extensions::partial::PartialRange, sequence::SequenceSet};
+use nom::{
+    branch::alt,
+    bytes::streaming::{tag, tag_no_case},
+    combinator::{map, value},
+    error::ErrorKind,
+    sequence::{delimited, preceded, separated_pair, tuple},
+};
+
+use crate::{
+    core::nz_number,
+    decode::{IMAPErrorKind, IMAPParseError, IMAPResult},
+    encode::{EncodeContext, EncodeIntoContext},
+    sequence::sequence_set,
+};
+
+/// ```abnf
+/// partial-range = partial-range-first / partial-range-last
+///
+/// partial-range-first = nz-number ":" nz-number
+///
+/// partial-range-last = MINUS nz-number ":" MINUS nz-number
+/// ```
+///
+/// Note: RFC 9394 treats `500:400` the same as `400:500` (and `-1:-100` the same as `-100:-1`).
+/// Thus, the anchors are put in order before the range is constructed.
+pub(crate) fn partial_range(input: &[u8]) -> IMAPResult<'_, &[u8], PartialRange> {
+    let mut parser = alt((
+        map(
+            separated_pair(nz_number, tag(b":"), nz_number),
+            |(from, to)| (i64::from(from.get()), i64::from(to.get())),
+        ),
+        map(
+            separated_pair(
+                preceded(tag(b"-"), nz_number),
+                tag(b":"),
+                preceded(tag(b"-"), nz_number),
+            ),
+            |(from, to)| (-i64::from(from.get()), -i64::from(to.get())),
+        ),
+    ));
+
+    let (remaining, (from, to)) = parser(input)?;
+
+    match PartialRange::new(from.min(to), from.max(to)) {
+        Ok(range) => Ok((remaining, range)),
+        Err(_) => Err(nom::Err::Failure(IMAPParseError {
+            input,
+            kind: IMAPErrorKind::Nom(ErrorKind::Verify),
+        })),
+    }
+}
+
+/// ```abnf
+/// search-return-opt =/ "PARTIAL" SP partial-range
+/// ```
+pub(crate) fn search_return_opt_partial(input: &[u8]) -> IMAPResult<'_, &[u8], PartialRange> {
+    preceded(tag_no_case(b"PARTIAL "), partial_range)(input)
+}
+
+/// ```abnf
+/// search-return-data =/ "PARTIAL" SP "(" partial-range SP partial-results ")"
+///
+/// partial-results = sequence-set / "NIL"
+/// ```
+pub(crate) fn search_return_data_partial(
+    input: &[u8],
+) -> IMAPResult<'_, &[u8], (PartialRange, Option<SequenceSet>)> {
+    preceded(
+        tag_no_case(b"PARTIAL "),
+        delimited(
+            tag(b"("),
+            map(
+                tuple((
+                    partial_range,
+                    sp,
+                    alt((
+                        value(None, tag_no_case(b"NIL")),
+                        map(sequence_set(usize::MAX), Some),
+                    )),
+                )),
+                |(range, _, results)| (range, results),
+            ),
+            tag(b")"),
+        ),
+    )(input)
+}
+
+/// ```abnf
+/// fetch-modifier =/ "PARTIAL" SP partial-range
+/// ```
+pub(crate) fn fetch_modifier_partial(input: &[u8]) -> IMAPResult<'_, &[u8], PartialRange> {
+    preceded(tag_no_case(b"PARTIAL "), partial_range)(input)
+}
+
+impl EncodeIntoContext for PartialRange {
+    fn encode_ctx(&self, ctx: &mut EncodeContext) -> std::io::Result<()> {
+        write!(ctx, "{}:{}", self.from(), self.to())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use imap_types::{
+        command::{Command, CommandBody},
+        core::Vec1,
+        extensions::{
+            condstore_qresync::FetchModifier,
+            esearch::{ESearchReturn, SearchReturnOption},
+        },
+        fetch::{MacroOrMessageDataItemNames, MessageDataItemName},
+        response::{Data, Response},
+        search::SearchKey,
+    };
+
+    use super::*;
+    use crate::testing::{kat_inverse_command, kat_inverse_response, known_answer_test_encode};
+
+    #[test]
+    fn test_parse_partial_range() {
+        let tests = [
+            (b"1:50 ".as_ref(), (1, 50)),
+            (b"7:7 ", (7, 7)),
+            (b"-50:-1 ", (-50, -1)),
+            (b"-4294967295:-1 ", (-4294967295, -1)),
+            // Reversed anchors are equivalent.
+            (b"500:400 ", (400, 500)),
+            (b"-1:-100 ", (-100, -1)),
+        ];
+
+        for (test, (from, to)) in tests {
+            let (rem, got) = partial_range(test).unwrap();
+            assert_eq!(rem, b" ");
+            assert_eq!(got, PartialRange::new(from, to).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_parse_partial_range_failing() {
+        for test in [b"0:50 ".as_ref(), b"1:-50 ", b"-1:50 ", b"-0:-1 "] {
+            assert!(partial_range(test).is_err());
+        }
+    }
+
+    #[test]
+    fn test_encode_partial_range() {
+        known_answer_test_encode((PartialRange::new(1, 50).unwrap(), b"1:50".as_ref()));
+        known_answer_test_encode((PartialRange::new(-50, -1).unwrap(), b"-50:-1".as_ref()));
+    }
+
+    #[test]
+    fn test_kat_inverse_command_partial() {
+        kat_inverse_command(&[
+            (
+                b"A UID SEARCH RETURN (PARTIAL 1:50) SEEN\r\n".as_ref(),
+                b"".as_ref(),
+                Command::new(
+                    "A",
+                    CommandBody::SearchExtended {
+                        charset: None,
+                        criteria: Vec1::from(SearchKey::Seen),
+                        uid: true,
+                        return_options: Vec1::from(SearchReturnOption::Partial(
+                            PartialRange::new(1, 50).unwrap(),
+                        )),
+                    },
+                )
+                .unwrap(),
+            ),
+            (
+                b"A SEARCH RETURN (COUNT PARTIAL -50:-1) ALL\r\n".as_ref(),
+                b"".as_ref(),
+                Command::new(
+                    "A",
+                    CommandBody::SearchExtended {
+                        charset: None,
+                        criteria: Vec1::from(SearchKey::All),
+                        uid: false,
+                        return_options: Vec1::try_from(vec![
+                            SearchReturnOption::Count,
+                            SearchReturnOption::Partial(PartialRange::new(-50, -1).unwrap()),
+                        ])
+                        .unwrap(),
+                    },
+                )
+                .unwrap(),
+            ),
+            (
+                b"A UID FETCH 1:* (FLAGS) (PARTIAL -30:-1)\r\n".as_ref(),
+                b"".as_ref(),
+                Command::new(
+                    "A",
+                    CommandBody::FetchWithModifiers {
+                        sequence_set: SequenceSet::try_from("1:*").unwrap(),
+                        macro_or_item_names: MacroOrMessageDataItemNames::MessageDataItemNames(
+                            vec![MessageDataItemName::Flags],
+                        ),
+                        uid: true,
+                        modifiers: Vec1::from(FetchModifier::Partial(
+                            PartialRange::new(-30, -1).unwrap(),
+                        )),
+                    },
+                )
+                .unwrap(),
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_kat_inverse_response_esearch_partial() {
+        kat_inverse_response(&[
+            (
+                b"* ESEARCH (TAG \"A1\") UID PARTIAL (-100:-1 200:250,252:300)\r\n".as_ref(),
+                b"".as_ref(),
+                Response::Data(Data::ESearch {
+                    tag: Some("A1".try_into().unwrap()),
+                    uid: true,
+                    returns: vec![ESearchReturn::Partial {
+                        range: PartialRange::new(-100, -1).unwrap(),
+                        results: Some(SequenceSet::try_from("200:250,252:300").unwrap()),
+                    }],
+                }),
+            ),
+            (
+                b"* ESEARCH (TAG \"A2\") COUNT 5 PARTIAL (6:10 NIL)\r\n".as_ref(),
+                b"".as_ref(),
+                Response::Data(Data::ESearch {
+                    tag: Some("A2".try_into().unwrap()),
+                    uid: false,
+                    returns: vec![
+                        ESearchReturn::Count(5),
+                        ESearchReturn::Partial {
+                            range: PartialRange::new(6, 10).unwrap(),
+                            results: None,
+                        },
+                    ],
+                }),
+            ),
+        ]);
+    }
+}
@@ -0,0 +1,132 @@
+//! IMAP4 Extension for Returning a Preview
+
+// Additional changes:
+//
+// capability =/ "PREVIEW"
+// fetch-att =/ "PREVIEW" [SP "(" preview-mod *(SP preview-mod) ")"]
+// msg-att-dynamic =/ "PREVIEW" SP nstring
+
+use abnf_core::streaming::sp;
+use imap_types::fetch::{MessageDataItem, MessageDataItemName};
+use nom::{
+    bytes::streaming::{tag, tag_no_case},
+    combinator::{map, opt},
+    multi::separated_list1,
+    sequence::{delimited, preceded},
+};
+
+use crate::{core::nstring, decode::IMAPResult};
+
+/// ```abnf
+/// fetch-att =/ "PREVIEW" [SP "(" preview-mod *(SP preview-mod) ")"]
+///
+/// preview-mod = "LAZY"
+/// ```
+pub(crate) fn fetch_att_preview(input: &[u8]) -> IMAPResult<'_, &[u8], MessageDataItemName<'_>> {
+    map(
+        preceded(
+            tag_no_case(b"PREVIEW"),
+            opt(preceded(
+                sp,
+                delimited(
+                    tag(b"("),
+                    separated_list1(sp, tag_no_case(b"LAZY")),
+                    tag(b")"),
+                ),
+            )),
+        ),
+        |modifiers| MessageDataItemName::Preview {
+            lazy: modifiers.is_some(),
+        },
+    )(input)
+}
+
+/// ```abnf
+/// msg-att-dynamic =/ "PREVIEW" SP nstring
+/// ```
+pub(crate) fn msg_att_preview(input: &[u8]) -> IMAPResult<'_, &[u8], MessageDataItem<'_>> {
+    map(
+        preceded(tag_no_case(b"PREVIEW "), nstring),
+        MessageDataItem::Preview,
+    )(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU32;
+
+    use imap_types::{
+        command::{Command, CommandBody},
+        core::{NString, Vec1},
+        fetch::MacroOrMessageDataItemNames,
+        response::{Data, Response},
+    };
+
+    use super::*;
+    use crate::testing::{kat_inverse_command, kat_inverse_response};
+
+    #[test]
+    fn test_kat_inverse_command_preview() {
+        kat_inverse_command(&[
+            (
+                b"A FETCH 1 PREVIEW\r\n".as_ref(),
+                b"".as_ref(),
+                Command::new(
+                    "A",
+                    CommandBody::fetch(
+                        1,
+                        vec![MessageDataItemName::Preview { lazy: false }],
+                        false,
+                    )
+                    .unwrap(),
+                )
+                .unwrap(),
+            ),
+            (
+                b"A UID FETCH 1:* (UID PREVIEW (LAZY))\r\n".as_ref(),
+                b"".as_ref(),
+                Command::new(
+                    "A",
+                    CommandBody::fetch(
+                        "1:*",
+                        MacroOrMessageDataItemNames::MessageDataItemNames(vec![
+                            MessageDataItemName::Uid,
+                            MessageDataItemName::Preview { lazy: true },
+                        ]),
+                        true,
+                    )
+                    .unwrap(),
+                )
+                .unwrap(),
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_kat_inverse_response_preview() {
+        kat_inverse_response(&[
+            (
+                b"* 1 FETCH (PREVIEW \"Hello, this is a preview.\")\r\n".as_ref(),
+                b"".as_ref(),
+                Response::Data(Data::Fetch {
+                    seq: NonZeroU32::new(1).unwrap(),
+                    items: Vec1::from(MessageDataItem::Preview(
+                        NString::try_from("Hello, this is a preview.").unwrap(),
+                    )),
+                }),
+            ),
+            (
+                b"* 2 FETCH (UID 5 PREVIEW NIL)\r\n".as_ref(),
+                b"".as_ref(),
+                Response::Data(Data::Fetch {
+                    seq: NonZeroU32::new(2).unwrap(),
+                    items: Vec1::try_from(vec![
+                        MessageDataItem::Uid(NonZeroU32::new(5).unwrap()),
+                        MessageDataItem::Preview(NString(None)),
+                    ])
+                    .unwrap(),
+                }),
+            ),
+        ]);
+    }
+}
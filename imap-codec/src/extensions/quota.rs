@@ -5,8 +5,8 @@ use std::io::Write;
 use abnf_core::streaming::sp;
 use imap_types::{
     command::CommandBody,
-    core::{AString, Vec1},
-    extensions::quota::{QuotaGet, QuotaSet, Resource},
+    core::Vec1,
+    extensions::quota::{QuotaGet, QuotaRoot, QuotaSet, Resource},
     response::Data,
 };
 use nom::{
@@ -27,15 +27,15 @@ use crate::{
 /// quota-root-name = astring
 /// ```
 #[inline]
-pub(crate) fn quota_root_name(input: &[u8]) -> IMAPResult<&[u8], AString> {
-    astring(input)
+pub(crate) fn quota_root_name(input: &[u8]) -> IMAPResult<'_, &[u8], QuotaRoot<'_>> {
+    map(astring, QuotaRoot::from)(input)
 }
 
 /// ```abnf
 /// getquota = "GETQUOTA" SP quota-root-name
 /// ```
 #[inline]
-pub(crate) fn getquota(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
+pub(crate) fn getquota(input: &[u8]) -> IMAPResult<'_, &[u8], CommandBody<'_>> {
     let mut parser = tuple((tag_no_case("GETQUOTA "), quota_root_name));
 
     let (remaining, (_, root)) = parser(input)?;
@@ -46,7 +46,7 @@ pub(crate) fn getquota(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
 /// ```abnf
 /// getquotaroot = "GETQUOTAROOT" SP mailbox
 /// ```
-pub(crate) fn getquotaroot(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
+pub(crate) fn getquotaroot(input: &[u8]) -> IMAPResult<'_, &[u8], CommandBody<'_>> {
     let mut parser = tuple((tag_no_case("GETQUOTAROOT "), mailbox));
 
     let (remaining, (_, mailbox)) = parser(input)?;
@@ -63,7 +63,7 @@ pub(crate) fn getquotaroot(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
 ///
 /// resource-limit = number64
 /// ```
-pub(crate) fn quota_resource(input: &[u8]) -> IMAPResult<&[u8], QuotaGet> {
+pub(crate) fn quota_resource(input: &[u8]) -> IMAPResult<'_, &[u8], QuotaGet<'_>> {
     let mut parser = tuple((resource_name, sp, number64, sp, number64));
 
     let (remaining, (resource, _, usage, _, limit)) = parser(input)?;
@@ -87,7 +87,7 @@ pub(crate) fn quota_resource(input: &[u8]) -> IMAPResult<&[u8], QuotaGet> {
 ///
 /// resource-name-ext = atom
 /// ```
-pub(crate) fn resource_name(input: &[u8]) -> IMAPResult<&[u8], Resource> {
+pub(crate) fn resource_name(input: &[u8]) -> IMAPResult<'_, &[u8], Resource<'_>> {
     map(atom, Resource::from)(input)
 }
 
@@ -96,7 +96,7 @@ pub(crate) fn resource_name(input: &[u8]) -> IMAPResult<&[u8], Resource> {
 ///
 /// quota-list = "(" quota-resource *(SP quota-resource) ")"
 /// ```
-pub(crate) fn quota_response(input: &[u8]) -> IMAPResult<&[u8], Data> {
+pub(crate) fn quota_response(input: &[u8]) -> IMAPResult<'_, &[u8], Data<'_>> {
     let mut parser = tuple((
         tag_no_case("QUOTA "),
         quota_root_name,
@@ -119,7 +119,7 @@ pub(crate) fn quota_response(input: &[u8]) -> IMAPResult<&[u8], Data> {
 /// ```abnf
 /// quotaroot-response = "QUOTAROOT" SP mailbox *(SP quota-root-name)
 /// ```
-pub(crate) fn quotaroot_response(input: &[u8]) -> IMAPResult<&[u8], Data> {
+pub(crate) fn quotaroot_response(input: &[u8]) -> IMAPResult<'_, &[u8], Data<'_>> {
     let mut parser = tuple((
         tag_no_case("QUOTAROOT "),
         mailbox,
@@ -136,7 +136,7 @@ pub(crate) fn quotaroot_response(input: &[u8]) -> IMAPResult<&[u8], Data> {
 ///
 /// setquota-list = "(" [setquota-resource *(SP setquota-resource)] ")"
 /// ```
-pub(crate) fn setquota(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
+pub(crate) fn setquota(input: &[u8]) -> IMAPResult<'_, &[u8], CommandBody<'_>> {
     let mut parser = tuple((
         tag_no_case("SETQUOTA "),
         quota_root_name,
@@ -152,7 +152,7 @@ pub(crate) fn setquota(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
 /// ```abnf
 /// setquota-resource = resource-name SP resource-limit
 /// ```
-pub(crate) fn setquota_resource(input: &[u8]) -> IMAPResult<&[u8], QuotaSet> {
+pub(crate) fn setquota_resource(input: &[u8]) -> IMAPResult<'_, &[u8], QuotaSet<'_>> {
     let mut parser = tuple((resource_name, sp, number64));
 
     let (remaining, (resource, _, limit)) = parser(input)?;
@@ -191,6 +191,12 @@ pub(crate) fn setquota_resource(input: &[u8]) -> IMAPResult<&[u8], QuotaSet> {
 //     Ok((remaining, Capability::QuotaRes(resource)))
 // }
 
+impl<'a> EncodeIntoContext for QuotaRoot<'a> {
+    fn encode_ctx(&self, ctx: &mut EncodeContext) -> std::io::Result<()> {
+        self.inner().encode_ctx(ctx)
+    }
+}
+
 impl<'a> EncodeIntoContext for Resource<'a> {
     fn encode_ctx(&self, ctx: &mut EncodeContext) -> std::io::Result<()> {
         ctx.write_all(self.to_string().as_bytes())
@@ -215,7 +221,7 @@ impl<'a> EncodeIntoContext for QuotaSet<'a> {
 mod tests {
     use imap_types::{
         command::{Command, CommandBody},
-        core::{IString, Tag},
+        core::{AString, IString, Tag},
         extensions::quota::{QuotaGet, QuotaSet, Resource},
         mailbox::Mailbox,
         response::{Capability, Code, Response, Status},
@@ -323,6 +329,16 @@ mod tests {
                 b"".as_ref(),
                 Command::new("A", CommandBody::set_quota("INBOX", vec![]).unwrap()).unwrap(),
             ),
+            (
+                b"A SETQUOTA \"\" (STORAGE 512)\r\n",
+                b"",
+                Command::new(
+                    "A",
+                    CommandBody::set_quota("", vec![QuotaSet::new(Resource::Storage, 512)])
+                        .unwrap(),
+                )
+                .unwrap(),
+            ),
             (
                 b"A SETQUOTA INBOX (STORAGE 256)\r\n",
                 b"",
@@ -512,7 +528,7 @@ mod tests {
                 b"* QUOTA \"#user/alice\" (MESSAGE 42 1000)\r\n",
                 b"",
                 Response::Data(Data::Quota {
-                    root: AString::String(IString::try_from("#user/alice").unwrap()),
+                    root: AString::String(IString::try_from("#user/alice").unwrap()).into(),
                     quotas: vec![QuotaGet::new(Resource::Message, 42, 1000)]
                         .try_into()
                         .unwrap(),
@@ -565,8 +581,8 @@ mod tests {
                 Response::Data(Data::QuotaRoot {
                     mailbox: Mailbox::try_from("inbox").unwrap(),
                     roots: vec![
-                        AString::String(IString::try_from("#user/alice").unwrap()),
-                        AString::String(IString::try_from("!partition/sda4").unwrap()),
+                        AString::String(IString::try_from("#user/alice").unwrap()).into(),
+                        AString::String(IString::try_from("!partition/sda4").unwrap()).into(),
                     ],
                 }),
             ),
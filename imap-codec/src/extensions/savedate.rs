@@ -0,0 +1,173 @@
+//! IMAP "SAVEDATE" Extension
+
+// Additional changes:
+//
+// capability =/ "SAVEDATE"
+// fetch-att =/ "SAVEDATE"
+// msg-att-static =/ "SAVEDATE" SP (date-time / nil)
+// search-key =/ "SAVEDBEFORE" SP date /
+//               "SAVEDON" SP date /
+//               "SAVEDSINCE" SP date /
+//               "SAVEDATESUPPORTED"
+
+use abnf_core::streaming::sp;
+use imap_types::{fetch::MessageDataItem, search::SearchKey};
+use nom::{
+    branch::alt,
+    bytes::streaming::tag_no_case,
+    combinator::{map, map_opt, value},
+    sequence::{preceded, tuple},
+};
+
+use crate::{
+    core::nil,
+    datetime::{date, date_time},
+    decode::IMAPResult,
+};
+
+/// ```abnf
+/// msg-att-static =/ "SAVEDATE" SP (date-time / nil)
+/// ```
+pub(crate) fn msg_att_savedate(input: &[u8]) -> IMAPResult<'_, &[u8], MessageDataItem<'_>> {
+    map(
+        preceded(
+            tag_no_case(b"SAVEDATE "),
+            alt((map(date_time, Some), value(None, nil))),
+        ),
+        MessageDataItem::SaveDate,
+    )(input)
+}
+
+/// ```abnf
+/// search-key =/ "SAVEDBEFORE" SP date /
+///               "SAVEDON" SP date /
+///               "SAVEDSINCE" SP date /
+///               "SAVEDATESUPPORTED"
+/// ```
+pub(crate) fn search_key_savedate(input: &[u8]) -> IMAPResult<'_, &[u8], SearchKey<'_>> {
+    alt((
+        map(
+            tuple((tag_no_case(b"SAVEDBEFORE"), sp, map_opt(date, |date| date))),
+            |(_, _, date)| SearchKey::SavedBefore(date.clone()),
+        ),
+        map(
+            tuple((tag_no_case(b"SAVEDON"), sp, map_opt(date, |date| date))),
+            |(_, _, date)| SearchKey::SavedOn(date),
+        ),
+        map(
+            tuple((tag_no_case(b"SAVEDSINCE"), sp, map_opt(date, |date| date))),
+            |(_, _, date)| SearchKey::SavedSince(date),
+        ),
+        value(
+            SearchKey::SaveDateSupported,
+            tag_no_case(b"SAVEDATESUPPORTED"),
+        ),
+    ))(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU32;
+
+    use imap_types::{
+        command::{Command, CommandBody},
+        core::Vec1,
+        datetime::{DateTime, NaiveDate},
+        fetch::{MacroOrMessageDataItemNames, MessageDataItemName},
+        response::{Data, Response},
+    };
+
+    use super::*;
+    use crate::testing::{kat_inverse_command, kat_inverse_response};
+
+    #[test]
+    fn test_kat_inverse_command_savedate() {
+        let date =
+            NaiveDate::try_from(chrono::NaiveDate::from_ymd_opt(2018, 7, 17).unwrap()).unwrap();
+
+        kat_inverse_command(&[
+            (
+                b"A UID FETCH 1:* (UID SAVEDATE)\r\n".as_ref(),
+                b"".as_ref(),
+                Command::new(
+                    "A",
+                    CommandBody::fetch(
+                        "1:*",
+                        MacroOrMessageDataItemNames::MessageDataItemNames(vec![
+                            MessageDataItemName::Uid,
+                            MessageDataItemName::SaveDate,
+                        ]),
+                        true,
+                    )
+                    .unwrap(),
+                )
+                .unwrap(),
+            ),
+            (
+                b"A SEARCH SAVEDATESUPPORTED SAVEDBEFORE \"17-Jul-2018\"\r\n".as_ref(),
+                b"".as_ref(),
+                Command::new(
+                    "A",
+                    CommandBody::search(
+                        None,
+                        Vec1::try_from(vec![
+                            SearchKey::SaveDateSupported,
+                            SearchKey::SavedBefore(date.clone()),
+                        ])
+                        .unwrap(),
+                        false,
+                    ),
+                )
+                .unwrap(),
+            ),
+            (
+                b"A SEARCH OR SAVEDON \"17-Jul-2018\" SAVEDSINCE \"17-Jul-2018\"\r\n".as_ref(),
+                b"".as_ref(),
+                Command::new(
+                    "A",
+                    CommandBody::search(
+                        None,
+                        Vec1::from(SearchKey::Or(
+                            Box::new(SearchKey::SavedOn(date.clone())),
+                            Box::new(SearchKey::SavedSince(date)),
+                        )),
+                        false,
+                    ),
+                )
+                .unwrap(),
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_kat_inverse_response_savedate() {
+        kat_inverse_response(&[
+            (
+                b"* 1 FETCH (SAVEDATE \"17-Jul-1996 02:44:25 -0700\")\r\n".as_ref(),
+                b"".as_ref(),
+                Response::Data(Data::Fetch {
+                    seq: NonZeroU32::new(1).unwrap(),
+                    items: Vec1::from(MessageDataItem::SaveDate(Some(
+                        DateTime::try_from(
+                            chrono::DateTime::parse_from_rfc2822("Wed, 17 Jul 1996 02:44:25 -0700")
+                                .unwrap(),
+                        )
+                        .unwrap(),
+                    ))),
+                }),
+            ),
+            (
+                b"* 2 FETCH (UID 7 SAVEDATE NIL)\r\n".as_ref(),
+                b"".as_ref(),
+                Response::Data(Data::Fetch {
+                    seq: NonZeroU32::new(2).unwrap(),
+                    items: Vec1::try_from(vec![
+                        MessageDataItem::Uid(NonZeroU32::new(7).unwrap()),
+                        MessageDataItem::SaveDate(None),
+                    ])
+                    .unwrap(),
+                }),
+            ),
+        ]);
+    }
+}
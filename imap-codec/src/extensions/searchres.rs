@@ -0,0 +1,125 @@
+//! The IMAP SEARCHRES Extension
+
+// Additional changes:
+//
+// search-return-opt =/ "SAVE"
+// sequence-set      =/ seq-last-command
+
+use imap_types::sequence::Sequence;
+use nom::{bytes::streaming::tag, combinator::value};
+
+use crate::decode::IMAPResult;
+
+/// ```abnf
+/// seq-last-command = "$"
+/// ```
+pub(crate) fn seq_last_command(input: &[u8]) -> IMAPResult<'_, &[u8], Sequence> {
+    value(Sequence::SavedResult, tag(b"$"))(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use imap_types::{
+        command::{Command, CommandBody},
+        core::Vec1,
+        extensions::esearch::SearchReturnOption,
+        fetch::MessageDataItemName,
+        search::SearchKey,
+        sequence::{SeqOrUid, SequenceSet},
+    };
+
+    use super::*;
+    use crate::testing::{kat_inverse_command, known_answer_test_encode};
+
+    #[test]
+    fn test_encode_sequence_set_saved_result() {
+        let tests = [
+            (SequenceSet::from(Sequence::SavedResult), b"$".as_ref()),
+            (SequenceSet::try_from("$,5:9").unwrap(), b"$,5:9"),
+            (
+                SequenceSet(
+                    Vec1::try_from(vec![
+                        Sequence::Range(
+                            SeqOrUid::Value(5.try_into().unwrap()),
+                            SeqOrUid::Value(9.try_into().unwrap()),
+                        ),
+                        Sequence::SavedResult,
+                    ])
+                    .unwrap(),
+                ),
+                b"5:9,$",
+            ),
+        ];
+
+        for test in tests {
+            known_answer_test_encode(test);
+        }
+    }
+
+    #[test]
+    fn test_kat_inverse_command_searchres() {
+        kat_inverse_command(&[
+            (
+                b"A UID FETCH $ (FLAGS)\r\n".as_ref(),
+                b"".as_ref(),
+                Command::new(
+                    "A",
+                    CommandBody::fetch(
+                        Sequence::SavedResult,
+                        vec![MessageDataItemName::Flags],
+                        true,
+                    )
+                    .unwrap(),
+                )
+                .unwrap(),
+            ),
+            (
+                b"A COPY $,5:9 Archive\r\n".as_ref(),
+                b"".as_ref(),
+                Command::new("A", CommandBody::copy("$,5:9", "Archive", false).unwrap()).unwrap(),
+            ),
+            (
+                b"A UID SEARCH RETURN (SAVE) CHARSET UTF-8 SEEN\r\n".as_ref(),
+                b"".as_ref(),
+                Command::new(
+                    "A",
+                    CommandBody::SearchExtended {
+                        charset: Some("UTF-8".try_into().unwrap()),
+                        criteria: Vec1::from(SearchKey::Seen),
+                        uid: true,
+                        return_options: Vec1::from(SearchReturnOption::Save),
+                    },
+                )
+                .unwrap(),
+            ),
+            (
+                b"A SEARCH UID $ SEEN\r\n".as_ref(),
+                b"".as_ref(),
+                Command::new(
+                    "A",
+                    CommandBody::search(
+                        None,
+                        Vec1::try_from(vec![
+                            SearchKey::Uid(Sequence::SavedResult.into()),
+                            SearchKey::Seen,
+                        ])
+                        .unwrap(),
+                        false,
+                    ),
+                )
+                .unwrap(),
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_iter_saved_result() {
+        let largest = 10.try_into().unwrap();
+
+        let sequence_set = SequenceSet::try_from("$").unwrap();
+        assert_eq!(sequence_set.iter(largest).count(), 0);
+
+        let sequence_set = SequenceSet::try_from("$,5:6").unwrap();
+        assert_eq!(sequence_set.iter(largest).count(), 2);
+    }
+}
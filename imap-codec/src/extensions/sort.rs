@@ -23,32 +23,36 @@ use crate::{
 /// ```abnf
 /// sort = ["UID" SP] "SORT" SP sort-criteria SP search-criteria
 /// ```
-pub(crate) fn sort(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
-    let mut parser = tuple((
-        map(opt(tag_no_case("UID ")), |thing| thing.is_some()),
-        tag_no_case("SORT "),
-        sort_criteria,
-        sp,
-        search_criteria,
-    ));
+pub(crate) fn sort(
+    max_sequence_set_len: usize,
+) -> impl Fn(&[u8]) -> IMAPResult<'_, &[u8], CommandBody<'_>> {
+    move |input: &[u8]| {
+        let mut parser = tuple((
+            map(opt(tag_no_case("UID ")), |thing| thing.is_some()),
+            tag_no_case("SORT "),
+            sort_criteria,
+            sp,
+            search_criteria(max_sequence_set_len),
+        ));
 
-    let (remaining, (uid, _, sort_criteria, _, (charset, search_key))) = parser(input)?;
+        let (remaining, (uid, _, sort_criteria, _, (charset, search_key))) = parser(input)?;
 
-    Ok((
-        remaining,
-        CommandBody::Sort {
-            sort_criteria,
-            charset,
-            search_criteria: search_key,
-            uid,
-        },
-    ))
+        Ok((
+            remaining,
+            CommandBody::Sort {
+                sort_criteria,
+                charset,
+                search_criteria: search_key,
+                uid,
+            },
+        ))
+    }
 }
 
 /// ```abnf
 /// sort-criteria = "(" sort-criterion *(SP sort-criterion) ")"
 /// ```
-pub(crate) fn sort_criteria(input: &[u8]) -> IMAPResult<&[u8], Vec1<SortCriterion>> {
+pub(crate) fn sort_criteria(input: &[u8]) -> IMAPResult<'_, &[u8], Vec1<SortCriterion>> {
     delimited(
         tag("("),
         map(separated_list1(sp, sort_criterion), Vec1::unvalidated),
@@ -59,7 +63,7 @@ pub(crate) fn sort_criteria(input: &[u8]) -> IMAPResult<&[u8], Vec1<SortCriterio
 /// ```abnf
 /// sort-criterion = ["REVERSE" SP] sort-key
 /// ```
-pub(crate) fn sort_criterion(input: &[u8]) -> IMAPResult<&[u8], SortCriterion> {
+pub(crate) fn sort_criterion(input: &[u8]) -> IMAPResult<'_, &[u8], SortCriterion> {
     let mut parser = tuple((
         map(opt(tag_no_case(b"REVERSE ")), |thing| thing.is_some()),
         sort_key,
@@ -73,7 +77,7 @@ pub(crate) fn sort_criterion(input: &[u8]) -> IMAPResult<&[u8], SortCriterion> {
 /// ```abnf
 /// sort-key = "ARRIVAL" / "CC" / "DATE" / "FROM" / "SIZE" / "SUBJECT" / "TO"
 /// ```
-pub(crate) fn sort_key(input: &[u8]) -> IMAPResult<&[u8], SortKey> {
+pub(crate) fn sort_key(input: &[u8]) -> IMAPResult<'_, &[u8], SortKey> {
     alt((
         value(SortKey::Arrival, tag_no_case("ARRIVAL")),
         value(SortKey::Cc, tag_no_case("CC")),
@@ -96,3 +100,90 @@ impl EncodeIntoContext for SortCriterion {
         ctx.write_all(self.key.as_ref().as_bytes())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU32;
+
+    use imap_types::{
+        command::{Command, CommandBody},
+        core::{Charset, Vec1},
+        extensions::sort::{SortCriterion, SortKey},
+        response::{Data, Response},
+        search::SearchKey,
+    };
+
+    use crate::testing::{kat_inverse_command, kat_inverse_response};
+
+    #[test]
+    fn test_kat_inverse_command_sort() {
+        kat_inverse_command(&[
+            (
+                b"A UID SORT (REVERSE DATE) UTF-8 ALL\r\n".as_ref(),
+                b"".as_ref(),
+                Command::new(
+                    "A",
+                    CommandBody::Sort {
+                        sort_criteria: Vec1::from(SortCriterion {
+                            reverse: true,
+                            key: SortKey::Date,
+                        }),
+                        charset: Charset::try_from("UTF-8").unwrap(),
+                        search_criteria: Vec1::from(SearchKey::All),
+                        uid: true,
+                    },
+                )
+                .unwrap(),
+            ),
+            (
+                b"A SORT (SUBJECT REVERSE SIZE) US-ASCII SINCE 1-Feb-1994 UNSEEN\r\n",
+                b"",
+                Command::new(
+                    "A",
+                    CommandBody::Sort {
+                        sort_criteria: Vec1::try_from(vec![
+                            SortCriterion {
+                                reverse: false,
+                                key: SortKey::Subject,
+                            },
+                            SortCriterion {
+                                reverse: true,
+                                key: SortKey::Size,
+                            },
+                        ])
+                        .unwrap(),
+                        charset: Charset::try_from("US-ASCII").unwrap(),
+                        search_criteria: Vec1::try_from(vec![
+                            SearchKey::Since(
+                                chrono::NaiveDate::from_ymd_opt(1994, 2, 1)
+                                    .unwrap()
+                                    .try_into()
+                                    .unwrap(),
+                            ),
+                            SearchKey::Unseen,
+                        ])
+                        .unwrap(),
+                        uid: false,
+                    },
+                )
+                .unwrap(),
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_kat_inverse_response_sort() {
+        kat_inverse_response(&[
+            (
+                b"* SORT 2 84 882\r\n".as_ref(),
+                b"".as_ref(),
+                Response::Data(Data::Sort(vec![
+                    NonZeroU32::new(2).unwrap(),
+                    NonZeroU32::new(84).unwrap(),
+                    NonZeroU32::new(882).unwrap(),
+                ])),
+            ),
+            (b"* SORT\r\n", b"", Response::Data(Data::Sort(vec![]))),
+        ]);
+    }
+}
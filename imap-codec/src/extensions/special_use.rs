@@ -0,0 +1,139 @@
+//! IMAP LIST Extension for Special-Use Mailboxes
+
+// Additional changes:
+//
+// create = "CREATE" SP mailbox [create-params]
+// mbx-list-oflag =/ use-attr
+
+use abnf_core::streaming::sp;
+use imap_types::flag::FlagNameAttribute;
+use nom::{
+    bytes::streaming::{tag, tag_no_case},
+    character::streaming::char,
+    combinator::map,
+    multi::separated_list0,
+    sequence::{delimited, preceded},
+};
+
+use crate::{core::atom, decode::IMAPResult};
+
+/// ```abnf
+/// create-params = SP "(" create-param *(SP create-param) ")"
+///
+/// create-param =/ "USE" SP "(" [use-attr *(SP use-attr)] ")"
+/// ```
+///
+/// Note: Only the `USE` parameter is supported.
+pub(crate) fn create_params(input: &[u8]) -> IMAPResult<'_, &[u8], Vec<FlagNameAttribute<'_>>> {
+    delimited(
+        tag_no_case(" (USE ("),
+        separated_list0(sp, use_attr),
+        tag("))"),
+    )(input)
+}
+
+/// ```abnf
+/// use-attr = "\All" / "\Archive" / "\Drafts" / "\Flagged" / "\Junk" / "\Sent" / "\Trash" / use-attr-ext
+///
+/// use-attr-ext = "\" atom
+/// ```
+pub(crate) fn use_attr(input: &[u8]) -> IMAPResult<'_, &[u8], FlagNameAttribute<'_>> {
+    map(preceded(char('\\'), atom), FlagNameAttribute::from)(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use imap_types::{
+        command::{Command, CommandBody},
+        mailbox::Mailbox,
+        response::{Data, Response},
+    };
+
+    use super::*;
+    use crate::{
+        encode::Encoder,
+        testing::{kat_inverse_command, kat_inverse_response},
+        CommandCodec,
+    };
+
+    #[test]
+    fn test_kat_inverse_command_create_special_use() {
+        kat_inverse_command(&[
+            (
+                b"A CREATE Drafts (USE (\\Drafts))\r\n".as_ref(),
+                b"".as_ref(),
+                Command::new(
+                    "A",
+                    CommandBody::create_special_use("Drafts", vec![FlagNameAttribute::Drafts])
+                        .unwrap(),
+                )
+                .unwrap(),
+            ),
+            (
+                b"A CREATE Stuff (USE (\\Archive \\Junk))\r\n".as_ref(),
+                b"".as_ref(),
+                Command::new(
+                    "A",
+                    CommandBody::create_special_use(
+                        "Stuff",
+                        vec![FlagNameAttribute::Archive, FlagNameAttribute::Junk],
+                    )
+                    .unwrap(),
+                )
+                .unwrap(),
+            ),
+            (
+                b"A CREATE Foo (USE ())\r\n".as_ref(),
+                b"".as_ref(),
+                Command::new(
+                    "A",
+                    CommandBody::CreateSpecialUse {
+                        mailbox: Mailbox::try_from("Foo").unwrap(),
+                        use_attributes: vec![],
+                    },
+                )
+                .unwrap(),
+            ),
+            (
+                b"A CREATE Foo\r\n".as_ref(),
+                b"".as_ref(),
+                Command::new("A", CommandBody::create("Foo").unwrap()).unwrap(),
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_create_special_use_without_attributes() {
+        assert_eq!(
+            CommandBody::create_special_use("Foo", vec![]).unwrap(),
+            CommandBody::create("Foo").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_encode_create_special_use_case() {
+        let cmd = Command::new(
+            "A",
+            CommandBody::create_special_use("Sent Items", vec![FlagNameAttribute::Sent]).unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            CommandCodec::default().encode(&cmd).dump(),
+            b"A CREATE \"Sent Items\" (USE (\\Sent))\r\n"
+        );
+    }
+
+    #[test]
+    fn test_kat_inverse_response_list_special_use() {
+        kat_inverse_response(&[(
+            b"* LIST (\\Sent \\Trash) \"/\" Foo\r\n".as_ref(),
+            b"".as_ref(),
+            Response::Data(Data::List {
+                items: vec![FlagNameAttribute::Sent, FlagNameAttribute::Trash],
+                delimiter: Some('/'.try_into().unwrap()),
+                mailbox: Mailbox::try_from("Foo").unwrap(),
+            }),
+        )]);
+    }
+}
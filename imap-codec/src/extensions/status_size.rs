@@ -0,0 +1,145 @@
+//! IMAP4 Extension for Returning STATUS=SIZE and the APPENDLIMIT Extension
+
+// Additional changes:
+//
+// capability     =/ "STATUS=SIZE" / "APPENDLIMIT" ["=" number]
+// status-att     =/ "SIZE" / "APPENDLIMIT"
+// status-att-val =/ "SIZE" SP number64 / "APPENDLIMIT" SP (number / nil)
+
+use imap_types::status::StatusDataItem;
+use nom::{
+    branch::alt,
+    bytes::streaming::tag_no_case,
+    combinator::{map, value},
+    sequence::preceded,
+};
+
+use crate::{
+    core::{nil, number, number64},
+    decode::IMAPResult,
+};
+
+/// ```abnf
+/// status-att-val =/ "SIZE" SP number64
+/// ```
+pub(crate) fn status_att_val_size(input: &[u8]) -> IMAPResult<'_, &[u8], StatusDataItem> {
+    map(
+        preceded(tag_no_case(b"SIZE "), number64),
+        StatusDataItem::Size,
+    )(input)
+}
+
+/// ```abnf
+/// status-att-val =/ "APPENDLIMIT" SP (number / nil)
+/// ```
+pub(crate) fn status_att_val_appendlimit(input: &[u8]) -> IMAPResult<'_, &[u8], StatusDataItem> {
+    map(
+        preceded(
+            tag_no_case(b"APPENDLIMIT "),
+            alt((map(number, Some), value(None, nil))),
+        ),
+        StatusDataItem::AppendLimit,
+    )(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use imap_types::{
+        command::{Command, CommandBody},
+        core::Vec1,
+        mailbox::Mailbox,
+        response::{Capability, Data, Response},
+        status::StatusDataItemName,
+    };
+
+    use super::*;
+    use crate::testing::{kat_inverse_command, kat_inverse_response, known_answer_test_encode};
+
+    #[test]
+    fn test_encode_status_data_item_status_size() {
+        let tests = [
+            (StatusDataItem::Size(1048576), b"SIZE 1048576".as_ref()),
+            (
+                StatusDataItem::AppendLimit(Some(10240)),
+                b"APPENDLIMIT 10240",
+            ),
+            (StatusDataItem::AppendLimit(None), b"APPENDLIMIT NIL"),
+        ];
+
+        for test in tests {
+            known_answer_test_encode(test);
+        }
+    }
+
+    #[test]
+    fn test_kat_inverse_command_status_size() {
+        kat_inverse_command(&[
+            (
+                b"A STATUS INBOX (MESSAGES SIZE)\r\n".as_ref(),
+                b"".as_ref(),
+                Command::new(
+                    "A",
+                    CommandBody::status(
+                        "INBOX",
+                        vec![StatusDataItemName::Messages, StatusDataItemName::Size],
+                    )
+                    .unwrap(),
+                )
+                .unwrap(),
+            ),
+            (
+                b"A STATUS Archive (APPENDLIMIT)\r\n".as_ref(),
+                b"".as_ref(),
+                Command::new(
+                    "A",
+                    CommandBody::status("Archive", vec![StatusDataItemName::AppendLimit]).unwrap(),
+                )
+                .unwrap(),
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_kat_inverse_response_status_size() {
+        kat_inverse_response(&[
+            (
+                b"* STATUS INBOX (MESSAGES 231 SIZE 1048576)\r\n".as_ref(),
+                b"".as_ref(),
+                Response::Data(Data::Status {
+                    mailbox: Mailbox::Inbox,
+                    items: vec![StatusDataItem::Messages(231), StatusDataItem::Size(1048576)]
+                        .into(),
+                }),
+            ),
+            (
+                b"* STATUS Archive (APPENDLIMIT 10240)\r\n".as_ref(),
+                b"".as_ref(),
+                Response::Data(Data::Status {
+                    mailbox: Mailbox::try_from("Archive").unwrap(),
+                    items: vec![StatusDataItem::AppendLimit(Some(10240))].into(),
+                }),
+            ),
+            (
+                b"* STATUS Archive (APPENDLIMIT NIL)\r\n".as_ref(),
+                b"".as_ref(),
+                Response::Data(Data::Status {
+                    mailbox: Mailbox::try_from("Archive").unwrap(),
+                    items: vec![StatusDataItem::AppendLimit(None)].into(),
+                }),
+            ),
+            (
+                b"* CAPABILITY IMAP4REV1 STATUS=SIZE APPENDLIMIT APPENDLIMIT=10240\r\n".as_ref(),
+                b"".as_ref(),
+                Response::Data(Data::Capability(
+                    Vec1::try_from(vec![
+                        Capability::Imap4Rev1,
+                        Capability::StatusSize,
+                        Capability::AppendLimit(None),
+                        Capability::AppendLimit(Some(10240)),
+                    ])
+                    .unwrap(),
+                )),
+            ),
+        ]);
+    }
+}
@@ -22,7 +22,7 @@ use crate::{
     search::search_criteria,
 };
 
-impl<'a> EncodeIntoContext for Thread {
+impl EncodeIntoContext for Thread {
     fn encode_ctx(&self, ctx: &mut EncodeContext) -> std::io::Result<()> {
         ctx.write_all(self.to_string().as_bytes())
     }
@@ -47,26 +47,30 @@ impl<'a> EncodeIntoContext for ThreadingAlgorithmOther<'a> {
 /// ```abnf
 /// thread = ["UID" SP] "THREAD" SP thread-alg SP search-criteria
 /// ```
-pub(crate) fn thread(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
-    let mut parser = tuple((
-        map(opt(tag_no_case("UID ")), |thing| thing.is_some()),
-        tag_no_case("THREAD "),
-        thread_alg,
-        sp,
-        search_criteria,
-    ));
+pub(crate) fn thread(
+    max_sequence_set_len: usize,
+) -> impl Fn(&[u8]) -> IMAPResult<'_, &[u8], CommandBody<'_>> {
+    move |input: &[u8]| {
+        let mut parser = tuple((
+            map(opt(tag_no_case("UID ")), |thing| thing.is_some()),
+            tag_no_case("THREAD "),
+            thread_alg,
+            sp,
+            search_criteria(max_sequence_set_len),
+        ));
 
-    let (remaining, (uid, _, algorithm, _, (charset, search_key))) = parser(input)?;
+        let (remaining, (uid, _, algorithm, _, (charset, search_key))) = parser(input)?;
 
-    Ok((
-        remaining,
-        CommandBody::Thread {
-            algorithm,
-            charset,
-            search_criteria: search_key,
-            uid,
-        },
-    ))
+        Ok((
+            remaining,
+            CommandBody::Thread {
+                algorithm,
+                charset,
+                search_criteria: search_key,
+                uid,
+            },
+        ))
+    }
 }
 
 /// ```abnf
@@ -74,14 +78,14 @@ pub(crate) fn thread(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
 ///
 /// thread-alg-ext = atom
 /// ```
-pub(crate) fn thread_alg(input: &[u8]) -> IMAPResult<&[u8], ThreadingAlgorithm> {
+pub(crate) fn thread_alg(input: &[u8]) -> IMAPResult<'_, &[u8], ThreadingAlgorithm<'_>> {
     map(atom, ThreadingAlgorithm::from)(input)
 }
 
 /// ```abnf
 /// thread-data = "THREAD" [SP 1*thread-list]
 /// ```
-pub(crate) fn thread_data(input: &[u8]) -> IMAPResult<&[u8], Data> {
+pub(crate) fn thread_data(input: &[u8]) -> IMAPResult<'_, &[u8], Data<'_>> {
     let mut parser = preceded(
         tag_no_case("THREAD"),
         opt(preceded(sp, many1(thread_list(8)))),
@@ -118,7 +122,7 @@ pub(crate) fn thread_list(
 pub(crate) fn thread_list_limited<'a>(
     input: &'a [u8],
     remaining_recursion: usize,
-) -> IMAPResult<&'a [u8], Thread> {
+) -> IMAPResult<'a, &'a [u8], Thread> {
     if remaining_recursion == 0 {
         return Err(nom::Err::Failure(IMAPParseError {
             input,
@@ -158,9 +162,84 @@ pub(crate) fn thread_list_limited<'a>(
 mod tests {
     use std::num::NonZeroU32;
 
-    use imap_types::core::{Vec1, Vec2};
+    use imap_types::{
+        command::{Command, CommandBody},
+        core::{Charset, Vec1, Vec2},
+        extensions::thread::ThreadingAlgorithm,
+        response::{Data, Response},
+        search::SearchKey,
+    };
 
     use super::{thread_list, Thread};
+    use crate::testing::{kat_inverse_command, kat_inverse_response};
+
+    #[test]
+    fn test_kat_inverse_command_thread() {
+        kat_inverse_command(&[
+            (
+                b"A THREAD ORDEREDSUBJECT UTF-8 SINCE 5-MAR-2000\r\n".as_ref(),
+                b"".as_ref(),
+                Command::new(
+                    "A",
+                    CommandBody::Thread {
+                        algorithm: ThreadingAlgorithm::OrderedSubject,
+                        charset: Charset::try_from("UTF-8").unwrap(),
+                        search_criteria: Vec1::from(SearchKey::Since(
+                            chrono::NaiveDate::from_ymd_opt(2000, 3, 5)
+                                .unwrap()
+                                .try_into()
+                                .unwrap(),
+                        )),
+                        uid: false,
+                    },
+                )
+                .unwrap(),
+            ),
+            (
+                b"A UID THREAD REFERENCES US-ASCII ALL\r\n",
+                b"",
+                Command::new(
+                    "A",
+                    CommandBody::Thread {
+                        algorithm: ThreadingAlgorithm::References,
+                        charset: Charset::try_from("US-ASCII").unwrap(),
+                        search_criteria: Vec1::from(SearchKey::All),
+                        uid: true,
+                    },
+                )
+                .unwrap(),
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_kat_inverse_response_thread() {
+        let members = |prefix: &[u32], answers: Option<Vec<Thread>>| Thread::Members {
+            prefix: Vec1::try_from(
+                prefix
+                    .iter()
+                    .map(|n| NonZeroU32::new(*n).unwrap())
+                    .collect::<Vec<_>>(),
+            )
+            .unwrap(),
+            answers: answers.map(|answers| Vec2::try_from(answers).unwrap()),
+        };
+
+        kat_inverse_response(&[
+            (
+                b"* THREAD (2)(3 6 (4 23)(44 7 96))\r\n".as_ref(),
+                b"".as_ref(),
+                Response::Data(Data::Thread(vec![
+                    members(&[2], None),
+                    members(
+                        &[3, 6],
+                        Some(vec![members(&[4, 23], None), members(&[44, 7, 96], None)]),
+                    ),
+                ])),
+            ),
+            (b"* THREAD\r\n", b"", Response::Data(Data::Thread(vec![]))),
+        ]);
+    }
 
     #[test]
     fn test_thread_list() {
@@ -319,7 +398,7 @@ mod tests {
             ),
         ];
 
-        for (test, expected) in tests.into_iter() {
+        for (test, expected) in tests.iter() {
             println!("test:     {}", test);
             println!("expected: {}\n", expected);
             assert_eq!(*test, expected.to_string().as_str());
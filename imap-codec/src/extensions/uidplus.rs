@@ -0,0 +1,155 @@
+use abnf_core::streaming::sp;
+use imap_types::response::Code;
+use nom::{bytes::streaming::tag_no_case, sequence::tuple};
+
+use crate::{core::nz_number, decode::IMAPResult, sequence::sequence_set};
+
+/// ```abnf
+/// resp-code-apnd = "APPENDUID" SP nz-number SP append-uid
+///
+/// append-uid = uniqueid / uid-set
+/// ```
+///
+/// Note: We use `sequence-set` for `append-uid`.
+pub(crate) fn resp_code_apnd(input: &[u8]) -> IMAPResult<'_, &[u8], Code<'_>> {
+    let mut parser = tuple((
+        tag_no_case(b"APPENDUID "),
+        nz_number,
+        sp,
+        sequence_set(usize::MAX),
+    ));
+
+    let (remaining, (_, uid_validity, _, uids)) = parser(input)?;
+
+    Ok((remaining, Code::AppendUid { uid_validity, uids }))
+}
+
+/// ```abnf
+/// resp-code-copy = "COPYUID" SP nz-number SP uid-set SP uid-set
+/// ```
+///
+/// Note: We use `sequence-set` for `uid-set`.
+pub(crate) fn resp_code_copy(input: &[u8]) -> IMAPResult<'_, &[u8], Code<'_>> {
+    let mut parser = tuple((
+        tag_no_case(b"COPYUID "),
+        nz_number,
+        sp,
+        sequence_set(usize::MAX),
+        sp,
+        sequence_set(usize::MAX),
+    ));
+
+    let (remaining, (_, uid_validity, _, source, _, destination)) = parser(input)?;
+
+    Ok((
+        remaining,
+        Code::CopyUid {
+            uid_validity,
+            source,
+            destination,
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU32;
+
+    use imap_types::{
+        response::{Response, Status},
+        sequence::SequenceSet,
+    };
+
+    use super::*;
+    use crate::testing::{kat_inverse_response, known_answer_test_encode};
+
+    #[test]
+    fn test_encode_code_uidplus() {
+        let tests = [
+            (
+                Code::AppendUid {
+                    uid_validity: NonZeroU32::new(38505).unwrap(),
+                    uids: SequenceSet::try_from(3955u32).unwrap(),
+                },
+                b"APPENDUID 38505 3955".as_ref(),
+            ),
+            (
+                Code::CopyUid {
+                    uid_validity: NonZeroU32::new(38505).unwrap(),
+                    source: SequenceSet::try_from("304,319:320").unwrap(),
+                    destination: SequenceSet::try_from("3956:3957").unwrap(),
+                },
+                b"COPYUID 38505 304,319:320 3956:3957",
+            ),
+            (Code::UidNotSticky, b"UIDNOTSTICKY"),
+        ];
+
+        for test in tests {
+            known_answer_test_encode(test);
+        }
+    }
+
+    #[test]
+    fn test_kat_inverse_response_uidplus() {
+        kat_inverse_response(&[
+            (
+                b"A003 OK [APPENDUID 38505 3955] APPEND completed\r\n".as_ref(),
+                b"".as_ref(),
+                Response::Status(
+                    Status::ok(
+                        Some("A003".try_into().unwrap()),
+                        Some(Code::AppendUid {
+                            uid_validity: NonZeroU32::new(38505).unwrap(),
+                            uids: SequenceSet::try_from(3955u32).unwrap(),
+                        }),
+                        "APPEND completed",
+                    )
+                    .unwrap(),
+                ),
+            ),
+            (
+                b"A003 OK [APPENDUID 38505 3955:3957] APPEND completed\r\n",
+                b"",
+                Response::Status(
+                    Status::ok(
+                        Some("A003".try_into().unwrap()),
+                        Some(Code::AppendUid {
+                            uid_validity: NonZeroU32::new(38505).unwrap(),
+                            uids: SequenceSet::try_from("3955:3957").unwrap(),
+                        }),
+                        "APPEND completed",
+                    )
+                    .unwrap(),
+                ),
+            ),
+            (
+                b"A004 OK [COPYUID 38505 304,319:320 3956:3957] Done\r\n",
+                b"",
+                Response::Status(
+                    Status::ok(
+                        Some("A004".try_into().unwrap()),
+                        Some(Code::CopyUid {
+                            uid_validity: NonZeroU32::new(38505).unwrap(),
+                            source: SequenceSet::try_from("304,319:320").unwrap(),
+                            destination: SequenceSet::try_from("3956:3957").unwrap(),
+                        }),
+                        "Done",
+                    )
+                    .unwrap(),
+                ),
+            ),
+            (
+                b"A005 OK [UIDNOTSTICKY] Non-persistent UIDs\r\n",
+                b"",
+                Response::Status(
+                    Status::ok(
+                        Some("A005".try_into().unwrap()),
+                        Some(Code::UidNotSticky),
+                        "Non-persistent UIDs",
+                    )
+                    .unwrap(),
+                ),
+            ),
+        ]);
+    }
+}
@@ -0,0 +1,43 @@
+//! IMAP UNAUTHENTICATE Extension for Connection Reuse
+
+// Additional changes:
+//
+// capability =/ "UNAUTHENTICATE"
+// command-auth =/ "UNAUTHENTICATE"
+
+use imap_types::command::CommandBody;
+use nom::{bytes::streaming::tag_no_case, combinator::value};
+
+use crate::decode::IMAPResult;
+
+/// ```abnf
+/// unauthenticate = "UNAUTHENTICATE"
+/// ```
+#[inline]
+pub(crate) fn unauthenticate(input: &[u8]) -> IMAPResult<'_, &[u8], CommandBody<'_>> {
+    value(CommandBody::Unauthenticate, tag_no_case("UNAUTHENTICATE"))(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use imap_types::command::Command;
+
+    use super::*;
+    use crate::testing::kat_inverse_command;
+
+    #[test]
+    fn test_kat_inverse_command_unauthenticate() {
+        kat_inverse_command(&[
+            (
+                b"A UNAUTHENTICATE\r\n".as_ref(),
+                b"".as_ref(),
+                Command::new("A", CommandBody::unauthenticate()).unwrap(),
+            ),
+            (
+                b"A unauthenticate\r\n".as_ref(),
+                b"".as_ref(),
+                Command::new("A", CommandBody::Unauthenticate).unwrap(),
+            ),
+        ]);
+    }
+}
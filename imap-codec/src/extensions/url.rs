@@ -0,0 +1,554 @@
+//! IMAP URL Scheme (RFC 5092)
+
+// imapurl = "imap://" iserver ipath-query
+//
+// Note: Search programs (`?...`) and URLAUTH (RFC 4467) are not supported.
+
+use std::{borrow::Cow, io::Write, num::NonZeroU32, str::from_utf8};
+
+use imap_types::{
+    auth::AuthMechanism,
+    core::Atom,
+    extensions::url::{error::ImapUrlError, ImapUrl, UrlAuth, UrlPartial},
+    utils::indicators::is_reg_name_char,
+};
+use nom::{
+    branch::alt,
+    bytes::streaming::{tag, tag_no_case, take_while1},
+    character::streaming::digit1,
+    combinator::{map, map_opt, map_res, opt, recognize, value, verify},
+    sequence::{delimited, preceded, terminated, tuple},
+};
+
+use crate::{
+    core::{number, nz_number},
+    decode::{IMAPErrorKind, IMAPParseError, IMAPResult},
+    encode::{EncodeContext, EncodeIntoContext},
+};
+
+/// ```abnf
+/// imapurl     = "imap://" iserver ipath-query
+///
+/// ipath-query = ["/" [ imessagepart ]]
+/// ```
+///
+/// Note: A trailing `/` without a mailbox is accepted, but not encoded.
+pub(crate) fn imap_url(input: &[u8]) -> IMAPResult<'_, &[u8], ImapUrl<'_>> {
+    let mut parser = tuple((
+        tag_no_case(b"imap://"),
+        iserver,
+        opt(preceded(tag(b"/"), opt(imessagepart))),
+    ));
+
+    let (remaining, (_, (userinfo, host, port), path)) = parser(input)?;
+
+    let build = || -> Result<ImapUrl, ImapUrlError> {
+        let mut url = ImapUrl::server(host)?;
+
+        if let Some((user, auth)) = userinfo {
+            if let Some(user) = user {
+                url = url.with_user(user)?;
+            }
+            if let Some(auth) = auth {
+                url = url.with_auth(auth);
+            }
+        }
+
+        if let Some(port) = port {
+            url = url.with_port(port);
+        }
+
+        if let Some(Some((mailbox, uid_validity, uid))) = path {
+            url = url.with_mailbox(mailbox)?;
+
+            if let Some(uid_validity) = uid_validity {
+                url = url.with_uid_validity(uid_validity)?;
+            }
+
+            if let Some((uid, section, partial)) = uid {
+                url = url.with_uid(uid)?;
+
+                if let Some(section) = section {
+                    url = url.with_section(section)?;
+                }
+                if let Some(partial) = partial {
+                    url = url.with_partial(partial)?;
+                }
+            }
+        }
+
+        Ok(url)
+    };
+
+    match build() {
+        Ok(url) => Ok((remaining, url)),
+        Err(_) => Err(nom::Err::Failure(IMAPParseError {
+            input,
+            kind: IMAPErrorKind::Nom(nom::error::ErrorKind::Verify),
+        })),
+    }
+}
+
+type UserInfo<'a> = (Option<Cow<'a, str>>, Option<UrlAuth<'a>>);
+
+/// ```abnf
+/// iserver   = [iuserinfo "@"] host [ ":" port ]
+///
+/// iuserinfo = enc-user [iauth] / [enc-user] iauth
+/// ```
+#[allow(clippy::type_complexity)]
+fn iserver(input: &[u8]) -> IMAPResult<'_, &[u8], (Option<UserInfo<'_>>, &str, Option<u16>)> {
+    let mut parser = tuple((
+        opt(terminated(
+            verify(
+                tuple((opt(enc_user), opt(iauth))),
+                |(user, auth): &UserInfo| user.is_some() || auth.is_some(),
+            ),
+            tag(b"@"),
+        )),
+        host,
+        opt(preceded(
+            tag(b":"),
+            // # Safety
+            //
+            // `unwrap` is safe because `1*DIGIT` contains ASCII-only characters.
+            map_res(
+                map(digit1, |val| from_utf8(val).unwrap()),
+                str::parse::<u16>,
+            ),
+        )),
+    ));
+
+    parser(input)
+}
+
+/// ```abnf
+/// iauth = ";AUTH=" ( "*" / enc-auth-type )
+/// ```
+fn iauth(input: &[u8]) -> IMAPResult<'_, &[u8], UrlAuth<'_>> {
+    preceded(
+        tag_no_case(b";AUTH="),
+        alt((
+            value(UrlAuth::Any, tag(b"*")),
+            map_opt(enc_user, |value| {
+                Atom::try_from(value)
+                    .ok()
+                    .map(|atom| UrlAuth::Mechanism(AuthMechanism::from(atom)))
+            }),
+        )),
+    )(input)
+}
+
+/// `host = IP-literal / reg-name`
+///
+/// Note: Percent-encoded registered names are not supported.
+fn host(input: &[u8]) -> IMAPResult<'_, &[u8], &str> {
+    map(
+        alt((
+            recognize(delimited(
+                tag(b"["),
+                take_while1(|b: u8| b.is_ascii_hexdigit() || b == b':' || b == b'.'),
+                tag(b"]"),
+            )),
+            take_while1(is_reg_name_char),
+        )),
+        // # Safety
+        //
+        // `unwrap` is safe because both alternatives only accept ASCII characters.
+        |host| from_utf8(host).unwrap(),
+    )(input)
+}
+
+type Uid<'a> = (NonZeroU32, Option<Cow<'a, str>>, Option<UrlPartial>);
+
+/// ```abnf
+/// imessagepart = imailbox-ref [iuid [isection] [ipartial]]
+///
+/// imailbox-ref = enc-mailbox [uidvalidity]
+///
+/// uidvalidity  = ";UIDVALIDITY=" nz-number
+///
+/// iuid         = "/" ";UID=" nz-number
+///
+/// isection     = "/" ";SECTION=" enc-section
+///
+/// ipartial     = "/" ";PARTIAL=" partial-range
+/// ```
+///
+/// Note: This also covers `imessagelist` (without search) and `imailbox-ref`.
+#[allow(clippy::type_complexity)]
+fn imessagepart(
+    input: &[u8],
+) -> IMAPResult<'_, &[u8], (Cow<'_, str>, Option<NonZeroU32>, Option<Uid<'_>>)> {
+    tuple((
+        enc_bchars,
+        opt(preceded(tag_no_case(b";UIDVALIDITY="), nz_number)),
+        opt(tuple((
+            preceded(tag_no_case(b"/;UID="), nz_number),
+            opt(preceded(tag_no_case(b"/;SECTION="), enc_bchars)),
+            opt(preceded(tag_no_case(b"/;PARTIAL="), partial_range)),
+        ))),
+    ))(input)
+}
+
+/// `partial-range = number ["." nz-number]`
+fn partial_range(input: &[u8]) -> IMAPResult<'_, &[u8], UrlPartial> {
+    map(
+        tuple((number, opt(preceded(tag(b"."), nz_number)))),
+        |(offset, length)| UrlPartial { offset, length },
+    )(input)
+}
+
+/// `enc-user = 1*achar`
+///
+/// Note: `@` must be percent-encoded because it ends `iuserinfo`.
+fn enc_user(input: &[u8]) -> IMAPResult<'_, &[u8], Cow<'_, str>> {
+    map_opt(
+        take_while1(|b| b == b'%' || is_user_char(b)),
+        percent_decode,
+    )(input)
+}
+
+/// `enc-mailbox = 1*bchar` and `enc-section = 1*bchar`
+///
+/// Note: A trailing `/` followed by `;` starts the next part, e.g., `/;UID=`.
+fn enc_bchars(input: &[u8]) -> IMAPResult<'_, &[u8], Cow<'_, str>> {
+    let (remaining, raw) = take_while1(|b| b == b'%' || is_bchar(b))(input)?;
+
+    let (remaining, raw) = match (raw, remaining.first()) {
+        ([rest @ .., b'/'], Some(b';')) if !rest.is_empty() => (&input[rest.len()..], rest),
+        _ => (remaining, raw),
+    };
+
+    match percent_decode(raw) {
+        Some(value) => Ok((remaining, value)),
+        None => Err(nom::Err::Error(IMAPParseError {
+            input,
+            kind: IMAPErrorKind::Nom(nom::error::ErrorKind::MapOpt),
+        })),
+    }
+}
+
+/// Decode `pct-encoded` characters and require the result to be UTF-8.
+fn percent_decode(raw: &[u8]) -> Option<Cow<'_, str>> {
+    if !raw.contains(&b'%') {
+        return from_utf8(raw).ok().map(Cow::Borrowed);
+    }
+
+    let mut decoded = Vec::with_capacity(raw.len());
+    let mut bytes = raw.iter();
+
+    while let Some(byte) = bytes.next() {
+        if *byte == b'%' {
+            let hi = (*bytes.next()? as char).to_digit(16)?;
+            let lo = (*bytes.next()? as char).to_digit(16)?;
+            decoded.push((hi << 4 | lo) as u8);
+        } else {
+            decoded.push(*byte);
+        }
+    }
+
+    String::from_utf8(decoded).ok().map(Cow::Owned)
+}
+
+/// `unreserved / sub-delims-sh / ":" / "&" / "="` (`achar` without `@` and `pct-encoded`)
+fn is_user_char(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric()
+        || matches!(
+            byte,
+            b'-' | b'.'
+                | b'_'
+                | b'~'
+                | b'!'
+                | b'$'
+                | b'\''
+                | b'('
+                | b')'
+                | b'*'
+                | b'+'
+                | b','
+                | b':'
+                | b'&'
+                | b'='
+        )
+}
+
+/// `achar / ":" / "@" / "/"` (without `pct-encoded`)
+fn is_bchar(byte: u8) -> bool {
+    is_user_char(byte) || matches!(byte, b'@' | b'/')
+}
+
+/// Write `value` and percent-encode all bytes not accepted by `keep`.
+fn write_percent_encoded(
+    ctx: &mut EncodeContext,
+    value: &str,
+    keep: fn(u8) -> bool,
+) -> std::io::Result<()> {
+    for byte in value.bytes() {
+        if keep(byte) {
+            ctx.write_all(&[byte])?;
+        } else {
+            write!(ctx, "%{byte:02X}")?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Like [`write_percent_encoded`] but also encode a trailing `/` (see [`enc_bchars`]).
+fn write_percent_encoded_bchars(ctx: &mut EncodeContext, value: &str) -> std::io::Result<()> {
+    match value.strip_suffix('/') {
+        Some(value) => {
+            write_percent_encoded(ctx, value, is_bchar)?;
+            ctx.write_all(b"%2F")
+        }
+        None => write_percent_encoded(ctx, value, is_bchar),
+    }
+}
+
+impl<'a> EncodeIntoContext for ImapUrl<'a> {
+    fn encode_ctx(&self, ctx: &mut EncodeContext) -> std::io::Result<()> {
+        ctx.write_all(b"imap://")?;
+
+        if let Some(user) = self.user() {
+            write_percent_encoded(ctx, user, is_user_char)?;
+        }
+
+        if let Some(auth) = self.auth() {
+            ctx.write_all(b";AUTH=")?;
+            match auth {
+                UrlAuth::Any => ctx.write_all(b"*")?,
+                UrlAuth::Mechanism(mechanism) => {
+                    write_percent_encoded(ctx, mechanism.as_ref(), is_user_char)?
+                }
+            }
+        }
+
+        if self.user().is_some() || self.auth().is_some() {
+            ctx.write_all(b"@")?;
+        }
+
+        ctx.write_all(self.host().as_bytes())?;
+
+        if let Some(port) = self.port() {
+            write!(ctx, ":{port}")?;
+        }
+
+        if let Some(mailbox) = self.mailbox() {
+            ctx.write_all(b"/")?;
+            write_percent_encoded_bchars(ctx, mailbox)?;
+
+            if let Some(uid_validity) = self.uid_validity() {
+                write!(ctx, ";UIDVALIDITY={uid_validity}")?;
+            }
+
+            if let Some(uid) = self.uid() {
+                write!(ctx, "/;UID={uid}")?;
+
+                if let Some(section) = self.section() {
+                    ctx.write_all(b"/;SECTION=")?;
+                    write_percent_encoded_bchars(ctx, section)?;
+                }
+
+                if let Some(UrlPartial { offset, length }) = self.partial() {
+                    write!(ctx, "/;PARTIAL={offset}")?;
+
+                    if let Some(length) = length {
+                        write!(ctx, ".{length}")?;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use imap_types::{
+        core::Vec1,
+        response::{Code, Response, Status},
+    };
+
+    use super::*;
+    use crate::testing::{kat_inverse_response, known_answer_test_encode};
+
+    #[test]
+    fn test_parse_imap_url() {
+        let tests = [
+            (
+                b"imap://example.org]".as_ref(),
+                ImapUrl::server("example.org").unwrap(),
+            ),
+            (
+                b"IMAP://example.org/]",
+                ImapUrl::server("example.org").unwrap(),
+            ),
+            (
+                b"imap://[::1]:993]",
+                ImapUrl::server("[::1]").unwrap().with_port(993),
+            ),
+            (
+                b"imap://alice;AUTH=*@example.org]",
+                ImapUrl::server("example.org")
+                    .unwrap()
+                    .with_user("alice")
+                    .unwrap()
+                    .with_auth(UrlAuth::Any),
+            ),
+            (
+                b"imap://;auth=XOAUTH2@example.org]",
+                ImapUrl::server("example.org")
+                    .unwrap()
+                    .with_auth(UrlAuth::Mechanism(AuthMechanism::XOAuth2)),
+            ),
+            (
+                b"imap://al%40ice@example.org/Caf%C3%A9/2024%3F]",
+                ImapUrl::server("example.org")
+                    .unwrap()
+                    .with_user("al@ice")
+                    .unwrap()
+                    .with_mailbox("Café/2024?")
+                    .unwrap(),
+            ),
+            (
+                b"imap://example.org/INBOX/;uid=20/;section=1.2/;partial=0.1024]",
+                ImapUrl::server("example.org")
+                    .unwrap()
+                    .with_mailbox("INBOX")
+                    .unwrap()
+                    .with_uid(NonZeroU32::new(20).unwrap())
+                    .unwrap()
+                    .with_section("1.2")
+                    .unwrap()
+                    .with_partial(UrlPartial {
+                        offset: 0,
+                        length: NonZeroU32::new(1024),
+                    })
+                    .unwrap(),
+            ),
+        ];
+
+        for (test, expected) in tests {
+            let (rem, got) = imap_url(test).unwrap();
+            assert_eq!(rem, b"]");
+            assert_eq!(got, expected);
+        }
+    }
+
+    #[test]
+    fn test_parse_imap_url_failing() {
+        for test in [
+            b"imap://]".as_ref(),
+            b"http://example.org]",
+            b"imap://@example.org]",
+            b"imap://example.org/%FF]",
+            b"imap://example.org/%4]",
+        ] {
+            assert!(!matches!(imap_url(test), Ok((b"]", _))), "{test:?}");
+        }
+    }
+
+    #[test]
+    fn test_encode_imap_url() {
+        let tests = [
+            (
+                ImapUrl::server("example.org").unwrap(),
+                b"imap://example.org".as_ref(),
+            ),
+            (
+                ImapUrl::server("example.org")
+                    .unwrap()
+                    .with_user("al@ice")
+                    .unwrap()
+                    .with_auth(UrlAuth::Mechanism(AuthMechanism::Plain))
+                    .with_port(143)
+                    .with_mailbox("a b/")
+                    .unwrap()
+                    .with_uid(NonZeroU32::new(1).unwrap())
+                    .unwrap()
+                    .with_partial(UrlPartial {
+                        offset: 42,
+                        length: None,
+                    })
+                    .unwrap(),
+                b"imap://al%40ice;AUTH=PLAIN@example.org:143/a%20b%2F/;UID=1/;PARTIAL=42",
+            ),
+        ];
+
+        for test in tests {
+            known_answer_test_encode(test);
+        }
+    }
+
+    #[test]
+    fn test_kat_inverse_response_referral() {
+        kat_inverse_response(&[
+            (
+                b"* OK [REFERRAL imap://example.org] Try another server\r\n".as_ref(),
+                b"".as_ref(),
+                Response::Status(
+                    Status::ok(
+                        None,
+                        Some(Code::Referral(Vec1::from(
+                            ImapUrl::server("example.org").unwrap(),
+                        ))),
+                        "Try another server",
+                    )
+                    .unwrap(),
+                ),
+            ),
+            (
+                b"A NO [REFERRAL imap://alice@example.org/Shared/Inbox;UIDVALIDITY=42/;UID=20/;SECTION=1.2] Remote\r\n".as_ref(),
+                b"".as_ref(),
+                Response::Status(
+                    Status::no(
+                        Some(imap_types::core::Tag::try_from("A").unwrap()),
+                        Some(Code::Referral(Vec1::from(
+                            ImapUrl::server("example.org")
+                                .unwrap()
+                                .with_user("alice")
+                                .unwrap()
+                                .with_mailbox("Shared/Inbox")
+                                .unwrap()
+                                .with_uid_validity(NonZeroU32::new(42).unwrap())
+                                .unwrap()
+                                .with_uid(NonZeroU32::new(20).unwrap())
+                                .unwrap()
+                                .with_section("1.2")
+                                .unwrap(),
+                        ))),
+                        "Remote",
+                    )
+                    .unwrap(),
+                ),
+            ),
+            (
+                b"* NO [REFERRAL imap://a.example.org/INBOX imap://b.example.org/INBOX] Moved\r\n"
+                    .as_ref(),
+                b"".as_ref(),
+                Response::Status(
+                    Status::no(
+                        None,
+                        Some(Code::Referral(
+                            Vec1::try_from(vec![
+                                ImapUrl::server("a.example.org")
+                                    .unwrap()
+                                    .with_mailbox("INBOX")
+                                    .unwrap(),
+                                ImapUrl::server("b.example.org")
+                                    .unwrap()
+                                    .with_mailbox("INBOX")
+                                    .unwrap(),
+                            ])
+                            .unwrap(),
+                        )),
+                        "Moved",
+                    )
+                    .unwrap(),
+                ),
+            ),
+        ]);
+    }
+}
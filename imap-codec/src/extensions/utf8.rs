@@ -0,0 +1,141 @@
+//! IMAP Support for UTF-8
+
+// Additional changes:
+//
+// capability  =/ "UTF8=ACCEPT" / "UTF8=ONLY"
+// append-data =/ "UTF8" SP "(" literal8 ")"
+
+use imap_types::extensions::binary::Literal8;
+use nom::{
+    bytes::streaming::{tag, tag_no_case},
+    sequence::{delimited, preceded},
+};
+
+use crate::{decode::IMAPResult, extensions::binary::literal8};
+
+/// ```abnf
+/// append-data =/ "UTF8" SP "(" literal8 ")"
+/// ```
+pub(crate) fn append_data_utf8(input: &[u8]) -> IMAPResult<'_, &[u8], Literal8<'_>> {
+    preceded(
+        tag_no_case(b"UTF8 "),
+        delimited(tag(b"("), literal8, tag(b")")),
+    )(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use imap_types::{
+        command::{Command, CommandBody},
+        core::{LiteralMode, Vec1},
+        extensions::{
+            binary::LiteralOrLiteral8,
+            enable::{CapabilityEnable, Utf8Kind},
+        },
+        response::{Capability, Data, Response},
+    };
+
+    use super::*;
+    use crate::{
+        decode::{CommandDecodeError, Decoder},
+        encode::Encoder,
+        testing::{kat_inverse_command, kat_inverse_response},
+        CommandCodec,
+    };
+
+    fn append_utf8(data: &'static [u8], mode: LiteralMode) -> CommandBody<'static> {
+        CommandBody::Append {
+            mailbox: "Drafts".try_into().unwrap(),
+            flags: vec![],
+            date: None,
+            message: LiteralOrLiteral8::Utf8(Literal8 {
+                data: Cow::Borrowed(data),
+                mode,
+            }),
+        }
+    }
+
+    #[test]
+    fn test_parse_append_data_utf8() {
+        let (rem, got) = append_data_utf8(b"UTF8 (~{5+}\r\nHallo) ").unwrap();
+        assert_eq!(rem, b" ");
+        assert_eq!(
+            got,
+            Literal8 {
+                data: Cow::Borrowed(b"Hallo"),
+                mode: LiteralMode::NonSync,
+            }
+        );
+
+        assert!(append_data_utf8(b"UTF8 ~{5+}\r\nHallo ").is_err());
+    }
+
+    #[test]
+    fn test_kat_inverse_command_enable_utf8() {
+        kat_inverse_command(&[(
+            b"A ENABLE UTF8=ACCEPT\r\n".as_ref(),
+            b"".as_ref(),
+            Command::new(
+                "A",
+                CommandBody::Enable {
+                    capabilities: Vec1::from(CapabilityEnable::Utf8(Utf8Kind::Accept)),
+                },
+            )
+            .unwrap(),
+        )]);
+    }
+
+    #[test]
+    fn test_kat_inverse_response_utf8() {
+        kat_inverse_response(&[
+            (
+                b"* CAPABILITY IMAP4REV1 UTF8=ACCEPT\r\n".as_ref(),
+                b"".as_ref(),
+                Response::Data(Data::Capability(
+                    Vec1::try_from(vec![
+                        Capability::Imap4Rev1,
+                        Capability::Utf8(Utf8Kind::Accept),
+                    ])
+                    .unwrap(),
+                )),
+            ),
+            (
+                b"* ENABLED UTF8=ACCEPT\r\n".as_ref(),
+                b"".as_ref(),
+                Response::Data(Data::Enabled {
+                    capabilities: vec![CapabilityEnable::Utf8(Utf8Kind::Accept)],
+                }),
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_kat_inverse_command_append_utf8() {
+        kat_inverse_command(&[(
+            b"A APPEND Drafts UTF8 (~{5+}\r\nHallo)\r\n".as_ref(),
+            b"".as_ref(),
+            Command::new("A", append_utf8(b"Hallo", LiteralMode::NonSync)).unwrap(),
+        )]);
+    }
+
+    #[test]
+    fn test_decode_append_utf8_sync() {
+        let command = Command::new("A", append_utf8(b"Hallo", LiteralMode::Sync)).unwrap();
+
+        let encoded = CommandCodec::default().encode(&command).dump();
+        assert_eq!(encoded, b"A APPEND Drafts UTF8 (~{5}\r\nHallo)\r\n");
+
+        // The server must acknowledge the literal before the client sends the message.
+        let prefix = b"A APPEND Drafts UTF8 (~{5}\r\n";
+        assert!(matches!(
+            CommandCodec::default().decode(prefix),
+            Err(CommandDecodeError::LiteralFound { length: 5, .. })
+        ));
+
+        let (rem, got) = CommandCodec::default().decode(&encoded).unwrap();
+        assert!(rem.is_empty());
+        assert_eq!(got, command);
+    }
+}
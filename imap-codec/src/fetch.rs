@@ -19,6 +19,14 @@ use nom::{
 
 #[cfg(feature = "ext_binary")]
 use crate::extensions::binary::{literal8, partial, section_binary};
+#[cfg(feature = "ext_condstore_qresync")]
+use crate::extensions::condstore_qresync::fetch_mod_resp;
+#[cfg(feature = "ext_objectid")]
+use crate::extensions::objectid::msg_att_objectid;
+#[cfg(feature = "ext_preview")]
+use crate::extensions::preview::{fetch_att_preview, msg_att_preview};
+#[cfg(feature = "ext_savedate")]
+use crate::extensions::savedate::msg_att_savedate;
 use crate::{
     body::body,
     core::{astring, nstring, number, nz_number},
@@ -39,9 +47,14 @@ use crate::{
 ///             "BODY.PEEK" section ["<" number "." nz-number ">"] /
 ///             "BINARY"      section-binary [partial] / ; RFC 3516
 ///             "BINARY.PEEK" section-binary [partial] / ; RFC 3516
-///             "BINARY.SIZE" section-binary             ; RFC 3516
+///             "BINARY.SIZE" section-binary /           ; RFC 3516
+///             "MODSEQ" /                               ; RFC 7162
+///             "EMAILID" /                              ; RFC 8474
+///             "THREADID" /                             ; RFC 8474
+///             "SAVEDATE" /                             ; RFC 8514
+///             "PREVIEW" [SP "(" preview-mod *(SP preview-mod) ")"] ; RFC 8970
 /// ```
-pub(crate) fn fetch_att(input: &[u8]) -> IMAPResult<&[u8], MessageDataItemName> {
+pub(crate) fn fetch_att(input: &[u8]) -> IMAPResult<'_, &[u8], MessageDataItemName<'_>> {
     alt((
         value(MessageDataItemName::Envelope, tag_no_case(b"ENVELOPE")),
         value(MessageDataItemName::Flags, tag_no_case(b"FLAGS")),
@@ -117,13 +130,23 @@ pub(crate) fn fetch_att(input: &[u8]) -> IMAPResult<&[u8], MessageDataItemName>
         value(MessageDataItemName::Rfc822Size, tag_no_case(b"RFC822.SIZE")),
         value(MessageDataItemName::Rfc822Text, tag_no_case(b"RFC822.TEXT")),
         value(MessageDataItemName::Rfc822, tag_no_case(b"RFC822")),
+        #[cfg(feature = "ext_condstore_qresync")]
+        value(MessageDataItemName::ModSeq, tag_no_case(b"MODSEQ")),
+        #[cfg(feature = "ext_objectid")]
+        value(MessageDataItemName::EmailId, tag_no_case(b"EMAILID")),
+        #[cfg(feature = "ext_objectid")]
+        value(MessageDataItemName::ThreadId, tag_no_case(b"THREADID")),
+        #[cfg(feature = "ext_savedate")]
+        value(MessageDataItemName::SaveDate, tag_no_case(b"SAVEDATE")),
+        #[cfg(feature = "ext_preview")]
+        fetch_att_preview,
     ))(input)
 }
 
 /// `msg-att = "("
 ///            (msg-att-dynamic / msg-att-static) *(SP (msg-att-dynamic / msg-att-static))
 ///            ")"`
-pub(crate) fn msg_att(input: &[u8]) -> IMAPResult<&[u8], Vec1<MessageDataItem>> {
+pub(crate) fn msg_att(input: &[u8]) -> IMAPResult<'_, &[u8], Vec1<MessageDataItem<'_>>> {
     delimited(
         tag(b"("),
         map(
@@ -134,19 +157,28 @@ pub(crate) fn msg_att(input: &[u8]) -> IMAPResult<&[u8], Vec1<MessageDataItem>>
     )(input)
 }
 
-/// `msg-att-dynamic = "FLAGS" SP "(" [flag-fetch *(SP flag-fetch)] ")"`
+/// ```abnf
+/// msg-att-dynamic = "FLAGS" SP "(" [flag-fetch *(SP flag-fetch)] ")" /
+///                   fetch-mod-resp /       ; RFC 7162
+///                   "PREVIEW" SP nstring   ; RFC 8970
+/// ```
 ///
 /// Note: MAY change for a message
-pub(crate) fn msg_att_dynamic(input: &[u8]) -> IMAPResult<&[u8], MessageDataItem> {
-    let mut parser = tuple((
-        tag_no_case(b"FLAGS"),
-        sp,
-        delimited(tag(b"("), opt(separated_list1(sp, flag_fetch)), tag(b")")),
-    ));
-
-    let (remaining, (_, _, flags)) = parser(input)?;
-
-    Ok((remaining, MessageDataItem::Flags(flags.unwrap_or_default())))
+pub(crate) fn msg_att_dynamic(input: &[u8]) -> IMAPResult<'_, &[u8], MessageDataItem<'_>> {
+    alt((
+        map(
+            tuple((
+                tag_no_case(b"FLAGS"),
+                sp,
+                delimited(tag(b"("), opt(separated_list1(sp, flag_fetch)), tag(b")")),
+            )),
+            |(_, _, flags)| MessageDataItem::Flags(flags.unwrap_or_default()),
+        ),
+        #[cfg(feature = "ext_condstore_qresync")]
+        fetch_mod_resp,
+        #[cfg(feature = "ext_preview")]
+        msg_att_preview,
+    ))(input)
 }
 
 /// ```abnf
@@ -158,11 +190,14 @@ pub(crate) fn msg_att_dynamic(input: &[u8]) -> IMAPResult<&[u8], MessageDataItem
 ///                  "BODY" section ["<" number ">"] SP nstring /
 ///                  "UID" SP uniqueid /
 ///                  "BINARY" section-binary SP (nstring / literal8) / ; RFC 3516
-///                  "BINARY.SIZE" section-binary SP number            ; RFC 3516
+///                  "BINARY.SIZE" section-binary SP number /          ; RFC 3516
+///                  fetch-emailid-resp /                              ; RFC 8474
+///                  fetch-threadid-resp /                             ; RFC 8474
+///                  "SAVEDATE" SP (date-time / nil)                   ; RFC 8514
 /// ```
 ///
 /// Note: MUST NOT change for a message
-pub(crate) fn msg_att_static(input: &[u8]) -> IMAPResult<&[u8], MessageDataItem> {
+pub(crate) fn msg_att_static(input: &[u8]) -> IMAPResult<'_, &[u8], MessageDataItem<'_>> {
     alt((
         map(
             tuple((tag_no_case(b"ENVELOPE"), sp, envelope)),
@@ -231,6 +266,10 @@ pub(crate) fn msg_att_static(input: &[u8]) -> IMAPResult<&[u8], MessageDataItem>
             tuple((tag_no_case(b"BINARY.SIZE"), section_binary, sp, number)),
             |(_, section, _, size)| MessageDataItem::BinarySize { section, size },
         ),
+        #[cfg(feature = "ext_objectid")]
+        msg_att_objectid,
+        #[cfg(feature = "ext_savedate")]
+        msg_att_savedate,
     ))(input)
 }
 
@@ -238,17 +277,17 @@ pub(crate) fn msg_att_static(input: &[u8]) -> IMAPResult<&[u8], MessageDataItem>
 /// `uniqueid = nz-number`
 ///
 /// Note: Strictly ascending
-pub(crate) fn uniqueid(input: &[u8]) -> IMAPResult<&[u8], NonZeroU32> {
+pub(crate) fn uniqueid(input: &[u8]) -> IMAPResult<'_, &[u8], NonZeroU32> {
     nz_number(input)
 }
 
 /// `section = "[" [section-spec] "]"`
-pub(crate) fn section(input: &[u8]) -> IMAPResult<&[u8], Option<Section>> {
+pub(crate) fn section(input: &[u8]) -> IMAPResult<'_, &[u8], Option<Section<'_>>> {
     delimited(tag(b"["), opt(section_spec), tag(b"]"))(input)
 }
 
 /// `section-spec = section-msgtext / (section-part ["." section-text])`
-pub(crate) fn section_spec(input: &[u8]) -> IMAPResult<&[u8], Section> {
+pub(crate) fn section_spec(input: &[u8]) -> IMAPResult<'_, &[u8], Section<'_>> {
     alt((
         map(section_msgtext, |part_specifier| match part_specifier {
             PartSpecifier::PartNumber(_) => unreachable!(),
@@ -285,7 +324,7 @@ pub(crate) fn section_spec(input: &[u8]) -> IMAPResult<&[u8], Section> {
 /// `section-msgtext = "HEADER" / "HEADER.FIELDS" [".NOT"] SP header-list / "TEXT"`
 ///
 /// Top-level or MESSAGE/RFC822 part
-pub(crate) fn section_msgtext(input: &[u8]) -> IMAPResult<&[u8], PartSpecifier> {
+pub(crate) fn section_msgtext(input: &[u8]) -> IMAPResult<'_, &[u8], PartSpecifier<'_>> {
     alt((
         map(
             tuple((tag_no_case(b"HEADER.FIELDS.NOT"), sp, header_list)),
@@ -304,14 +343,14 @@ pub(crate) fn section_msgtext(input: &[u8]) -> IMAPResult<&[u8], PartSpecifier>
 /// `section-part = nz-number *("." nz-number)`
 ///
 /// Body part nesting
-pub(crate) fn section_part(input: &[u8]) -> IMAPResult<&[u8], Vec1<NonZeroU32>> {
+pub(crate) fn section_part(input: &[u8]) -> IMAPResult<'_, &[u8], Vec1<NonZeroU32>> {
     map(separated_list1(tag(b"."), nz_number), Vec1::unvalidated)(input)
 }
 
 /// `section-text = section-msgtext / "MIME"`
 ///
 /// Text other than actual body part (headers, etc.)
-pub(crate) fn section_text(input: &[u8]) -> IMAPResult<&[u8], PartSpecifier> {
+pub(crate) fn section_text(input: &[u8]) -> IMAPResult<'_, &[u8], PartSpecifier<'_>> {
     alt((
         section_msgtext,
         value(PartSpecifier::Mime, tag_no_case(b"MIME")),
@@ -319,7 +358,7 @@ pub(crate) fn section_text(input: &[u8]) -> IMAPResult<&[u8], PartSpecifier> {
 }
 
 /// `header-list = "(" header-fld-name *(SP header-fld-name) ")"`
-pub(crate) fn header_list(input: &[u8]) -> IMAPResult<&[u8], Vec1<AString>> {
+pub(crate) fn header_list(input: &[u8]) -> IMAPResult<'_, &[u8], Vec1<AString<'_>>> {
     map(
         delimited(tag(b"("), separated_list1(sp, header_fld_name), tag(b")")),
         Vec1::unvalidated,
@@ -328,7 +367,7 @@ pub(crate) fn header_list(input: &[u8]) -> IMAPResult<&[u8], Vec1<AString>> {
 
 #[inline]
 /// `header-fld-name = astring`
-pub(crate) fn header_fld_name(input: &[u8]) -> IMAPResult<&[u8], AString> {
+pub(crate) fn header_fld_name(input: &[u8]) -> IMAPResult<'_, &[u8], AString<'_>> {
     astring(input)
 }
 
@@ -336,13 +375,14 @@ pub(crate) fn header_fld_name(input: &[u8]) -> IMAPResult<&[u8], AString> {
 mod tests {
     use imap_types::{
         body::{BasicFields, Body, BodyStructure, SpecificFields},
-        core::{IString, NString},
+        core::{IString, Literal, NString},
         datetime::DateTime,
         envelope::Envelope,
+        response::{Data, Response},
     };
 
     use super::*;
-    use crate::testing::known_answer_test_encode;
+    use crate::{decode::Decoder, testing::known_answer_test_encode, ResponseCodec};
 
     #[test]
     fn test_encode_message_data_item_name() {
@@ -476,6 +516,31 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_rfc822_header_accessors() {
+        let (rem, got) = ResponseCodec::default()
+            .decode(b"* 12 FETCH (RFC822.HEADER {18}\r\nSubject: Hello\r\n\r\n)\r\n")
+            .unwrap();
+        assert!(rem.is_empty());
+
+        let Response::Data(Data::Fetch { items, .. }) = got else {
+            panic!("expected FETCH response, got {got:?}");
+        };
+        let item = items.as_ref().first().unwrap();
+
+        assert!(item.is_rfc822());
+        assert_eq!(
+            item.rfc822_header().and_then(|header| header.0.as_ref()),
+            Some(&IString::Literal(
+                Literal::try_from(b"Subject: Hello\r\n\r\n".as_ref()).unwrap()
+            ))
+        );
+        assert_eq!(item.rfc822_body(), None);
+        assert_eq!(item.rfc822_text(), None);
+
+        assert!(!MessageDataItem::Rfc822Size(17).is_rfc822());
+    }
+
     #[test]
     fn test_encode_section() {
         let tests = [
@@ -22,7 +22,7 @@ use crate::{core::atom, decode::IMAPResult};
 /// ```
 ///
 /// Note: Does not include "\Recent"
-pub(crate) fn flag(input: &[u8]) -> IMAPResult<&[u8], Flag> {
+pub(crate) fn flag(input: &[u8]) -> IMAPResult<'_, &[u8], Flag<'_>> {
     alt((
         map(preceded(char('\\'), atom), Flag::system),
         map(atom, Flag::Keyword),
@@ -51,14 +51,14 @@ pub(crate) fn flag(input: &[u8]) -> IMAPResult<&[u8], Flag> {
 // }
 
 /// `flag-list = "(" [flag *(SP flag)] ")"`
-pub(crate) fn flag_list(input: &[u8]) -> IMAPResult<&[u8], Vec<Flag>> {
+pub(crate) fn flag_list(input: &[u8]) -> IMAPResult<'_, &[u8], Vec<Flag<'_>>> {
     delimited(tag(b"("), separated_list0(sp, flag), tag(b")"))(input)
 }
 
 /// `flag-fetch = flag / "\Recent"`
-pub(crate) fn flag_fetch(input: &[u8]) -> IMAPResult<&[u8], FlagFetch> {
+pub(crate) fn flag_fetch(input: &[u8]) -> IMAPResult<'_, &[u8], FlagFetch<'_>> {
     if let Ok((rem, peek)) = recognize(tuple((char('\\'), atom)))(input) {
-        if peek.to_ascii_lowercase() == b"\\recent" {
+        if peek.eq_ignore_ascii_case(b"\\recent") {
             return Ok((rem, FlagFetch::Recent));
         }
     }
@@ -67,7 +67,7 @@ pub(crate) fn flag_fetch(input: &[u8]) -> IMAPResult<&[u8], FlagFetch> {
 }
 
 /// `flag-perm = flag / "\*"`
-pub(crate) fn flag_perm(input: &[u8]) -> IMAPResult<&[u8], FlagPerm> {
+pub(crate) fn flag_perm(input: &[u8]) -> IMAPResult<'_, &[u8], FlagPerm<'_>> {
     alt((
         value(FlagPerm::Asterisk, tag("\\*")),
         map(flag, FlagPerm::Flag),
@@ -81,7 +81,7 @@ pub(crate) fn flag_perm(input: &[u8]) -> IMAPResult<&[u8], FlagPerm> {
 ///
 /// TODO(#155): ABNF enforces that sflag is not used more than once.
 ///             We could parse any flag and check for multiple occurrences of sflag later.
-pub(crate) fn mbx_list_flags(input: &[u8]) -> IMAPResult<&[u8], Vec<FlagNameAttribute>> {
+pub(crate) fn mbx_list_flags(input: &[u8]) -> IMAPResult<'_, &[u8], Vec<FlagNameAttribute<'_>>> {
     let (remaining, flags) =
         separated_list1(sp, map(preceded(char('\\'), atom), FlagNameAttribute::from))(input)?;
 
@@ -83,6 +83,10 @@
 //! | quirk_crlf_relaxed    | Make `\r` in `\r\n` optional.  | No                 |
 //! | quirk_rectify_numbers | Rectify (invalid) numbers.     | No                 |
 //! | quirk_missing_text    | Rectify missing `text` element.| No                 |
+//! | quirk_atom_char_relaxed | Accept `{` in atoms and tags.| No                 |
+//! | quirk_spaces_relaxed  | Accept multiple spaces in `mailbox-data`. | No      |
+//! | kat                   | Expose known-answer test helpers (see [`testing`](crate::testing)). | No |
+//! | corpus                | Expose a fuzzing seed corpus (see [`corpus`](crate::corpus)). | No |
 //!
 //! ## Quirks
 //!
@@ -117,6 +121,9 @@ mod body;
 mod codec;
 mod command;
 mod core;
+#[cfg(any(test, feature = "corpus"))]
+#[cfg_attr(docsrs, doc(cfg(feature = "corpus")))]
+pub mod corpus;
 mod datetime;
 mod envelope;
 mod extensions;
@@ -127,8 +134,9 @@ mod response;
 mod search;
 mod sequence;
 mod status;
-#[cfg(test)]
-mod testing;
+#[cfg(any(test, feature = "kat"))]
+#[cfg_attr(docsrs, doc(cfg(feature = "kat")))]
+pub mod testing;
 
 pub use codec::*;
 // Re-export.
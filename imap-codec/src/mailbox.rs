@@ -1,4 +1,6 @@
-use abnf_core::streaming::{dquote, sp};
+use abnf_core::streaming::dquote;
+#[cfg(not(feature = "quirk_spaces_relaxed"))]
+use abnf_core::streaming::sp;
 use imap_types::{
     core::QuotedChar,
     flag::FlagNameAttribute,
@@ -14,6 +16,12 @@ use nom::{
     sequence::{delimited, preceded, tuple},
 };
 
+#[cfg(feature = "quirk_spaces_relaxed")]
+use crate::core::sp_relaxed as sp;
+#[cfg(feature = "ext_acl")]
+use crate::extensions::acl::acl_data;
+#[cfg(feature = "ext_esearch")]
+use crate::extensions::esearch::esearch_response;
 #[cfg(feature = "ext_metadata")]
 use crate::extensions::metadata::metadata_resp;
 #[cfg(feature = "ext_sort_thread")]
@@ -27,7 +35,7 @@ use crate::{
 };
 
 /// `list-mailbox = 1*list-char / string`
-pub(crate) fn list_mailbox(input: &[u8]) -> IMAPResult<&[u8], ListMailbox> {
+pub(crate) fn list_mailbox(input: &[u8]) -> IMAPResult<'_, &[u8], ListMailbox<'_>> {
     alt((
         map(take_while1(is_list_char), |bytes: &[u8]| {
             // # Safety
@@ -52,7 +60,7 @@ pub(crate) fn list_mailbox(input: &[u8]) -> IMAPResult<&[u8], ListMailbox> {
 /// "I" "N" "B" "O" "X" is considered to be INBOX and not an astring.
 ///
 /// Refer to section 5.1 for further semantic details of mailbox names.
-pub(crate) fn mailbox(input: &[u8]) -> IMAPResult<&[u8], Mailbox> {
+pub(crate) fn mailbox(input: &[u8]) -> IMAPResult<'_, &[u8], Mailbox<'_>> {
     map(astring, Mailbox::from)(input)
 }
 
@@ -63,10 +71,12 @@ pub(crate) fn mailbox(input: &[u8]) -> IMAPResult<&[u8], Mailbox> {
 ///                "SEARCH" *(SP nz-number) /
 ///                "STATUS" SP mailbox SP "(" [status-att-list] ")" /
 ///                "METADATA" SP mailbox SP (entry-values / entry-list) / ; RFC 5464
+///                esearch-response / ; RFC 4731
+///                acl-data / listrights-data / myrights-data /           ; RFC 4314
 ///                number SP "EXISTS" /
 ///                number SP "RECENT"
 /// ```
-pub(crate) fn mailbox_data(input: &[u8]) -> IMAPResult<&[u8], Data> {
+pub(crate) fn mailbox_data(input: &[u8]) -> IMAPResult<'_, &[u8], Data<'_>> {
     alt((
         map(
             tuple((tag_no_case(b"FLAGS"), sp, flag_list)),
@@ -107,7 +117,7 @@ pub(crate) fn mailbox_data(input: &[u8]) -> IMAPResult<&[u8], Data> {
                 sp,
                 delimited(tag(b"("), opt(status_att_list), tag(b")")),
                 #[cfg(feature = "quirk_trailing_space")]
-                opt(sp),
+                opt(abnf_core::streaming::sp),
                 #[cfg(not(feature = "quirk_trailing_space"))]
                 nom::combinator::success(()),
             )),
@@ -118,6 +128,10 @@ pub(crate) fn mailbox_data(input: &[u8]) -> IMAPResult<&[u8], Data> {
         ),
         #[cfg(feature = "ext_metadata")]
         metadata_resp,
+        #[cfg(feature = "ext_acl")]
+        acl_data,
+        #[cfg(feature = "ext_esearch")]
+        esearch_response,
         map(
             tuple((number, sp, tag_no_case(b"EXISTS"))),
             |(num, _, _)| Data::Exists(num),
@@ -137,7 +151,15 @@ pub(crate) fn mailbox_data(input: &[u8]) -> IMAPResult<&[u8], Data> {
 #[allow(clippy::type_complexity)]
 pub(crate) fn mailbox_list(
     input: &[u8],
-) -> IMAPResult<&[u8], (Option<Vec<FlagNameAttribute>>, Option<QuotedChar>, Mailbox)> {
+) -> IMAPResult<
+    '_,
+    &[u8],
+    (
+        Option<Vec<FlagNameAttribute<'_>>>,
+        Option<QuotedChar>,
+        Mailbox<'_>,
+    ),
+> {
     let mut parser = tuple((
         delimited(tag(b"("), opt(mbx_list_flags), tag(b")")),
         sp,
@@ -1,3 +1,5 @@
+use std::{num::NonZeroU32, str::from_utf8};
+
 #[cfg(not(feature = "quirk_crlf_relaxed"))]
 use abnf_core::streaming::crlf;
 #[cfg(feature = "quirk_crlf_relaxed")]
@@ -7,27 +9,40 @@ use base64::{engine::general_purpose::STANDARD as _base64, Engine};
 use imap_types::{
     core::{Text, Vec1},
     response::{
-        Bye, Capability, Code, CodeOther, CommandContinuationRequest, Data, Greeting, GreetingKind,
-        Response, Status, StatusBody, StatusKind, Tagged,
+        Bye, Capability, Code, CodeOther, CodeOtherValue, CommandContinuationRequest, Data,
+        Greeting, GreetingKind, Response, Status, StatusBody, StatusKind, Tagged,
     },
+    utils::indicators::is_text_char,
 };
 #[cfg(feature = "quirk_missing_text")]
 use nom::combinator::peek;
 use nom::{
     branch::alt,
-    bytes::streaming::{tag, tag_no_case, take_until, take_while},
-    combinator::{map, map_res, opt, value},
+    bytes::streaming::{tag, tag_no_case, take_until, take_while, take_while1},
+    combinator::{map, map_res, opt, value, verify},
+    error::ErrorKind,
     multi::separated_list1,
     sequence::{delimited, preceded, terminated, tuple},
+    Needed,
 };
 
+#[cfg(feature = "ext_condstore_qresync")]
+use crate::extensions::condstore_qresync::resp_text_code_condstore;
 #[cfg(feature = "ext_id")]
 use crate::extensions::id::id_response;
 #[cfg(feature = "ext_metadata")]
 use crate::extensions::metadata::metadata_code;
+#[cfg(feature = "ext_namespace")]
+use crate::extensions::namespace::namespace_response;
+#[cfg(feature = "ext_objectid")]
+use crate::extensions::objectid::resp_code_mailboxid;
+#[cfg(feature = "ext_uidplus")]
+use crate::extensions::uidplus::{resp_code_apnd, resp_code_copy};
+#[cfg(any(feature = "ext_login_referrals", feature = "ext_mailbox_referrals"))]
+use crate::extensions::url::imap_url;
 use crate::{
-    core::{atom, charset, nz_number, tag_imap, text},
-    decode::IMAPResult,
+    core::{atom, charset, number, nz_number, tag_imap, text},
+    decode::{CodeArgumentError, IMAPErrorKind, IMAPParseError, IMAPResult},
     extensions::enable::enable_data,
     fetch::msg_att,
     flag::flag_perm,
@@ -37,7 +52,7 @@ use crate::{
 // ----- greeting -----
 
 /// `greeting = "*" SP (resp-cond-auth / resp-cond-bye) CRLF`
-pub(crate) fn greeting(input: &[u8]) -> IMAPResult<&[u8], Greeting> {
+pub(crate) fn greeting(input: &[u8]) -> IMAPResult<'_, &[u8], Greeting<'_>> {
     let mut parser = tuple((
         tag(b"*"),
         sp,
@@ -59,7 +74,7 @@ pub(crate) fn greeting(input: &[u8]) -> IMAPResult<&[u8], Greeting> {
 #[allow(clippy::type_complexity)]
 pub(crate) fn resp_cond_auth(
     input: &[u8],
-) -> IMAPResult<&[u8], (GreetingKind, (Option<Code>, Text))> {
+) -> IMAPResult<'_, &[u8], (GreetingKind, (Option<Code<'_>>, Text<'_>))> {
     let mut parser = tuple((
         alt((
             value(GreetingKind::Ok, tag_no_case(b"OK")),
@@ -75,7 +90,7 @@ pub(crate) fn resp_cond_auth(
 }
 
 /// `resp-text = ["[" resp-text-code "]" SP] text`
-pub(crate) fn resp_text(input: &[u8]) -> IMAPResult<&[u8], (Option<Code>, Text)> {
+pub(crate) fn resp_text(input: &[u8]) -> IMAPResult<'_, &[u8], (Option<Code<'_>>, Text<'_>)> {
     // When the text starts with "[", we insist to parse a code.
     // Otherwise, a broken code could be interpreted as text.
     let (_, start) = opt(tag(b"["))(input)?;
@@ -87,6 +102,7 @@ pub(crate) fn resp_text(input: &[u8]) -> IMAPResult<&[u8], (Option<Code>, Text)>
                 map(
                     alt((
                         terminated(resp_text_code, tag(b"]")),
+                        terminated(resp_text_code_other, tag(b"]")),
                         map(
                             terminated(
                                 take_while(|b: u8| b != b']' && b != b'\r' && b != b'\n'),
@@ -137,11 +153,17 @@ pub(crate) fn resp_text(input: &[u8]) -> IMAPResult<&[u8], (Option<Code>, Text)>
 ///                    "NOPRIVATE"
 ///                  ) /
 ///                  "UNKNOWN-CTE" /       ; RFC 3516
+///                  "HIGHESTMODSEQ" SP mod-sequence-value / ; RFC 7162
+///                  "NOMODSEQ" /          ; RFC 7162
+///                  "MODIFIED" SP sequence-set /            ; RFC 7162
+///                  resp-code-apnd /      ; RFC 4315
+///                  resp-code-copy /      ; RFC 4315
+///                  "UIDNOTSTICKY" /      ; RFC 4315
 ///                  atom [SP 1*<any TEXT-CHAR except "]">]
 /// ```
 ///
 /// Note: See errata id: 261
-pub(crate) fn resp_text_code(input: &[u8]) -> IMAPResult<&[u8], Code> {
+pub(crate) fn resp_text_code(input: &[u8]) -> IMAPResult<'_, &[u8], Code<'_>> {
     alt((
         value(Code::Alert, tag_no_case(b"ALERT")),
         map(
@@ -175,18 +197,9 @@ pub(crate) fn resp_text_code(input: &[u8]) -> IMAPResult<&[u8], Code> {
         value(Code::ReadOnly, tag_no_case(b"READ-ONLY")),
         value(Code::ReadWrite, tag_no_case(b"READ-WRITE")),
         value(Code::TryCreate, tag_no_case(b"TRYCREATE")),
-        map(
-            tuple((tag_no_case(b"UIDNEXT"), sp, nz_number)),
-            |(_, _, num)| Code::UidNext(num),
-        ),
-        map(
-            tuple((tag_no_case(b"UIDVALIDITY"), sp, nz_number)),
-            |(_, _, num)| Code::UidValidity(num),
-        ),
-        map(
-            tuple((tag_no_case(b"UNSEEN"), sp, nz_number)),
-            |(_, _, num)| Code::Unseen(num),
-        ),
+        map(resp_text_code_nz_number(b"UIDNEXT"), Code::UidNext),
+        map(resp_text_code_nz_number(b"UIDVALIDITY"), Code::UidValidity),
+        map(resp_text_code_nz_number(b"UNSEEN"), Code::Unseen),
         value(Code::CompressionActive, tag_no_case(b"COMPRESSIONACTIVE")),
         value(Code::OverQuota, tag_no_case(b"OVERQUOTA")),
         value(Code::TooBig, tag_no_case(b"TOOBIG")),
@@ -197,14 +210,138 @@ pub(crate) fn resp_text_code(input: &[u8]) -> IMAPResult<&[u8], Code> {
         ),
         #[cfg(feature = "ext_binary")]
         value(Code::UnknownCte, tag_no_case(b"UNKNOWN-CTE")),
+        #[cfg(feature = "ext_condstore_qresync")]
+        resp_text_code_condstore,
+        // Note: Grouped because `alt` is limited to 21 alternatives.
+        #[cfg(feature = "ext_uidplus")]
+        alt((
+            resp_code_apnd,
+            resp_code_copy,
+            value(Code::UidNotSticky, tag_no_case(b"UIDNOTSTICKY")),
+        )),
+        #[cfg(feature = "ext_objectid")]
+        resp_code_mailboxid,
+        #[cfg(any(feature = "ext_login_referrals", feature = "ext_mailbox_referrals"))]
+        map(
+            preceded(tag_no_case(b"REFERRAL "), separated_list1(sp, imap_url)),
+            |urls| Code::Referral(Vec1::unvalidated(urls)),
+        ),
     ))(input)
 }
 
+/// `name SP nz-number` (used in `resp-text-code`)
+///
+/// Once `name` matched, a zero, non-numeric (e.g. `*`), or missing argument is rejected with a
+/// failure (see [`CodeArgumentError`]). Otherwise, the code would silently fall through to
+/// [`resp_text_code_other`].
+fn resp_text_code_nz_number<'a>(
+    name: &'static [u8],
+) -> impl FnMut(&'a [u8]) -> IMAPResult<'a, &'a [u8], NonZeroU32> {
+    move |input| {
+        let (remaining, _) = tag_no_case(name)(input)?;
+
+        match remaining.first() {
+            // A longer atom, e.g., `UNSEENX`, is not ours.
+            Some(byte) if *byte != b' ' && *byte != b']' => {
+                return Err(nom::Err::Error(IMAPParseError {
+                    input,
+                    kind: IMAPErrorKind::Nom(ErrorKind::Tag),
+                }));
+            }
+            Some(_) => {}
+            None => return Err(nom::Err::Incomplete(Needed::new(1))),
+        }
+
+        let (remaining, number) = match preceded(sp, number)(remaining) {
+            Ok(ok) => ok,
+            Err(nom::Err::Error(_)) => {
+                return Err(nom::Err::Failure(IMAPParseError {
+                    input: remaining,
+                    kind: IMAPErrorKind::InvalidCode(CodeArgumentError::BadNumber),
+                }));
+            }
+            Err(error) => return Err(error),
+        };
+
+        match NonZeroU32::new(number) {
+            Some(number) => Ok((remaining, number)),
+            None => Err(nom::Err::Failure(IMAPParseError {
+                input: remaining,
+                kind: IMAPErrorKind::InvalidCode(CodeArgumentError::ZeroNotAllowed),
+            })),
+        }
+    }
+}
+
+/// Names of the codes recognized by [`resp_text_code`] (see [`resp_text_code_other`]).
+const RESP_TEXT_CODE_NAMES: &[&str] = &[
+    "ALERT",
+    "BADCHARSET",
+    "CAPABILITY",
+    "PARSE",
+    "PERMANENTFLAGS",
+    "READ-ONLY",
+    "READ-WRITE",
+    "TRYCREATE",
+    "UIDNEXT",
+    "UIDVALIDITY",
+    "UNSEEN",
+    "COMPRESSIONACTIVE",
+    "OVERQUOTA",
+    "TOOBIG",
+    #[cfg(feature = "ext_metadata")]
+    "METADATA",
+    #[cfg(feature = "ext_binary")]
+    "UNKNOWN-CTE",
+    #[cfg(feature = "ext_condstore_qresync")]
+    "HIGHESTMODSEQ",
+    #[cfg(feature = "ext_condstore_qresync")]
+    "NOMODSEQ",
+    #[cfg(feature = "ext_condstore_qresync")]
+    "MODIFIED",
+    #[cfg(feature = "ext_uidplus")]
+    "APPENDUID",
+    #[cfg(feature = "ext_uidplus")]
+    "COPYUID",
+    #[cfg(feature = "ext_uidplus")]
+    "UIDNOTSTICKY",
+    #[cfg(feature = "ext_objectid")]
+    "MAILBOXID",
+    #[cfg(any(feature = "ext_login_referrals", feature = "ext_mailbox_referrals"))]
+    "REFERRAL",
+];
+
+/// `atom [SP 1*<any TEXT-CHAR except "]">]`
+///
+/// Generic structure of unknown codes (see `resp-text-code`).
+///
+/// Note: The names of known codes are rejected. Thus, a known code with malformed arguments,
+/// e.g., `[PERMANENTFLAGS]`, is kept as a raw [`Code::Other`].
+pub(crate) fn resp_text_code_other(input: &[u8]) -> IMAPResult<'_, &[u8], Code<'_>> {
+    map(
+        tuple((
+            verify(atom, |name| {
+                !RESP_TEXT_CODE_NAMES
+                    .iter()
+                    .any(|known| known.eq_ignore_ascii_case(name.as_ref()))
+            }),
+            opt(preceded(sp, take_while1(|b| is_text_char(b) && b != b']'))),
+        )),
+        |(name, value)| Code::OtherParsed {
+            name,
+            // # Safety
+            //
+            // `unwrap` is safe, because `is_text_char` enforces ASCII-only characters.
+            value: value.map(|value| CodeOtherValue::unvalidated(from_utf8(value).unwrap())),
+        },
+    )(input)
+}
+
 /// `capability-data = "CAPABILITY" *(SP capability) SP "IMAP4rev1" *(SP capability)`
 ///
 /// Servers MUST implement the STARTTLS, AUTH=PLAIN, and LOGINDISABLED capabilities
 /// Servers which offer RFC 1730 compatibility MUST list "IMAP4" as the first capability.
-pub(crate) fn capability_data(input: &[u8]) -> IMAPResult<&[u8], Vec1<Capability>> {
+pub(crate) fn capability_data(input: &[u8]) -> IMAPResult<'_, &[u8], Vec1<Capability<'_>>> {
     let mut parser = tuple((
         tag_no_case("CAPABILITY"),
         sp,
@@ -219,12 +356,12 @@ pub(crate) fn capability_data(input: &[u8]) -> IMAPResult<&[u8], Vec1<Capability
 /// `capability = ("AUTH=" auth-type) /
 ///               "COMPRESS=" algorithm / ; RFC 4978
 ///               atom`
-pub(crate) fn capability(input: &[u8]) -> IMAPResult<&[u8], Capability> {
+pub(crate) fn capability(input: &[u8]) -> IMAPResult<'_, &[u8], Capability<'_>> {
     map(atom, Capability::from)(input)
 }
 
 /// `resp-cond-bye = "BYE" SP resp-text`
-pub(crate) fn resp_cond_bye(input: &[u8]) -> IMAPResult<&[u8], (Option<Code>, Text)> {
+pub(crate) fn resp_cond_bye(input: &[u8]) -> IMAPResult<'_, &[u8], (Option<Code<'_>>, Text<'_>)> {
     let mut parser = tuple((tag_no_case(b"BYE"), sp, resp_text));
 
     let (remaining, (_, _, resp_text)) = parser(input)?;
@@ -235,7 +372,7 @@ pub(crate) fn resp_cond_bye(input: &[u8]) -> IMAPResult<&[u8], (Option<Code>, Te
 // ----- response -----
 
 /// `response = *(continue-req / response-data) response-done`
-pub(crate) fn response(input: &[u8]) -> IMAPResult<&[u8], Response> {
+pub(crate) fn response(input: &[u8]) -> IMAPResult<'_, &[u8], Response<'_>> {
     // Divert from standard here for better usability.
     // response_data already contains the bye response, thus
     // response_done could also be response_tagged.
@@ -249,7 +386,7 @@ pub(crate) fn response(input: &[u8]) -> IMAPResult<&[u8], Response> {
 }
 
 /// `continue-req = "+" SP (resp-text / base64) CRLF`
-pub(crate) fn continue_req(input: &[u8]) -> IMAPResult<&[u8], CommandContinuationRequest> {
+pub(crate) fn continue_req(input: &[u8]) -> IMAPResult<'_, &[u8], CommandContinuationRequest<'_>> {
     // We can't map the output of `resp_text` directly to `Continue::basic()` because we might end
     // up with a subset of `Text` that is valid base64 and will panic on `unwrap()`. Thus, we first
     // let the parsing finish and only later map to `Continue`.
@@ -301,10 +438,11 @@ pub(crate) fn continue_req(input: &[u8]) -> IMAPResult<&[u8], CommandContinuatio
 ///                    mailbox-data /
 ///                    message-data /
 ///                    capability-data /
-///                    id_response ; (See RFC 2971)
+///                    id_response /       ; (See RFC 2971)
+///                    namespace_response  ; (See RFC 2342)
 ///                  ) CRLF
 /// ```
-pub(crate) fn response_data(input: &[u8]) -> IMAPResult<&[u8], Response> {
+pub(crate) fn response_data(input: &[u8]) -> IMAPResult<'_, &[u8], Response<'_>> {
     let mut parser = tuple((
         tag(b"*"),
         sp,
@@ -325,6 +463,8 @@ pub(crate) fn response_data(input: &[u8]) -> IMAPResult<&[u8], Response> {
             map(id_response, |parameters| {
                 Response::Data(Data::Id { parameters })
             }),
+            #[cfg(feature = "ext_namespace")]
+            map(namespace_response, Response::Data),
         )),
         crlf,
     ));
@@ -337,7 +477,9 @@ pub(crate) fn response_data(input: &[u8]) -> IMAPResult<&[u8], Response> {
 /// `resp-cond-state = ("OK" / "NO" / "BAD") SP resp-text`
 ///
 /// Status condition
-pub(crate) fn resp_cond_state(input: &[u8]) -> IMAPResult<&[u8], (StatusKind, Option<Code>, Text)> {
+pub(crate) fn resp_cond_state(
+    input: &[u8],
+) -> IMAPResult<'_, &[u8], (StatusKind, Option<Code<'_>>, Text<'_>)> {
     let mut parser = tuple((
         alt((
             value(StatusKind::Ok, tag_no_case("OK")),
@@ -354,12 +496,12 @@ pub(crate) fn resp_cond_state(input: &[u8]) -> IMAPResult<&[u8], (StatusKind, Op
 }
 
 /// `response-done = response-tagged / response-fatal`
-pub(crate) fn response_done(input: &[u8]) -> IMAPResult<&[u8], Status> {
+pub(crate) fn response_done(input: &[u8]) -> IMAPResult<'_, &[u8], Status<'_>> {
     alt((response_tagged, response_fatal))(input)
 }
 
 /// `response-tagged = tag SP resp-cond-state CRLF`
-pub(crate) fn response_tagged(input: &[u8]) -> IMAPResult<&[u8], Status> {
+pub(crate) fn response_tagged(input: &[u8]) -> IMAPResult<'_, &[u8], Status<'_>> {
     let mut parser = tuple((tag_imap, sp, resp_cond_state, crlf));
 
     let (remaining, (tag, _, (kind, code, text), _)) = parser(input)?;
@@ -376,7 +518,7 @@ pub(crate) fn response_tagged(input: &[u8]) -> IMAPResult<&[u8], Status> {
 /// `response-fatal = "*" SP resp-cond-bye CRLF`
 ///
 /// Server closes connection immediately
-pub(crate) fn response_fatal(input: &[u8]) -> IMAPResult<&[u8], Status> {
+pub(crate) fn response_fatal(input: &[u8]) -> IMAPResult<'_, &[u8], Status<'_>> {
     let mut parser = tuple((tag(b"*"), sp, resp_cond_bye, crlf));
 
     let (remaining, (_, _, (code, text), _)) = parser(input)?;
@@ -385,7 +527,7 @@ pub(crate) fn response_fatal(input: &[u8]) -> IMAPResult<&[u8], Status> {
 }
 
 /// `message-data = nz-number SP ("EXPUNGE" / ("FETCH" SP msg-att))`
-pub(crate) fn message_data(input: &[u8]) -> IMAPResult<&[u8], Data> {
+pub(crate) fn message_data(input: &[u8]) -> IMAPResult<'_, &[u8], Data<'_>> {
     let (remaining, seq) = terminated(nz_number, sp)(input)?;
 
     alt((
@@ -399,19 +541,38 @@ pub(crate) fn message_data(input: &[u8]) -> IMAPResult<&[u8], Data> {
 
 #[cfg(test)]
 mod tests {
-    use std::num::NonZeroU32;
-
     use imap_types::{
+        auth::AuthMechanism,
         body::{
             BasicFields, Body, BodyExtension, BodyStructure, Disposition, Language, Location,
             SinglePartExtensionData, SpecificFields,
         },
-        core::{IString, NString, QuotedChar, Tag},
+        core::{Atom, IString, NString, QuotedChar, Tag},
         flag::FlagNameAttribute,
     };
 
     use super::*;
-    use crate::testing::{kat_inverse_greeting, kat_inverse_response, known_answer_test_encode};
+    use crate::{
+        decode::{Decoder, ResponseDecodeError},
+        encode::Encoder,
+        testing::{kat_inverse_greeting, kat_inverse_response, known_answer_test_encode},
+        GreetingCodec, ResponseCodec,
+    };
+
+    #[test]
+    fn test_greeting_rejection() {
+        let (_, greeting) = GreetingCodec::default()
+            .decode(b"* BYE Too many connections\r\n")
+            .unwrap();
+
+        assert_eq!(
+            greeting,
+            Greeting::bye(None, "Too many connections").unwrap()
+        );
+        assert!(greeting.is_rejection());
+        assert!(!Greeting::ok(None, "Hello").unwrap().is_rejection());
+        assert!(!Greeting::preauth(None, "Hello").unwrap().is_rejection());
+    }
 
     #[test]
     fn test_kat_inverse_greeting() {
@@ -580,6 +741,76 @@ mod tests {
                 b"".as_ref(),
                 Response::Status(Status::bad(None, None, "hello").unwrap()),
             ),
+            // unknown codes
+            (
+                b"* OK [XVENDOR-TOKEN abc] hello\r\n",
+                b"".as_ref(),
+                Response::Status(
+                    Status::ok(
+                        None,
+                        Some(Code::OtherParsed {
+                            name: Atom::try_from("XVENDOR-TOKEN").unwrap(),
+                            value: Some(CodeOtherValue::try_from("abc").unwrap()),
+                        }),
+                        "hello",
+                    )
+                    .unwrap(),
+                ),
+            ),
+            (
+                b"A1 NO [XVENDOR] hello\r\n",
+                b"".as_ref(),
+                Response::Status(
+                    Status::no(
+                        Some(Tag::try_from("A1").unwrap()),
+                        Some(Code::OtherParsed {
+                            name: Atom::try_from("XVENDOR").unwrap(),
+                            value: None,
+                        }),
+                        "hello",
+                    )
+                    .unwrap(),
+                ),
+            ),
+            (
+                b"* OK [X(VENDOR)] hello\r\n",
+                b"".as_ref(),
+                Response::Status(
+                    Status::ok(
+                        None,
+                        Some(Code::Other(CodeOther::unvalidated(b"X(VENDOR)".as_ref()))),
+                        "hello",
+                    )
+                    .unwrap(),
+                ),
+            ),
+            // known codes with malformed arguments
+            (
+                b"* OK [PERMANENTFLAGS] hello\r\n",
+                b"".as_ref(),
+                Response::Status(
+                    Status::ok(
+                        None,
+                        Some(Code::Other(CodeOther::unvalidated(
+                            b"PERMANENTFLAGS".as_ref(),
+                        ))),
+                        "hello",
+                    )
+                    .unwrap(),
+                ),
+            ),
+            (
+                b"* OK [alert now] hello\r\n",
+                b"".as_ref(),
+                Response::Status(
+                    Status::ok(
+                        None,
+                        Some(Code::Other(CodeOther::unvalidated(b"alert now".as_ref()))),
+                        "hello",
+                    )
+                    .unwrap(),
+                ),
+            ),
             // bye
             (
                 b"* BYE [ALERT] hello\r\n",
@@ -737,4 +968,235 @@ mod tests {
             assert!(response_data(b"* STATUS INBOX (MESSAGES 100 UNSEEN 0) \r\n").is_ok());
         }
     }
+
+    #[test]
+    fn test_parse_recent() {
+        let tests = [
+            (b"* 3 RECENT\r\n".as_ref(), true),
+            (b"* 1 FETCH (FLAGS (\\Seen \\Recent))\r\n", true),
+            (b"* STATUS INBOX (MESSAGES 2 RECENT 1)\r\n", true),
+            (b"* 1 FETCH (FLAGS (\\Seen))\r\n", false),
+            (b"* 3 EXISTS\r\n", false),
+        ];
+
+        for (test, expected) in tests {
+            // IMAP4rev1 responses using `RECENT` or `\Recent` are accepted ...
+            let (rem, response) = response_data(test).unwrap();
+            assert!(rem.is_empty());
+
+            // ... but can be detected.
+            match response {
+                Response::Data(data) => assert_eq!(data.uses_recent(), expected),
+                _ => panic!("expected data"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_informational() {
+        let tests = [
+            (b"* OK Still here\r\n".as_ref(), true),
+            (
+                b"* NO Disk is 98% full, please delete unnecessary data\r\n",
+                true,
+            ),
+            (b"* BAD Command line too long\r\n", false),
+            (b"* BYE Autologout; idle for too long\r\n", false),
+            (b"A1 OK FETCH completed\r\n", false),
+            (b"A1 NO FETCH failed\r\n", false),
+            (b"* 3 EXISTS\r\n", false),
+        ];
+
+        for (test, expected) in tests {
+            let (rem, got) = ResponseCodec::default().decode(test).unwrap();
+            assert!(rem.is_empty());
+            assert_eq!(got.is_informational(), expected);
+        }
+    }
+
+    #[test]
+    fn test_parse_parse_error() {
+        kat_inverse_response(&[(
+            b"* NO [PARSE] Unexpected characters in address\r\n".as_ref(),
+            b"".as_ref(),
+            Response::Status(
+                Status::no(None, Some(Code::Parse), "Unexpected characters in address").unwrap(),
+            ),
+        )]);
+
+        let tests = [
+            (
+                b"* NO [PARSE] Unexpected characters in address\r\n".as_ref(),
+                Some("Unexpected characters in address"),
+            ),
+            (
+                b"A1 OK [PARSE] FETCH completed\r\n",
+                Some("FETCH completed"),
+            ),
+            (b"* NO [ALERT] Unexpected characters in address\r\n", None),
+            (b"* NO Unexpected characters in address\r\n", None),
+        ];
+
+        for (test, expected) in tests {
+            let (rem, got) = ResponseCodec::default().decode(test).unwrap();
+            assert!(rem.is_empty());
+
+            match got {
+                Response::Status(status) => {
+                    assert_eq!(status.is_parse_error(), expected.is_some());
+                    assert_eq!(status.parse_error_context().map(AsRef::as_ref), expected);
+                }
+                _ => panic!("expected status"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_continue_long_challenge() {
+        // A SASL challenge (e.g., GSSAPI) containing every byte value, including `\r\n`.
+        let challenge: Vec<u8> = (0..=255u8).cycle().take(8 * 1024).collect();
+        let encoded = _base64.encode(&challenge);
+
+        // A challenge that was wrapped by a SASL library ...
+        let wrapped = encoded
+            .as_bytes()
+            .chunks(76)
+            .collect::<Vec<_>>()
+            .join(b"\r\n".as_ref());
+        let response = Response::CommandContinuationRequest(
+            CommandContinuationRequest::from_base64(&wrapped).unwrap(),
+        );
+
+        // ... is sent as a single line.
+        let expected = [b"+ ".as_ref(), encoded.as_bytes(), b"\r\n"].concat();
+        let got = ResponseCodec::default().encode(&response).dump();
+        assert_eq!(got, expected);
+
+        // The challenge may arrive in multiple chunks.
+        for split in [1, 2, 76, 77, expected.len() - 2, expected.len() - 1] {
+            assert_eq!(
+                ResponseCodec::default().decode(&expected[..split]),
+                Err(ResponseDecodeError::Incomplete)
+            );
+        }
+
+        let (rem, got) = ResponseCodec::default().decode(&expected).unwrap();
+        assert!(rem.is_empty());
+        assert_eq!(
+            got,
+            Response::CommandContinuationRequest(CommandContinuationRequest::base64(challenge))
+        );
+    }
+
+    #[test]
+    fn test_encode_ok_with_capabilities() {
+        known_answer_test_encode((
+            Response::Status(
+                Status::ok_with_capabilities(
+                    Tag::try_from("A1").unwrap(),
+                    vec![
+                        Capability::Imap4Rev1,
+                        Capability::Idle,
+                        Capability::Auth(AuthMechanism::Plain),
+                    ],
+                    "Logged in",
+                )
+                .unwrap(),
+            ),
+            b"A1 OK [CAPABILITY IMAP4REV1 IDLE AUTH=PLAIN] Logged in\r\n".as_ref(),
+        ));
+    }
+
+    #[test]
+    fn test_parse_resp_text_code_nz_number() {
+        let tests = [
+            (
+                b"[UIDNEXT 4392] x\r\n".as_ref(),
+                Code::UidNext(NonZeroU32::new(4392).unwrap()),
+            ),
+            (
+                b"[uidvalidity 1] x\r\n",
+                Code::UidValidity(NonZeroU32::new(1).unwrap()),
+            ),
+            (
+                b"[UNSEEN 12] x\r\n",
+                Code::Unseen(NonZeroU32::new(12).unwrap()),
+            ),
+        ];
+
+        for (test, expected) in tests {
+            let (_, (code, _)) = resp_text(test).unwrap();
+            assert_eq!(code, Some(expected));
+        }
+
+        // A longer atom is still an unknown code.
+        let (_, (code, _)) = resp_text(b"[UNSEENX 0] x\r\n").unwrap();
+        assert!(matches!(code, Some(Code::OtherParsed { .. })));
+
+        for test in [b"[UIDVALIDITY 0] x\r\n".as_ref(), b"[UNSEEN 00] x\r\n"] {
+            assert!(matches!(
+                resp_text(test),
+                Err(nom::Err::Failure(IMAPParseError {
+                    kind: IMAPErrorKind::InvalidCode(CodeArgumentError::ZeroNotAllowed),
+                    ..
+                }))
+            ));
+        }
+
+        for test in [
+            b"[UIDNEXT abc] x\r\n".as_ref(),
+            b"[UIDNEXT *] x\r\n",
+            b"[UIDNEXT] x\r\n",
+            b"[UNSEEN -1] x\r\n",
+            b"[UIDVALIDITY 4294967296] x\r\n",
+        ] {
+            assert!(matches!(
+                resp_text(test),
+                Err(nom::Err::Failure(IMAPParseError {
+                    kind: IMAPErrorKind::InvalidCode(CodeArgumentError::BadNumber),
+                    ..
+                }))
+            ));
+        }
+
+        assert!(resp_text(b"[UIDNEXT 12").is_err());
+
+        for (test, error) in [
+            (
+                b"* OK [UIDVALIDITY 0] UIDs valid\r\n".as_ref(),
+                CodeArgumentError::ZeroNotAllowed,
+            ),
+            (b"* OK [UIDNEXT abc] x\r\n", CodeArgumentError::BadNumber),
+        ] {
+            assert_eq!(
+                ResponseCodec::default().decode(test),
+                Err(ResponseDecodeError::InvalidCode { error })
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_resp_spaces_quirk() {
+        assert!(response_data(b"* SEARCH 1 2\r\n").is_ok());
+
+        #[cfg(not(feature = "quirk_spaces_relaxed"))]
+        {
+            assert!(response_data(b"* SEARCH  1 2\r\n").is_err());
+            assert!(response_data(b"* 3  EXISTS\r\n").is_err());
+        }
+
+        #[cfg(feature = "quirk_spaces_relaxed")]
+        {
+            let (rem, data) = response_data(b"* SEARCH  1   2\r\n").unwrap();
+            assert!(rem.is_empty());
+            assert_eq!(
+                data,
+                Response::Data(Data::Search(vec![
+                    NonZeroU32::new(1).unwrap(),
+                    NonZeroU32::new(2).unwrap(),
+                ]))
+            );
+            assert!(response_data(b"* 3  EXISTS\r\n").is_ok());
+        }
+    }
 }
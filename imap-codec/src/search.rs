@@ -12,6 +12,14 @@ use nom::{
     sequence::{delimited, tuple},
 };
 
+#[cfg(feature = "ext_within")]
+use crate::core::nz_number;
+#[cfg(feature = "ext_esearch")]
+use crate::extensions::esearch::search_return_opts;
+#[cfg(feature = "ext_savedate")]
+use crate::extensions::savedate::search_key_savedate;
+#[cfg(feature = "ext_annotate")]
+use crate::{core::nstring, mailbox::list_mailbox};
 use crate::{
     core::{astring, atom, charset, number},
     datetime::date,
@@ -20,32 +28,60 @@ use crate::{
     sequence::sequence_set,
 };
 
-/// `search = "SEARCH" [SP "CHARSET" SP charset] 1*(SP search-key)`
+/// ```abnf
+/// search = "SEARCH"
+///          [search-return-opts] ; RFC 4731
+///          [SP "CHARSET" SP charset] 1*(SP search-key)
+/// ```
 ///
 /// Note: CHARSET argument MUST be registered with IANA
 ///
 /// errata id: 261
-pub(crate) fn search(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
-    let mut parser = tuple((
-        tag_no_case(b"SEARCH"),
-        opt(map(
-            tuple((sp, tag_no_case(b"CHARSET"), sp, charset)),
-            |(_, _, _, charset)| charset,
-        )),
-        sp,
-        map(separated_list1(sp, search_key(9)), Vec1::unvalidated),
-    ));
+pub(crate) fn search(
+    max_sequence_set_len: usize,
+) -> impl Fn(&[u8]) -> IMAPResult<'_, &[u8], CommandBody<'_>> {
+    move |input: &[u8]| {
+        let (remaining, _) = tag_no_case(b"SEARCH")(input)?;
 
-    let (remaining, (_, charset, _, criteria)) = parser(input)?;
+        #[cfg(feature = "ext_esearch")]
+        let (remaining, return_options) = opt(search_return_opts)(remaining)?;
 
-    Ok((
-        remaining,
-        CommandBody::Search {
-            charset,
-            criteria,
-            uid: false,
-        },
-    ))
+        let mut parser = tuple((
+            opt(map(
+                tuple((sp, tag_no_case(b"CHARSET"), sp, charset)),
+                |(_, _, _, charset)| charset,
+            )),
+            sp,
+            map(
+                separated_list1(sp, search_key(9, max_sequence_set_len)),
+                Vec1::unvalidated,
+            ),
+        ));
+
+        let (remaining, (charset, _, criteria)) = parser(remaining)?;
+
+        #[cfg(feature = "ext_esearch")]
+        if let Some(return_options) = return_options {
+            return Ok((
+                remaining,
+                CommandBody::SearchExtended {
+                    return_options,
+                    charset,
+                    criteria,
+                    uid: false,
+                },
+            ));
+        }
+
+        Ok((
+            remaining,
+            CommandBody::Search {
+                charset,
+                criteria,
+                uid: false,
+            },
+        ))
+    }
 }
 
 /// `search-key = "ALL" /
@@ -85,20 +121,31 @@ pub(crate) fn search(input: &[u8]) -> IMAPResult<&[u8], CommandBody> {
 ///               "UID" SP sequence-set /
 ///               "UNDRAFT" /
 ///               sequence-set /
-///               "(" search-key *(SP search-key) ")"`
+///               "(" search-key *(SP search-key) ")" /
+///               "ANNOTATION" SP entry-match SP attrib-match SP value / ; RFC 5257
+///               "OLDER" SP nz-number /   ; RFC 5032
+///               "YOUNGER" SP nz-number / ; RFC 5032
+///               "SAVEDBEFORE" SP date /  ; RFC 8514
+///               "SAVEDON" SP date /      ; RFC 8514
+///               "SAVEDSINCE" SP date /   ; RFC 8514
+///               "SAVEDATESUPPORTED"      ; RFC 8514`
+///
+/// Note: We use `list-mailbox` for `entry-match`, `astring` for `attrib-match`, and `nstring` for `value`.
 ///
 /// This parser is recursively defined. Thus, in order to not overflow the stack,
 /// it is needed to limit how may recursions are allowed. (8 should suffice).
 pub(crate) fn search_key(
     remaining_recursions: usize,
+    max_sequence_set_len: usize,
 ) -> impl Fn(&[u8]) -> IMAPResult<&[u8], SearchKey> {
-    move |input: &[u8]| search_key_limited(input, remaining_recursions)
+    move |input: &[u8]| search_key_limited(input, remaining_recursions, max_sequence_set_len)
 }
 
 fn search_key_limited<'a>(
     input: &'a [u8],
     remaining_recursion: usize,
-) -> IMAPResult<&'a [u8], SearchKey> {
+    max_sequence_set_len: usize,
+) -> IMAPResult<'a, &'a [u8], SearchKey<'a>> {
     if remaining_recursion == 0 {
         return Err(nom::Err::Failure(IMAPParseError {
             input,
@@ -106,11 +153,33 @@ fn search_key_limited<'a>(
         }));
     }
 
-    let search_key =
-        move |input: &'a [u8]| search_key_limited(input, remaining_recursion.saturating_sub(1));
+    let search_key = move |input: &'a [u8]| {
+        search_key_limited(
+            input,
+            remaining_recursion.saturating_sub(1),
+            max_sequence_set_len,
+        )
+    };
 
     alt((
         alt((
+            #[cfg(feature = "ext_annotate")]
+            map(
+                tuple((
+                    tag_no_case(b"ANNOTATION"),
+                    sp,
+                    list_mailbox,
+                    sp,
+                    astring,
+                    sp,
+                    nstring,
+                )),
+                |(_, _, entry, _, attribute, _, value)| SearchKey::Annotation {
+                    entry,
+                    attribute,
+                    value,
+                },
+            ),
             value(SearchKey::All, tag_no_case(b"ALL")),
             value(SearchKey::Answered, tag_no_case(b"ANSWERED")),
             map(tuple((tag_no_case(b"BCC"), sp, astring)), |(_, _, val)| {
@@ -139,6 +208,12 @@ fn search_key_limited<'a>(
                 |(_, _, val)| SearchKey::Keyword(val),
             ),
             value(SearchKey::New, tag_no_case(b"NEW")),
+            // Note: Must come before "OLD".
+            #[cfg(feature = "ext_within")]
+            map(
+                tuple((tag_no_case(b"OLDER"), sp, nz_number)),
+                |(_, _, val)| SearchKey::Older(val),
+            ),
             value(SearchKey::Old, tag_no_case(b"OLD")),
             map(
                 tuple((tag_no_case(b"ON"), sp, map_opt(date, |date| date))),
@@ -207,11 +282,18 @@ fn search_key_limited<'a>(
                 |(_, _, val)| SearchKey::Smaller(val),
             ),
             map(
-                tuple((tag_no_case(b"UID"), sp, sequence_set)),
+                tuple((tag_no_case(b"UID"), sp, sequence_set(max_sequence_set_len))),
                 |(_, _, val)| SearchKey::Uid(val),
             ),
             value(SearchKey::Undraft, tag_no_case(b"UNDRAFT")),
-            map(sequence_set, SearchKey::SequenceSet),
+            #[cfg(feature = "ext_within")]
+            map(
+                tuple((tag_no_case(b"YOUNGER"), sp, nz_number)),
+                |(_, _, val)| SearchKey::Younger(val),
+            ),
+            #[cfg(feature = "ext_savedate")]
+            search_key_savedate,
+            map(sequence_set(max_sequence_set_len), SearchKey::SequenceSet),
             map(
                 delimited(tag(b"("), separated_list1(sp, search_key), tag(b")")),
                 |val| SearchKey::And(Vec1::unvalidated(val)),
@@ -225,16 +307,23 @@ fn search_key_limited<'a>(
 /// ```abnf
 /// search-criteria = charset 1*(SP search-key)
 /// ```
-pub(crate) fn search_criteria(input: &[u8]) -> IMAPResult<&[u8], (Charset, Vec1<SearchKey>)> {
-    let mut parser = separated_pair(
-        charset,
-        sp,
-        map(separated_list1(sp, search_key(9)), Vec1::unvalidated),
-    );
+pub(crate) fn search_criteria(
+    max_sequence_set_len: usize,
+) -> impl Fn(&[u8]) -> IMAPResult<'_, &[u8], (Charset<'_>, Vec1<SearchKey<'_>>)> {
+    move |input: &[u8]| {
+        let mut parser = separated_pair(
+            charset,
+            sp,
+            map(
+                separated_list1(sp, search_key(9, max_sequence_set_len)),
+                Vec1::unvalidated,
+            ),
+        );
 
-    let (remaining, (charset, search_keys)) = parser(input)?;
+        let (remaining, (charset, search_keys)) = parser(input)?;
 
-    Ok((remaining, (charset, search_keys)))
+        Ok((remaining, (charset, search_keys)))
+    }
 }
 
 #[cfg(test)]
@@ -246,7 +335,7 @@ mod tests {
     };
 
     use super::*;
-    use crate::testing::known_answer_test_encode;
+    use crate::{sequence::DEFAULT_SEQUENCE_SET_MAX_LEN, testing::known_answer_test_encode};
 
     #[test]
     fn test_parse_search() {
@@ -255,7 +344,7 @@ mod tests {
             sequence::{SeqOrUid::Value, Sequence::*, SequenceSet as SequenceSetData},
         };
 
-        let (_rem, val) = search(b"search (uid 5)???").unwrap();
+        let (_rem, val) = search(DEFAULT_SEQUENCE_SET_MAX_LEN)(b"search (uid 5)???").unwrap();
         assert_eq!(
             val,
             CommandBody::Search {
@@ -269,7 +358,10 @@ mod tests {
             }
         );
 
-        let (_rem, val) = search(b"search (uid 5 or uid 5 (uid 1 uid 2) not uid 5)???").unwrap();
+        let (_rem, val) = search(DEFAULT_SEQUENCE_SET_MAX_LEN)(
+            b"search (uid 5 or uid 5 (uid 1 uid 2) not uid 5)???",
+        )
+        .unwrap();
         let expected = CommandBody::Search {
             charset: None,
             criteria: Vec1::from(And(vec![
@@ -314,10 +406,62 @@ mod tests {
 
     #[test]
     fn test_parse_search_key() {
-        assert!(search_key(1)(b"1:5|").is_ok());
-        assert!(search_key(1)(b"(1:5)|").is_err());
-        assert!(search_key(2)(b"(1:5)|").is_ok());
-        assert!(search_key(2)(b"((1:5))|").is_err());
+        assert!(search_key(1, DEFAULT_SEQUENCE_SET_MAX_LEN)(b"1:5|").is_ok());
+        assert!(search_key(1, DEFAULT_SEQUENCE_SET_MAX_LEN)(b"(1:5)|").is_err());
+        assert!(search_key(2, DEFAULT_SEQUENCE_SET_MAX_LEN)(b"(1:5)|").is_ok());
+        assert!(search_key(2, DEFAULT_SEQUENCE_SET_MAX_LEN)(b"((1:5))|").is_err());
+
+        // System flags have dedicated search keys and are not keywords.
+        assert!(search_key(1, DEFAULT_SEQUENCE_SET_MAX_LEN)(b"KEYWORD \\Seen|").is_err());
+        assert!(search_key(1, DEFAULT_SEQUENCE_SET_MAX_LEN)(b"UNKEYWORD \\Seen|").is_err());
+    }
+
+    #[test]
+    fn test_search_key_dates() {
+        let date = NaiveDate::try_from_imap("1-Jan-2020").unwrap();
+
+        // Both the quoted and the unquoted form are accepted ...
+        for test in [
+            b"BEFORE \"1-Jan-2020\"|".as_ref(),
+            b"BEFORE 1-Jan-2020|",
+            b"before \"01-JAN-2020\"|",
+        ] {
+            let (rem, got) = search_key(1, DEFAULT_SEQUENCE_SET_MAX_LEN)(test).unwrap();
+            assert_eq!(rem, b"|");
+            assert_eq!(got, SearchKey::Before(date.clone()));
+        }
+
+        // ... but dates are always encoded quoted.
+        let tests = [
+            (
+                SearchKey::Before(date.clone()),
+                b"BEFORE \"01-Jan-2020\"".as_ref(),
+            ),
+            (SearchKey::On(date.clone()), b"ON \"01-Jan-2020\""),
+            (SearchKey::Since(date), b"SINCE \"01-Jan-2020\""),
+        ];
+
+        for test in tests {
+            known_answer_test_encode(test);
+        }
+    }
+
+    #[test]
+    fn test_encode_search_key_uids_seqs() {
+        let tests = [
+            (
+                SearchKey::uids(SequenceSet::try_from("1:*").unwrap()),
+                b"UID 1:*".as_ref(),
+            ),
+            (
+                SearchKey::seqs(SequenceSet::try_from("1:*").unwrap()),
+                b"1:*".as_ref(),
+            ),
+        ];
+
+        for test in tests {
+            known_answer_test_encode(test);
+        }
     }
 
     #[test]
@@ -415,7 +559,7 @@ mod tests {
             (SearchKey::Text(AString::try_from("A").unwrap()), b"TEXT A"),
             (SearchKey::To(AString::try_from("A").unwrap()), b"TO A"),
             (
-                SearchKey::Uid(SequenceSet::try_from(Sequence::try_from(1..).unwrap()).unwrap()),
+                SearchKey::Uid(SequenceSet::from(Sequence::try_from(1..).unwrap())),
                 b"UID 1:*",
             ),
             (SearchKey::Unanswered, b"UNANSWERED"),
@@ -433,4 +577,95 @@ mod tests {
             known_answer_test_encode(test);
         }
     }
+
+    #[cfg(feature = "ext_annotate")]
+    #[test]
+    fn test_kat_inverse_search_annotation() {
+        use imap_types::{
+            command::Command,
+            core::{NString, Quoted},
+            mailbox::ListMailbox,
+        };
+
+        use crate::testing::kat_inverse_command;
+
+        kat_inverse_command(&[(
+            b"A SEARCH ANNOTATION /comment value.priv \"text\"\r\n".as_ref(),
+            b"".as_ref(),
+            Command::new(
+                "A",
+                CommandBody::search(
+                    None,
+                    Vec1::from(SearchKey::Annotation {
+                        entry: ListMailbox::try_from("/comment").unwrap(),
+                        attribute: AString::try_from("value.priv").unwrap(),
+                        value: NString(Some(Quoted::try_from("text").unwrap().into())),
+                    }),
+                    false,
+                ),
+            )
+            .unwrap(),
+        )]);
+    }
+
+    #[cfg(feature = "ext_within")]
+    #[test]
+    fn test_kat_inverse_search_within() {
+        use std::num::NonZeroU32;
+
+        use imap_types::command::Command;
+
+        use crate::testing::kat_inverse_command;
+
+        kat_inverse_command(&[
+            (
+                b"A SEARCH OLDER 1800\r\n".as_ref(),
+                b"".as_ref(),
+                Command::new(
+                    "A",
+                    CommandBody::search(
+                        None,
+                        Vec1::from(SearchKey::Older(NonZeroU32::new(1800).unwrap())),
+                        false,
+                    ),
+                )
+                .unwrap(),
+            ),
+            (
+                b"A SEARCH OR OLDER 60 YOUNGER 3600\r\n".as_ref(),
+                b"".as_ref(),
+                Command::new(
+                    "A",
+                    CommandBody::search(
+                        None,
+                        Vec1::from(SearchKey::Or(
+                            Box::new(SearchKey::Older(NonZeroU32::new(60).unwrap())),
+                            Box::new(SearchKey::Younger(NonZeroU32::new(3600).unwrap())),
+                        )),
+                        false,
+                    ),
+                )
+                .unwrap(),
+            ),
+            (
+                b"A UID SEARCH NOT YOUNGER 86400 OLD\r\n".as_ref(),
+                b"".as_ref(),
+                Command::new(
+                    "A",
+                    CommandBody::search(
+                        None,
+                        Vec1::try_from(vec![
+                            SearchKey::Not(Box::new(SearchKey::Younger(
+                                NonZeroU32::new(86400).unwrap(),
+                            ))),
+                            SearchKey::Old,
+                        ])
+                        .unwrap(),
+                        true,
+                    ),
+                )
+                .unwrap(),
+            ),
+        ]);
+    }
 }
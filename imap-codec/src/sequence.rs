@@ -1,16 +1,25 @@
 use imap_types::{
     core::Vec1,
-    sequence::{SeqOrUid, Sequence, SequenceSet},
+    sequence::{error::SequenceSetError, SeqOrUid, Sequence, SequenceSet},
 };
 use nom::{
     branch::alt,
     bytes::streaming::tag,
     combinator::{map, value},
-    multi::separated_list1,
-    sequence::tuple,
+    sequence::{preceded, tuple},
 };
 
-use crate::{core::nz_number, decode::IMAPResult};
+#[cfg(feature = "ext_searchres")]
+use crate::extensions::searchres::seq_last_command;
+use crate::{
+    core::{number, nz_number},
+    decode::{IMAPErrorKind, IMAPParseError, IMAPResult},
+};
+
+/// Default maximum number of [`Sequence`]s accepted in a single [`SequenceSet`] of a command.
+///
+/// Protects against, e.g., `UID COPY 1,2,3,...` with millions of elements.
+pub(crate) const DEFAULT_SEQUENCE_SET_MAX_LEN: usize = 100_000;
 
 /// `sequence-set = (seq-number / seq-range) ["," sequence-set]`
 ///
@@ -31,18 +40,82 @@ use crate::{core::nz_number, decode::IMAPResult};
 /// Simplified:
 ///
 /// `sequence-set = (seq-number / seq-range) *("," (seq-number / seq-range))`
-pub(crate) fn sequence_set(input: &[u8]) -> IMAPResult<&[u8], SequenceSet> {
-    map(
-        separated_list1(
-            tag(b","),
-            alt((
-                // Ordering is important!
-                map(seq_range, |(from, to)| Sequence::Range(from, to)),
-                map(seq_number, Sequence::Single),
-            )),
-        ),
-        |set| SequenceSet(Vec1::unvalidated(set)),
-    )(input)
+///
+/// Note: A zero, e.g., in `0`, `1:0`, or `1,0`, is rejected with a failure
+/// ([`SequenceSetError::ZeroNotAllowed`]). Otherwise, the set would silently end before it.
+///
+/// Note: A set with more than `max_len` sequences is rejected with a failure
+/// ([`SequenceSetError::TooLarge`]). Commands use the limit configured in [`CommandCodec`];
+/// responses are not limited (`usize::MAX`).
+///
+/// [`CommandCodec`]: crate::CommandCodec
+pub(crate) fn sequence_set(max_len: usize) -> impl Fn(&[u8]) -> IMAPResult<'_, &[u8], SequenceSet> {
+    move |input: &[u8]| sequence_set_limited(input, max_len)
+}
+
+fn sequence_set_limited(input: &[u8], max_len: usize) -> IMAPResult<'_, &[u8], SequenceSet> {
+    let mut sequence = alt((
+        // Ordering is important!
+        map(seq_range, |(from, to)| Sequence::Range(from, to)),
+        map(seq_number, Sequence::Single),
+        #[cfg(feature = "ext_searchres")]
+        seq_last_command,
+    ));
+
+    let (mut remaining, first) = match sequence(input) {
+        Ok(ok) => ok,
+        Err(nom::Err::Error(error)) => {
+            return Err(zero_not_allowed(input, input).unwrap_or(nom::Err::Error(error)));
+        }
+        Err(error) => return Err(error),
+    };
+    let mut set = vec![first];
+
+    // Note: Same as `separated_list1`, but bails out early instead of collecting everything.
+    loop {
+        match preceded(tag(b","), &mut sequence)(remaining) {
+            Ok((rem, next)) => {
+                if set.len() >= max_len {
+                    return Err(nom::Err::Failure(IMAPParseError {
+                        input: remaining,
+                        kind: IMAPErrorKind::SequenceSet(SequenceSetError::TooLarge {
+                            max: max_len,
+                        }),
+                    }));
+                }
+
+                set.push(next);
+                remaining = rem;
+            }
+            Err(nom::Err::Error(_)) => break,
+            Err(error) => return Err(error),
+        }
+    }
+
+    // A `,0` or `:0` (from a `seq-range`) would otherwise be left for the caller.
+    if let Some(b',' | b':') = remaining.first() {
+        if let Some(error) = zero_not_allowed(input, &remaining[1..]) {
+            return Err(error);
+        }
+    }
+
+    Ok((remaining, SequenceSet(Vec1::unvalidated(set))))
+}
+
+/// Return a failure when `at` (a suffix of `input`) starts with a zero.
+fn zero_not_allowed<'a>(
+    input: &'a [u8],
+    at: &'a [u8],
+) -> Option<nom::Err<IMAPParseError<'a, &'a [u8]>>> {
+    match number(at) {
+        Ok((_, 0)) => Some(nom::Err::Failure(IMAPParseError {
+            input: at,
+            kind: IMAPErrorKind::SequenceSet(SequenceSetError::ZeroNotAllowed {
+                at: input.len() - at.len(),
+            }),
+        })),
+        _ => None,
+    }
 }
 
 /// `seq-range = seq-number ":" seq-number`
@@ -53,7 +126,7 @@ pub(crate) fn sequence_set(input: &[u8]) -> IMAPResult<&[u8], SequenceSet> {
 ///
 /// Example: a unique identifier sequence range of 3291:* includes the UID
 ///          of the last message in the mailbox, even if that value is less than 3291.
-pub(crate) fn seq_range(input: &[u8]) -> IMAPResult<&[u8], (SeqOrUid, SeqOrUid)> {
+pub(crate) fn seq_range(input: &[u8]) -> IMAPResult<'_, &[u8], (SeqOrUid, SeqOrUid)> {
     let mut parser = tuple((seq_number, tag(b":"), seq_number));
 
     let (remaining, (from, _, to)) = parser(input)?;
@@ -66,7 +139,7 @@ pub(crate) fn seq_range(input: &[u8]) -> IMAPResult<&[u8], (SeqOrUid, SeqOrUid)>
 /// Message sequence number (COPY, FETCH, STORE commands) or unique
 /// identifier (UID COPY, UID FETCH, UID STORE commands).
 ///
-/// * represents the largest number in use.
+/// `*` represents the largest number in use.
 /// In the case of message sequence numbers, it is the number of messages in a non-empty mailbox.
 /// In the case of unique identifiers, it is the unique identifier of the last message in the mailbox or,
 /// if the mailbox is empty, the mailbox's current UIDNEXT value.
@@ -74,7 +147,17 @@ pub(crate) fn seq_range(input: &[u8]) -> IMAPResult<&[u8], (SeqOrUid, SeqOrUid)>
 /// The server should respond with a tagged BAD response to a command that uses a message
 /// sequence number greater than the number of messages in the selected mailbox.
 /// This includes "*" if the selected mailbox is empty.
-pub(crate) fn seq_number(input: &[u8]) -> IMAPResult<&[u8], SeqOrUid> {
+///
+/// Note: `0` is rejected with [`IMAPErrorKind::ZeroNotAllowed`]. This is a recoverable error
+/// so that enclosing alternatives can still be tried.
+pub(crate) fn seq_number(input: &[u8]) -> IMAPResult<'_, &[u8], SeqOrUid> {
+    if let Ok((_, 0)) = number(input) {
+        return Err(nom::Err::Error(IMAPParseError {
+            input,
+            kind: IMAPErrorKind::ZeroNotAllowed,
+        }));
+    }
+
     alt((
         map(nz_number, SeqOrUid::Value),
         value(SeqOrUid::Asterisk, tag(b"*")),
@@ -109,19 +192,100 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_sequence_set_from_str_roundtrip() {
+        for test in ["1", "1,2:4,8:*", "5:1", "*:*", "*", "3:3,1,2,*:10"] {
+            let expected = SequenceSet::try_from(test).unwrap();
+
+            // Encoding yields the (normalized) input again ...
+            let mut ctx = EncodeContext::new();
+            expected.encode_ctx(&mut ctx).unwrap();
+            let out = ctx.dump();
+            assert_eq!(out, test.as_bytes());
+
+            // ... which the IMAP parser agrees with.
+            let input = [out.as_slice(), b"?"].concat();
+            let (rem, got) = sequence_set(DEFAULT_SEQUENCE_SET_MAX_LEN)(&input).unwrap();
+            assert_eq!(rem, b"?");
+            assert_eq!(got, expected);
+        }
+    }
+
+    #[test]
+    fn test_parse_sequence_set_limit() {
+        let input = |len: usize| {
+            let mut input = vec!["1"; len].join(",").into_bytes();
+            input.push(b'?');
+            input
+        };
+
+        let input_ok = input(DEFAULT_SEQUENCE_SET_MAX_LEN);
+        let (rem, val) = sequence_set(DEFAULT_SEQUENCE_SET_MAX_LEN)(&input_ok).unwrap();
+        assert_eq!(rem, b"?");
+        assert_eq!(val.0.as_ref().len(), DEFAULT_SEQUENCE_SET_MAX_LEN);
+
+        let input_too_large = input(DEFAULT_SEQUENCE_SET_MAX_LEN + 1);
+        assert!(matches!(
+            sequence_set(DEFAULT_SEQUENCE_SET_MAX_LEN)(&input_too_large),
+            Err(nom::Err::Failure(IMAPParseError {
+                kind: IMAPErrorKind::SequenceSet(SequenceSetError::TooLarge {
+                    max: DEFAULT_SEQUENCE_SET_MAX_LEN
+                }),
+                ..
+            }))
+        ));
+
+        assert!(sequence_set(3)(b"1,2,3?").is_ok());
+        assert!(sequence_set(3)(b"1,2,3,4?").is_err());
+        assert!(sequence_set(usize::MAX)(&input_too_large).is_ok());
+    }
+
     #[test]
     fn test_parse_sequence_set() {
-        let (rem, val) = sequence_set(b"1:*?").unwrap();
+        let (rem, val) = sequence_set(DEFAULT_SEQUENCE_SET_MAX_LEN)(b"1:*?").unwrap();
         println!("{:?}, {:?}", rem, val);
 
-        let (rem, val) = sequence_set(b"1:*,5?").unwrap();
+        let (rem, val) = sequence_set(DEFAULT_SEQUENCE_SET_MAX_LEN)(b"1:*,5?").unwrap();
         println!("{:?}, {:?}", rem, val);
     }
 
+    #[test]
+    fn test_parse_sequence_set_zero() {
+        for (test, at) in [
+            (b"0?".as_ref(), 0),
+            (b"0:*?", 0),
+            (b"00?", 0),
+            (b"1:0?", 2),
+            (b"1,0?", 2),
+            (b"1,2:*,0:3?", 6),
+            (b"1,2:00?", 4),
+        ] {
+            assert!(matches!(
+                sequence_set(DEFAULT_SEQUENCE_SET_MAX_LEN)(test),
+                Err(nom::Err::Failure(IMAPParseError {
+                    kind: IMAPErrorKind::SequenceSet(SequenceSetError::ZeroNotAllowed { at: got }),
+                    ..
+                })) if got == at
+            ));
+        }
+
+        // Other trailing data is left for the caller.
+        for (test, expected) in [(b"1:a?".as_ref(), b":a?".as_ref()), (b"1,*x", b"x")] {
+            let (rem, _) = sequence_set(DEFAULT_SEQUENCE_SET_MAX_LEN)(test).unwrap();
+            assert_eq!(rem, expected);
+        }
+    }
+
     #[test]
     fn test_parse_seq_number() {
         // Must not be 0.
-        assert!(seq_number(b"0?").is_err());
+        assert!(matches!(
+            seq_number(b"0?"),
+            Err(nom::Err::Error(IMAPParseError {
+                kind: IMAPErrorKind::ZeroNotAllowed,
+                ..
+            }))
+        ));
 
         let (rem, val) = seq_number(b"1?").unwrap();
         println!("{:?}, {:?}", rem, val);
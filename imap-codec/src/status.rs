@@ -8,6 +8,12 @@ use nom::{
     sequence::tuple,
 };
 
+#[cfg(feature = "ext_condstore_qresync")]
+use crate::extensions::condstore_qresync::status_att_val_highestmodseq;
+#[cfg(feature = "ext_objectid")]
+use crate::extensions::objectid::status_att_val_mailboxid;
+#[cfg(feature = "ext_status_size")]
+use crate::extensions::status_size::{status_att_val_appendlimit, status_att_val_size};
 use crate::{
     core::{number, number64, nz_number},
     decode::IMAPResult,
@@ -17,8 +23,11 @@ use crate::{
 ///               "RECENT" /
 ///               "UIDNEXT" /
 ///               "UIDVALIDITY" /
-///               "UNSEEN"`
-pub(crate) fn status_att(input: &[u8]) -> IMAPResult<&[u8], StatusDataItemName> {
+///               "UNSEEN" /
+///               "MAILBOXID" /   ; RFC 8474
+///               "SIZE" /        ; RFC 8438
+///               "APPENDLIMIT"`  ; RFC 7889
+pub(crate) fn status_att(input: &[u8]) -> IMAPResult<'_, &[u8], StatusDataItemName> {
     alt((
         value(StatusDataItemName::Messages, tag_no_case(b"MESSAGES")),
         value(StatusDataItemName::Recent, tag_no_case(b"RECENT")),
@@ -35,13 +44,19 @@ pub(crate) fn status_att(input: &[u8]) -> IMAPResult<&[u8], StatusDataItemName>
             StatusDataItemName::HighestModSeq,
             tag_no_case(b"HIGHESTMODSEQ"),
         ),
+        #[cfg(feature = "ext_objectid")]
+        value(StatusDataItemName::MailboxId, tag_no_case(b"MAILBOXID")),
+        #[cfg(feature = "ext_status_size")]
+        value(StatusDataItemName::Size, tag_no_case(b"SIZE")),
+        #[cfg(feature = "ext_status_size")]
+        value(StatusDataItemName::AppendLimit, tag_no_case(b"APPENDLIMIT")),
     ))(input)
 }
 
 /// `status-att-list = status-att-val *(SP status-att-val)`
 ///
 /// Note: See errata id: 261
-pub(crate) fn status_att_list(input: &[u8]) -> IMAPResult<&[u8], Vec<StatusDataItem>> {
+pub(crate) fn status_att_list(input: &[u8]) -> IMAPResult<'_, &[u8], Vec<StatusDataItem>> {
     separated_list1(sp, status_att_val)(input)
 }
 
@@ -49,10 +64,14 @@ pub(crate) fn status_att_list(input: &[u8]) -> IMAPResult<&[u8], Vec<StatusDataI
 ///                    ("RECENT" SP number) /
 ///                    ("UIDNEXT" SP nz-number) /
 ///                    ("UIDVALIDITY" SP nz-number) /
-///                    ("UNSEEN" SP number)`
+///                    ("UNSEEN" SP number) /
+///                    ("HIGHESTMODSEQ" SP mod-sequence-value) / ; RFC 7162
+///                    ("MAILBOXID" SP "(" objectid ")") /  ; RFC 8474
+///                    ("SIZE" SP number64) /               ; RFC 8438
+///                    ("APPENDLIMIT" SP (number / nil))`   ; RFC 7889
 ///
 /// Note: See errata id: 261
-fn status_att_val(input: &[u8]) -> IMAPResult<&[u8], StatusDataItem> {
+fn status_att_val(input: &[u8]) -> IMAPResult<'_, &[u8], StatusDataItem> {
     alt((
         map(
             tuple((tag_no_case(b"MESSAGES"), sp, number)),
@@ -82,6 +101,14 @@ fn status_att_val(input: &[u8]) -> IMAPResult<&[u8], StatusDataItem> {
             tuple((tag_no_case(b"DELETED"), sp, number)),
             |(_, _, num)| StatusDataItem::Deleted(num),
         ),
+        #[cfg(feature = "ext_condstore_qresync")]
+        status_att_val_highestmodseq,
+        #[cfg(feature = "ext_objectid")]
+        status_att_val_mailboxid,
+        #[cfg(feature = "ext_status_size")]
+        status_att_val_size,
+        #[cfg(feature = "ext_status_size")]
+        status_att_val_appendlimit,
     ))(input)
 }
 
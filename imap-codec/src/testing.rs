@@ -1,3 +1,25 @@
+//! # Known-answer tests
+//!
+//! Helpers to validate messages against their expected wire format.
+//!
+//! Every function asserts that the message encodes to the expected bytes *and* that the expected
+//! bytes decode back to the message. The functions `panic!` on mismatch and are meant to be used
+//! in tests.
+//!
+//! # Example
+//!
+//! ```rust
+//! use imap_codec::{
+//!     imap_types::command::{Command, CommandBody},
+//!     testing::kat_command,
+//! };
+//!
+//! let command = Command::new("A1", CommandBody::login("alice", "password").unwrap()).unwrap();
+//!
+//! kat_command(&command, b"A1 LOGIN alice password\r\n");
+//! ```
+
+#[cfg(test)]
 use std::fmt::Debug;
 
 use imap_types::{
@@ -9,33 +31,64 @@ use imap_types::{
 };
 
 use crate::{
-    decode::{Decoder, IMAPResult},
+    decode::Decoder, encode::Encoder, AuthenticateDataCodec, CommandCodec, GreetingCodec,
+    IdleDoneCodec, ResponseCodec,
+};
+#[cfg(test)]
+use crate::{
+    decode::IMAPResult,
     encode::{EncodeContext, EncodeIntoContext},
-    AuthenticateDataCodec, CommandCodec, GreetingCodec, IdleDoneCodec, ResponseCodec,
 };
 
+/// Compare encoded bytes and show both (escaped, encapsulated by `<<<` and `>>>`) on mismatch.
+fn assert_encoded(expected: &[u8], got: &[u8]) {
+    assert!(
+        expected == got,
+        "Encoding mismatch\nLeft:  <<<{}>>>\nRight: <<<{}>>>\nLeft:  {:02x?}\nRight: {:02x?}",
+        escape_byte_string(expected),
+        escape_byte_string(got),
+        expected,
+        got,
+    );
+}
+
+macro_rules! impl_kat {
+    ($fn_name:ident, $codec:ident, $message:ty) => {
+        #[doc = concat!("Assert that `message` encodes to `expected` (and back) using [`", stringify!($codec), "`].")]
+        pub fn $fn_name(message: &$message, expected: &[u8]) {
+            assert_encoded(expected, &$codec::default().encode(message).dump());
+
+            let (remaining, decoded) = $codec::default()
+                .decode(expected)
+                .expect("decoding failed");
+
+            assert!(
+                remaining.is_empty(),
+                "Decoding left remaining data: <<<{}>>>",
+                escape_byte_string(remaining)
+            );
+            assert_eq!(*message, decoded);
+        }
+    };
+}
+
+impl_kat! {kat_greeting, GreetingCodec, Greeting<'_>}
+impl_kat! {kat_command, CommandCodec, Command<'_>}
+impl_kat! {kat_response, ResponseCodec, Response<'_>}
+impl_kat! {kat_authenticate_data, AuthenticateDataCodec, AuthenticateData<'_>}
+impl_kat! {kat_idle_done, IdleDoneCodec, IdleDone}
+
+#[cfg(test)]
 pub(crate) fn known_answer_test_encode(
     (test_object, expected_bytes): (impl EncodeIntoContext, impl AsRef<[u8]>),
 ) {
-    let expected_bytes = expected_bytes.as_ref();
     let mut ctx = EncodeContext::new();
     test_object.encode_ctx(&mut ctx).unwrap();
 
-    let got_bytes = ctx.dump();
-    let got_bytes = got_bytes.as_slice();
-
-    if expected_bytes != got_bytes {
-        println!("# Debug (`escape_byte_string`, encapsulated by `<<<` and `>>>`)");
-        println!(
-            "Left:  <<<{}>>>\nRight: <<<{}>>>",
-            escape_byte_string(expected_bytes),
-            escape_byte_string(got_bytes),
-        );
-        println!("# Debug");
-        panic!("Left:  {:02x?}\nRight: {:02x?}", expected_bytes, got_bytes);
-    }
+    assert_encoded(expected_bytes.as_ref(), &ctx.dump());
 }
 
+#[cfg(test)]
 pub(crate) fn known_answer_test_parse<'a, O, P>(
     (test, expected_remainder, expected_object): (&'a [u8], &[u8], O),
     parser: P,
@@ -52,16 +105,15 @@ pub(crate) fn known_answer_test_parse<'a, O, P>(
 // we tried it and failed to provide a cleaner solution. Thus, it's a macro for now.
 macro_rules! impl_kat_inverse {
     ($fn_name:ident, $decoder:ident, $item:ty) => {
+        #[cfg(test)]
         pub(crate) fn $fn_name(tests: &[(&[u8], &[u8], $item)]) {
             for (no, (test_input, expected_remainder, expected_object)) in tests.iter().enumerate()
             {
-                println!("# {no}");
-
                 let (got_remainder, got_object) = $decoder::default()
                     .decode(test_input)
-                    .expect("first parsing failed");
-                assert_eq!(*expected_object, got_object);
-                assert_eq!(*expected_remainder, got_remainder);
+                    .unwrap_or_else(|_| panic!("# {no}: first parsing failed"));
+                assert_eq!(*expected_object, got_object, "# {no}");
+                assert_eq!(*expected_remainder, got_remainder, "# {no}");
 
                 let mut ctx = EncodeContext::new();
                 got_object.encode_ctx(&mut ctx).unwrap();
@@ -72,9 +124,9 @@ macro_rules! impl_kat_inverse {
                 // different lifetime.
                 let (got_remainder, got_object_again) = $decoder::default()
                     .decode(&got_output)
-                    .expect("second parsing failed");
-                assert_eq!(got_object, got_object_again);
-                assert!(got_remainder.is_empty());
+                    .unwrap_or_else(|_| panic!("# {no}: second parsing failed"));
+                assert_eq!(got_object, got_object_again, "# {no}");
+                assert!(got_remainder.is_empty(), "# {no}");
             }
         }
     };
@@ -89,7 +141,16 @@ impl_kat_inverse! {kat_inverse_done, IdleDoneCodec, IdleDone}
 
 #[cfg(test)]
 mod tests {
-    use imap_types::command::{Command, CommandBody};
+    use std::num::NonZeroU32;
+
+    use imap_types::{
+        command::CommandBody,
+        core::{AString, Vec1},
+        fetch::{MessageDataItemName, Section},
+        flag::{Flag, StoreResponse, StoreType},
+        response::{Code, CommandContinuationRequest, Data, Status},
+        status::StatusDataItemName,
+    };
 
     use super::*;
 
@@ -98,4 +159,151 @@ mod tests {
     fn test_known_answer_test_encode() {
         known_answer_test_encode((Command::new("A", CommandBody::Noop).unwrap(), b""));
     }
+
+    #[test]
+    fn test_kat_rfc3501_commands() {
+        let tests = [
+            (CommandBody::Capability, b"abcd CAPABILITY\r\n".as_ref()),
+            (CommandBody::Noop, b"abcd NOOP\r\n"),
+            (CommandBody::Logout, b"abcd LOGOUT\r\n"),
+            (
+                CommandBody::login("SMITH", "SESAME").unwrap(),
+                b"abcd LOGIN SMITH SESAME\r\n",
+            ),
+            (
+                CommandBody::select("INBOX").unwrap(),
+                b"abcd SELECT INBOX\r\n",
+            ),
+            (
+                CommandBody::examine("blurdybloop").unwrap(),
+                b"abcd EXAMINE blurdybloop\r\n",
+            ),
+            (
+                CommandBody::create("owatagusiam/").unwrap(),
+                b"abcd CREATE owatagusiam/\r\n",
+            ),
+            (
+                CommandBody::status(
+                    "blurdybloop",
+                    vec![StatusDataItemName::UidNext, StatusDataItemName::Messages],
+                )
+                .unwrap(),
+                b"abcd STATUS blurdybloop (UIDNEXT MESSAGES)\r\n",
+            ),
+            (
+                CommandBody::fetch(
+                    "2:4",
+                    vec![
+                        MessageDataItemName::Flags,
+                        MessageDataItemName::BodyExt {
+                            section: Some(Section::HeaderFields(
+                                None,
+                                Vec1::try_from(vec![
+                                    AString::try_from("DATE").unwrap(),
+                                    AString::try_from("FROM").unwrap(),
+                                ])
+                                .unwrap(),
+                            )),
+                            partial: None,
+                            peek: false,
+                        },
+                    ],
+                    false,
+                )
+                .unwrap(),
+                b"abcd FETCH 2:4 (FLAGS BODY[HEADER.FIELDS (DATE FROM)])\r\n",
+            ),
+            (
+                CommandBody::store(
+                    "2:4",
+                    StoreType::Add,
+                    StoreResponse::Answer,
+                    vec![Flag::Deleted],
+                    false,
+                )
+                .unwrap(),
+                b"abcd STORE 2:4 +FLAGS (\\Deleted)\r\n",
+            ),
+        ];
+
+        for (body, expected) in tests {
+            kat_command(&body.tag("abcd").unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_kat_rfc3501_responses() {
+        kat_greeting(
+            &Greeting::ok(None, "IMAP4rev1 Service Ready").unwrap(),
+            b"* OK IMAP4rev1 Service Ready\r\n",
+        );
+
+        let tests = [
+            (
+                Response::Data(Data::Exists(172)),
+                b"* 172 EXISTS\r\n".as_ref(),
+            ),
+            (Response::Data(Data::Recent(1)), b"* 1 RECENT\r\n"),
+            (
+                Response::Data(Data::Search(vec![
+                    NonZeroU32::new(2).unwrap(),
+                    NonZeroU32::new(84).unwrap(),
+                    NonZeroU32::new(882).unwrap(),
+                ])),
+                b"* SEARCH 2 84 882\r\n",
+            ),
+            (
+                Response::Data(Data::Flags(vec![
+                    Flag::Answered,
+                    Flag::Flagged,
+                    Flag::Deleted,
+                    Flag::Seen,
+                    Flag::Draft,
+                ])),
+                b"* FLAGS (\\Answered \\Flagged \\Deleted \\Seen \\Draft)\r\n",
+            ),
+            (
+                Response::Status(
+                    Status::ok(
+                        None,
+                        Some(Code::Unseen(NonZeroU32::new(12).unwrap())),
+                        "Message 12 is first unseen",
+                    )
+                    .unwrap(),
+                ),
+                b"* OK [UNSEEN 12] Message 12 is first unseen\r\n",
+            ),
+            (
+                Response::Status(
+                    Status::ok(
+                        Some("A142".try_into().unwrap()),
+                        Some(Code::ReadWrite),
+                        "SELECT completed",
+                    )
+                    .unwrap(),
+                ),
+                b"A142 OK [READ-WRITE] SELECT completed\r\n",
+            ),
+            (
+                Response::CommandContinuationRequest(
+                    CommandContinuationRequest::basic(None, "Ready for additional command text")
+                        .unwrap(),
+                ),
+                b"+ Ready for additional command text\r\n",
+            ),
+        ];
+
+        for (response, expected) in tests {
+            kat_response(&response, expected);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_kat_mismatch() {
+        kat_command(
+            &Command::new("A", CommandBody::Noop).unwrap(),
+            b"A CAPABILITY\r\n",
+        );
+    }
 }
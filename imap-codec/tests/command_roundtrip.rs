@@ -0,0 +1,287 @@
+//! Round-trip every `CommandBody` variant through the `CommandCodec`.
+//!
+//! Every command is decoded, re-encoded, and decoded again. Both decoded commands must be equal.
+//! The test also checks that every variant is covered.
+
+use std::collections::BTreeSet;
+
+use imap_codec::{
+    decode::Decoder,
+    encode::Encoder,
+    imap_types::{command::CommandBody, utils::escape_byte_string},
+    CommandCodec,
+};
+
+/// Name of a variant.
+///
+/// Note: This `match` is exhaustive on purpose. Adding a variant to `CommandBody` makes this test
+/// fail to compile until the variant is covered below.
+fn variant_name(body: &CommandBody) -> &'static str {
+    match body {
+        CommandBody::Capability => "Capability",
+        CommandBody::Noop => "Noop",
+        CommandBody::Logout => "Logout",
+        #[cfg(feature = "starttls")]
+        CommandBody::StartTLS => "StartTLS",
+        CommandBody::Authenticate { .. } => "Authenticate",
+        CommandBody::Login { .. } => "Login",
+        CommandBody::Select { .. } => "Select",
+        CommandBody::Unselect => "Unselect",
+        CommandBody::Examine { .. } => "Examine",
+        CommandBody::Create { .. } => "Create",
+        #[cfg(feature = "ext_special_use")]
+        CommandBody::CreateSpecialUse { .. } => "CreateSpecialUse",
+        CommandBody::Delete { .. } => "Delete",
+        CommandBody::Rename { .. } => "Rename",
+        CommandBody::Subscribe { .. } => "Subscribe",
+        CommandBody::Unsubscribe { .. } => "Unsubscribe",
+        CommandBody::List { .. } => "List",
+        #[cfg(feature = "ext_list_extended")]
+        CommandBody::ListExtended { .. } => "ListExtended",
+        CommandBody::Lsub { .. } => "Lsub",
+        CommandBody::Status { .. } => "Status",
+        CommandBody::Append { .. } => "Append",
+        #[cfg(feature = "ext_multiappend")]
+        CommandBody::MultiAppend { .. } => "MultiAppend",
+        CommandBody::Check => "Check",
+        CommandBody::Close => "Close",
+        CommandBody::Expunge => "Expunge",
+        CommandBody::Search { .. } => "Search",
+        #[cfg(feature = "ext_esearch")]
+        CommandBody::SearchExtended { .. } => "SearchExtended",
+        #[cfg(feature = "ext_sort_thread")]
+        CommandBody::Sort { .. } => "Sort",
+        #[cfg(feature = "ext_sort_thread")]
+        CommandBody::Thread { .. } => "Thread",
+        CommandBody::Fetch { .. } => "Fetch",
+        #[cfg(feature = "ext_condstore_qresync")]
+        CommandBody::FetchWithModifiers { .. } => "FetchWithModifiers",
+        CommandBody::Store { .. } => "Store",
+        #[cfg(feature = "ext_condstore_qresync")]
+        CommandBody::StoreWithModifiers { .. } => "StoreWithModifiers",
+        CommandBody::Copy { .. } => "Copy",
+        CommandBody::Idle => "Idle",
+        CommandBody::Enable { .. } => "Enable",
+        CommandBody::Compress { .. } => "Compress",
+        CommandBody::GetQuota { .. } => "GetQuota",
+        CommandBody::GetQuotaRoot { .. } => "GetQuotaRoot",
+        CommandBody::SetQuota { .. } => "SetQuota",
+        CommandBody::Move { .. } => "Move",
+        #[cfg(feature = "ext_id")]
+        CommandBody::Id { .. } => "Id",
+        #[cfg(feature = "ext_metadata")]
+        CommandBody::SetMetadata { .. } => "SetMetadata",
+        #[cfg(feature = "ext_metadata")]
+        CommandBody::GetMetadata { .. } => "GetMetadata",
+        #[cfg(feature = "ext_namespace")]
+        CommandBody::Namespace => "Namespace",
+        #[cfg(feature = "ext_acl")]
+        CommandBody::SetAcl { .. } => "SetAcl",
+        #[cfg(feature = "ext_acl")]
+        CommandBody::DeleteAcl { .. } => "DeleteAcl",
+        #[cfg(feature = "ext_acl")]
+        CommandBody::GetAcl { .. } => "GetAcl",
+        #[cfg(feature = "ext_acl")]
+        CommandBody::ListRights { .. } => "ListRights",
+        #[cfg(feature = "ext_acl")]
+        CommandBody::MyRights { .. } => "MyRights",
+        #[cfg(feature = "ext_unauthenticate")]
+        CommandBody::Unauthenticate => "Unauthenticate",
+    }
+}
+
+/// Number of variants in the current feature configuration.
+fn variant_count() -> usize {
+    let mut count = 31;
+
+    if cfg!(feature = "starttls") {
+        count += 1;
+    }
+    if cfg!(feature = "ext_sort_thread") {
+        count += 2;
+    }
+    if cfg!(feature = "ext_id") {
+        count += 1;
+    }
+    if cfg!(feature = "ext_metadata") {
+        count += 2;
+    }
+    if cfg!(feature = "ext_namespace") {
+        count += 1;
+    }
+    if cfg!(feature = "ext_acl") {
+        count += 5;
+    }
+    if cfg!(feature = "ext_unauthenticate") {
+        count += 1;
+    }
+    if cfg!(feature = "ext_list_extended") {
+        count += 1;
+    }
+    if cfg!(feature = "ext_multiappend") {
+        count += 1;
+    }
+    if cfg!(feature = "ext_condstore_qresync") {
+        count += 2;
+    }
+    if cfg!(feature = "ext_esearch") {
+        count += 1;
+    }
+    if cfg!(feature = "ext_special_use") {
+        count += 1;
+    }
+
+    count
+}
+
+#[test]
+fn test_command_body_roundtrip() {
+    #[allow(unused_mut)]
+    let mut tests: Vec<&[u8]> = vec![
+        b"A CAPABILITY\r\n",
+        b"A NOOP\r\n",
+        b"A LOGOUT\r\n",
+        b"A AUTHENTICATE PLAIN\r\n",
+        b"A AUTHENTICATE PLAIN AGFsaWNlAHBhc3N3b3Jk\r\n",
+        b"A AUTHENTICATE PLAIN =\r\n",
+        b"A LOGIN alice \"pass word\"\r\n",
+        b"A LOGIN {5}\r\nalice {8}\r\npassword\r\n",
+        b"A SELECT INBOX\r\n",
+        b"A UNSELECT\r\n",
+        b"A EXAMINE \"Sent Items\"\r\n",
+        b"A CREATE foo/bar\r\n",
+        b"A DELETE foo/bar\r\n",
+        b"A RENAME foo bar\r\n",
+        b"A SUBSCRIBE foo\r\n",
+        b"A UNSUBSCRIBE foo\r\n",
+        b"A LIST \"\" *\r\n",
+        b"A LIST foo/ %\r\n",
+        b"A LSUB \"\" *\r\n",
+        b"A STATUS INBOX (MESSAGES RECENT UIDNEXT UIDVALIDITY UNSEEN)\r\n",
+        b"A APPEND INBOX {5}\r\nhello\r\n",
+        b"A APPEND INBOX (\\Seen $Important) \"14-Jul-2023 02:44:25 +0200\" {5}\r\nhello\r\n",
+        b"A CHECK\r\n",
+        b"A CLOSE\r\n",
+        b"A EXPUNGE\r\n",
+        b"A SEARCH ALL\r\n",
+        b"A SEARCH CHARSET UTF-8 OR SEEN (FLAGGED NOT DELETED) SUBJECT foo\r\n",
+        b"A UID SEARCH UID 1:* SINCE 1-Feb-1994\r\n",
+        b"A FETCH 1:* ALL\r\n",
+        b"A FETCH 1 (BODY[HEADER.FIELDS (DATE FROM)]<0.2048> BODY.PEEK[1.2.MIME] RFC822.SIZE)\r\n",
+        b"A UID FETCH 1,3:5 (UID FLAGS BODYSTRUCTURE ENVELOPE)\r\n",
+        b"A STORE 1 +FLAGS (\\Deleted)\r\n",
+        b"A UID STORE 1:5 -FLAGS.SILENT (\\Seen keyword)\r\n",
+        b"A COPY 1:3 Archive\r\n",
+        b"A UID COPY 1:3 Archive\r\n",
+        b"A IDLE\r\n",
+        b"A ENABLE UTF8=ACCEPT\r\n",
+        b"A COMPRESS DEFLATE\r\n",
+        b"A GETQUOTA \"\"\r\n",
+        b"A GETQUOTAROOT INBOX\r\n",
+        b"A SETQUOTA \"\" (STORAGE 512 MESSAGE 1000)\r\n",
+        b"A MOVE 1:3 Archive\r\n",
+        b"A UID MOVE 1:3 Archive\r\n",
+    ];
+
+    #[cfg(feature = "starttls")]
+    tests.push(b"A STARTTLS\r\n");
+
+    #[cfg(feature = "ext_sort_thread")]
+    tests.extend_from_slice(&[
+        b"A SORT (SUBJECT REVERSE DATE) UTF-8 ALL\r\n".as_ref(),
+        b"A UID SORT (ARRIVAL) US-ASCII SEEN\r\n",
+        b"A THREAD REFERENCES UTF-8 ALL\r\n",
+        b"A UID THREAD ORDEREDSUBJECT UTF-8 SINCE 5-MAR-2000\r\n",
+    ]);
+
+    #[cfg(feature = "ext_id")]
+    tests.extend_from_slice(&[
+        b"A ID NIL\r\n".as_ref(),
+        b"A ID (\"name\" \"imap-codec\" \"version\" NIL)\r\n",
+    ]);
+
+    #[cfg(feature = "ext_metadata")]
+    tests.extend_from_slice(&[
+        b"A SETMETADATA INBOX (/private/comment \"My comment\")\r\n".as_ref(),
+        b"A SETMETADATA INBOX (/private/comment NIL)\r\n",
+        b"A GETMETADATA INBOX /private/comment\r\n",
+        b"A GETMETADATA (MAXSIZE 1024 DEPTH 1) INBOX (/shared/comment /private/comment)\r\n",
+    ]);
+
+    #[cfg(feature = "ext_acl")]
+    tests.extend_from_slice(&[
+        b"A SETACL INBOX Fred +rw\r\n".as_ref(),
+        b"A SETACL INBOX \"Fred Smith\" \"\"\r\n",
+        b"A DELETEACL INBOX Fred\r\n",
+        b"A GETACL INBOX\r\n",
+        b"A LISTRIGHTS INBOX Fred\r\n",
+        b"A MYRIGHTS INBOX\r\n",
+    ]);
+
+    #[cfg(feature = "ext_binary")]
+    tests.push(b"A APPEND INBOX ~{5}\r\nhello\r\n");
+
+    #[cfg(feature = "ext_condstore_qresync")]
+    tests.extend_from_slice(&[
+        b"A FETCH 1:* (FLAGS MODSEQ) (CHANGEDSINCE 12345)\r\n".as_ref(),
+        b"A UID STORE 1:3 (UNCHANGEDSINCE 0) +FLAGS (\\Seen)\r\n",
+    ]);
+
+    #[cfg(feature = "ext_namespace")]
+    tests.push(b"A NAMESPACE\r\n");
+
+    #[cfg(feature = "ext_esearch")]
+    tests.extend_from_slice(&[
+        b"A SEARCH RETURN (MIN MAX COUNT) ALL\r\n".as_ref(),
+        b"A UID SEARCH RETURN (ALL) CHARSET UTF-8 SEEN\r\n",
+    ]);
+
+    #[cfg(feature = "ext_list_extended")]
+    tests.extend_from_slice(&[
+        b"A LIST (SUBSCRIBED RECURSIVEMATCH) \"\" (INBOX %) RETURN (CHILDREN)\r\n".as_ref(),
+        b"A LIST \"\" * RETURN (SUBSCRIBED)\r\n",
+    ]);
+
+    #[cfg(feature = "ext_special_use")]
+    tests.push(b"A CREATE MySpecial (USE (\\Drafts \\Sent))\r\n");
+
+    #[cfg(feature = "ext_multiappend")]
+    tests.push(b"A APPEND INBOX (\\Seen) {5}\r\nhello {5}\r\nworld\r\n");
+
+    #[cfg(feature = "ext_searchres")]
+    tests.extend_from_slice(&[
+        b"A SEARCH RETURN (SAVE) ALL\r\n".as_ref(),
+        b"A UID FETCH $ (FLAGS)\r\n",
+        b"A COPY $,5:9 Archive\r\n",
+    ]);
+
+    #[cfg(feature = "ext_unauthenticate")]
+    tests.push(b"A UNAUTHENTICATE\r\n");
+
+    let mut covered = BTreeSet::new();
+    let mut failures = Vec::new();
+
+    for test in tests {
+        let (rem, parsed) = CommandCodec::default()
+            .decode(test)
+            .unwrap_or_else(|error| panic!("{}: {:?}", escape_byte_string(test), error));
+        assert!(rem.is_empty(), "{}", escape_byte_string(test));
+
+        covered.insert(variant_name(&parsed.body));
+
+        let encoded = CommandCodec::default().encode(&parsed).dump();
+
+        match CommandCodec::default().decode(&encoded) {
+            Ok((rem, reparsed)) if rem.is_empty() && reparsed == parsed => {}
+            result => failures.push(format!(
+                "{} -> {} -> {:?}",
+                escape_byte_string(test),
+                escape_byte_string(&encoded),
+                result
+            )),
+        }
+    }
+
+    assert!(failures.is_empty(), "{:#?}", failures);
+    assert_eq!(covered.len(), variant_count(), "covered: {:?}", covered);
+}
@@ -15,8 +15,8 @@ use crate::{
     flag::{Flag, FlagNameAttribute},
     mailbox::{ListCharString, Mailbox, MailboxOther},
     response::{
-        Bye, Capability, Code, CodeOther, CommandContinuationRequestBasic, Greeting, GreetingKind,
-        Status, StatusBody, StatusKind, Tagged,
+        Bye, Capability, Code, CodeOther, CodeOtherValue, CommandContinuationRequestBasic,
+        Greeting, GreetingKind, Status, StatusBody, StatusKind, Tagged,
     },
     search::SearchKey,
     sequence::SequenceSet,
@@ -37,6 +37,11 @@ macro_rules! impl_arbitrary_try_from {
     };
 }
 
+#[cfg(any(
+    feature = "ext_sort_thread",
+    feature = "ext_acl",
+    feature = "ext_objectid"
+))]
 pub(crate) use impl_arbitrary_try_from;
 
 macro_rules! impl_arbitrary_try_from_t {
@@ -60,6 +65,7 @@ impl_arbitrary_try_from! { AtomExt<'a>, &str }
 impl_arbitrary_try_from! { Quoted<'a>, &str }
 impl_arbitrary_try_from! { Tag<'a>, &str }
 impl_arbitrary_try_from! { Text<'a>, &str }
+impl_arbitrary_try_from! { CodeOtherValue<'a>, &str }
 impl_arbitrary_try_from! { ListCharString<'a>, &str }
 impl_arbitrary_try_from! { QuotedChar, char }
 impl_arbitrary_try_from! { Mailbox<'a>, &str }
@@ -178,6 +184,14 @@ impl<'a> Arbitrary<'a> for CodeOther<'a> {
     }
 }
 
+/// Generates names for [`Code::OtherParsed`].
+///
+/// Names are prefixed with "X" so that they can't collide with a known code.
+pub(crate) fn arbitrary_code_name<'a>(u: &mut Unstructured<'a>) -> arbitrary::Result<Atom<'a>> {
+    Atom::try_from(format!("X{}", Atom::arbitrary(u)?.inner()))
+        .map_err(|_| arbitrary::Error::IncorrectFormat)
+}
+
 impl<'a> Arbitrary<'a> for SearchKey<'a> {
     fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
         #[cfg(not(feature = "arbitrary_simplified"))]
@@ -14,6 +14,7 @@ use bounded_static::ToStatic;
 use serde::{Deserialize, Serialize};
 
 use crate::{
+    auth::error::PlainError,
     core::{impl_try_from, Atom},
     error::ValidationError,
     secret::Secret,
@@ -210,6 +211,63 @@ impl<'a> AuthenticateData<'a> {
     {
         Self::Continue(Secret::new(data.into()))
     }
+
+    /// Construct the initial response of the SASL PLAIN mechanism (see RFC 4616).
+    ///
+    /// The data is `[authzid] NUL authcid NUL password` and will be BASE64-encoded during
+    /// encoding. It can be used as continuation or as initial response (SASL-IR).
+    ///
+    /// # Errors
+    ///
+    /// Fails when `authzid`, `authcid`, or `password` contains a NUL, because NUL separates the
+    /// fields.
+    pub fn plain(
+        authzid: Option<&str>,
+        authcid: &str,
+        password: Secret<&str>,
+    ) -> Result<Self, PlainError> {
+        let authzid = authzid.unwrap_or_default();
+        let password = password.declassify();
+
+        if let Some(at) = authzid.bytes().position(|b| b == 0x00) {
+            return Err(PlainError::Authzid { at });
+        }
+
+        if let Some(at) = authcid.bytes().position(|b| b == 0x00) {
+            return Err(PlainError::Authcid { at });
+        }
+
+        if password.bytes().any(|b| b == 0x00) {
+            return Err(PlainError::Password);
+        }
+
+        let mut data = Vec::with_capacity(authzid.len() + authcid.len() + password.len() + 2);
+        data.extend_from_slice(authzid.as_bytes());
+        data.push(0x00);
+        data.extend_from_slice(authcid.as_bytes());
+        data.push(0x00);
+        data.extend_from_slice(password.as_bytes());
+
+        Ok(Self::r#continue(data))
+    }
+}
+
+/// Error-related types.
+pub mod error {
+    use thiserror::Error;
+
+    /// Error during construction of SASL PLAIN data (see
+    /// [`AuthenticateData::plain`](super::AuthenticateData::plain)).
+    #[derive(Clone, Debug, Eq, Error, Hash, Ord, PartialEq, PartialOrd)]
+    pub enum PlainError {
+        #[error("Authorization identity must not contain NUL (at {at})")]
+        Authzid { at: usize },
+        #[error("Authentication identity must not contain NUL (at {at})")]
+        Authcid { at: usize },
+        /// Note: The position is not reported to not leak information about the password.
+        #[error("Password must not contain NUL")]
+        Password,
+    }
 }
 
 #[cfg(test)]
@@ -225,4 +283,31 @@ mod tests {
         assert!(AuthMechanism::try_from("xxxlogin").is_ok());
         assert!(AuthMechanism::try_from("xxxxoauth2").is_ok());
     }
+
+    #[test]
+    fn test_authenticate_data_plain() {
+        assert_eq!(
+            AuthenticateData::plain(None, "alice", Secret::new("pass")),
+            Ok(AuthenticateData::r#continue(b"\x00alice\x00pass".as_ref()))
+        );
+        assert_eq!(
+            AuthenticateData::plain(Some("admin"), "alice", Secret::new("pass")),
+            Ok(AuthenticateData::r#continue(
+                b"admin\x00alice\x00pass".as_ref()
+            ))
+        );
+
+        assert_eq!(
+            AuthenticateData::plain(Some("ad\0min"), "alice", Secret::new("pass")),
+            Err(PlainError::Authzid { at: 2 })
+        );
+        assert_eq!(
+            AuthenticateData::plain(None, "\0alice", Secret::new("pass")),
+            Err(PlainError::Authcid { at: 0 })
+        );
+        assert_eq!(
+            AuthenticateData::plain(None, "alice", Secret::new("pa\0ss")),
+            Err(PlainError::Password)
+        );
+    }
 }
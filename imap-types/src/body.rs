@@ -8,7 +8,7 @@ use bounded_static::ToStatic;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    core::{IString, NString, Vec1},
+    core::{IString, NString, Vec1, VecN},
     envelope::Envelope,
 };
 
@@ -251,6 +251,48 @@ pub enum BodyStructure<'a> {
     },
 }
 
+impl<'a> BodyStructure<'a> {
+    /// Returns the non-extensible form of this body structure, i.e., without extension data.
+    ///
+    /// This is the form used in a `BODY` response (as opposed to a `BODYSTRUCTURE` response.)
+    pub fn without_extension_data(&self) -> Self {
+        match self {
+            Self::Single { body, .. } => Self::Single {
+                body: Body {
+                    basic: body.basic.clone(),
+                    specific: match &body.specific {
+                        SpecificFields::Message {
+                            envelope,
+                            body_structure,
+                            number_of_lines,
+                        } => SpecificFields::Message {
+                            envelope: envelope.clone(),
+                            body_structure: Box::new(body_structure.without_extension_data()),
+                            number_of_lines: *number_of_lines,
+                        },
+                        other => other.clone(),
+                    },
+                },
+                extension_data: None,
+            },
+            Self::Multi {
+                bodies, subtype, ..
+            } => Self::Multi {
+                // Safety: Mapping a `Vec1` can't produce an empty `Vec`.
+                bodies: VecN(
+                    bodies
+                        .as_ref()
+                        .iter()
+                        .map(BodyStructure::without_extension_data)
+                        .collect(),
+                ),
+                subtype: subtype.clone(),
+                extension_data: None,
+            },
+        }
+    }
+}
+
 /// The extension data of a non-multipart body part.
 #[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
 #[cfg_attr(feature = "bounded-static", derive(ToStatic))]
@@ -264,6 +306,51 @@ pub struct SinglePartExtensionData<'a> {
     pub tail: Option<Disposition<'a>>,
 }
 
+impl<'a> SinglePartExtensionData<'a> {
+    /// Returns `true` when the body MD5 value is present, i.e., not `NIL`.
+    pub fn has_md5(&self) -> bool {
+        self.md5.0.is_some()
+    }
+
+    /// Returns `true` when the body disposition is present, i.e., not `NIL`.
+    pub fn has_disposition(&self) -> bool {
+        self.disposition().is_some()
+    }
+
+    /// Return the body MD5 value (if present).
+    pub fn md5(&self) -> Option<&[u8]> {
+        self.md5.0.as_ref().map(AsRef::as_ref)
+    }
+
+    /// Return the body disposition type and its attribute/value pairs (if present).
+    pub fn disposition(&self) -> Option<(&IString<'a>, &[(IString<'a>, IString<'a>)])> {
+        self.tail
+            .as_ref()?
+            .disposition
+            .as_ref()
+            .map(|(kind, params)| (kind, params.as_slice()))
+    }
+
+    /// Return the body language (if present).
+    pub fn language(&self) -> Option<&[IString<'a>]> {
+        Some(self.tail.as_ref()?.tail.as_ref()?.language.as_slice())
+    }
+
+    /// Return the body location (if present).
+    pub fn location(&self) -> Option<&[u8]> {
+        self.tail
+            .as_ref()?
+            .tail
+            .as_ref()?
+            .tail
+            .as_ref()?
+            .location
+            .0
+            .as_ref()
+            .map(AsRef::as_ref)
+    }
+}
+
 /// The extension data of a multipart body part.
 ///
 /// # Trace (not in RFC)
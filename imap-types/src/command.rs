@@ -13,26 +13,45 @@ use serde::{Deserialize, Serialize};
 
 #[cfg(feature = "ext_id")]
 use crate::core::{IString, NString};
+#[cfg(feature = "ext_acl")]
+use crate::extensions::acl::AclRights;
 #[cfg(feature = "ext_binary")]
 use crate::extensions::binary::LiteralOrLiteral8;
+#[cfg(feature = "ext_condstore_qresync")]
+use crate::extensions::condstore_qresync::{FetchModifier, StoreModifier};
+#[cfg(feature = "ext_esearch")]
+use crate::extensions::esearch::SearchReturnOption;
+#[cfg(feature = "ext_list_extended")]
+use crate::extensions::list_extended::{ListReturnOption, ListSelectionOption, MailboxPatterns};
 #[cfg(feature = "ext_metadata")]
 use crate::extensions::metadata::{Entry, EntryValue, GetMetadataOption};
 #[cfg(feature = "ext_sort_thread")]
 use crate::extensions::{sort::SortCriterion, thread::ThreadingAlgorithm};
+#[cfg(feature = "ext_special_use")]
+use crate::flag::FlagNameAttribute;
 use crate::{
     auth::AuthMechanism,
-    command::error::{AppendError, CopyError, ListError, LoginError, RenameError},
-    core::{AString, Charset, Literal, Tag, Vec1},
+    command::error::{
+        AppendError, AuthenticateError, CopyError, ListError, LoginError, RenameError,
+    },
+    core::{AString, Charset, Literal, Tag, TagGenerator, Vec1},
     datetime::DateTime,
-    extensions::{compress::CompressionAlgorithm, enable::CapabilityEnable, quota::QuotaSet},
-    fetch::MacroOrMessageDataItemNames,
+    extensions::{
+        compress::CompressionAlgorithm,
+        enable::CapabilityEnable,
+        quota::{QuotaRoot, QuotaSet},
+    },
+    fetch::{MacroOrMessageDataItemNames, MessageDataItemName},
     flag::{Flag, StoreResponse, StoreType},
     mailbox::{ListMailbox, Mailbox},
+    response::Capability,
     search::SearchKey,
     secret::Secret,
-    sequence::SequenceSet,
+    sequence::{SeqOrUid, Sequence, SequenceSet},
     status::StatusDataItemName,
 };
+#[cfg(feature = "ext_multiappend")]
+use crate::{core::Vec2, extensions::multiappend::AppendMessage};
 
 /// Command.
 #[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
@@ -64,6 +83,85 @@ impl<'a> Command<'a> {
     }
 }
 
+#[cfg(feature = "bounded-static")]
+impl<'a> Command<'a> {
+    /// Convert into an owned command.
+    ///
+    /// This is a shortcut for [`IntoBoundedStatic::into_static`](bounded_static::IntoBoundedStatic::into_static)
+    /// that doesn't require the trait to be in scope.
+    ///
+    /// ```
+    /// use imap_types::command::{Command, CommandBody};
+    ///
+    /// let tag = String::from("A1");
+    /// let command: Command<'static> = Command::new(tag.as_str(), CommandBody::Noop)
+    ///     .unwrap()
+    ///     .into_static();
+    /// drop(tag);
+    ///
+    /// assert_eq!(command.tag.as_ref(), "A1");
+    /// ```
+    pub fn into_static(self) -> Command<'static> {
+        bounded_static::IntoBoundedStatic::into_static(self)
+    }
+}
+
+/// Keepalive helper that emits a freshly-tagged command, e.g., NOOP, on every call.
+///
+/// ```
+/// use imap_types::{
+///     command::{CommandBody, Keepalive},
+///     core::TagGenerator,
+/// };
+///
+/// let mut keepalive = Keepalive::new(TagGenerator::new());
+///
+/// let first = keepalive.next_command();
+/// let second = keepalive.next_command();
+///
+/// assert_eq!(first.body, CommandBody::Noop);
+/// assert_ne!(first.tag, second.tag);
+/// ```
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct Keepalive {
+    generator: TagGenerator,
+    command: KeepaliveCommand,
+}
+
+impl Keepalive {
+    /// Create a keepalive that emits NOOP.
+    pub fn new(generator: TagGenerator) -> Self {
+        Self::with_command(generator, KeepaliveCommand::Noop)
+    }
+
+    /// Create a keepalive that emits the given command.
+    pub fn with_command(generator: TagGenerator, command: KeepaliveCommand) -> Self {
+        Self { generator, command }
+    }
+
+    /// Return the next command with a fresh tag.
+    pub fn next_command(&mut self) -> Command<'static> {
+        let body = match self.command {
+            KeepaliveCommand::Noop => CommandBody::Noop,
+            KeepaliveCommand::Idle => CommandBody::Idle,
+        };
+
+        Command {
+            tag: self.generator.generate(),
+            body,
+        }
+    }
+}
+
+/// Command used by [`Keepalive`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeepaliveCommand {
+    /// `NOOP`.
+    Noop,
+    /// `IDLE`, i.e., refresh IDLE after `DONE` was sent.
+    Idle,
+}
+
 /// Command body.
 ///
 /// This enum is used to encode all the different commands.
@@ -177,9 +275,9 @@ pub enum CommandBody<'a> {
     /// * Result:
     ///   * OK - authenticate completed, now in authenticated state
     ///   * NO - authenticate failure: unsupported authentication
-    ///          mechanism, credentials rejected
+    ///     mechanism, credentials rejected
     ///   * BAD - command unknown or arguments invalid,
-    ///           authentication exchange cancelled
+    ///     authentication exchange cancelled
     ///
     /// The AUTHENTICATE command indicates a \[SASL\] authentication
     /// mechanism to the server.  If the server supports the requested
@@ -471,6 +569,20 @@ pub enum CommandBody<'a> {
         mailbox: Mailbox<'a>,
     },
 
+    /// CREATE command with special-use attributes, i.e., `(USE (...))` (RFC 6154).
+    ///
+    /// A CREATE command without the `USE` parameter is a [`CommandBody::Create`].
+    ///
+    /// Note: Use this only when the server advertised [`Capability::CreateSpecialUse`](crate::response::Capability::CreateSpecialUse).
+    #[cfg(feature = "ext_special_use")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext_special_use")))]
+    CreateSpecialUse {
+        /// Mailbox.
+        mailbox: Mailbox<'a>,
+        /// Special-use attributes of the new mailbox.
+        use_attributes: Vec<FlagNameAttribute<'a>>,
+    },
+
     /// 6.3.4.  DELETE Command
     ///
     /// Arguments:  mailbox name
@@ -739,6 +851,24 @@ pub enum CommandBody<'a> {
         mailbox_wildcard: ListMailbox<'a>,
     },
 
+    /// LIST command with selection options, multiple mailbox patterns, or return options (RFC 5258).
+    ///
+    /// A LIST command without any of these is a [`CommandBody::List`].
+    ///
+    /// Note: Use this only when the server advertised [`Capability::ListExtended`](crate::response::Capability::ListExtended).
+    #[cfg(feature = "ext_list_extended")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext_list_extended")))]
+    ListExtended {
+        /// Selection options, e.g., `SUBSCRIBED`.
+        selection_options: Vec<ListSelectionOption>,
+        /// Reference.
+        reference: Mailbox<'a>,
+        /// Mailbox pattern(s).
+        mailbox_patterns: MailboxPatterns<'a>,
+        /// Return options, e.g., `CHILDREN`.
+        return_options: Vec<ListReturnOption>,
+    },
+
     /// ### 6.3.9.  LSUB Command
     ///
     /// * Arguments:
@@ -901,6 +1031,20 @@ pub enum CommandBody<'a> {
         message: LiteralOrLiteral8<'a>,
     },
 
+    /// APPEND command with multiple messages (RFC 3502).
+    ///
+    /// An APPEND command with a single message is a [`CommandBody::Append`].
+    ///
+    /// Note: Use this only when the server advertised [`Capability::MultiAppend`](crate::response::Capability::MultiAppend).
+    #[cfg(feature = "ext_multiappend")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext_multiappend")))]
+    MultiAppend {
+        /// Mailbox.
+        mailbox: Mailbox<'a>,
+        /// Messages to append.
+        messages: Vec2<AppendMessage<'a>>,
+    },
+
     // ----- Selected State (https://tools.ietf.org/html/rfc3501#section-6.4) -----
     /// ### 6.4.1.  CHECK Command
     ///
@@ -926,28 +1070,28 @@ pub enum CommandBody<'a> {
 
     /// ### 6.4.2.  CLOSE Command
     ///
-    ///    * Arguments:  none
-    ///    * Responses:  no specific responses for this command
-    ///    * Result:
-    ///      * OK - close completed, now in authenticated state
-    ///      * BAD - command unknown or arguments invalid
-    ///
-    ///       The CLOSE command permanently removes all messages that have the
-    ///       \Deleted flag set from the currently selected mailbox, and returns
-    ///       to the authenticated state from the selected state.  No untagged
-    ///       EXPUNGE responses are sent.
-    ///
-    ///       No messages are removed, and no error is given, if the mailbox is
-    ///       selected by an EXAMINE command or is otherwise selected read-only.
-    ///
-    ///       Even if a mailbox is selected, a SELECT, EXAMINE, or LOGOUT
-    ///       command MAY be issued without previously issuing a CLOSE command.
-    ///       The SELECT, EXAMINE, and LOGOUT commands implicitly close the
-    ///       currently selected mailbox without doing an expunge.  However,
-    ///       when many messages are deleted, a CLOSE-LOGOUT or CLOSE-SELECT
-    ///       sequence is considerably faster than an EXPUNGE-LOGOUT or
-    ///       EXPUNGE-SELECT because no untagged EXPUNGE responses (which the
-    ///       client would probably ignore) are sent.
+    /// * Arguments:  none
+    /// * Responses:  no specific responses for this command
+    /// * Result:
+    ///   * OK - close completed, now in authenticated state
+    ///   * BAD - command unknown or arguments invalid
+    ///
+    /// The CLOSE command permanently removes all messages that have the
+    /// \Deleted flag set from the currently selected mailbox, and returns
+    /// to the authenticated state from the selected state.  No untagged
+    /// EXPUNGE responses are sent.
+    ///
+    /// No messages are removed, and no error is given, if the mailbox is
+    /// selected by an EXAMINE command or is otherwise selected read-only.
+    ///
+    /// Even if a mailbox is selected, a SELECT, EXAMINE, or LOGOUT
+    /// command MAY be issued without previously issuing a CLOSE command.
+    /// The SELECT, EXAMINE, and LOGOUT commands implicitly close the
+    /// currently selected mailbox without doing an expunge.  However,
+    /// when many messages are deleted, a CLOSE-LOGOUT or CLOSE-SELECT
+    /// sequence is considerably faster than an EXPUNGE-LOGOUT or
+    /// EXPUNGE-SELECT because no untagged EXPUNGE responses (which the
+    /// client would probably ignore) are sent.
     Close,
 
     /// 6.4.3.  EXPUNGE Command
@@ -1030,6 +1174,24 @@ pub enum CommandBody<'a> {
         uid: bool,
     },
 
+    /// SEARCH command with result options (RFC 4731).
+    ///
+    /// A SEARCH command without result options is a [`CommandBody::Search`].
+    ///
+    /// Note: Use this only when the server advertised [`Capability::ESearch`](crate::response::Capability::ESearch).
+    #[cfg(feature = "ext_esearch")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext_esearch")))]
+    SearchExtended {
+        /// Result options, e.g., `MIN`, `COUNT` (RFC 4731), or `SAVE` (RFC 5182).
+        return_options: Vec1<SearchReturnOption>,
+        /// Charset.
+        charset: Option<Charset<'a>>,
+        /// Criteria.
+        criteria: Vec1<SearchKey<'a>>,
+        /// Use UID variant.
+        uid: bool,
+    },
+
     #[cfg(feature = "ext_sort_thread")]
     /// SORT command.
     ///
@@ -1109,6 +1271,24 @@ pub enum CommandBody<'a> {
         uid: bool,
     },
 
+    /// FETCH command with modifiers, e.g., `CHANGEDSINCE` (RFC 7162).
+    ///
+    /// A FETCH command without modifiers is a [`CommandBody::Fetch`].
+    ///
+    /// Note: Use this only when the server advertised the `CONDSTORE` capability.
+    #[cfg(feature = "ext_condstore_qresync")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext_condstore_qresync")))]
+    FetchWithModifiers {
+        /// Set of messages.
+        sequence_set: SequenceSet,
+        /// Message data items (or a macro).
+        macro_or_item_names: MacroOrMessageDataItemNames<'a>,
+        /// Use UID variant.
+        uid: bool,
+        /// Modifiers.
+        modifiers: Vec1<FetchModifier>,
+    },
+
     /// ### 6.4.6.  STORE Command
     ///
     /// * Arguments:
@@ -1172,6 +1352,28 @@ pub enum CommandBody<'a> {
         uid: bool,
     },
 
+    /// STORE command with modifiers, e.g., `UNCHANGEDSINCE` (RFC 7162).
+    ///
+    /// A STORE command without modifiers is a [`CommandBody::Store`].
+    ///
+    /// Note: Use this only when the server advertised the `CONDSTORE` capability.
+    #[cfg(feature = "ext_condstore_qresync")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext_condstore_qresync")))]
+    StoreWithModifiers {
+        /// Set of messages.
+        sequence_set: SequenceSet,
+        /// Kind of storage, i.e., replace, add, or remove.
+        kind: StoreType,
+        /// Kind of response, i.e., answer or silent.
+        response: StoreResponse,
+        /// Flags.
+        flags: Vec<Flag<'a>>,
+        /// Use UID variant.
+        uid: bool,
+        /// Modifiers.
+        modifiers: Vec1<StoreModifier>,
+    },
+
     /// 6.4.7.  COPY Command
     ///
     /// Arguments:  sequence set
@@ -1202,6 +1404,8 @@ pub enum CommandBody<'a> {
         /// Set of messages.
         sequence_set: SequenceSet,
         /// Destination mailbox.
+        ///
+        /// Note: A destination named "INBOX" (in any case) is always [`Mailbox::Inbox`].
         mailbox: Mailbox<'a>,
         /// Use UID variant.
         uid: bool,
@@ -1332,7 +1536,7 @@ pub enum CommandBody<'a> {
     /// ```
     GetQuota {
         /// Name of quota root.
-        root: AString<'a>,
+        root: QuotaRoot<'a>,
     },
 
     /// Takes a mailbox name and returns the list of quota roots for the mailbox in an untagged QUOTAROOT response.
@@ -1408,16 +1612,21 @@ pub enum CommandBody<'a> {
     /// ```
     SetQuota {
         /// Name of quota root.
-        root: AString<'a>,
+        root: QuotaRoot<'a>,
         /// List of resource limits.
         quotas: Vec<QuotaSet<'a>>,
     },
 
-    /// MOVE command.
+    /// MOVE command (RFC 6851).
+    ///
+    /// Note: MOVE is not gated behind a feature, i.e., there is no `ext_move` feature.
+    /// Clients should only send it when the server advertises the `MOVE` capability.
     Move {
         /// Set of messages.
         sequence_set: SequenceSet,
         /// Destination mailbox.
+        ///
+        /// Note: A destination named "INBOX" (in any case) is always [`Mailbox::Inbox`].
         mailbox: Mailbox<'a>,
         /// Use UID variant.
         uid: bool,
@@ -1430,6 +1639,10 @@ pub enum CommandBody<'a> {
         parameters: Option<Vec<(IString<'a>, NString<'a>)>>,
     },
 
+    #[cfg(feature = "ext_namespace")]
+    /// NAMESPACE command.
+    Namespace,
+
     #[cfg(feature = "ext_metadata")]
     SetMetadata {
         mailbox: Mailbox<'a>,
@@ -1442,6 +1655,109 @@ pub enum CommandBody<'a> {
         mailbox: Mailbox<'a>,
         entries: Vec1<Entry<'a>>,
     },
+
+    /// SETACL command (RFC 4314).
+    ///
+    /// Change the rights of an identifier on a mailbox.
+    ///
+    /// ```imap
+    /// C: A035 SETACL INBOX/Drafts John lrQswicda
+    /// S: A035 BAD Uppercase rights are not allowed
+    /// C: A036 SETACL INBOX/Drafts John lrqswicda
+    /// S: A036 BAD The q right is not supported
+    /// ```
+    #[cfg(feature = "ext_acl")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext_acl")))]
+    SetAcl {
+        /// Mailbox.
+        mailbox: Mailbox<'a>,
+        /// Identifier, e.g., a user or group name.
+        identifier: AString<'a>,
+        /// Rights to set (or add/remove with a `+`/`-` prefix).
+        rights: AclRights<'a>,
+    },
+
+    /// DELETEACL command (RFC 4314).
+    ///
+    /// Remove any rights of an identifier on a mailbox.
+    ///
+    /// ```imap
+    /// C: B001 getacl INBOX
+    /// S: * ACL INBOX Fred rwipslxetad -Fred wetd $team w
+    /// S: B001 OK Getacl complete
+    /// C: B002 DeleteAcl INBOX Fred
+    /// S: B002 OK Deleteacl complete
+    /// ```
+    #[cfg(feature = "ext_acl")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext_acl")))]
+    DeleteAcl {
+        /// Mailbox.
+        mailbox: Mailbox<'a>,
+        /// Identifier, e.g., a user or group name.
+        identifier: AString<'a>,
+    },
+
+    /// GETACL command (RFC 4314).
+    ///
+    /// Get the access control list of a mailbox.
+    ///
+    /// ```imap
+    /// C: A002 GETACL INBOX
+    /// S: * ACL INBOX Fred rwipsldexta
+    /// S: A002 OK Getacl complete
+    /// ```
+    #[cfg(feature = "ext_acl")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext_acl")))]
+    GetAcl {
+        /// Mailbox.
+        mailbox: Mailbox<'a>,
+    },
+
+    /// LISTRIGHTS command (RFC 4314).
+    ///
+    /// Get the rights that may be granted to an identifier on a mailbox.
+    ///
+    /// ```imap
+    /// C: a001 LISTRIGHTS ~/Mail/saved smith
+    /// S: * LISTRIGHTS ~/Mail/saved smith la r swicdkxte
+    /// S: a001 OK Listrights completed
+    /// ```
+    #[cfg(feature = "ext_acl")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext_acl")))]
+    ListRights {
+        /// Mailbox.
+        mailbox: Mailbox<'a>,
+        /// Identifier, e.g., a user or group name.
+        identifier: AString<'a>,
+    },
+
+    /// MYRIGHTS command (RFC 4314).
+    ///
+    /// Get the rights the current user has on a mailbox.
+    ///
+    /// ```imap
+    /// C: A003 MYRIGHTS INBOX
+    /// S: * MYRIGHTS INBOX rwiptsldaex
+    /// S: A003 OK Myrights complete
+    /// ```
+    #[cfg(feature = "ext_acl")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext_acl")))]
+    MyRights {
+        /// Mailbox.
+        mailbox: Mailbox<'a>,
+    },
+
+    /// UNAUTHENTICATE command (RFC 8437).
+    ///
+    /// Reset the connection to the NOT AUTHENTICATED state, e.g., to reuse it for another user.
+    ///
+    /// ```imap
+    /// C: A004 UNAUTHENTICATE
+    /// S: A004 OK Unauthenticate completed
+    /// ```
+    #[cfg(feature = "ext_unauthenticate")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext_unauthenticate")))]
+    Unauthenticate,
 }
 
 impl<'a> CommandBody<'a> {
@@ -1469,6 +1785,7 @@ impl<'a> CommandBody<'a> {
     /// Construct an AUTHENTICATE command (with an initial response, SASL-IR).
     ///
     /// Note: Use this only when the server advertised the `SASL-IR` capability.
+    /// See [`CommandBody::authenticate_checked`] for a constructor that checks this.
     pub fn authenticate_with_ir<I>(mechanism: AuthMechanism<'a>, initial_response: I) -> Self
     where
         I: Into<Cow<'a, [u8]>>,
@@ -1479,6 +1796,31 @@ impl<'a> CommandBody<'a> {
         }
     }
 
+    /// Construct an AUTHENTICATE command, checking the initial response against the capabilities.
+    ///
+    /// `capabilities` are the capabilities advertised by the server. An initial response is only
+    /// permitted when the server advertised `SASL-IR`. Otherwise,
+    /// [`AuthenticateError::SaslIrNotAdvertised`] is returned.
+    pub fn authenticate_checked<I>(
+        mechanism: AuthMechanism<'a>,
+        initial_response: Option<I>,
+        capabilities: &[Capability],
+    ) -> Result<Self, AuthenticateError>
+    where
+        I: Into<Cow<'a, [u8]>>,
+    {
+        match initial_response {
+            Some(initial_response) => {
+                if !capabilities.contains(&Capability::SaslIr) {
+                    return Err(AuthenticateError::SaslIrNotAdvertised);
+                }
+
+                Ok(Self::authenticate_with_ir(mechanism, initial_response))
+            }
+            None => Ok(Self::authenticate(mechanism)),
+        }
+    }
+
     /// Construct a LOGIN command.
     pub fn login<U, P>(username: U, password: P) -> Result<Self, LoginError<U::Error, P::Error>>
     where
@@ -1532,6 +1874,9 @@ impl<'a> CommandBody<'a> {
     }
 
     /// Construct a RENAME command.
+    ///
+    /// Note: Renaming INBOX has special behavior. It moves all messages in INBOX to the new
+    /// mailbox, leaving INBOX empty. See [`CommandBody::is_inbox_rename`].
     pub fn rename<F, T>(mailbox: F, new_mailbox: T) -> Result<Self, RenameError<F::Error, T::Error>>
     where
         F: TryInto<Mailbox<'a>>,
@@ -1629,6 +1974,37 @@ impl<'a> CommandBody<'a> {
         })
     }
 
+    /// Construct an APPEND command with a non-synchronizing literal (see RFC 7888).
+    ///
+    /// Note: Use this only when the server advertised [`Capability::LiteralPlus`](crate::response::Capability::LiteralPlus),
+    /// or [`Capability::LiteralMinus`](crate::response::Capability::LiteralMinus) and the message
+    /// is not larger than 4096 bytes.
+    pub fn append_non_sync<M, D>(
+        mailbox: M,
+        flags: Vec<Flag<'a>>,
+        date: Option<DateTime>,
+        message: D,
+    ) -> Result<Self, AppendError<M::Error, D::Error>>
+    where
+        M: TryInto<Mailbox<'a>>,
+        D: TryInto<Literal<'a>>,
+    {
+        let message = message
+            .try_into()
+            .map_err(AppendError::Data)?
+            .into_non_sync();
+
+        Ok(CommandBody::Append {
+            mailbox: mailbox.try_into().map_err(AppendError::Mailbox)?,
+            flags,
+            date,
+            #[cfg(not(feature = "ext_binary"))]
+            message,
+            #[cfg(feature = "ext_binary")]
+            message: LiteralOrLiteral8::Literal(message),
+        })
+    }
+
     /// Construct a SEARCH command.
     pub fn search(charset: Option<Charset<'a>>, criteria: Vec1<SearchKey<'a>>, uid: bool) -> Self {
         CommandBody::Search {
@@ -1676,6 +2052,9 @@ impl<'a> CommandBody<'a> {
     }
 
     /// Construct a COPY command.
+    ///
+    /// The destination is validated as a [`Mailbox`]. "INBOX" (in any case) becomes
+    /// [`Mailbox::Inbox`] and is encoded as `INBOX`.
     pub fn copy<S, M>(
         sequence_set: S,
         mailbox: M,
@@ -1692,6 +2071,73 @@ impl<'a> CommandBody<'a> {
         })
     }
 
+    /// Returns `true` when this is a RENAME command with INBOX as source.
+    ///
+    /// Renaming INBOX doesn't rename the mailbox but moves all of its messages to the new mailbox.
+    pub fn is_inbox_rename(&self) -> bool {
+        matches!(
+            self,
+            Self::Rename {
+                from: Mailbox::Inbox,
+                ..
+            }
+        )
+    }
+
+    /// Compute a heuristic cost of this command.
+    ///
+    /// The score grows with the number of (nested) search keys, the number of messages addressed
+    /// by a sequence set, and the number of requested message data items. It is not meant to be
+    /// exact but to compare commands, e.g., for rate-limiting or abuse detection.
+    ///
+    /// Note: A range with `*` addresses an unknown number of messages and is counted as 1000.
+    pub fn complexity_score(&self) -> u64 {
+        let score = match self {
+            Self::Search { criteria, .. } => search_keys_score(criteria.as_ref()),
+            #[cfg(feature = "ext_esearch")]
+            Self::SearchExtended { criteria, .. } => search_keys_score(criteria.as_ref()),
+            #[cfg(feature = "ext_sort_thread")]
+            Self::Sort {
+                sort_criteria,
+                search_criteria,
+                ..
+            } => (sort_criteria.as_ref().len() as u64)
+                .saturating_add(search_keys_score(search_criteria.as_ref())),
+            #[cfg(feature = "ext_sort_thread")]
+            Self::Thread {
+                search_criteria, ..
+            } => search_keys_score(search_criteria.as_ref()),
+            Self::Fetch {
+                sequence_set,
+                macro_or_item_names,
+                ..
+            } => fetch_score(sequence_set, macro_or_item_names),
+            #[cfg(feature = "ext_condstore_qresync")]
+            Self::FetchWithModifiers {
+                sequence_set,
+                macro_or_item_names,
+                ..
+            } => fetch_score(sequence_set, macro_or_item_names),
+            Self::Store {
+                sequence_set,
+                flags,
+                ..
+            } => store_score(sequence_set, flags),
+            #[cfg(feature = "ext_condstore_qresync")]
+            Self::StoreWithModifiers {
+                sequence_set,
+                flags,
+                ..
+            } => store_score(sequence_set, flags),
+            Self::Copy { sequence_set, .. } | Self::Move { sequence_set, .. } => {
+                sequence_set_score(sequence_set)
+            }
+            _ => 0,
+        };
+
+        score.saturating_add(1)
+    }
+
     /// Get the name of the command.
     pub fn name(&self) -> &'static str {
         match self {
@@ -1710,20 +2156,32 @@ impl<'a> CommandBody<'a> {
             Self::Unselect => "UNSELECT",
             Self::Examine { .. } => "EXAMINE",
             Self::Create { .. } => "CREATE",
+            #[cfg(feature = "ext_special_use")]
+            Self::CreateSpecialUse { .. } => "CREATE",
             Self::Delete { .. } => "DELETE",
             Self::Rename { .. } => "RENAME",
             Self::Subscribe { .. } => "SUBSCRIBE",
             Self::Unsubscribe { .. } => "UNSUBSCRIBE",
             Self::List { .. } => "LIST",
+            #[cfg(feature = "ext_list_extended")]
+            Self::ListExtended { .. } => "LIST",
             Self::Lsub { .. } => "LSUB",
             Self::Status { .. } => "STATUS",
             Self::Append { .. } => "APPEND",
+            #[cfg(feature = "ext_multiappend")]
+            Self::MultiAppend { .. } => "APPEND",
             Self::Check => "CHECK",
             Self::Close => "CLOSE",
             Self::Expunge => "EXPUNGE",
             Self::Search { .. } => "SEARCH",
+            #[cfg(feature = "ext_esearch")]
+            Self::SearchExtended { .. } => "SEARCH",
             Self::Fetch { .. } => "FETCH",
+            #[cfg(feature = "ext_condstore_qresync")]
+            Self::FetchWithModifiers { .. } => "FETCH",
             Self::Store { .. } => "STORE",
+            #[cfg(feature = "ext_condstore_qresync")]
+            Self::StoreWithModifiers { .. } => "STORE",
             Self::Copy { .. } => "COPY",
             Self::Idle => "IDLE",
             Self::Enable { .. } => "ENABLE",
@@ -1734,18 +2192,104 @@ impl<'a> CommandBody<'a> {
             Self::Move { .. } => "MOVE",
             #[cfg(feature = "ext_id")]
             Self::Id { .. } => "ID",
+            #[cfg(feature = "ext_namespace")]
+            Self::Namespace => "NAMESPACE",
             #[cfg(feature = "ext_metadata")]
             Self::SetMetadata { .. } => "SETMETADATA",
             #[cfg(feature = "ext_metadata")]
             Self::GetMetadata { .. } => "GETMETADATA",
+            #[cfg(feature = "ext_acl")]
+            Self::SetAcl { .. } => "SETACL",
+            #[cfg(feature = "ext_acl")]
+            Self::DeleteAcl { .. } => "DELETEACL",
+            #[cfg(feature = "ext_acl")]
+            Self::GetAcl { .. } => "GETACL",
+            #[cfg(feature = "ext_acl")]
+            Self::ListRights { .. } => "LISTRIGHTS",
+            #[cfg(feature = "ext_acl")]
+            Self::MyRights { .. } => "MYRIGHTS",
+            #[cfg(feature = "ext_unauthenticate")]
+            Self::Unauthenticate => "UNAUTHENTICATE",
         }
     }
 }
 
+fn search_keys_score(keys: &[SearchKey]) -> u64 {
+    keys.iter()
+        .fold(0u64, |acc, key| acc.saturating_add(search_key_score(key)))
+}
+
+fn search_key_score(key: &SearchKey) -> u64 {
+    let nested = match key {
+        SearchKey::And(keys) => search_keys_score(keys.as_ref()),
+        SearchKey::Not(key) => search_key_score(key),
+        SearchKey::Or(left, right) => {
+            search_key_score(left).saturating_add(search_key_score(right))
+        }
+        SearchKey::SequenceSet(sequence_set) | SearchKey::Uid(sequence_set) => {
+            sequence_set_score(sequence_set)
+        }
+        _ => 0,
+    };
+
+    nested.saturating_add(1)
+}
+
+fn fetch_score(
+    sequence_set: &SequenceSet,
+    macro_or_item_names: &MacroOrMessageDataItemNames,
+) -> u64 {
+    let items = match macro_or_item_names {
+        MacroOrMessageDataItemNames::Macro(m) => m.expand(),
+        MacroOrMessageDataItemNames::MessageDataItemNames(items) => items.clone(),
+    };
+
+    let items_score = items
+        .iter()
+        .map(|item| match item {
+            MessageDataItemName::Body
+            | MessageDataItemName::BodyStructure
+            | MessageDataItemName::BodyExt { .. }
+            | MessageDataItemName::Rfc822
+            | MessageDataItemName::Rfc822Text => 10,
+            _ => 1,
+        })
+        .sum::<u64>();
+
+    sequence_set_score(sequence_set).saturating_mul(items_score.max(1))
+}
+
+fn store_score(sequence_set: &SequenceSet, flags: &[Flag]) -> u64 {
+    sequence_set_score(sequence_set).saturating_mul((flags.len() as u64).max(1))
+}
+
+fn sequence_set_score(sequence_set: &SequenceSet) -> u64 {
+    sequence_set
+        .0
+        .as_ref()
+        .iter()
+        .map(|sequence| match sequence {
+            Sequence::Single(_) => 1,
+            Sequence::Range(SeqOrUid::Value(from), SeqOrUid::Value(to)) => {
+                u64::from(from.get().abs_diff(to.get())) + 1
+            }
+            Sequence::Range(_, _) => 1000,
+            #[cfg(feature = "ext_searchres")]
+            Sequence::SavedResult => 1000,
+        })
+        .fold(0u64, |acc, score| acc.saturating_add(score))
+}
+
 /// Error-related types.
 pub mod error {
     use thiserror::Error;
 
+    #[derive(Clone, Debug, Eq, Error, Hash, Ord, PartialEq, PartialOrd)]
+    pub enum AuthenticateError {
+        #[error("Initial response requires SASL-IR")]
+        SaslIrNotAdvertised,
+    }
+
     #[derive(Clone, Debug, Eq, Error, Hash, Ord, PartialEq, PartialOrd)]
     pub enum LoginError<U, P> {
         #[error("Invalid username: {0}")]
@@ -1792,11 +2336,11 @@ mod tests {
     use chrono::DateTime as ChronoDateTime;
 
     use super::*;
-    #[cfg(feature = "ext_binary")]
-    use crate::extensions::binary::Literal8;
+    #[cfg(feature = "ext_multiappend")]
+    use crate::extensions::multiappend::AppendMessage;
     use crate::{
         auth::AuthMechanism,
-        core::{AString, Charset, IString, Literal, LiteralMode, Vec1},
+        core::{AString, Charset, IString, Literal, Vec1},
         datetime::DateTime,
         extensions::{
             compress::CompressionAlgorithm,
@@ -1810,6 +2354,8 @@ mod tests {
         sequence::{SeqOrUid, Sequence, SequenceSet},
         status::StatusDataItemName,
     };
+    #[cfg(feature = "ext_binary")]
+    use crate::{core::LiteralMode, extensions::binary::Literal8};
 
     #[test]
     fn test_conversion_command_body() {
@@ -1990,6 +2536,39 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_authenticate_checked() {
+        let capabilities = [Capability::Auth(AuthMechanism::Plain), Capability::SaslIr];
+
+        assert_eq!(
+            CommandBody::authenticate_checked(
+                AuthMechanism::Plain,
+                Some(b"\x00alice\x00pass".as_ref()),
+                &capabilities
+            ),
+            Ok(CommandBody::authenticate_with_ir(
+                AuthMechanism::Plain,
+                b"\x00alice\x00pass".as_ref()
+            ))
+        );
+        assert_eq!(
+            CommandBody::authenticate_checked(
+                AuthMechanism::Plain,
+                None::<&[u8]>,
+                &capabilities[..1]
+            ),
+            Ok(CommandBody::authenticate(AuthMechanism::Plain))
+        );
+        assert_eq!(
+            CommandBody::authenticate_checked(
+                AuthMechanism::Plain,
+                Some(b"\x00alice\x00pass".as_ref()),
+                &capabilities[..1]
+            ),
+            Err(AuthenticateError::SaslIrNotAdvertised)
+        );
+    }
+
     #[test]
     fn test_command_body_name() {
         let tests = [
@@ -2025,12 +2604,7 @@ mod tests {
                 },
                 "EXAMINE",
             ),
-            (
-                CommandBody::Create {
-                    mailbox: Mailbox::Inbox,
-                },
-                "CREATE",
-            ),
+            (CommandBody::create(Mailbox::Inbox).unwrap(), "CREATE"),
             (
                 CommandBody::Delete {
                     mailbox: Mailbox::Inbox,
@@ -2063,6 +2637,16 @@ mod tests {
                 },
                 "LIST",
             ),
+            #[cfg(feature = "ext_list_extended")]
+            (
+                CommandBody::ListExtended {
+                    selection_options: vec![ListSelectionOption::Subscribed],
+                    reference: Mailbox::Inbox,
+                    mailbox_patterns: MailboxPatterns::Single(ListMailbox::try_from("").unwrap()),
+                    return_options: vec![],
+                },
+                "LIST",
+            ),
             (
                 CommandBody::Lsub {
                     reference: Mailbox::Inbox,
@@ -2102,6 +2686,18 @@ mod tests {
                 },
                 "APPEND",
             ),
+            #[cfg(feature = "ext_multiappend")]
+            (
+                CommandBody::multiappend(
+                    Mailbox::Inbox,
+                    vec![
+                        AppendMessage::new(vec![Flag::Seen], None, "Hello").unwrap(),
+                        AppendMessage::new(vec![], None, "World").unwrap(),
+                    ],
+                )
+                .unwrap(),
+                "APPEND",
+            ),
             (CommandBody::Check, "CHECK"),
             (CommandBody::Close, "CLOSE"),
             (CommandBody::Expunge, "EXPUNGE"),
@@ -2154,7 +2750,7 @@ mod tests {
             ),
             (
                 CommandBody::GetQuota {
-                    root: AString::try_from("root").unwrap(),
+                    root: QuotaRoot::try_from("root").unwrap(),
                 },
                 "GETQUOTA",
             ),
@@ -2166,7 +2762,7 @@ mod tests {
             ),
             (
                 CommandBody::SetQuota {
-                    root: AString::try_from("root").unwrap(),
+                    root: QuotaRoot::try_from("root").unwrap(),
                     quotas: vec![],
                 },
                 "SETQUOTA",
@@ -2185,4 +2781,50 @@ mod tests {
             assert_eq!(test.name(), expected);
         }
     }
+
+    #[test]
+    fn test_complexity_score() {
+        let noop = CommandBody::Noop.complexity_score();
+
+        let mut key = SearchKey::Subject(AString::try_from("x").unwrap());
+        for _ in 0..100 {
+            key = SearchKey::Or(
+                Box::new(key),
+                Box::new(SearchKey::From(AString::try_from("y").unwrap())),
+            );
+        }
+        let search = CommandBody::search(None, Vec1::from(key), false).complexity_score();
+
+        let fetch = CommandBody::fetch("1:*", vec![MessageDataItemName::BodyStructure], false)
+            .unwrap()
+            .complexity_score();
+        let fetch_small = CommandBody::fetch("1", vec![MessageDataItemName::Flags], false)
+            .unwrap()
+            .complexity_score();
+
+        assert_eq!(noop, 1);
+        assert!(search > 200);
+        assert!(fetch > fetch_small);
+        assert!(fetch_small > noop);
+    }
+
+    #[test]
+    fn test_keepalive() {
+        let mut keepalive = Keepalive::new(TagGenerator::new());
+
+        let tags: Vec<_> = (0..3)
+            .map(|_| {
+                let command = keepalive.next_command();
+                assert_eq!(command.body, CommandBody::Noop);
+                command.tag
+            })
+            .collect();
+
+        assert_ne!(tags[0], tags[1]);
+        assert_ne!(tags[1], tags[2]);
+        assert_ne!(tags[0], tags[2]);
+
+        let mut keepalive = Keepalive::with_command(TagGenerator::new(), KeepaliveCommand::Idle);
+        assert_eq!(keepalive.next_command().body, CommandBody::Idle);
+    }
 }
@@ -34,11 +34,17 @@
 //!             └───────┘ └──────┘
 //! ```
 
-use std::{
+use alloc::{
     borrow::Cow,
+    format,
+    string::String,
+    vec,
+    vec::{IntoIter, Vec},
+};
+use core::{
     fmt::{Debug, Display, Formatter},
+    slice::Iter,
     str::from_utf8,
-    vec::IntoIter,
 };
 
 #[cfg(feature = "arbitrary")]
@@ -49,9 +55,13 @@ use bounded_static::ToStatic;
 use serde::{Deserialize, Serialize};
 
 use crate::utils::indicators::{
-    is_any_text_char_except_quoted_specials, is_astring_char, is_atom_char, is_char8, is_text_char,
+    is_any_text_char_except_quoted_specials, is_astring_char, is_atom_char, is_char8, is_ctl,
+    is_tag_char, is_text_char,
 };
+#[cfg(all(feature = "unvalidated", feature = "quirk_atom_char_relaxed"))]
+use crate::utils::indicators::{is_atom_char_relaxed, is_tag_char_relaxed};
 
+#[cfg(feature = "std")]
 macro_rules! impl_try_from {
     ($via:ty, $lifetime:lifetime, $from:ty, $target:ty) => {
         impl<$lifetime> TryFrom<$from> for $target {
@@ -66,11 +76,15 @@ macro_rules! impl_try_from {
     };
 }
 
+#[cfg(feature = "std")]
 pub(crate) use impl_try_from;
 
-use crate::error::{ValidationError, ValidationErrorKind};
-#[cfg(any(feature = "ext_binary", feature = "ext_metadata"))]
+#[cfg(all(feature = "std", any(feature = "ext_binary", feature = "ext_metadata")))]
 use crate::extensions::binary::Literal8;
+use crate::{
+    core::error::TagError,
+    error::{ValidationError, ValidationErrorKind},
+};
 
 /// A string subset to model IMAP's `atom`s.
 ///
@@ -103,7 +117,7 @@ pub struct Atom<'a>(pub(crate) Cow<'a, str>);
 
 // We want a slightly more dense `Debug` implementation.
 impl<'a> Debug for Atom<'a> {
-    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
         write!(f, "Atom({:?})", self.0)
     }
 }
@@ -145,7 +159,8 @@ impl<'a> Atom<'a> {
     /// so may create invalid/unparsable IMAP messages, or even produce unintended protocol flows.
     /// Do not call this constructor with untrusted data.
     ///
-    /// Note: This method will `panic!` on wrong input in debug builds.
+    /// Note: This method will `panic!` on wrong input in debug builds. With the
+    /// `quirk_atom_char_relaxed` feature, `{` is tolerated here (but not in [`Self::validate`]).
     #[cfg(feature = "unvalidated")]
     #[cfg_attr(docsrs, doc(cfg(feature = "unvalidated")))]
     pub fn unvalidated<C>(inner: C) -> Self
@@ -154,8 +169,10 @@ impl<'a> Atom<'a> {
     {
         let inner = inner.into();
 
-        #[cfg(debug_assertions)]
+        #[cfg(all(debug_assertions, not(feature = "quirk_atom_char_relaxed")))]
         Self::validate(inner.as_bytes()).unwrap();
+        #[cfg(all(debug_assertions, feature = "quirk_atom_char_relaxed"))]
+        assert!(!inner.is_empty() && inner.bytes().all(is_atom_char_relaxed));
 
         Self(inner)
     }
@@ -220,7 +237,7 @@ impl<'a> AsRef<str> for Atom<'a> {
 }
 
 impl<'a> Display for Atom<'a> {
-    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
         write!(f, "{}", self.0)
     }
 }
@@ -255,7 +272,7 @@ pub struct AtomExt<'a>(pub(crate) Cow<'a, str>);
 
 // We want a slightly more dense `Debug` implementation.
 impl<'a> Debug for AtomExt<'a> {
-    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
         write!(f, "AtomExt({:?})", self.0)
     }
 }
@@ -393,6 +410,19 @@ pub enum IString<'a> {
 }
 
 impl<'a> IString<'a> {
+    /// Create an [`IString`] using the most compact representation of `value`.
+    ///
+    /// A quoted string is used when possible. (Quoted-specials are escaped during encoding.)
+    /// Otherwise, e.g., when `value` contains CR or LF, a literal is used.
+    ///
+    /// # Errors
+    ///
+    /// Fails if, and only if, `value` contains NUL. Neither a quoted string nor a literal can
+    /// represent NUL. Every other (UTF-8) string is accepted.
+    pub fn from_str_smart(value: &'a str) -> Result<Self, ValidationError> {
+        Self::try_from(value)
+    }
+
     pub fn into_inner(self) -> Cow<'a, [u8]> {
         match self {
             Self::Literal(literal) => literal.into_inner(),
@@ -515,11 +545,11 @@ pub struct Literal<'a> {
 
 // We want a more readable `Debug` implementation.
 impl<'a> Debug for Literal<'a> {
-    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
         struct BStr<'a>(&'a Cow<'a, [u8]>);
 
         impl<'a> Debug for BStr<'a> {
-            fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
                 write!(
                     f,
                     "b\"{}\"",
@@ -553,6 +583,17 @@ impl<'a> Literal<'a> {
         self.data.as_ref()
     }
 
+    /// Iterate over the literal data in chunks of (at most) `size` bytes.
+    ///
+    /// This allows to send large literals piece by piece, e.g., to apply backpressure.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    pub fn chunks(&self, size: usize) -> impl Iterator<Item = &[u8]> {
+        self.data.chunks(size)
+    }
+
     pub fn mode(&self) -> LiteralMode {
         self.mode
     }
@@ -575,6 +616,26 @@ impl<'a> Literal<'a> {
         self.data
     }
 
+    /// Try to convert this literal into a quoted string.
+    ///
+    /// This succeeds when the data is valid according to [`Quoted::validate`], i.e., it doesn't
+    /// contain CR, LF, or non-ASCII bytes. Sending a quoted string avoids the literal flow
+    /// (and possibly a continuation request round trip). On failure, the literal is returned
+    /// unchanged.
+    ///
+    /// Note: This doesn't check the length. It's up to the caller to only downgrade short literals.
+    pub fn try_into_quoted(self) -> Result<Quoted<'a>, Self> {
+        if Quoted::validate(&self.data).is_err() {
+            return Err(self);
+        }
+
+        // Safety: `unwrap` can't panic due to `validate`.
+        Ok(match self.data {
+            Cow::Borrowed(data) => Quoted(Cow::Borrowed(from_utf8(data).unwrap())),
+            Cow::Owned(data) => Quoted(Cow::Owned(String::from_utf8(data).unwrap())),
+        })
+    }
+
     /// Constructs a literal without validation.
     ///
     /// # Warning: IMAP conformance
@@ -726,7 +787,7 @@ pub enum LiteralMode {
 pub struct Quoted<'a>(pub(crate) Cow<'a, str>);
 
 impl<'a> Debug for Quoted<'a> {
-    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
         write!(f, "Quoted({:?})", self.0)
     }
 }
@@ -904,6 +965,21 @@ pub enum AString<'a> {
     String(IString<'a>), // string
 }
 
+impl<'a> AString<'a> {
+    /// Create an [`AString`] using the most compact representation of `value`.
+    ///
+    /// An atom is used when possible, followed by a quoted string, and a literal.
+    /// See [`IString::from_str_smart`].
+    ///
+    /// # Errors
+    ///
+    /// Fails if, and only if, `value` contains NUL. Neither an atom, a quoted string, nor a
+    /// literal can represent NUL. Every other (UTF-8) string is accepted.
+    pub fn from_str_smart(value: &'a str) -> Result<Self, ValidationError> {
+        Self::try_from(value)
+    }
+}
+
 impl<'a> TryFrom<&'a [u8]> for AString<'a> {
     type Error = ValidationError;
 
@@ -1017,27 +1093,32 @@ pub struct Tag<'a>(pub(crate) Cow<'a, str>);
 
 // We want a slightly more dense `Debug` implementation.
 impl<'a> Debug for Tag<'a> {
-    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
         write!(f, "Tag({:?})", self.0)
     }
 }
 
 impl<'a> Tag<'a> {
-    pub fn validate(value: impl AsRef<[u8]>) -> Result<(), ValidationError> {
+    /// Validates if value conforms to tag's ABNF definition.
+    ///
+    /// Note: `]` (resp-specials) is an `ASTRING-CHAR` and thus allowed in a tag.
+    pub fn validate(value: impl AsRef<[u8]>) -> Result<(), TagError> {
         let value = value.as_ref();
 
         if value.is_empty() {
-            return Err(ValidationError::new(ValidationErrorKind::Empty));
+            return Err(TagError::Empty);
         }
 
-        if let Some(at) = value
-            .iter()
-            .position(|b| !is_astring_char(*b) || *b == b'+')
-        {
-            return Err(ValidationError::new(ValidationErrorKind::InvalidByteAt {
-                byte: value[at],
-                at,
-            }));
+        if let Some(at) = value.iter().position(|b| !is_tag_char(*b)) {
+            let byte = value[at];
+
+            return Err(match byte {
+                b'+' => TagError::Plus { at },
+                b' ' => TagError::Space { at },
+                _ if is_ctl(byte) => TagError::Control { byte, at },
+                _ if !byte.is_ascii() => TagError::NonAscii { byte, at },
+                _ => TagError::Special { byte, at },
+            });
         };
 
         Ok(())
@@ -1055,7 +1136,8 @@ impl<'a> Tag<'a> {
     /// so may create invalid/unparsable IMAP messages, or even produce unintended protocol flows.
     /// Do not call this constructor with untrusted data.
     ///
-    /// Note: This method will `panic!` on wrong input in debug builds.
+    /// Note: This method will `panic!` on wrong input in debug builds. With the
+    /// `quirk_atom_char_relaxed` feature, `{` is tolerated here (but not in [`Self::validate`]).
     #[cfg(feature = "unvalidated")]
     #[cfg_attr(docsrs, doc(cfg(feature = "unvalidated")))]
     pub fn unvalidated<C>(inner: C) -> Self
@@ -1064,15 +1146,17 @@ impl<'a> Tag<'a> {
     {
         let inner = inner.into();
 
-        #[cfg(debug_assertions)]
+        #[cfg(all(debug_assertions, not(feature = "quirk_atom_char_relaxed")))]
         Self::validate(inner.as_bytes()).unwrap();
+        #[cfg(all(debug_assertions, feature = "quirk_atom_char_relaxed"))]
+        assert!(!inner.is_empty() && inner.bytes().all(is_tag_char_relaxed));
 
         Self(inner)
     }
 }
 
 impl<'a> TryFrom<&'a [u8]> for Tag<'a> {
-    type Error = ValidationError;
+    type Error = TagError;
 
     fn try_from(value: &'a [u8]) -> Result<Self, Self::Error> {
         Self::validate(value)?;
@@ -1083,7 +1167,7 @@ impl<'a> TryFrom<&'a [u8]> for Tag<'a> {
 }
 
 impl<'a> TryFrom<Vec<u8>> for Tag<'a> {
-    type Error = ValidationError;
+    type Error = TagError;
 
     fn try_from(value: Vec<u8>) -> Result<Self, Self::Error> {
         Self::validate(&value)?;
@@ -1094,7 +1178,7 @@ impl<'a> TryFrom<Vec<u8>> for Tag<'a> {
 }
 
 impl<'a> TryFrom<&'a str> for Tag<'a> {
-    type Error = ValidationError;
+    type Error = TagError;
 
     fn try_from(value: &'a str) -> Result<Self, Self::Error> {
         Self::validate(value)?;
@@ -1104,7 +1188,7 @@ impl<'a> TryFrom<&'a str> for Tag<'a> {
 }
 
 impl<'a> TryFrom<String> for Tag<'a> {
-    type Error = ValidationError;
+    type Error = TagError;
 
     fn try_from(value: String) -> Result<Self, Self::Error> {
         Self::validate(&value)?;
@@ -1119,6 +1203,43 @@ impl<'a> AsRef<str> for Tag<'a> {
     }
 }
 
+/// Generator of unique tags, i.e., `A1`, `A2`, `A3`, etc.
+///
+/// ```
+/// use imap_types::core::TagGenerator;
+///
+/// let mut generator = TagGenerator::new();
+///
+/// assert_eq!(generator.generate().as_ref(), "A1");
+/// assert_eq!(generator.generate().as_ref(), "A2");
+/// ```
+///
+/// Note: [`TagGenerator`] is deliberately not `Clone` because clones would hand out the same tags.
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct TagGenerator {
+    counter: u64,
+}
+
+impl TagGenerator {
+    pub fn new() -> Self {
+        Self { counter: 0 }
+    }
+
+    /// Generate the next tag.
+    pub fn generate(&mut self) -> Tag<'static> {
+        self.counter = self.counter.wrapping_add(1);
+
+        // Note: `A` followed by digits is always a valid tag.
+        Tag(Cow::Owned(format!("A{}", self.counter)))
+    }
+}
+
+impl Default for TagGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// A human-readable text string used in some server responses.
 ///
 /// # Example
@@ -1146,13 +1267,13 @@ pub struct Text<'a>(pub(crate) Cow<'a, str>);
 
 // We want a slightly more dense `Debug` implementation.
 impl<'a> Debug for Text<'a> {
-    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
         write!(f, "Text({:?})", self.0)
     }
 }
 
 impl<'a> Display for Text<'a> {
-    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
         write!(f, "{}", self.0.as_ref())
     }
 }
@@ -1333,7 +1454,7 @@ impl TryFrom<char> for QuotedChar {
 /// ;                                           `Charset`
 //                     ; CHARSET argument to MUST be registered with IANA
 /// ```
-/// 
+///
 /// So, it seems that it should be an `AString`. However the IMAP standard also points to ...
 /// ```abnf
 /// mime-charset       = 1*mime-charset-chars
@@ -1415,6 +1536,16 @@ impl<'a> TryFrom<String> for Charset<'a> {
     }
 }
 
+impl<'a> Charset<'a> {
+    /// Returns `true` if this is the `UTF-8` charset.
+    ///
+    /// The charset is compared case-insensitively and regardless of whether it is an atom (`UTF-8`)
+    /// or quoted (`"UTF-8"`).
+    pub fn is_utf8(&self) -> bool {
+        self.as_ref().eq_ignore_ascii_case("UTF-8")
+    }
+}
+
 impl<'a> AsRef<str> for Charset<'a> {
     fn as_ref(&self) -> &str {
         match self {
@@ -1424,7 +1555,7 @@ impl<'a> AsRef<str> for Charset<'a> {
     }
 }
 
-#[cfg(any(feature = "ext_binary", feature = "ext_metadata"))]
+#[cfg(all(feature = "std", any(feature = "ext_binary", feature = "ext_metadata")))]
 #[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
 #[cfg_attr(feature = "bounded-static", derive(ToStatic))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -1452,7 +1583,7 @@ impl<T, const N: usize> Debug for VecN<T, N>
 where
     T: Debug,
 {
-    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
         self.0.fmt(f)?;
         match N {
             0 => write!(f, "*"),
@@ -1494,6 +1625,22 @@ impl<T, const N: usize> VecN<T, N> {
     pub fn into_inner(self) -> Vec<T> {
         self.0
     }
+
+    pub fn as_slice(&self) -> &[T] {
+        &self.0
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.0.iter()
+    }
+
+    /// Map every element, preserving the (minimum) length.
+    pub fn map<U, F>(self, f: F) -> VecN<U, N>
+    where
+        F: FnMut(T) -> U,
+    {
+        VecN(self.0.into_iter().map(f).collect())
+    }
 }
 
 impl<T, const N: usize> From<[T; N]> for VecN<T, N> {
@@ -1521,6 +1668,15 @@ impl<T, const N: usize> IntoIterator for VecN<T, N> {
     }
 }
 
+impl<'a, T, const N: usize> IntoIterator for &'a VecN<T, N> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
 impl<T, const N: usize> AsRef<[T]> for VecN<T, N> {
     fn as_ref(&self) -> &[T] {
         &self.0
@@ -1549,9 +1705,51 @@ impl<T> From<(T, T)> for Vec2<T> {
     }
 }
 
+/// Error-related types.
+pub mod error {
+    use core::fmt::{Display, Formatter};
+
+    /// Error returned when a tag violates the `tag` ABNF.
+    #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+    pub enum TagError {
+        Empty,
+        Plus { at: usize },
+        Space { at: usize },
+        Control { byte: u8, at: usize },
+        NonAscii { byte: u8, at: usize },
+        Special { byte: u8, at: usize },
+    }
+
+    impl Display for TagError {
+        fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+            match self {
+                Self::Empty => write!(f, "Must not be empty"),
+                Self::Plus { at } => write!(f, "Must not contain \"+\" (at index {at})"),
+                Self::Space { at } => write!(f, "Must not contain SP (at index {at})"),
+                Self::Control { byte, at } => write!(
+                    f,
+                    "Must not contain control character b'\\x{byte:02x}' (at index {at})"
+                ),
+                Self::NonAscii { byte, at } => write!(
+                    f,
+                    "Must not contain non-ASCII byte b'\\x{byte:02x}' (at index {at})"
+                ),
+                Self::Special { byte, at } => write!(
+                    f,
+                    "Must not contain atom-special b'{}' (at index {at})",
+                    char::from(*byte)
+                ),
+            }
+        }
+    }
+
+    #[cfg(feature = "std")]
+    impl std::error::Error for TagError {}
+}
+
 #[cfg(test)]
 mod tests {
-    use std::str::from_utf8;
+    use alloc::borrow::ToOwned;
 
     use super::*;
 
@@ -1938,6 +2136,107 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_from_str_smart() {
+        assert!(matches!(
+            AString::from_str_smart("hello"),
+            Ok(AString::Atom(_))
+        ));
+        assert!(matches!(
+            AString::from_str_smart("hello world"),
+            Ok(AString::String(IString::Quoted(_)))
+        ));
+        assert!(matches!(
+            AString::from_str_smart("say \"hi\""),
+            Ok(AString::String(IString::Quoted(_)))
+        ));
+        assert!(matches!(
+            AString::from_str_smart(""),
+            Ok(AString::String(IString::Quoted(_)))
+        ));
+        assert!(matches!(
+            AString::from_str_smart("hello\r\nworld"),
+            Ok(AString::String(IString::Literal(_)))
+        ));
+        assert!(AString::from_str_smart("gr\u{fc}\u{df}e").is_ok());
+        assert!(AString::from_str_smart("\x00").is_err());
+
+        assert!(matches!(
+            IString::from_str_smart("hello"),
+            Ok(IString::Quoted(_))
+        ));
+        assert!(matches!(
+            IString::from_str_smart("hello\nworld"),
+            Ok(IString::Literal(_))
+        ));
+        assert!(IString::from_str_smart("\x00").is_err());
+    }
+
+    #[test]
+    fn test_conversion_quoted_failing() {
+        for (test, byte, at) in [
+            ("\x00", 0x00, 0),
+            ("A\x00B", 0x00, 1),
+            ("A\rB", b'\r', 1),
+            ("AB\n", b'\n', 2),
+            ("A\r\nB", b'\r', 1),
+        ] {
+            let expected = Err(ValidationError::new(ValidationErrorKind::InvalidByteAt {
+                byte,
+                at,
+            }));
+
+            assert_eq!(Quoted::validate(test), expected.clone());
+            assert_eq!(Quoted::try_from(test).map(|_| ()), expected.clone());
+            assert_eq!(
+                Quoted::try_from(test.to_owned()).map(|_| ()),
+                expected.clone()
+            );
+            assert_eq!(Quoted::try_from(test.as_bytes()).map(|_| ()), expected);
+        }
+
+        // `IString` (and thus `AString`) falls back to a literal, which can represent CR and LF.
+        assert!(matches!(
+            IString::try_from("A\r\nB").unwrap(),
+            IString::Literal(_)
+        ));
+    }
+
+    #[test]
+    fn test_literal_chunks() {
+        let literal = Literal::try_from(vec![b'x'; 10 * 1024]).unwrap();
+
+        let chunks = literal.chunks(1024).collect::<Vec<_>>();
+        assert_eq!(chunks.len(), 10);
+        assert!(chunks.iter().all(|chunk| chunk.len() == 1024));
+        assert_eq!(chunks.concat(), literal.data());
+
+        let literal = Literal::try_from(vec![b'x'; 10 * 1024 + 1]).unwrap();
+        let chunks = literal.chunks(1024).collect::<Vec<_>>();
+        assert_eq!(chunks.len(), 11);
+        assert_eq!(chunks.last().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_literal_try_into_quoted() {
+        let literal = Literal::try_from("Hello, \"World\"!").unwrap();
+        assert_eq!(
+            literal.try_into_quoted(),
+            Ok(Quoted::try_from("Hello, \"World\"!").unwrap())
+        );
+
+        let literal = Literal::try_from(b"foo".to_vec()).unwrap().into_non_sync();
+        assert_eq!(
+            literal.try_into_quoted(),
+            Ok(Quoted::try_from("foo").unwrap())
+        );
+
+        for data in [b"foo\r\nbar".as_ref(), b"\xCA\xFE"] {
+            let literal = Literal::try_from(data).unwrap().into_non_sync();
+            assert_eq!(literal.clone().try_into_quoted(), Err(literal));
+        }
+    }
+
     #[test]
     fn test_vec_n() {
         // Note: Don't use `VecN<T, 0>`, it's only a sanity test here.
@@ -1957,4 +2256,83 @@ mod tests {
         assert!(VecN::<u8, 2>::try_from(vec![1]).is_err());
         assert!(VecN::<u8, 2>::try_from(vec![1, 2]).is_ok());
     }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_vec_n_iter() {
+        use crate::fetch::MessageDataItem;
+
+        let items = Vec1::try_from(vec![
+            MessageDataItem::Rfc822Size(1),
+            MessageDataItem::Rfc822Size(2),
+        ])
+        .unwrap();
+
+        let mut sum = 0;
+        for item in &items {
+            if let MessageDataItem::Rfc822Size(size) = item {
+                sum += size;
+            }
+        }
+        assert_eq!(sum, 3);
+        assert_eq!(items.iter().count(), 2);
+        assert_eq!(items.as_slice().len(), 2);
+
+        let sizes = items.map(|item| match item {
+            MessageDataItem::Rfc822Size(size) => size,
+            _ => unreachable!(),
+        });
+        assert_eq!(sizes, Vec1::try_from(vec![1, 2]).unwrap());
+        assert_eq!(sizes.into_iter().collect::<Vec<_>>(), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_conversion_tag() {
+        for valid in ["A", "a1", "A001", "!#$&'-./:;<=>?@[]^_`|~", "x]"] {
+            assert!(Tag::try_from(valid).is_ok(), "{valid}");
+            assert!(Tag::try_from(valid.as_bytes()).is_ok(), "{valid}");
+            assert!(Tag::try_from(valid.to_owned()).is_ok(), "{valid}");
+            assert!(Tag::try_from(valid.as_bytes().to_vec()).is_ok(), "{valid}");
+        }
+
+        let tests = [
+            (b"".as_ref(), TagError::Empty),
+            (b"+", TagError::Plus { at: 0 }),
+            (b"A+", TagError::Plus { at: 1 }),
+            (b"A B", TagError::Space { at: 1 }),
+            (b"A\t", TagError::Control { byte: b'\t', at: 1 }),
+            (b"A\r\n", TagError::Control { byte: b'\r', at: 1 }),
+            (b"\x00", TagError::Control { byte: 0x00, at: 0 }),
+            (b"A\x7f", TagError::Control { byte: 0x7f, at: 1 }),
+            (b"A\xc3\xa4", TagError::NonAscii { byte: 0xc3, at: 1 }),
+            (b"A(", TagError::Special { byte: b'(', at: 1 }),
+            (b"A)", TagError::Special { byte: b')', at: 1 }),
+            (b"A{", TagError::Special { byte: b'{', at: 1 }),
+            (b"A%", TagError::Special { byte: b'%', at: 1 }),
+            (b"*", TagError::Special { byte: b'*', at: 0 }),
+            (b"A\"", TagError::Special { byte: b'"', at: 1 }),
+            (b"A\\", TagError::Special { byte: b'\\', at: 1 }),
+        ];
+
+        for (test, expected) in tests {
+            assert_eq!(Tag::validate(test), Err(expected.clone()));
+            assert_eq!(Tag::try_from(test), Err(expected.clone()));
+            assert_eq!(Tag::try_from(test.to_vec()), Err(expected));
+        }
+
+        // Every byte that is not allowed must be rejected.
+        for byte in 0x00..=0xff {
+            let allowed = Tag::try_from(vec![b'A', byte]).is_ok();
+            assert_eq!(allowed, is_tag_char(byte), "b'\\x{byte:02x}'");
+        }
+    }
+
+    #[test]
+    fn test_charset_is_utf8() {
+        assert!(Charset::try_from("UTF-8").unwrap().is_utf8());
+        assert!(Charset::try_from("utf-8").unwrap().is_utf8());
+        assert!(Charset::Quoted(Quoted::try_from("UTF-8").unwrap()).is_utf8());
+        assert!(!Charset::try_from("US-ASCII").unwrap().is_utf8());
+        assert!(!Charset::try_from("UTF-8 ").unwrap().is_utf8());
+    }
 }
@@ -109,6 +109,60 @@ impl NaiveDate {
         Ok(())
     }
 
+    /// Parse a naive date from its IMAP form, e.g., `1-Jan-2020` or `"01-Jan-2020"`.
+    ///
+    /// This is the `date` form used in SEARCH, e.g., `SEARCH BEFORE "1-Jan-2020"`. The month is
+    /// case-insensitive.
+    ///
+    /// ```abnf
+    /// date      = date-text / DQUOTE date-text DQUOTE
+    /// date-text = date-day "-" date-month "-" date-year
+    /// ```
+    pub fn try_from_imap(value: &str) -> Result<Self, NaiveDateError> {
+        let malformed = || NaiveDateError::Malformed {
+            got: value.to_owned(),
+        };
+
+        let text = match value.strip_prefix('"') {
+            Some(quoted) => quoted.strip_suffix('"').ok_or_else(malformed)?,
+            None => value,
+        };
+
+        let mut parts = text.split('-');
+        let (Some(day), Some(month), Some(year), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(malformed());
+        };
+
+        let is_number = |part: &str, len: std::ops::RangeInclusive<usize>| {
+            len.contains(&part.len()) && part.bytes().all(|byte| byte.is_ascii_digit())
+        };
+
+        if !is_number(day, 1..=2) || !is_number(year, 4..=4) {
+            return Err(malformed());
+        }
+
+        const MONTHS: [&str; 12] = [
+            "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+        ];
+
+        let month = MONTHS
+            .iter()
+            .position(|candidate| candidate.eq_ignore_ascii_case(month))
+            .ok_or_else(malformed)?;
+
+        // Safety: `day` and `year` are checked to be 1-2 and 4 digits above.
+        let date = chrono::NaiveDate::from_ymd_opt(
+            year.parse().unwrap(),
+            month as u32 + 1,
+            day.parse().unwrap(),
+        )
+        .ok_or_else(malformed)?;
+
+        Self::try_from(date)
+    }
+
     /// Constructs a naive date without validation.
     ///
     /// # Warning: IMAP conformance
@@ -186,6 +240,8 @@ pub mod error {
     pub enum NaiveDateError {
         #[error("expected `0 <= year <= 9999`, got {got}")]
         YearOutOfRange { got: i32 },
+        #[error("expected a date such as `1-Jan-2020`, got `{got}`")]
+        Malformed { got: String },
     }
 }
 
@@ -195,6 +251,41 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_naive_date_try_from_imap() {
+        let expected =
+            NaiveDate::try_from(chrono::NaiveDate::from_ymd_opt(2020, 1, 1).unwrap()).unwrap();
+
+        for test in [
+            "1-Jan-2020",
+            "01-Jan-2020",
+            "\"1-Jan-2020\"",
+            "\"01-jan-2020\"",
+        ] {
+            assert_eq!(NaiveDate::try_from_imap(test), Ok(expected.clone()));
+        }
+
+        for test in [
+            "",
+            "\"\"",
+            "\"1-Jan-2020",
+            "1-Jan-2020\"",
+            "1-January-2020",
+            "1-Jan-20",
+            "001-Jan-2020",
+            "+1-Jan-2020",
+            "1-Jan-2020-1",
+            "1 Jan 2020",
+            "31-Feb-2020",
+            "0-Jan-2020",
+        ] {
+            assert_eq!(
+                NaiveDate::try_from_imap(test),
+                Err(NaiveDateError::Malformed { got: test.into() })
+            );
+        }
+    }
+
     #[test]
     fn test_conversion_date_time_failing() {
         let tests = [
@@ -4,28 +4,123 @@
 use arbitrary::Arbitrary;
 #[cfg(feature = "bounded-static")]
 use bounded_static::ToStatic;
+use chrono::{DateTime as ChronoDateTime, FixedOffset};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use crate::core::NString;
+use crate::{core::NString, envelope::error::EnvelopeError};
 
 #[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
 #[cfg_attr(feature = "bounded-static", derive(ToStatic))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// Note: Use [`Envelope::new`] to uphold the invariants documented below. The fields are public
+/// so that (slightly) malformed envelopes sent by servers can be represented.
 pub struct Envelope<'a> {
+    /// Date
+    ///
+    /// Must not be the empty string (see [`Envelope::new`]).
     pub date: NString<'a>,
+    /// Subject
     pub subject: NString<'a>,
+    /// From
     pub from: Vec<Address<'a>>,
+    /// Sender
+    ///
+    /// Should be the same as `from` if the Sender header is absent or empty.
     pub sender: Vec<Address<'a>>,
+    /// Reply-To
+    ///
+    /// Should be the same as `from` if the Reply-To header is absent or empty.
     pub reply_to: Vec<Address<'a>>,
+    /// To
     pub to: Vec<Address<'a>>,
+    /// Cc
     pub cc: Vec<Address<'a>>,
+    /// Bcc
     pub bcc: Vec<Address<'a>>,
+    /// In-Reply-To
+    ///
+    /// Must not be the empty string (see [`Envelope::new`]).
     pub in_reply_to: NString<'a>,
+    /// Message-ID
+    ///
+    /// Must not be the empty string (see [`Envelope::new`]).
     pub message_id: NString<'a>,
 }
 
+impl<'a> Envelope<'a> {
+    /// Construct an envelope.
+    ///
+    /// This upholds the invariants of RFC 3501 (Section 7.4.2):
+    ///
+    /// * `date`, `in_reply_to`, and `message_id` must be `NIL` or non-empty, and
+    /// * an empty `sender` or `reply_to` is set to `from`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        date: NString<'a>,
+        subject: NString<'a>,
+        from: Vec<Address<'a>>,
+        sender: Vec<Address<'a>>,
+        reply_to: Vec<Address<'a>>,
+        to: Vec<Address<'a>>,
+        cc: Vec<Address<'a>>,
+        bcc: Vec<Address<'a>>,
+        in_reply_to: NString<'a>,
+        message_id: NString<'a>,
+    ) -> Result<Self, EnvelopeError> {
+        if is_empty_string(&date) {
+            return Err(EnvelopeError::EmptyDate);
+        }
+
+        if is_empty_string(&in_reply_to) {
+            return Err(EnvelopeError::EmptyInReplyTo);
+        }
+
+        if is_empty_string(&message_id) {
+            return Err(EnvelopeError::EmptyMessageId);
+        }
+
+        let sender = if sender.is_empty() {
+            from.clone()
+        } else {
+            sender
+        };
+
+        let reply_to = if reply_to.is_empty() {
+            from.clone()
+        } else {
+            reply_to
+        };
+
+        Ok(Self {
+            date,
+            subject,
+            from,
+            sender,
+            reply_to,
+            to,
+            cc,
+            bcc,
+            in_reply_to,
+            message_id,
+        })
+    }
+
+    /// Parse the `date` field as an RFC 2822 date.
+    ///
+    /// Returns `None` if the date is `NIL` or malformed.
+    pub fn parsed_date(&self) -> Option<ChronoDateTime<FixedOffset>> {
+        let date = std::str::from_utf8(self.date.0.as_ref()?.as_ref()).ok()?;
+
+        ChronoDateTime::parse_from_rfc2822(date.trim()).ok()
+    }
+}
+
+pub(crate) fn is_empty_string(value: &NString) -> bool {
+    matches!(&value.0, Some(string) if string.as_ref().is_empty())
+}
+
 /// An address structure describes an electronic mail address.
 #[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
 #[cfg_attr(feature = "bounded-static", derive(ToStatic))]
@@ -53,3 +148,138 @@ pub struct Address<'a> {
     /// Host name
     pub host: NString<'a>,
 }
+
+/// Error-related types.
+pub mod error {
+    use thiserror::Error;
+
+    #[derive(Clone, Debug, Eq, Error, Hash, Ord, PartialEq, PartialOrd)]
+    pub enum EnvelopeError {
+        #[error("Date must be NIL or non-empty")]
+        EmptyDate,
+        #[error("In-Reply-To must be NIL or non-empty")]
+        EmptyInReplyTo,
+        #[error("Message-ID must be NIL or non-empty")]
+        EmptyMessageId,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn envelope(date: NString) -> Envelope {
+        Envelope {
+            date,
+            subject: NString(None),
+            from: vec![],
+            sender: vec![],
+            reply_to: vec![],
+            to: vec![],
+            cc: vec![],
+            bcc: vec![],
+            in_reply_to: NString(None),
+            message_id: NString(None),
+        }
+    }
+
+    fn address(mailbox: &'static str) -> Address<'static> {
+        Address {
+            name: NString(None),
+            adl: NString(None),
+            mailbox: NString::try_from(mailbox).unwrap(),
+            host: NString::try_from("example.com").unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_envelope_new() {
+        let date = NString::try_from("Wed, 17 Jul 1996 02:23:25 -0700").unwrap();
+        let message_id = NString::try_from("<1234@example.com>").unwrap();
+
+        let got = Envelope::new(
+            date.clone(),
+            NString::try_from("").unwrap(),
+            vec![address("alice")],
+            vec![],
+            vec![],
+            vec![address("bob")],
+            vec![],
+            vec![],
+            NString(None),
+            message_id.clone(),
+        )
+        .unwrap();
+
+        // An empty subject is fine ...
+        assert_eq!(got.subject, NString::try_from("").unwrap());
+        // ... and sender and reply-to default to from.
+        assert_eq!(got.sender, vec![address("alice")]);
+        assert_eq!(got.reply_to, vec![address("alice")]);
+
+        let got = Envelope::new(
+            NString(None),
+            NString(None),
+            vec![address("alice")],
+            vec![address("secretary")],
+            vec![address("list")],
+            vec![],
+            vec![],
+            vec![],
+            NString(None),
+            NString(None),
+        )
+        .unwrap();
+        assert_eq!(got.sender, vec![address("secretary")]);
+        assert_eq!(got.reply_to, vec![address("list")]);
+    }
+
+    #[test]
+    fn test_envelope_new_failing() {
+        let empty = || NString::try_from("").unwrap();
+        let new = |date, in_reply_to, message_id| {
+            Envelope::new(
+                date,
+                NString(None),
+                vec![],
+                vec![],
+                vec![],
+                vec![],
+                vec![],
+                vec![],
+                in_reply_to,
+                message_id,
+            )
+        };
+
+        assert_eq!(
+            new(empty(), NString(None), NString(None)),
+            Err(EnvelopeError::EmptyDate)
+        );
+        assert_eq!(
+            new(NString(None), empty(), NString(None)),
+            Err(EnvelopeError::EmptyInReplyTo)
+        );
+        assert_eq!(
+            new(NString(None), NString(None), empty()),
+            Err(EnvelopeError::EmptyMessageId)
+        );
+    }
+
+    #[test]
+    fn test_parsed_date() {
+        let date = envelope(NString::try_from("Wed, 17 Jul 1996 02:23:25 -0700").unwrap())
+            .parsed_date()
+            .unwrap();
+        assert_eq!(
+            date,
+            ChronoDateTime::parse_from_rfc3339("1996-07-17T02:23:25-07:00").unwrap()
+        );
+
+        assert_eq!(envelope(NString(None)).parsed_date(), None);
+        assert_eq!(
+            envelope(NString::try_from("yesterday at noon").unwrap()).parsed_date(),
+            None
+        );
+    }
+}
@@ -1,33 +1,54 @@
 //! Error-related types.
 
-use std::fmt::{Display, Formatter};
+use core::fmt::{Display, Formatter};
 
-use thiserror::Error;
+// Note: `thiserror` requires `std`, so the errors of the `no_std`-compatible core types implement
+// `Display` (and `Error`) manually.
 
 /// A validation error.
 ///
 /// This error can be returned during validation of a value, e.g., a tag, atom, etc.
-#[derive(Clone, Debug, Eq, Error, Hash, Ord, PartialEq, PartialOrd)]
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct ValidationError {
     kind: ValidationErrorKind,
 }
 
 impl Display for ValidationError {
-    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
         write!(f, "Validation failed: {}", self.kind)
     }
 }
 
-#[derive(Clone, Debug, Eq, Error, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg(feature = "std")]
+impl std::error::Error for ValidationError {}
+
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub(crate) enum ValidationErrorKind {
-    #[error("Must not be empty")]
     Empty,
-    #[error("Must have at least {min} elements")]
-    NotEnough { min: usize },
-    #[error("Invalid value")]
+    NotEnough {
+        min: usize,
+    },
     Invalid,
-    #[error("Invalid byte b'\\x{byte:02x}' at index {at}")]
-    InvalidByteAt { byte: u8, at: usize },
+    #[cfg_attr(not(feature = "std"), allow(dead_code))]
+    ZeroNotAllowed,
+    InvalidByteAt {
+        byte: u8,
+        at: usize,
+    },
+}
+
+impl Display for ValidationErrorKind {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+        match self {
+            Self::Empty => write!(f, "Must not be empty"),
+            Self::NotEnough { min } => write!(f, "Must have at least {min} elements"),
+            Self::Invalid => write!(f, "Invalid value"),
+            Self::ZeroNotAllowed => write!(f, "Must not be zero"),
+            Self::InvalidByteAt { byte, at } => {
+                write!(f, "Invalid byte b'\\x{byte:02x}' at index {at}")
+            }
+        }
+    }
 }
 
 impl ValidationError {
@@ -1,16 +1,51 @@
 //! IMAP extensions.
 
+#[cfg(feature = "ext_acl")]
+pub mod acl;
 #[cfg(feature = "ext_binary")]
 pub mod binary;
 pub mod compress;
+#[cfg(feature = "ext_condstore_qresync")]
+pub mod condstore_qresync;
 pub mod enable;
+#[cfg(feature = "ext_esearch")]
+pub mod esearch;
+#[cfg(feature = "ext_id")]
+pub mod id;
 pub mod idle;
+#[cfg(feature = "ext_list_extended")]
+pub mod list_extended;
+#[cfg(feature = "ext_list_status")]
+pub mod list_status;
 #[cfg(feature = "ext_metadata")]
 pub mod metadata;
 pub mod r#move;
+#[cfg(feature = "ext_multiappend")]
+pub mod multiappend;
+#[cfg(feature = "ext_namespace")]
+pub mod namespace;
+#[cfg(feature = "ext_objectid")]
+pub mod objectid;
+#[cfg(feature = "ext_partial")]
+pub mod partial;
+#[cfg(feature = "ext_preview")]
+pub mod preview;
 pub mod quota;
+#[cfg(feature = "ext_savedate")]
+pub mod savedate;
+#[cfg(feature = "ext_searchres")]
+pub mod searchres;
 #[cfg(feature = "ext_sort_thread")]
 pub mod sort;
+#[cfg(feature = "ext_special_use")]
+pub mod special_use;
+#[cfg(feature = "ext_status_size")]
+pub mod status_size;
 #[cfg(feature = "ext_sort_thread")]
 pub mod thread;
+#[cfg(feature = "ext_unauthenticate")]
+pub mod unauthenticate;
 pub mod unselect;
+pub mod url;
+#[cfg(feature = "ext_utf8")]
+pub mod utf8;
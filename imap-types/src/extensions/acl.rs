@@ -0,0 +1,311 @@
+//! The IMAP4 Access Control List (ACL) Extension (RFC 4314)
+//!
+//! This extends ...
+//!
+//! * [`Capability`](crate::response::Capability) with a new variant:
+//!
+//!     - [`Capability::Acl`](crate::response::Capability::Acl)
+//!
+//! * [`CommandBody`] with new variants:
+//!
+//!     - [`CommandBody::SetAcl`]
+//!     - [`CommandBody::DeleteAcl`]
+//!     - [`CommandBody::GetAcl`]
+//!     - [`CommandBody::ListRights`]
+//!     - [`CommandBody::MyRights`]
+//!
+//! * [`Data`](crate::response::Data) with new variants:
+//!
+//!     - [`Data::Acl`](crate::response::Data::Acl)
+//!     - [`Data::ListRights`](crate::response::Data::ListRights)
+//!     - [`Data::MyRights`](crate::response::Data::MyRights)
+
+#[cfg(feature = "arbitrary")]
+use arbitrary::{Arbitrary, Unstructured};
+#[cfg(feature = "bounded-static")]
+use bounded_static::ToStatic;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "arbitrary")]
+use crate::arbitrary::impl_arbitrary_try_from;
+use crate::{
+    command::CommandBody,
+    core::AString,
+    error::{ValidationError, ValidationErrorKind},
+    extensions::acl::error::{AclError, SetAclError},
+    mailbox::Mailbox,
+};
+
+impl<'a> CommandBody<'a> {
+    /// Construct a SETACL command.
+    #[allow(clippy::type_complexity)]
+    pub fn set_acl<M, I, R>(
+        mailbox: M,
+        identifier: I,
+        rights: R,
+    ) -> Result<Self, SetAclError<M::Error, I::Error, R::Error>>
+    where
+        M: TryInto<Mailbox<'a>>,
+        I: TryInto<AString<'a>>,
+        R: TryInto<AclRights<'a>>,
+    {
+        Ok(CommandBody::SetAcl {
+            mailbox: mailbox.try_into().map_err(SetAclError::Mailbox)?,
+            identifier: identifier.try_into().map_err(SetAclError::Identifier)?,
+            rights: rights.try_into().map_err(SetAclError::Rights)?,
+        })
+    }
+
+    /// Construct a DELETEACL command.
+    pub fn delete_acl<M, I>(mailbox: M, identifier: I) -> Result<Self, AclError<M::Error, I::Error>>
+    where
+        M: TryInto<Mailbox<'a>>,
+        I: TryInto<AString<'a>>,
+    {
+        Ok(CommandBody::DeleteAcl {
+            mailbox: mailbox.try_into().map_err(AclError::Mailbox)?,
+            identifier: identifier.try_into().map_err(AclError::Identifier)?,
+        })
+    }
+
+    /// Construct a GETACL command.
+    pub fn get_acl<M>(mailbox: M) -> Result<Self, M::Error>
+    where
+        M: TryInto<Mailbox<'a>>,
+    {
+        Ok(CommandBody::GetAcl {
+            mailbox: mailbox.try_into()?,
+        })
+    }
+
+    /// Construct a LISTRIGHTS command.
+    pub fn list_rights<M, I>(
+        mailbox: M,
+        identifier: I,
+    ) -> Result<Self, AclError<M::Error, I::Error>>
+    where
+        M: TryInto<Mailbox<'a>>,
+        I: TryInto<AString<'a>>,
+    {
+        Ok(CommandBody::ListRights {
+            mailbox: mailbox.try_into().map_err(AclError::Mailbox)?,
+            identifier: identifier.try_into().map_err(AclError::Identifier)?,
+        })
+    }
+
+    /// Construct a MYRIGHTS command.
+    pub fn my_rights<M>(mailbox: M) -> Result<Self, M::Error>
+    where
+        M: TryInto<Mailbox<'a>>,
+    {
+        Ok(CommandBody::MyRights {
+            mailbox: mailbox.try_into()?,
+        })
+    }
+}
+
+/// An identifier and the rights granted to it.
+///
+/// Used in the ACL response.
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "bounded-static", derive(ToStatic))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AclEntry<'a> {
+    /// Identifier, e.g., a user or group name.
+    pub identifier: AString<'a>,
+    /// Rights granted to the identifier.
+    pub rights: AclRights<'a>,
+}
+
+impl<'a> AclEntry<'a> {
+    pub fn new(identifier: AString<'a>, rights: AclRights<'a>) -> Self {
+        Self { identifier, rights }
+    }
+}
+
+/// A set of rights, e.g., `lrswi`.
+///
+/// Rights are single lowercase letters (`lrswipkxtecda`) or digits (reserved for
+/// implementation-defined rights). In SETACL, the rights may be prefixed with `+` (add rights) or
+/// `-` (remove rights).
+///
+/// ```abnf
+/// rights     = astring
+///              ; only lowercase ASCII letters and digits are allowed.
+///
+/// mod-rights = astring
+///              ; +rights to add, -rights to remove
+///              ; rights to replace
+/// ```
+#[cfg_attr(feature = "bounded-static", derive(ToStatic))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AclRights<'a>(AString<'a>);
+
+#[cfg(feature = "arbitrary")]
+impl_arbitrary_try_from! { AclRights<'a>, &str }
+
+impl<'a> AclRights<'a> {
+    /// Validates if value conforms to `mod-rights`.
+    pub fn validate(value: impl AsRef<[u8]>) -> Result<(), ValidationError> {
+        let value = value.as_ref();
+
+        let offset = match value.first() {
+            Some(b'+' | b'-') => 1,
+            _ => 0,
+        };
+
+        if let Some(at) = value[offset..].iter().position(|b| !is_acl_right(*b)) {
+            return Err(ValidationError::new(ValidationErrorKind::InvalidByteAt {
+                byte: value[offset + at],
+                at: offset + at,
+            }));
+        }
+
+        Ok(())
+    }
+
+    /// Returns the modifier, i.e., whether the rights should be added or removed (SETACL only).
+    pub fn modifier(&self) -> Option<AclModifier> {
+        match self.as_ref().first() {
+            Some(b'+') => Some(AclModifier::Add),
+            Some(b'-') => Some(AclModifier::Remove),
+            _ => None,
+        }
+    }
+
+    /// Returns the rights without the modifier.
+    pub fn rights(&self) -> &[u8] {
+        match self.modifier() {
+            Some(_) => &self.as_ref()[1..],
+            None => self.as_ref(),
+        }
+    }
+
+    pub fn inner(&self) -> &AString<'a> {
+        &self.0
+    }
+
+    pub fn into_inner(self) -> AString<'a> {
+        self.0
+    }
+}
+
+/// Returns `true` for the RFC 4314 rights (`lrswipkxtecda`) and digits.
+fn is_acl_right(byte: u8) -> bool {
+    matches!(
+        byte,
+        b'l' | b'r'
+            | b's'
+            | b'w'
+            | b'i'
+            | b'p'
+            | b'k'
+            | b'x'
+            | b't'
+            | b'e'
+            | b'c'
+            | b'd'
+            | b'a'
+            | b'0'..=b'9'
+    )
+}
+
+macro_rules! impl_try_from_acl_rights {
+    ($from:ty) => {
+        impl<'a> TryFrom<$from> for AclRights<'a> {
+            type Error = ValidationError;
+
+            fn try_from(value: $from) -> Result<Self, Self::Error> {
+                Self::validate(&value)?;
+
+                Ok(Self(AString::try_from(value)?))
+            }
+        }
+    };
+}
+
+impl_try_from_acl_rights!(&'a [u8]);
+impl_try_from_acl_rights!(Vec<u8>);
+impl_try_from_acl_rights!(&'a str);
+impl_try_from_acl_rights!(String);
+
+impl<'a> TryFrom<AString<'a>> for AclRights<'a> {
+    type Error = ValidationError;
+
+    fn try_from(value: AString<'a>) -> Result<Self, Self::Error> {
+        Self::validate(&value)?;
+
+        Ok(Self(value))
+    }
+}
+
+impl<'a> AsRef<[u8]> for AclRights<'a> {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_ref()
+    }
+}
+
+/// Modifier of [`AclRights`] used in SETACL.
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "bounded-static", derive(ToStatic))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AclModifier {
+    /// Add the rights (`+`).
+    Add,
+    /// Remove the rights (`-`).
+    Remove,
+}
+
+/// Error-related types.
+pub mod error {
+    use thiserror::Error;
+
+    #[derive(Clone, Debug, Eq, Error, Hash, Ord, PartialEq, PartialOrd)]
+    pub enum AclError<M, I> {
+        #[error("Invalid mailbox: {0}")]
+        Mailbox(M),
+        #[error("Invalid identifier: {0}")]
+        Identifier(I),
+    }
+
+    #[derive(Clone, Debug, Eq, Error, Hash, Ord, PartialEq, PartialOrd)]
+    pub enum SetAclError<M, I, R> {
+        #[error("Invalid mailbox: {0}")]
+        Mailbox(M),
+        #[error("Invalid identifier: {0}")]
+        Identifier(I),
+        #[error("Invalid rights: {0}")]
+        Rights(R),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_acl_rights() {
+        for (test, modifier, rights) in [
+            ("", None, b"".as_ref()),
+            ("lrswipkxtecda", None, b"lrswipkxtecda".as_ref()),
+            ("+rw", Some(AclModifier::Add), b"rw".as_ref()),
+            ("-x", Some(AclModifier::Remove), b"x".as_ref()),
+            ("0123456789", None, b"0123456789".as_ref()),
+        ] {
+            let got = AclRights::try_from(test).unwrap();
+            assert_eq!(got.modifier(), modifier);
+            assert_eq!(got.rights(), rights);
+        }
+    }
+
+    #[test]
+    fn test_acl_rights_failing() {
+        for test in ["rwz", "RW", "+-r", "r+w", "r w", "\"r\""] {
+            assert!(AclRights::try_from(test).is_err(), "{test}");
+        }
+    }
+}
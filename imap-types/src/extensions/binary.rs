@@ -22,6 +22,12 @@ use crate::core::{Literal, LiteralMode};
 pub enum LiteralOrLiteral8<'a> {
     Literal(Literal<'a>),
     Literal8(Literal8<'a>),
+    /// Message wrapped in `UTF8 (...)` (RFC 6855).
+    ///
+    /// Note: Use this only when the client enabled `UTF8=ACCEPT`.
+    #[cfg(feature = "ext_utf8")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext_utf8")))]
+    Utf8(Literal8<'a>),
 }
 
 /// String that might contain NULs.
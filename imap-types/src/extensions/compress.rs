@@ -48,7 +48,7 @@ impl Display for CompressionAlgorithm {
     }
 }
 
-impl<'a> TryFrom<&'a str> for CompressionAlgorithm {
+impl TryFrom<&str> for CompressionAlgorithm {
     type Error = ValidationError;
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
@@ -59,7 +59,7 @@ impl<'a> TryFrom<&'a str> for CompressionAlgorithm {
     }
 }
 
-impl<'a> TryFrom<&'a [u8]> for CompressionAlgorithm {
+impl TryFrom<&[u8]> for CompressionAlgorithm {
     type Error = ValidationError;
 
     fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
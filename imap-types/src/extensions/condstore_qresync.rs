@@ -0,0 +1,129 @@
+//! The IMAP CONDSTORE and QRESYNC extensions (RFC 7162)
+
+use std::num::NonZeroU64;
+
+#[cfg(feature = "arbitrary")]
+use arbitrary::Arbitrary;
+#[cfg(feature = "bounded-static")]
+use bounded_static::ToStatic;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "ext_partial")]
+use crate::extensions::partial::PartialRange;
+use crate::{
+    command::CommandBody,
+    fetch::{MacroOrMessageDataItemNames, MessageDataItemName},
+    response::{Code, Status},
+    sequence::SequenceSet,
+};
+
+/// Modifier of a FETCH command.
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "bounded-static", derive(ToStatic))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum FetchModifier {
+    /// Only fetch messages with a mod-sequence greater than the given value (`CHANGEDSINCE`).
+    ChangedSince(NonZeroU64),
+    /// Only fetch the messages within the given range of the sequence set (`PARTIAL`, RFC 9394).
+    #[cfg(feature = "ext_partial")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext_partial")))]
+    Partial(PartialRange),
+}
+
+/// Modifier of a STORE command.
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "bounded-static", derive(ToStatic))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum StoreModifier {
+    /// Only store flags of messages with a mod-sequence less than or equal to the given value
+    /// (`UNCHANGEDSINCE`).
+    UnchangedSince(u64),
+}
+
+impl<'a> Status<'a> {
+    /// Return the messages that failed the `UNCHANGEDSINCE` test of a STORE command (`MODIFIED`).
+    pub fn modified(&self) -> Option<&SequenceSet> {
+        match self.code() {
+            Some(Code::Modified(sequence_set)) => Some(sequence_set),
+            _ => None,
+        }
+    }
+}
+
+impl<'a> CommandBody<'a> {
+    /// Return the FETCH command to re-fetch the messages reported as `MODIFIED` after this STORE
+    /// command.
+    ///
+    /// A conditional STORE is not applied to messages that were modified in the meantime. The
+    /// client must fetch their flags (and mod-sequence) again before it can retry the STORE. The
+    /// FETCH command uses UIDs if, and only if, the STORE command did.
+    ///
+    /// `None` is returned when this is not a STORE command or `status` has no `MODIFIED` code.
+    pub fn refetch_modified(&self, status: &Status) -> Option<CommandBody<'static>> {
+        let (Self::Store { uid, .. } | Self::StoreWithModifiers { uid, .. }) = self else {
+            return None;
+        };
+
+        Some(CommandBody::Fetch {
+            sequence_set: status.modified()?.clone(),
+            macro_or_item_names: MacroOrMessageDataItemNames::MessageDataItemNames(vec![
+                MessageDataItemName::Flags,
+                MessageDataItemName::ModSeq,
+            ]),
+            uid: *uid,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        core::Vec1,
+        flag::{Flag, StoreResponse, StoreType},
+    };
+
+    #[test]
+    fn test_refetch_modified() {
+        let status = Status::ok(
+            Some("A".try_into().unwrap()),
+            Some(Code::Modified(SequenceSet::try_from("7,9").unwrap())),
+            "Conditional STORE failed",
+        )
+        .unwrap();
+        assert_eq!(
+            status.modified(),
+            Some(&SequenceSet::try_from("7,9").unwrap())
+        );
+
+        for uid in [false, true] {
+            let store = CommandBody::StoreWithModifiers {
+                sequence_set: SequenceSet::try_from("7:9").unwrap(),
+                kind: StoreType::Add,
+                response: StoreResponse::Answer,
+                flags: vec![Flag::Seen],
+                uid,
+                modifiers: Vec1::from(StoreModifier::UnchangedSince(12345)),
+            };
+
+            assert_eq!(
+                store.refetch_modified(&status),
+                Some(CommandBody::Fetch {
+                    sequence_set: SequenceSet::try_from("7,9").unwrap(),
+                    macro_or_item_names: MacroOrMessageDataItemNames::MessageDataItemNames(vec![
+                        MessageDataItemName::Flags,
+                        MessageDataItemName::ModSeq,
+                    ]),
+                    uid,
+                })
+            );
+        }
+
+        let status = Status::ok(Some("A".try_into().unwrap()), None, "STORE completed").unwrap();
+        assert_eq!(status.modified(), None);
+        assert_eq!(CommandBody::Noop.refetch_modified(&status), None);
+    }
+}
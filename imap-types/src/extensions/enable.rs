@@ -19,6 +19,7 @@ use crate::{
     command::CommandBody,
     core::{Atom, Vec1},
     error::ValidationError,
+    response::Data,
 };
 
 impl<'a> CommandBody<'a> {
@@ -32,6 +33,31 @@ impl<'a> CommandBody<'a> {
     }
 }
 
+impl<'a> Data<'a> {
+    /// Whether `capability` is listed in an ENABLED response.
+    ///
+    /// Use this to confirm that the server actually enabled, e.g., CONDSTORE or QRESYNC. Unknown
+    /// capabilities are compared case-insensitively. Other responses never contain a capability.
+    pub fn is_enabled(&self, capability: &CapabilityEnable) -> bool {
+        match self {
+            Data::Enabled { capabilities } => {
+                capabilities
+                    .iter()
+                    .any(|enabled| match (enabled, capability) {
+                        (CapabilityEnable::Other(enabled), CapabilityEnable::Other(capability)) => {
+                            enabled
+                                .0
+                                .as_ref()
+                                .eq_ignore_ascii_case(capability.0.as_ref())
+                        }
+                        _ => enabled == capability,
+                    })
+            }
+            _ => false,
+        }
+    }
+}
+
 #[cfg_attr(feature = "bounded-static", derive(ToStatic))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -0,0 +1,69 @@
+//! IMAP4 Extension for Returning SEARCH Results in Extended Format
+//!
+//! This extension extends ...
+//!
+//! * the [Capability](crate::response::Capability) enum with a new variant [Capability::ESearch](crate::response::Capability#variant.ESearch),
+//! * the [CommandBody](crate::command::CommandBody) enum with a new variant [CommandBody::SearchExtended](crate::command::CommandBody#variant.SearchExtended), and
+//! * the [Data](crate::response::Data) enum with a new variant [Data::ESearch](crate::response::Data#variant.ESearch).
+
+use std::num::NonZeroU32;
+
+#[cfg(feature = "arbitrary")]
+use arbitrary::Arbitrary;
+#[cfg(feature = "bounded-static")]
+use bounded_static::ToStatic;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "ext_partial")]
+use crate::extensions::partial::PartialRange;
+use crate::sequence::SequenceSet;
+
+/// Result option of a SEARCH command (`RETURN (...)`).
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "bounded-static", derive(ToStatic))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SearchReturnOption {
+    /// Return the lowest message number/UID that satisfies the SEARCH criteria.
+    Min,
+    /// Return the highest message number/UID that satisfies the SEARCH criteria.
+    Max,
+    /// Return all message numbers/UIDs that satisfy the SEARCH criteria.
+    All,
+    /// Return the number of messages that satisfy the SEARCH criteria.
+    Count,
+    /// Save the result of the search so that it can be referenced with `$` (RFC 5182).
+    #[cfg(feature = "ext_searchres")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext_searchres")))]
+    Save,
+    /// Return the message numbers/UIDs within the given range of results (RFC 9394).
+    #[cfg(feature = "ext_partial")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext_partial")))]
+    Partial(PartialRange),
+}
+
+/// Result data of an ESEARCH response.
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "bounded-static", derive(ToStatic))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ESearchReturn {
+    /// Lowest message number/UID that satisfied the SEARCH criteria.
+    Min(NonZeroU32),
+    /// Highest message number/UID that satisfied the SEARCH criteria.
+    Max(NonZeroU32),
+    /// All message numbers/UIDs that satisfied the SEARCH criteria.
+    All(SequenceSet),
+    /// Number of messages that satisfied the SEARCH criteria.
+    Count(u32),
+    /// Message numbers/UIDs within the requested range of results (RFC 9394).
+    ///
+    /// `results` is `None` (`NIL`) when there are no results in the range.
+    #[cfg(feature = "ext_partial")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext_partial")))]
+    Partial {
+        range: PartialRange,
+        results: Option<SequenceSet>,
+    },
+}
@@ -0,0 +1,133 @@
+//! IMAP4 ID extension
+//!
+//! This extension extends ...
+//!
+//! * the [Capability](crate::response::Capability) enum with a new variant [Capability::Id](crate::response::Capability#variant.Id),
+//! * the [CommandBody] enum with a new variant [CommandBody::Id], and
+//! * the [Data] enum with a new variant [Data::Id](crate::response::Data#variant.Id).
+
+use crate::{
+    command::CommandBody,
+    core::{IString, NString},
+    extensions::id::error::IdError,
+    response::Data,
+};
+
+impl<'a> CommandBody<'a> {
+    /// Create an ID command from a flat `field value field value ...` list.
+    ///
+    /// `None` is encoded as `ID NIL`. Fails when the list has an odd number of elements or when a
+    /// field is `NIL`.
+    pub fn id(list: Option<Vec<NString<'a>>>) -> Result<Self, IdError> {
+        Ok(CommandBody::Id {
+            parameters: list.map(pair_up).transpose()?,
+        })
+    }
+}
+
+impl<'a> Data<'a> {
+    /// Create an ID response from a flat `field value field value ...` list.
+    ///
+    /// See [`CommandBody::id`].
+    pub fn id(list: Option<Vec<NString<'a>>>) -> Result<Self, IdError> {
+        Ok(Data::Id {
+            parameters: list.map(pair_up).transpose()?,
+        })
+    }
+}
+
+fn pair_up(list: Vec<NString>) -> Result<Vec<(IString, NString)>, IdError> {
+    if list.len() % 2 != 0 {
+        return Err(IdError::OddNumberOfElements { got: list.len() });
+    }
+
+    let mut parameters = Vec::with_capacity(list.len() / 2);
+    let mut iter = list.into_iter().enumerate();
+
+    while let (Some((index, field)), Some((_, value))) = (iter.next(), iter.next()) {
+        match field.0 {
+            Some(field) => parameters.push((field, value)),
+            None => return Err(IdError::NilField { index }),
+        }
+    }
+
+    Ok(parameters)
+}
+
+/// Error-related types.
+pub mod error {
+    use thiserror::Error;
+
+    #[derive(Clone, Debug, Eq, Error, Hash, Ord, PartialEq, PartialOrd)]
+    pub enum IdError {
+        #[error("Expected an even number of elements, got {got}")]
+        OddNumberOfElements { got: usize },
+        #[error("Field at index {index} must not be NIL")]
+        NilField { index: usize },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_id_from_list() {
+        let got = CommandBody::id(Some(vec![
+            NString::try_from("name").unwrap(),
+            NString::try_from("Cyrus").unwrap(),
+            NString::try_from("version").unwrap(),
+            NString(None),
+        ]))
+        .unwrap();
+
+        assert_eq!(
+            CommandBody::Id {
+                parameters: Some(vec![
+                    (
+                        IString::try_from("name").unwrap(),
+                        NString::try_from("Cyrus").unwrap()
+                    ),
+                    (IString::try_from("version").unwrap(), NString(None)),
+                ])
+            },
+            got
+        );
+
+        assert_eq!(
+            CommandBody::id(None).unwrap(),
+            CommandBody::Id { parameters: None }
+        );
+        assert_eq!(
+            Data::id(Some(vec![])).unwrap(),
+            Data::Id {
+                parameters: Some(vec![])
+            }
+        );
+    }
+
+    #[test]
+    fn test_id_from_list_failing() {
+        assert_eq!(
+            CommandBody::id(Some(vec![
+                NString::try_from("name").unwrap(),
+                NString::try_from("Cyrus").unwrap(),
+                NString::try_from("version").unwrap(),
+            ])),
+            Err(IdError::OddNumberOfElements { got: 3 })
+        );
+        assert_eq!(
+            Data::id(Some(vec![NString::try_from("name").unwrap()])),
+            Err(IdError::OddNumberOfElements { got: 1 })
+        );
+        assert_eq!(
+            CommandBody::id(Some(vec![
+                NString::try_from("name").unwrap(),
+                NString::try_from("Cyrus").unwrap(),
+                NString(None),
+                NString::try_from("1.5").unwrap(),
+            ])),
+            Err(IdError::NilField { index: 2 })
+        );
+    }
+}
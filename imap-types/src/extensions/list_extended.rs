@@ -0,0 +1,80 @@
+//! IMAP4 LIST Command Extensions
+//!
+//! This extension extends ...
+//!
+//! * the [Capability](crate::response::Capability) enum with a new variant [Capability::ListExtended](crate::response::Capability#variant.ListExtended),
+//! * the [CommandBody](crate::command::CommandBody) enum with a new variant [CommandBody::ListExtended](crate::command::CommandBody#variant.ListExtended) for selection options, multiple mailbox patterns, and return options, and
+//! * the [FlagNameAttribute](crate::flag::FlagNameAttribute) enum with the `\HasChildren`, `\HasNoChildren`, and `\NonExistent` attributes.
+
+#[cfg(feature = "arbitrary")]
+use arbitrary::Arbitrary;
+#[cfg(feature = "bounded-static")]
+use bounded_static::ToStatic;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "ext_list_status")]
+use crate::status::StatusDataItemName;
+use crate::{core::Vec1, mailbox::ListMailbox};
+
+/// Mailbox pattern(s) of a LIST command.
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "bounded-static", derive(ToStatic))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum MailboxPatterns<'a> {
+    /// A single pattern, e.g., `"*"`.
+    Single(ListMailbox<'a>),
+    /// A parenthesized list of patterns, e.g., `("INBOX" "Drafts")`.
+    Multiple(Vec1<ListMailbox<'a>>),
+}
+
+impl<'a> From<ListMailbox<'a>> for MailboxPatterns<'a> {
+    fn from(pattern: ListMailbox<'a>) -> Self {
+        Self::Single(pattern)
+    }
+}
+
+impl<'a> MailboxPatterns<'a> {
+    /// Returns all patterns.
+    pub fn as_slice(&self) -> &[ListMailbox<'a>] {
+        match self {
+            Self::Single(pattern) => std::slice::from_ref(pattern),
+            Self::Multiple(patterns) => patterns.as_ref(),
+        }
+    }
+}
+
+/// Selection option of a LIST command.
+///
+/// Note: `RECURSIVEMATCH` must not be used without a base option, i.e., `SUBSCRIBED`.
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "bounded-static", derive(ToStatic))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ListSelectionOption {
+    /// Only return subscribed mailboxes (`SUBSCRIBED`).
+    Subscribed,
+    /// Also return remote mailboxes (`REMOTE`).
+    Remote,
+    /// Also return parents of matched mailboxes (`RECURSIVEMATCH`).
+    RecursiveMatch,
+}
+
+/// Return option of a LIST command.
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "bounded-static", derive(ToStatic))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ListReturnOption {
+    /// Return the `\Subscribed` attribute (`SUBSCRIBED`).
+    Subscribed,
+    /// Return the `\HasChildren` and `\HasNoChildren` attributes (`CHILDREN`).
+    Children,
+    /// Return a STATUS response for every listed mailbox (`STATUS (...)`).
+    ///
+    /// See [`ListStatus::associate`](crate::extensions::list_status::ListStatus::associate).
+    #[cfg(feature = "ext_list_status")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext_list_status")))]
+    Status(Vec1<StatusDataItemName>),
+}
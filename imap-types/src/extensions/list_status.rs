@@ -0,0 +1,136 @@
+//! IMAP4 Extension for Returning STATUS Information in Extended LIST
+//!
+//! This extension extends ...
+//!
+//! * the [Capability](crate::response::Capability) enum with a new variant [Capability::ListStatus](crate::response::Capability#variant.ListStatus), and
+//! * the [ListReturnOption](crate::extensions::list_extended::ListReturnOption) enum with a new variant [ListReturnOption::Status](crate::extensions::list_extended::ListReturnOption#variant.Status).
+//!
+//! The server answers with a `* STATUS` response after every matching `* LIST` response.
+//! Use [`ListStatus::associate`] to pair them up.
+
+use std::borrow::Cow;
+
+#[cfg(feature = "arbitrary")]
+use arbitrary::Arbitrary;
+#[cfg(feature = "bounded-static")]
+use bounded_static::ToStatic;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    core::QuotedChar, flag::FlagNameAttribute, mailbox::Mailbox, response::Data,
+    status::StatusDataItem,
+};
+
+/// A mailbox of a LIST response together with its (optional) STATUS response.
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "bounded-static", derive(ToStatic))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ListStatus<'a> {
+    /// Name attributes
+    pub items: Vec<FlagNameAttribute<'a>>,
+    /// Hierarchy delimiter
+    pub delimiter: Option<QuotedChar>,
+    /// Name
+    pub mailbox: Mailbox<'a>,
+    /// Status parenthesized list
+    ///
+    /// `None` when the server didn't send a STATUS response, e.g., for a `\NoSelect` mailbox.
+    pub status: Option<Cow<'a, [StatusDataItem]>>,
+}
+
+impl<'a> ListStatus<'a> {
+    /// Associates every STATUS response with the preceding LIST response of the same mailbox.
+    ///
+    /// The result is in LIST order. Other data, and STATUS responses without a preceding LIST
+    /// response, are ignored.
+    pub fn associate<I>(data: I) -> Vec<Self>
+    where
+        I: IntoIterator<Item = Data<'a>>,
+    {
+        let mut associated: Vec<Self> = Vec::new();
+
+        for data in data {
+            match data {
+                Data::List {
+                    items,
+                    delimiter,
+                    mailbox,
+                } => associated.push(Self {
+                    items,
+                    delimiter,
+                    mailbox,
+                    status: None,
+                }),
+                Data::Status { mailbox, items } => {
+                    if let Some(list) = associated
+                        .iter_mut()
+                        .rev()
+                        .find(|list| list.mailbox == mailbox)
+                    {
+                        list.status = Some(items);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        associated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_associate() {
+        let data = vec![
+            Data::List {
+                items: vec![],
+                delimiter: Some(QuotedChar::try_from('/').unwrap()),
+                mailbox: Mailbox::Inbox,
+            },
+            Data::Status {
+                mailbox: Mailbox::Inbox,
+                items: vec![StatusDataItem::Messages(17)].into(),
+            },
+            Data::List {
+                items: vec![FlagNameAttribute::Noselect],
+                delimiter: Some(QuotedChar::try_from('/').unwrap()),
+                mailbox: Mailbox::try_from("Archive").unwrap(),
+            },
+            Data::Exists(3),
+            Data::List {
+                items: vec![],
+                delimiter: Some(QuotedChar::try_from('/').unwrap()),
+                mailbox: Mailbox::try_from("Archive/2024").unwrap(),
+            },
+            Data::Status {
+                mailbox: Mailbox::try_from("Archive/2024").unwrap(),
+                items: vec![StatusDataItem::Unseen(2)].into(),
+            },
+            // No LIST response for this mailbox.
+            Data::Status {
+                mailbox: Mailbox::try_from("Drafts").unwrap(),
+                items: vec![StatusDataItem::Messages(1)].into(),
+            },
+        ];
+
+        let got = ListStatus::associate(data);
+
+        assert_eq!(got.len(), 3);
+        assert_eq!(got[0].mailbox, Mailbox::Inbox);
+        assert_eq!(
+            got[0].status.as_deref(),
+            Some([StatusDataItem::Messages(17)].as_ref())
+        );
+        assert_eq!(got[1].items, vec![FlagNameAttribute::Noselect]);
+        assert_eq!(got[1].status, None);
+        assert_eq!(
+            got[2].status.as_deref(),
+            Some([StatusDataItem::Unseen(2)].as_ref())
+        );
+    }
+}
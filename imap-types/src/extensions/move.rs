@@ -6,6 +6,13 @@ use crate::{
 };
 
 impl<'a> CommandBody<'a> {
+    /// Construct a MOVE command.
+    ///
+    /// The destination is validated as a [`Mailbox`]. "INBOX" (in any case) becomes
+    /// [`Mailbox::Inbox`] and is encoded as `INBOX`.
+    ///
+    /// Note: This constructor is always available (there is no `ext_move` feature). Only use it
+    /// when the server advertises the `MOVE` capability.
     pub fn r#move<S, M>(
         sequence_set: S,
         mailbox: M,
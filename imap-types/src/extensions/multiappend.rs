@@ -0,0 +1,84 @@
+//! IMAP MULTIAPPEND Extension
+//!
+//! This extension extends ...
+//!
+//! * the [Capability](crate::response::Capability) enum with a new variant [Capability::MultiAppend](crate::response::Capability#variant.MultiAppend), and
+//! * the [CommandBody](crate::command::CommandBody) enum with a new variant [CommandBody::MultiAppend](crate::command::CommandBody#variant.MultiAppend) to carry two or more [`AppendMessage`]s.
+
+#[cfg(feature = "arbitrary")]
+use arbitrary::Arbitrary;
+#[cfg(feature = "bounded-static")]
+use bounded_static::ToStatic;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "ext_binary")]
+use crate::extensions::binary::LiteralOrLiteral8;
+use crate::{
+    command::{error::AppendError, CommandBody},
+    core::{Literal, Vec2},
+    datetime::DateTime,
+    flag::Flag,
+    mailbox::Mailbox,
+};
+
+/// A single message of an APPEND command.
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "bounded-static", derive(ToStatic))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AppendMessage<'a> {
+    /// Flags.
+    pub flags: Vec<Flag<'a>>,
+    /// Datetime.
+    pub date: Option<DateTime>,
+    #[cfg(not(feature = "ext_binary"))]
+    /// Message to append.
+    pub message: Literal<'a>,
+    #[cfg(feature = "ext_binary")]
+    /// Message to append.
+    ///
+    /// Note: Use [`LiteralOrLiteral8::Literal8`] only when the server advertised [`Capability::Binary`](crate::response::Capability::Binary).
+    pub message: LiteralOrLiteral8<'a>,
+}
+
+impl<'a> AppendMessage<'a> {
+    pub fn new<D>(
+        flags: Vec<Flag<'a>>,
+        date: Option<DateTime>,
+        message: D,
+    ) -> Result<Self, D::Error>
+    where
+        D: TryInto<Literal<'a>>,
+    {
+        let message = message.try_into()?;
+
+        Ok(Self {
+            flags,
+            date,
+            #[cfg(not(feature = "ext_binary"))]
+            message,
+            #[cfg(feature = "ext_binary")]
+            message: LiteralOrLiteral8::Literal(message),
+        })
+    }
+}
+
+impl<'a> CommandBody<'a> {
+    /// Construct an APPEND command with multiple messages.
+    ///
+    /// Note: Use this only when the server advertised [`Capability::MultiAppend`](crate::response::Capability::MultiAppend).
+    pub fn multiappend<M, I>(
+        mailbox: M,
+        messages: I,
+    ) -> Result<Self, AppendError<M::Error, I::Error>>
+    where
+        M: TryInto<Mailbox<'a>>,
+        I: TryInto<Vec2<AppendMessage<'a>>>,
+    {
+        Ok(CommandBody::MultiAppend {
+            mailbox: mailbox.try_into().map_err(AppendError::Mailbox)?,
+            messages: messages.try_into().map_err(AppendError::Data)?,
+        })
+    }
+}
@@ -0,0 +1,40 @@
+//! IMAP4 Namespace
+//!
+//! This extension extends ...
+//!
+//! * the [Capability](crate::response::Capability) enum with a new variant [Capability::Namespace](crate::response::Capability#variant.Namespace),
+//! * the [CommandBody](crate::command::CommandBody) enum with a new variant [CommandBody::Namespace](crate::command::CommandBody#variant.Namespace), and
+//! * the [Data](crate::response::Data) enum with a new variant [Data::Namespace](crate::response::Data#variant.Namespace).
+
+#[cfg(feature = "arbitrary")]
+use arbitrary::Arbitrary;
+#[cfg(feature = "bounded-static")]
+use bounded_static::ToStatic;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::core::{IString, QuotedChar, Vec1};
+
+/// A single namespace as returned in a NAMESPACE response.
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "bounded-static", derive(ToStatic))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NamespaceDescriptor<'a> {
+    /// Prefix of the namespace, e.g., `""` or `"#shared/"`.
+    pub prefix: IString<'a>,
+    /// Hierarchy delimiter (or `NIL` for a flat namespace.)
+    pub delimiter: Option<QuotedChar>,
+    /// Namespace response extensions, i.e., `(name, values)` pairs.
+    pub extensions: Vec<(IString<'a>, Vec1<IString<'a>>)>,
+}
+
+impl<'a> NamespaceDescriptor<'a> {
+    pub fn new(prefix: IString<'a>, delimiter: Option<QuotedChar>) -> Self {
+        Self {
+            prefix,
+            delimiter,
+            extensions: Vec::new(),
+        }
+    }
+}
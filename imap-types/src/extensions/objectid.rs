@@ -0,0 +1,155 @@
+//! IMAP Extension for Object Identifiers
+//!
+//! This extension extends ...
+//!
+//! * the [Capability](crate::response::Capability) enum with a new variant [Capability::ObjectId](crate::response::Capability#variant.ObjectId),
+//! * the [Code](crate::response::Code) enum with a new variant [Code::MailboxId](crate::response::Code#variant.MailboxId),
+//! * the [MessageDataItemName](crate::fetch::MessageDataItemName) enum with new variants `EmailId` and `ThreadId`,
+//! * the [MessageDataItem](crate::fetch::MessageDataItem) enum with new variants `EmailId` and `ThreadId`, and
+//! * the [StatusDataItemName](crate::status::StatusDataItemName) and [StatusDataItem](crate::status::StatusDataItem) enums with a new variant `MailboxId`.
+
+use std::fmt::{Display, Formatter};
+
+#[cfg(feature = "arbitrary")]
+use arbitrary::{Arbitrary, Unstructured};
+#[cfg(feature = "bounded-static")]
+use bounded_static::ToStatic;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "arbitrary")]
+use crate::arbitrary::impl_arbitrary_try_from;
+use crate::error::{ValidationError, ValidationErrorKind};
+
+/// Object identifier of a mailbox, message, or thread.
+///
+/// ```abnf
+/// objectid = 1*255(ALPHA / DIGIT / "_" / "-")
+///            ; characters in object identifiers are case
+///            ; significant
+/// ```
+///
+/// Note: Object identifiers are short and ASCII-only. Thus, they are always owned. This keeps
+/// [`StatusDataItem`](crate::status::StatusDataItem) free of a lifetime.
+#[cfg_attr(feature = "bounded-static", derive(ToStatic))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ObjectId(String);
+
+#[cfg(feature = "arbitrary")]
+impl_arbitrary_try_from! { ObjectId, &str }
+
+impl ObjectId {
+    /// Maximum length of an object identifier.
+    pub const MAX_LEN: usize = 255;
+
+    pub fn validate(value: impl AsRef<[u8]>) -> Result<(), ValidationError> {
+        let value = value.as_ref();
+
+        if value.is_empty() {
+            return Err(ValidationError::new(ValidationErrorKind::Empty));
+        }
+
+        if value.len() > Self::MAX_LEN {
+            return Err(ValidationError::new(ValidationErrorKind::Invalid));
+        }
+
+        if let Some(at) = value.iter().position(|b| !is_objectid_char(*b)) {
+            return Err(ValidationError::new(ValidationErrorKind::InvalidByteAt {
+                byte: value[at],
+                at,
+            }));
+        }
+
+        Ok(())
+    }
+
+    pub fn inner(&self) -> &str {
+        self.0.as_ref()
+    }
+
+    pub fn into_inner(self) -> String {
+        self.0
+    }
+}
+
+/// `ALPHA / DIGIT / "_" / "-"`
+fn is_objectid_char(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || byte == b'_' || byte == b'-'
+}
+
+impl TryFrom<&[u8]> for ObjectId {
+    type Error = ValidationError;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        Self::validate(value)?;
+
+        // Safety: `unwrap` can't panic due to `validate`.
+        Ok(Self(String::from_utf8(value.to_vec()).unwrap()))
+    }
+}
+
+impl TryFrom<Vec<u8>> for ObjectId {
+    type Error = ValidationError;
+
+    fn try_from(value: Vec<u8>) -> Result<Self, Self::Error> {
+        Self::validate(&value)?;
+
+        // Safety: `unwrap` can't panic due to `validate`.
+        Ok(Self(String::from_utf8(value).unwrap()))
+    }
+}
+
+impl TryFrom<&str> for ObjectId {
+    type Error = ValidationError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Self::validate(value)?;
+
+        Ok(Self(value.to_owned()))
+    }
+}
+
+impl TryFrom<String> for ObjectId {
+    type Error = ValidationError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::validate(&value)?;
+
+        Ok(Self(value))
+    }
+}
+
+impl AsRef<str> for ObjectId {
+    fn as_ref(&self) -> &str {
+        self.0.as_ref()
+    }
+}
+
+impl Display for ObjectId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conversion_object_id() {
+        assert!(ObjectId::try_from("M00000001").is_ok());
+        assert!(ObjectId::try_from("F2212ea87-6097-4256-9d51-71338625").is_ok());
+        assert!(ObjectId::try_from("T_1").is_ok());
+        assert!(ObjectId::try_from("a".repeat(255)).is_ok());
+    }
+
+    #[test]
+    fn test_conversion_object_id_failing() {
+        assert!(ObjectId::try_from("").is_err());
+        assert!(ObjectId::try_from("a".repeat(256)).is_err());
+        assert!(ObjectId::try_from("M 1").is_err());
+        assert!(ObjectId::try_from("M.1").is_err());
+        assert!(ObjectId::try_from(b"M\x001".as_ref()).is_err());
+    }
+}
@@ -0,0 +1,174 @@
+//! IMAP PARTIAL Extension for Paged SEARCH and FETCH
+//!
+//! This extension extends ...
+//!
+//! * the [Capability](crate::response::Capability) enum with a new variant [Capability::Partial](crate::response::Capability#variant.Partial),
+//! * the [SearchReturnOption](crate::extensions::esearch::SearchReturnOption) enum with a new variant [SearchReturnOption::Partial](crate::extensions::esearch::SearchReturnOption#variant.Partial),
+//! * the [ESearchReturn](crate::extensions::esearch::ESearchReturn) enum with a new variant [ESearchReturn::Partial](crate::extensions::esearch::ESearchReturn#variant.Partial), and
+//! * the [FetchModifier](crate::extensions::condstore_qresync::FetchModifier) enum with a new variant [FetchModifier::Partial](crate::extensions::condstore_qresync::FetchModifier#variant.Partial).
+//!
+//! Note: FETCH modifiers are only available with `ext_condstore_qresync`. Thus, `ext_partial`
+//! enables `ext_condstore_qresync` (and `ext_esearch`).
+
+use std::fmt::{Display, Formatter};
+
+#[cfg(feature = "arbitrary")]
+use arbitrary::{Arbitrary, Unstructured};
+#[cfg(feature = "bounded-static")]
+use bounded_static::ToStatic;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::extensions::partial::error::PartialRangeError;
+
+/// Range of a paged SEARCH or FETCH, e.g., `1:50` or `-50:-1`.
+///
+/// ```abnf
+/// partial-range = partial-range-first / partial-range-last
+///
+/// partial-range-first = nz-number ":" nz-number
+///
+/// partial-range-last = MINUS nz-number ":" MINUS nz-number
+/// ```
+///
+/// Positive ranges count from the first (oldest) result, negative ranges count from the last
+/// (newest) result, i.e., `-1` is the last result. A range is never empty: Both anchors must
+/// be non-zero, have the same sign, and `from <= to` must hold.
+#[cfg_attr(feature = "bounded-static", derive(ToStatic))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PartialRange {
+    from: i64,
+    to: i64,
+}
+
+impl PartialRange {
+    pub fn new(from: i64, to: i64) -> Result<Self, PartialRangeError> {
+        Self::validate(from, to)?;
+
+        Ok(Self { from, to })
+    }
+
+    pub fn validate(from: i64, to: i64) -> Result<(), PartialRangeError> {
+        for anchor in [from, to] {
+            if anchor == 0 {
+                return Err(PartialRangeError::Zero);
+            }
+
+            if anchor.unsigned_abs() > u64::from(u32::MAX) {
+                return Err(PartialRangeError::OutOfRange { anchor });
+            }
+        }
+
+        if from.signum() != to.signum() {
+            return Err(PartialRangeError::MixedSigns { from, to });
+        }
+
+        if from > to {
+            return Err(PartialRangeError::Inverted { from, to });
+        }
+
+        Ok(())
+    }
+
+    /// Returns the first anchor of the range.
+    pub fn from(&self) -> i64 {
+        self.from
+    }
+
+    /// Returns the second anchor of the range.
+    pub fn to(&self) -> i64 {
+        self.to
+    }
+
+    /// Returns `true` when the range counts from the last (newest) result.
+    pub fn is_last(&self) -> bool {
+        self.from < 0
+    }
+}
+
+impl TryFrom<(i64, i64)> for PartialRange {
+    type Error = PartialRangeError;
+
+    fn try_from((from, to): (i64, i64)) -> Result<Self, Self::Error> {
+        Self::new(from, to)
+    }
+}
+
+impl Display for PartialRange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.from, self.to)
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> Arbitrary<'a> for PartialRange {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        let a = i64::from(u.int_in_range(1..=u32::MAX)?);
+        let b = i64::from(u.int_in_range(1..=u32::MAX)?);
+        let (from, to) = (a.min(b), a.max(b));
+
+        if bool::arbitrary(u)? {
+            Ok(Self { from, to })
+        } else {
+            Ok(Self {
+                from: -to,
+                to: -from,
+            })
+        }
+    }
+}
+
+/// Error-related types.
+pub mod error {
+    use thiserror::Error;
+
+    #[derive(Clone, Debug, Eq, Error, Hash, Ord, PartialEq, PartialOrd)]
+    pub enum PartialRangeError {
+        #[error("Anchors must not be zero")]
+        Zero,
+        #[error("Anchor {anchor} is out of range")]
+        OutOfRange { anchor: i64 },
+        #[error("Anchors must have the same sign, got {from}:{to}")]
+        MixedSigns { from: i64, to: i64 },
+        #[error("Range must not be inverted, got {from}:{to}")]
+        Inverted { from: i64, to: i64 },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_partial_range() {
+        for (from, to) in [(1, 50), (51, 100), (7, 7), (-50, -1), (-100, -51)] {
+            let range = PartialRange::new(from, to).unwrap();
+            assert_eq!((range.from(), range.to()), (from, to));
+            assert_eq!(range.is_last(), from < 0);
+        }
+
+        assert_eq!(PartialRange::new(1, 50).unwrap().to_string(), "1:50");
+        assert_eq!(PartialRange::new(-50, -1).unwrap().to_string(), "-50:-1");
+    }
+
+    #[test]
+    fn test_partial_range_failing() {
+        let tests = [
+            ((0, 50), PartialRangeError::Zero),
+            ((1, 0), PartialRangeError::Zero),
+            ((-1, 0), PartialRangeError::Zero),
+            ((50, 1), PartialRangeError::Inverted { from: 50, to: 1 }),
+            ((-1, -50), PartialRangeError::Inverted { from: -1, to: -50 }),
+            ((-1, 1), PartialRangeError::MixedSigns { from: -1, to: 1 }),
+            (
+                (1, 4294967296),
+                PartialRangeError::OutOfRange { anchor: 4294967296 },
+            ),
+        ];
+
+        for ((from, to), expected) in tests {
+            assert_eq!(PartialRange::new(from, to), Err(expected));
+        }
+    }
+}
@@ -0,0 +1,7 @@
+//! IMAP4 Extension for Returning a Preview
+//!
+//! This extension extends ...
+//!
+//! * the [Capability](crate::response::Capability) enum with a new variant [Capability::Preview](crate::response::Capability#variant.Preview),
+//! * the [MessageDataItemName](crate::fetch::MessageDataItemName) enum with a new variant [MessageDataItemName::Preview](crate::fetch::MessageDataItemName#variant.Preview), and
+//! * the [MessageDataItem](crate::fetch::MessageDataItem) enum with a new variant [MessageDataItem::Preview](crate::fetch::MessageDataItem#variant.Preview).
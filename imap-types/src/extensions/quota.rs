@@ -54,15 +54,19 @@ use crate::{
 };
 
 impl<'a> CommandBody<'a> {
+    /// Construct a GETQUOTA command.
+    ///
+    /// Note: Use `""` for the default quota root.
     pub fn get_quota<A>(root: A) -> Result<Self, A::Error>
     where
-        A: TryInto<AString<'a>>,
+        A: TryInto<QuotaRoot<'a>>,
     {
         Ok(CommandBody::GetQuota {
             root: root.try_into()?,
         })
     }
 
+    /// Construct a GETQUOTAROOT command.
     pub fn get_quota_root<M>(mailbox: M) -> Result<Self, M::Error>
     where
         M: TryInto<Mailbox<'a>>,
@@ -72,9 +76,10 @@ impl<'a> CommandBody<'a> {
         })
     }
 
+    /// Construct a SETQUOTA command.
     pub fn set_quota<R, S>(root: R, quotas: S) -> Result<Self, SetQuotaError<R::Error, S::Error>>
     where
-        R: TryInto<AString<'a>>,
+        R: TryInto<QuotaRoot<'a>>,
         S: TryInto<Vec<QuotaSet<'a>>>,
     {
         Ok(CommandBody::SetQuota {
@@ -87,7 +92,7 @@ impl<'a> CommandBody<'a> {
 impl<'a> Data<'a> {
     pub fn quota<R, Q>(root: R, quotas: Q) -> Result<Self, QuotaError<R::Error, Q::Error>>
     where
-        R: TryInto<AString<'a>>,
+        R: TryInto<QuotaRoot<'a>>,
         Q: TryInto<Vec1<QuotaGet<'a>>>,
     {
         Ok(Self::Quota {
@@ -102,7 +107,7 @@ impl<'a> Data<'a> {
     ) -> Result<Self, QuotaRootError<M::Error, R::Error>>
     where
         M: TryInto<Mailbox<'a>>,
-        R: TryInto<Vec<AString<'a>>>,
+        R: TryInto<Vec<QuotaRoot<'a>>>,
     {
         Ok(Self::QuotaRoot {
             mailbox: mailbox.try_into().map_err(QuotaRootError::Mailbox)?,
@@ -111,6 +116,43 @@ impl<'a> Data<'a> {
     }
 }
 
+/// The name of a quota root.
+///
+/// Quota root names are server-defined. The empty name, i.e., `""`, is often used as the default
+/// quota root.
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "bounded-static", derive(ToStatic))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct QuotaRoot<'a>(AString<'a>);
+
+impl<'a> QuotaRoot<'a> {
+    pub fn inner(&self) -> &AString<'a> {
+        &self.0
+    }
+
+    pub fn into_inner(self) -> AString<'a> {
+        self.0
+    }
+}
+
+impl_try_from!(AString<'a>, 'a, &'a [u8], QuotaRoot<'a>);
+impl_try_from!(AString<'a>, 'a, Vec<u8>, QuotaRoot<'a>);
+impl_try_from!(AString<'a>, 'a, &'a str, QuotaRoot<'a>);
+impl_try_from!(AString<'a>, 'a, String, QuotaRoot<'a>);
+
+impl<'a> From<AString<'a>> for QuotaRoot<'a> {
+    fn from(value: AString<'a>) -> Self {
+        Self(value)
+    }
+}
+
+impl<'a> AsRef<[u8]> for QuotaRoot<'a> {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_ref()
+    }
+}
+
 /// A resource type for use in IMAP's QUOTA extension.
 ///
 /// Supported resource names MUST be advertised as a capability by prepending the resource name with "QUOTA=RES-".
@@ -0,0 +1,22 @@
+//! IMAP "SAVEDATE" Extension (RFC 8514)
+//!
+//! This extends ...
+//!
+//! * [`Capability`](crate::response::Capability) with a new variant:
+//!
+//!     - [`Capability::SaveDate`](crate::response::Capability::SaveDate)
+//!
+//! * [`MessageDataItemName`](crate::fetch::MessageDataItemName) with a new variant:
+//!
+//!     - [`MessageDataItemName::SaveDate`](crate::fetch::MessageDataItemName::SaveDate)
+//!
+//! * [`MessageDataItem`](crate::fetch::MessageDataItem) with a new variant:
+//!
+//!     - [`MessageDataItem::SaveDate`](crate::fetch::MessageDataItem::SaveDate)
+//!
+//! * [`SearchKey`](crate::search::SearchKey) with new variants:
+//!
+//!     - [`SearchKey::SaveDateSupported`](crate::search::SearchKey::SaveDateSupported)
+//!     - [`SearchKey::SavedBefore`](crate::search::SearchKey::SavedBefore)
+//!     - [`SearchKey::SavedOn`](crate::search::SearchKey::SavedOn)
+//!     - [`SearchKey::SavedSince`](crate::search::SearchKey::SavedSince)
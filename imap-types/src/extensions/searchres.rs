@@ -0,0 +1,13 @@
+//! The IMAP SEARCHRES Extension (RFC 5182)
+//!
+//! This extends ...
+//!
+//! * [`Sequence`](crate::sequence::Sequence) with a new variant:
+//!
+//!     - [`Sequence::SavedResult`](crate::sequence::Sequence::SavedResult), i.e., `$`
+//!
+//! * [`SearchReturnOption`](crate::extensions::esearch::SearchReturnOption) with a new variant:
+//!
+//!     - [`SearchReturnOption::Save`](crate::extensions::esearch::SearchReturnOption::Save), i.e., `RETURN (SAVE)`
+//!
+//! Note: SEARCHRES builds on the `RETURN` syntax of ESEARCH. Thus, `ext_searchres` enables `ext_esearch`.
@@ -0,0 +1,50 @@
+//! IMAP LIST Extension for Special-Use Mailboxes
+//!
+//! This extension extends ...
+//!
+//! * the [Capability](crate::response::Capability) enum with new variants [Capability::SpecialUse](crate::response::Capability#variant.SpecialUse) and [Capability::CreateSpecialUse](crate::response::Capability#variant.CreateSpecialUse),
+//! * the [CommandBody](crate::command::CommandBody) enum with a new variant [CommandBody::CreateSpecialUse](crate::command::CommandBody#variant.CreateSpecialUse), and
+//! * the [FlagNameAttribute] enum with the `\All`, `\Archive`, `\Drafts`, `\Flagged`, `\Junk`, `\Sent`, and `\Trash` attributes.
+
+use crate::{command::CommandBody, flag::FlagNameAttribute, mailbox::Mailbox};
+
+impl<'a> CommandBody<'a> {
+    /// Construct a CREATE command with special-use attributes, i.e., `CREATE ... (USE (...))`.
+    ///
+    /// Without attributes, a plain [`CommandBody::Create`] is returned, i.e., the `(USE (...))`
+    /// group is omitted.
+    pub fn create_special_use<M>(
+        mailbox: M,
+        use_attributes: Vec<FlagNameAttribute<'a>>,
+    ) -> Result<Self, M::Error>
+    where
+        M: TryInto<Mailbox<'a>>,
+    {
+        let mailbox = mailbox.try_into()?;
+
+        if use_attributes.is_empty() {
+            return Ok(CommandBody::Create { mailbox });
+        }
+
+        Ok(CommandBody::CreateSpecialUse {
+            mailbox,
+            use_attributes,
+        })
+    }
+}
+
+impl<'a> FlagNameAttribute<'a> {
+    /// Returns `true` when this is a special-use attribute (RFC 6154).
+    pub fn is_special_use(&self) -> bool {
+        matches!(
+            self,
+            Self::All
+                | Self::Archive
+                | Self::Drafts
+                | Self::Flagged
+                | Self::Junk
+                | Self::Sent
+                | Self::Trash
+        )
+    }
+}
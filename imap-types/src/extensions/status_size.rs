@@ -0,0 +1,7 @@
+//! IMAP4 Extension for Returning STATUS=SIZE and the APPENDLIMIT Extension
+//!
+//! This extension extends ...
+//!
+//! * the [Capability](crate::response::Capability) enum with new variants [Capability::StatusSize](crate::response::Capability#variant.StatusSize) and [Capability::AppendLimit](crate::response::Capability#variant.AppendLimit),
+//! * the [StatusDataItemName](crate::status::StatusDataItemName) enum with new variants `Size` and `AppendLimit`, and
+//! * the [StatusDataItem](crate::status::StatusDataItem) enum with new variants `Size` and `AppendLimit`.
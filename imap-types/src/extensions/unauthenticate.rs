@@ -0,0 +1,15 @@
+//! IMAP UNAUTHENTICATE Extension for Connection Reuse
+//!
+//! This extension extends ...
+//!
+//! * the [Capability](crate::response::Capability) enum with a new variant [Capability::Unauthenticate](crate::response::Capability#variant.Unauthenticate),
+//! * the [CommandBody](crate::command::CommandBody) enum with a new variant [CommandBody::Unauthenticate](crate::command::CommandBody#variant.Unauthenticate), and
+//! * the [State](crate::state::State) enum with a new transition [State::unauthenticate](crate::state::State::unauthenticate).
+
+use crate::command::CommandBody;
+
+impl CommandBody<'_> {
+    pub fn unauthenticate() -> Self {
+        CommandBody::Unauthenticate
+    }
+}
@@ -0,0 +1,421 @@
+//! IMAP URL Scheme (RFC 5092)
+//!
+//! IMAP URLs are used by other extensions, e.g., in the REFERRAL response code of
+//! [IMAP4 Login Referrals](https://datatracker.ietf.org/doc/html/rfc2221) and
+//! [IMAP4 Mailbox Referrals](https://datatracker.ietf.org/doc/html/rfc2193).
+//!
+//! Note: Search programs (`?...`) and URLAUTH (RFC 4467) are not supported.
+
+use std::{borrow::Cow, num::NonZeroU32};
+
+#[cfg(feature = "arbitrary")]
+use arbitrary::{Arbitrary, Unstructured};
+#[cfg(feature = "bounded-static")]
+use bounded_static::ToStatic;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    auth::AuthMechanism, extensions::url::error::ImapUrlError, utils::indicators::is_reg_name_char,
+};
+
+/// IMAP URL, e.g., `imap://alice@example.org/INBOX;UIDVALIDITY=42/;UID=20/;SECTION=1.2`.
+///
+/// ```abnf
+/// imapurl      = "imap://" iserver ipath-query
+///
+/// iserver      = [iuserinfo "@"] host [ ":" port ]
+///
+/// iuserinfo    = enc-user [iauth] / [enc-user] iauth
+///
+/// imessagepart = imailbox-ref iuid [isection] [ipartial]
+///
+/// imailbox-ref = enc-mailbox [uidvalidity]
+/// ```
+///
+/// The user, mailbox, and section are stored percent-decoded. Every part depends on the
+/// previous one, e.g., a UID requires a mailbox. Thus, an URL is built step-by-step, starting
+/// with [`ImapUrl::server`].
+#[cfg_attr(feature = "bounded-static", derive(ToStatic))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ImapUrl<'a> {
+    user: Option<Cow<'a, str>>,
+    auth: Option<UrlAuth<'a>>,
+    host: Cow<'a, str>,
+    port: Option<u16>,
+    mailbox: Option<Cow<'a, str>>,
+    uid_validity: Option<NonZeroU32>,
+    uid: Option<NonZeroU32>,
+    section: Option<Cow<'a, str>>,
+    partial: Option<UrlPartial>,
+}
+
+/// Authentication mechanism of an IMAP URL.
+///
+/// ```abnf
+/// iauth = ";AUTH=" ( "*" / enc-auth-type )
+/// ```
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "bounded-static", derive(ToStatic))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum UrlAuth<'a> {
+    /// Any mechanism (`*`) the client supports.
+    Any,
+    /// A specific mechanism.
+    Mechanism(AuthMechanism<'a>),
+}
+
+/// Partial range of an IMAP URL.
+///
+/// ```abnf
+/// partial-range = number ["." nz-number]
+/// ```
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "bounded-static", derive(ToStatic))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct UrlPartial {
+    /// Offset of the first octet.
+    pub offset: u32,
+    /// Number of octets (up to the end when `None`).
+    pub length: Option<NonZeroU32>,
+}
+
+impl<'a> ImapUrl<'a> {
+    /// Create an URL of an IMAP server, e.g., `imap://example.org`.
+    pub fn server<H>(host: H) -> Result<Self, ImapUrlError>
+    where
+        H: Into<Cow<'a, str>>,
+    {
+        let host = host.into();
+
+        Self::validate_host(host.as_ref())?;
+
+        Ok(Self {
+            user: None,
+            auth: None,
+            host,
+            port: None,
+            mailbox: None,
+            uid_validity: None,
+            uid: None,
+            section: None,
+            partial: None,
+        })
+    }
+
+    /// Validate a host, i.e., a registered name or an IP literal.
+    ///
+    /// ```abnf
+    /// host       = IP-literal / IPv4address / reg-name
+    ///
+    /// IP-literal = "[" ( IPv6address / IPvFuture  ) "]"
+    ///
+    /// reg-name   = *( unreserved / pct-encoded / sub-delims )
+    /// ```
+    ///
+    /// Note: Percent-encoded registered names are not supported.
+    pub fn validate_host(host: impl AsRef<[u8]>) -> Result<(), ImapUrlError> {
+        let host = host.as_ref();
+
+        let valid = match host {
+            [b'[', inner @ .., b']'] => {
+                !inner.is_empty()
+                    && inner
+                        .iter()
+                        .all(|b| b.is_ascii_hexdigit() || matches!(b, b':' | b'.'))
+            }
+            _ => !host.is_empty() && host.iter().all(|b| is_reg_name_char(*b)),
+        };
+
+        if valid {
+            Ok(())
+        } else {
+            Err(ImapUrlError::InvalidHost)
+        }
+    }
+
+    /// Set the user, e.g., `alice` in `imap://alice@example.org`.
+    ///
+    /// The user is passed percent-decoded and must not be empty.
+    pub fn with_user<U>(mut self, user: U) -> Result<Self, ImapUrlError>
+    where
+        U: Into<Cow<'a, str>>,
+    {
+        let user = user.into();
+
+        if user.is_empty() {
+            return Err(ImapUrlError::EmptyUser);
+        }
+
+        self.user = Some(user);
+        Ok(self)
+    }
+
+    /// Set the authentication mechanism, e.g., `;AUTH=*`.
+    pub fn with_auth(mut self, auth: UrlAuth<'a>) -> Self {
+        self.auth = Some(auth);
+        self
+    }
+
+    /// Set the port, e.g., `143` in `imap://example.org:143`.
+    pub fn with_port(mut self, port: u16) -> Self {
+        self.port = Some(port);
+        self
+    }
+
+    /// Set the mailbox, e.g., `INBOX` in `imap://example.org/INBOX`.
+    ///
+    /// The mailbox is passed percent-decoded and must not be empty.
+    pub fn with_mailbox<M>(mut self, mailbox: M) -> Result<Self, ImapUrlError>
+    where
+        M: Into<Cow<'a, str>>,
+    {
+        let mailbox = mailbox.into();
+
+        if mailbox.is_empty() {
+            return Err(ImapUrlError::EmptyMailbox);
+        }
+
+        self.mailbox = Some(mailbox);
+        Ok(self)
+    }
+
+    /// Set the UIDVALIDITY of the mailbox, i.e., `;UIDVALIDITY=<n>`.
+    ///
+    /// Requires a mailbox.
+    pub fn with_uid_validity(mut self, uid_validity: NonZeroU32) -> Result<Self, ImapUrlError> {
+        if self.mailbox.is_none() {
+            return Err(ImapUrlError::MissingMailbox);
+        }
+
+        self.uid_validity = Some(uid_validity);
+        Ok(self)
+    }
+
+    /// Set the UID of a message in the mailbox, i.e., `/;UID=<n>`.
+    ///
+    /// Requires a mailbox.
+    pub fn with_uid(mut self, uid: NonZeroU32) -> Result<Self, ImapUrlError> {
+        if self.mailbox.is_none() {
+            return Err(ImapUrlError::MissingMailbox);
+        }
+
+        self.uid = Some(uid);
+        Ok(self)
+    }
+
+    /// Set the body section of the message, e.g., `/;SECTION=1.2`.
+    ///
+    /// The section is passed percent-decoded and must not be empty. Requires a UID.
+    pub fn with_section<S>(mut self, section: S) -> Result<Self, ImapUrlError>
+    where
+        S: Into<Cow<'a, str>>,
+    {
+        if self.uid.is_none() {
+            return Err(ImapUrlError::MissingUid);
+        }
+
+        let section = section.into();
+
+        if section.is_empty() {
+            return Err(ImapUrlError::EmptySection);
+        }
+
+        self.section = Some(section);
+        Ok(self)
+    }
+
+    /// Set the partial range of the message, e.g., `/;PARTIAL=0.1024`.
+    ///
+    /// Requires a UID.
+    pub fn with_partial(mut self, partial: UrlPartial) -> Result<Self, ImapUrlError> {
+        if self.uid.is_none() {
+            return Err(ImapUrlError::MissingUid);
+        }
+
+        self.partial = Some(partial);
+        Ok(self)
+    }
+
+    /// Percent-decoded user, if any.
+    pub fn user(&self) -> Option<&str> {
+        self.user.as_deref()
+    }
+
+    /// Authentication mechanism, if any.
+    pub fn auth(&self) -> Option<&UrlAuth<'a>> {
+        self.auth.as_ref()
+    }
+
+    /// Host, i.e., a registered name or an IP literal.
+    pub fn host(&self) -> &str {
+        self.host.as_ref()
+    }
+
+    /// Port, if any.
+    pub fn port(&self) -> Option<u16> {
+        self.port
+    }
+
+    /// Percent-decoded mailbox, if any.
+    pub fn mailbox(&self) -> Option<&str> {
+        self.mailbox.as_deref()
+    }
+
+    /// UIDVALIDITY of the mailbox, if any.
+    pub fn uid_validity(&self) -> Option<NonZeroU32> {
+        self.uid_validity
+    }
+
+    /// UID of the message, if any.
+    pub fn uid(&self) -> Option<NonZeroU32> {
+        self.uid
+    }
+
+    /// Percent-decoded body section, if any.
+    pub fn section(&self) -> Option<&str> {
+        self.section.as_deref()
+    }
+
+    /// Partial range, if any.
+    pub fn partial(&self) -> Option<UrlPartial> {
+        self.partial
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> Arbitrary<'a> for ImapUrl<'a> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        fn non_empty<'a>(u: &mut Unstructured<'a>) -> arbitrary::Result<&'a str> {
+            let value = <&str>::arbitrary(u)?;
+
+            if value.is_empty() {
+                Err(arbitrary::Error::IncorrectFormat)
+            } else {
+                Ok(value)
+            }
+        }
+
+        let host = {
+            let length = u.int_in_range(1..=16)?;
+            let mut host = String::with_capacity(length);
+            for _ in 0..length {
+                host.push(*u.choose(b"abcdefghijklmnopqrstuvwxyz0123456789-.".as_ref())? as char);
+            }
+            host
+        };
+
+        let mut url = Self::server(host).map_err(|_| arbitrary::Error::IncorrectFormat)?;
+
+        if bool::arbitrary(u)? {
+            url = url
+                .with_user(non_empty(u)?)
+                .map_err(|_| arbitrary::Error::IncorrectFormat)?;
+        }
+        if let Some(auth) = Option::<UrlAuth>::arbitrary(u)? {
+            url = url.with_auth(auth);
+        }
+        if let Some(port) = Option::<u16>::arbitrary(u)? {
+            url = url.with_port(port);
+        }
+
+        if bool::arbitrary(u)? {
+            url = url
+                .with_mailbox(non_empty(u)?)
+                .map_err(|_| arbitrary::Error::IncorrectFormat)?;
+
+            if let Some(uid_validity) = Option::<NonZeroU32>::arbitrary(u)? {
+                url.uid_validity = Some(uid_validity);
+            }
+
+            if let Some(uid) = Option::<NonZeroU32>::arbitrary(u)? {
+                url.uid = Some(uid);
+
+                if bool::arbitrary(u)? {
+                    url.section = Some(non_empty(u)?.into());
+                }
+                url.partial = Option::<UrlPartial>::arbitrary(u)?;
+            }
+        }
+
+        Ok(url)
+    }
+}
+
+/// Error-related types.
+pub mod error {
+    use thiserror::Error;
+
+    #[derive(Clone, Debug, Eq, Error, Hash, Ord, PartialEq, PartialOrd)]
+    pub enum ImapUrlError {
+        #[error("Invalid host")]
+        InvalidHost,
+        #[error("User must not be empty")]
+        EmptyUser,
+        #[error("Mailbox must not be empty")]
+        EmptyMailbox,
+        #[error("Section must not be empty")]
+        EmptySection,
+        #[error("Expected a mailbox")]
+        MissingMailbox,
+        #[error("Expected a UID")]
+        MissingUid,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_imap_url() {
+        let url = ImapUrl::server("example.org")
+            .unwrap()
+            .with_user("alice")
+            .unwrap()
+            .with_port(143)
+            .with_mailbox("INBOX")
+            .unwrap()
+            .with_uid_validity(NonZeroU32::new(42).unwrap())
+            .unwrap()
+            .with_uid(NonZeroU32::new(20).unwrap())
+            .unwrap()
+            .with_section("1.2")
+            .unwrap();
+
+        assert_eq!(url.user(), Some("alice"));
+        assert_eq!(url.host(), "example.org");
+        assert_eq!(url.port(), Some(143));
+        assert_eq!(url.mailbox(), Some("INBOX"));
+        assert_eq!(url.uid_validity(), NonZeroU32::new(42));
+        assert_eq!(url.uid(), NonZeroU32::new(20));
+        assert_eq!(url.section(), Some("1.2"));
+        assert_eq!(url.partial(), None);
+    }
+
+    #[test]
+    fn test_imap_url_failing() {
+        for host in ["", "a/b", "a@b", "[]", "[::1", "[zz]"] {
+            assert_eq!(ImapUrl::server(host), Err(ImapUrlError::InvalidHost));
+        }
+        assert!(ImapUrl::server("[::1]").is_ok());
+
+        let server = ImapUrl::server("example.org").unwrap();
+        assert_eq!(server.clone().with_user(""), Err(ImapUrlError::EmptyUser));
+        assert_eq!(
+            server.clone().with_mailbox(""),
+            Err(ImapUrlError::EmptyMailbox)
+        );
+        assert_eq!(
+            server.clone().with_uid(crate::sequence::ONE),
+            Err(ImapUrlError::MissingMailbox)
+        );
+        assert_eq!(
+            server.with_mailbox("INBOX").unwrap().with_section("1"),
+            Err(ImapUrlError::MissingUid)
+        );
+    }
+}
@@ -0,0 +1,9 @@
+//! IMAP Support for UTF-8
+//!
+//! This extension extends ...
+//!
+//! * the [Capability](crate::response::Capability) enum with a new variant [Capability::Utf8](crate::response::Capability#variant.Utf8), and
+//! * the [LiteralOrLiteral8](crate::extensions::binary::LiteralOrLiteral8) enum with a new variant [LiteralOrLiteral8::Utf8](crate::extensions::binary::LiteralOrLiteral8#variant.Utf8).
+//!
+//! Note: The APPEND data extension uses a `literal8`. Thus, `ext_utf8` enables `ext_binary`.
+//! `UTF8=ACCEPT` is enabled via [CapabilityEnable::Utf8](crate::extensions::enable::CapabilityEnable#variant.Utf8).
@@ -1,5 +1,7 @@
 //! Fetch-related types.
 
+#[cfg(feature = "ext_condstore_qresync")]
+use std::num::NonZeroU64;
 use std::{
     fmt::{Display, Formatter},
     num::NonZeroU32,
@@ -14,6 +16,8 @@ use serde::{Deserialize, Serialize};
 
 #[cfg(feature = "ext_binary")]
 use crate::core::NString8;
+#[cfg(feature = "ext_objectid")]
+use crate::extensions::objectid::ObjectId;
 use crate::{
     body::BodyStructure,
     core::{AString, NString, Vec1},
@@ -38,7 +42,7 @@ pub enum Macro {
 }
 
 impl Macro {
-    pub fn expand(&self) -> Vec<MessageDataItemName> {
+    pub fn expand<'a>(&self) -> Vec<MessageDataItemName<'a>> {
         use MessageDataItemName::*;
 
         match self {
@@ -71,6 +75,41 @@ pub enum MacroOrMessageDataItemNames<'a> {
     MessageDataItemNames(Vec<MessageDataItemName<'a>>),
 }
 
+impl<'a> MacroOrMessageDataItemNames<'a> {
+    /// Return the explicit list of message data item names.
+    pub fn expand(&self) -> Vec<MessageDataItemName<'a>> {
+        match self {
+            Self::Macro(m) => m.expand(),
+            Self::MessageDataItemNames(item_names) => item_names.clone(),
+        }
+    }
+
+    /// Normalize to the shortest representation.
+    ///
+    /// A list of message data item names is replaced by a macro when it requests exactly the same
+    /// items (regardless of order and duplicates). Otherwise, the value is returned unchanged.
+    ///
+    /// Use [`MacroOrMessageDataItemNames::expand`] for the opposite direction.
+    pub fn normalize(self) -> Self {
+        match self {
+            Self::MessageDataItemNames(item_names) => {
+                for m in [Macro::Fast, Macro::All, Macro::Full] {
+                    let expanded = m.expand();
+
+                    if item_names.iter().all(|item| expanded.contains(item))
+                        && expanded.iter().all(|item| item_names.contains(item))
+                    {
+                        return Self::Macro(m);
+                    }
+                }
+
+                Self::MessageDataItemNames(item_names)
+            }
+            m => m,
+        }
+    }
+}
+
 impl<'a> From<Macro> for MacroOrMessageDataItemNames<'a> {
     fn from(m: Macro) -> Self {
         MacroOrMessageDataItemNames::Macro(m)
@@ -241,6 +280,55 @@ pub enum MessageDataItemName<'a> {
 
     #[cfg(feature = "ext_binary")]
     BinarySize { section: Vec<NonZeroU32> },
+
+    /// The mod-sequence of a message (RFC 7162).
+    ///
+    /// ```imap
+    /// MODSEQ
+    /// ```
+    #[cfg(feature = "ext_condstore_qresync")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext_condstore_qresync")))]
+    ModSeq,
+
+    /// The object identifier of a message (RFC 8474).
+    ///
+    /// ```imap
+    /// EMAILID
+    /// ```
+    #[cfg(feature = "ext_objectid")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext_objectid")))]
+    EmailId,
+
+    /// The object identifier of the thread a message belongs to (RFC 8474).
+    ///
+    /// ```imap
+    /// THREADID
+    /// ```
+    #[cfg(feature = "ext_objectid")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext_objectid")))]
+    ThreadId,
+
+    /// The date and time when a message was saved to the mailbox (RFC 8514).
+    ///
+    /// ```imap
+    /// SAVEDATE
+    /// ```
+    #[cfg(feature = "ext_savedate")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext_savedate")))]
+    SaveDate,
+
+    /// A short preview of the message text (RFC 8970).
+    ///
+    /// With `lazy`, the server may return `NIL` instead of computing a preview that isn't
+    /// readily available.
+    ///
+    /// ```imap
+    /// PREVIEW
+    /// PREVIEW (LAZY)
+    /// ```
+    #[cfg(feature = "ext_preview")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext_preview")))]
+    Preview { lazy: bool },
 }
 
 /// Message data item.
@@ -377,6 +465,118 @@ pub enum MessageDataItem<'a> {
 
     #[cfg(feature = "ext_binary")]
     BinarySize { section: Vec<NonZeroU32>, size: u32 },
+
+    /// The mod-sequence of a message (RFC 7162).
+    ///
+    /// ```imap
+    /// MODSEQ
+    /// ```
+    #[cfg(feature = "ext_condstore_qresync")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext_condstore_qresync")))]
+    ModSeq(NonZeroU64),
+
+    /// The object identifier of a message (RFC 8474).
+    ///
+    /// ```imap
+    /// EMAILID (M6d99ac3275bb4e)
+    /// ```
+    #[cfg(feature = "ext_objectid")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext_objectid")))]
+    EmailId(ObjectId),
+
+    /// The object identifier of the thread a message belongs to (RFC 8474).
+    ///
+    /// `None` (`NIL`) means that the server doesn't support threads for this message.
+    ///
+    /// ```imap
+    /// THREADID (T64b478a75b7ea9)
+    /// ```
+    #[cfg(feature = "ext_objectid")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext_objectid")))]
+    ThreadId(Option<ObjectId>),
+
+    /// The date and time when a message was saved to the mailbox (RFC 8514).
+    ///
+    /// `None` (`NIL`) means that the mailbox doesn't support save dates for this message.
+    ///
+    /// ```imap
+    /// SAVEDATE "17-Jul-1996 02:44:25 -0700"
+    /// ```
+    #[cfg(feature = "ext_savedate")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext_savedate")))]
+    SaveDate(Option<DateTime>),
+
+    /// A short preview of the message text (RFC 8970).
+    ///
+    /// `NIL` means that no preview is available (yet), e.g., in response to `PREVIEW (LAZY)`.
+    ///
+    /// ```imap
+    /// PREVIEW "Hello, this is a preview."
+    /// ```
+    #[cfg(feature = "ext_preview")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext_preview")))]
+    Preview(NString<'a>),
+}
+
+impl<'a> MessageDataItem<'a> {
+    /// Return the message if this is `RFC822`.
+    pub fn rfc822_body(&self) -> Option<&NString<'a>> {
+        match self {
+            Self::Rfc822(data) => Some(data),
+            _ => None,
+        }
+    }
+
+    /// Return the header if this is `RFC822.HEADER`.
+    pub fn rfc822_header(&self) -> Option<&NString<'a>> {
+        match self {
+            Self::Rfc822Header(data) => Some(data),
+            _ => None,
+        }
+    }
+
+    /// Return the text body if this is `RFC822.TEXT`.
+    pub fn rfc822_text(&self) -> Option<&NString<'a>> {
+        match self {
+            Self::Rfc822Text(data) => Some(data),
+            _ => None,
+        }
+    }
+
+    /// Return `true` if this is `RFC822`, `RFC822.HEADER`, or `RFC822.TEXT`.
+    ///
+    /// Note: `RFC822.SIZE` is not included because it does not carry message content.
+    pub fn is_rfc822(&self) -> bool {
+        matches!(
+            self,
+            Self::Rfc822(_) | Self::Rfc822Header(_) | Self::Rfc822Text(_)
+        )
+    }
+}
+
+/// Properties of a message that a server needs to answer a FETCH.
+///
+/// imap-types does not parse messages. Thus, a server is expected to compute these properties,
+/// e.g., when a message is appended, and use [`Data::fetch_response`](crate::response::Data::fetch_response)
+/// to assemble the FETCH response.
+#[cfg_attr(feature = "bounded-static", derive(ToStatic))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ParsedMessage<'a> {
+    /// Unique identifier (`UID`.)
+    pub uid: NonZeroU32,
+    /// Flags that are set for the message (`FLAGS`.)
+    pub flags: Vec<FlagFetch<'a>>,
+    /// Internal date (`INTERNALDATE`.)
+    pub internal_date: DateTime,
+    /// [RFC-2822] size (`RFC822.SIZE`.)
+    pub size: u32,
+    /// Envelope structure (`ENVELOPE`.)
+    pub envelope: Envelope<'a>,
+    /// Body structure including extension data (`BODYSTRUCTURE`.)
+    ///
+    /// The `BODY` form is derived by removing the extension data.
+    pub body_structure: BodyStructure<'a>,
 }
 
 /// A part specifier is either a part number or one of the following:
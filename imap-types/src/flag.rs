@@ -107,6 +107,8 @@ pub enum FlagFetch<'a> {
     /// is read-write, subsequent sessions will not see \Recent set for this message.
     ///
     /// Note: This flag can not be altered by the client.
+    ///
+    /// Note: This flag was removed in IMAP4rev2 (see [`Data::uses_recent`](crate::response::Data::uses_recent)).
     Recent,
 }
 
@@ -144,6 +146,56 @@ pub enum FlagNameAttribute<'a> {
     /// last time the mailbox was selected. (`\Unmarked`)
     Unmarked,
 
+    /// The mailbox has child mailboxes that are accessible to the user. (`\HasChildren`)
+    #[cfg(feature = "ext_list_extended")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext_list_extended")))]
+    HasChildren,
+
+    /// The mailbox has no child mailboxes that are accessible to the user. (`\HasNoChildren`)
+    #[cfg(feature = "ext_list_extended")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext_list_extended")))]
+    HasNoChildren,
+
+    /// The mailbox name does not refer to an existing mailbox. (`\NonExistent`)
+    #[cfg(feature = "ext_list_extended")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext_list_extended")))]
+    NonExistent,
+
+    /// Special-use mailbox for all messages. (`\All`)
+    #[cfg(feature = "ext_special_use")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext_special_use")))]
+    All,
+
+    /// Special-use mailbox for archived messages. (`\Archive`)
+    #[cfg(feature = "ext_special_use")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext_special_use")))]
+    Archive,
+
+    /// Special-use mailbox for messages that are working drafts. (`\Drafts`)
+    #[cfg(feature = "ext_special_use")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext_special_use")))]
+    Drafts,
+
+    /// Special-use mailbox for messages with the `\Flagged` flag. (`\Flagged`)
+    #[cfg(feature = "ext_special_use")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext_special_use")))]
+    Flagged,
+
+    /// Special-use mailbox for messages identified as spam. (`\Junk`)
+    #[cfg(feature = "ext_special_use")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext_special_use")))]
+    Junk,
+
+    /// Special-use mailbox for copies of sent messages. (`\Sent`)
+    #[cfg(feature = "ext_special_use")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext_special_use")))]
+    Sent,
+
+    /// Special-use mailbox for messages the user has discarded. (`\Trash`)
+    #[cfg(feature = "ext_special_use")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext_special_use")))]
+    Trash,
+
     /// An extension flags.
     Extension(FlagNameAttributeExtension<'a>),
 }
@@ -170,6 +222,26 @@ impl<'a> From<Atom<'a>> for FlagNameAttribute<'a> {
             "noselect" => Self::Noselect,
             "marked" => Self::Marked,
             "unmarked" => Self::Unmarked,
+            #[cfg(feature = "ext_list_extended")]
+            "haschildren" => Self::HasChildren,
+            #[cfg(feature = "ext_list_extended")]
+            "hasnochildren" => Self::HasNoChildren,
+            #[cfg(feature = "ext_list_extended")]
+            "nonexistent" => Self::NonExistent,
+            #[cfg(feature = "ext_special_use")]
+            "all" => Self::All,
+            #[cfg(feature = "ext_special_use")]
+            "archive" => Self::Archive,
+            #[cfg(feature = "ext_special_use")]
+            "drafts" => Self::Drafts,
+            #[cfg(feature = "ext_special_use")]
+            "flagged" => Self::Flagged,
+            #[cfg(feature = "ext_special_use")]
+            "junk" => Self::Junk,
+            #[cfg(feature = "ext_special_use")]
+            "sent" => Self::Sent,
+            #[cfg(feature = "ext_special_use")]
+            "trash" => Self::Trash,
             _ => Self::Extension(FlagNameAttributeExtension(atom)),
         }
     }
@@ -182,6 +254,26 @@ impl<'a> Display for FlagNameAttribute<'a> {
             Self::Noselect => f.write_str("\\Noselect"),
             Self::Marked => f.write_str("\\Marked"),
             Self::Unmarked => f.write_str("\\Unmarked"),
+            #[cfg(feature = "ext_list_extended")]
+            Self::HasChildren => f.write_str("\\HasChildren"),
+            #[cfg(feature = "ext_list_extended")]
+            Self::HasNoChildren => f.write_str("\\HasNoChildren"),
+            #[cfg(feature = "ext_list_extended")]
+            Self::NonExistent => f.write_str("\\NonExistent"),
+            #[cfg(feature = "ext_special_use")]
+            Self::All => f.write_str("\\All"),
+            #[cfg(feature = "ext_special_use")]
+            Self::Archive => f.write_str("\\Archive"),
+            #[cfg(feature = "ext_special_use")]
+            Self::Drafts => f.write_str("\\Drafts"),
+            #[cfg(feature = "ext_special_use")]
+            Self::Flagged => f.write_str("\\Flagged"),
+            #[cfg(feature = "ext_special_use")]
+            Self::Junk => f.write_str("\\Junk"),
+            #[cfg(feature = "ext_special_use")]
+            Self::Sent => f.write_str("\\Sent"),
+            #[cfg(feature = "ext_special_use")]
+            Self::Trash => f.write_str("\\Trash"),
             Self::Extension(extension) => write!(f, "\\{}", extension.0),
         }
     }
@@ -27,6 +27,8 @@
 //! ### Example
 //!
 //! ```
+//! # #[cfg(feature = "std")]
+//! # {
 //! use imap_types::{
 //!     command::{Command, CommandBody},
 //!     core::Tag,
@@ -48,6 +50,7 @@
 //!     tag: Tag::try_from("A123").unwrap(),
 //!     body: CommandBody::Noop,
 //! };
+//! # }
 //! ```
 //!
 //! ## More complex messages
@@ -57,6 +60,8 @@
 //! The following example is a server fetch response containing the size and MIME structure of a message with the sequence number (or UID) 42.
 //!
 //! ```
+//! # #[cfg(feature = "std")]
+//! # {
 //! use std::{borrow::Cow, num::NonZeroU32};
 //!
 //! use imap_types::{
@@ -95,6 +100,7 @@
 //!
 //!     Response::Data(data)
 //! };
+//! # }
 //! ```
 //!
 //! # Supported IMAP extensions
@@ -123,6 +129,24 @@
 //! |ext_mailbox_referrals|IMAP4 Mailbox Referrals ([RFC 2193])                                                   |Unfinished|
 //! |ext_binary           |IMAP4 Binary Content Extension ([RFC 3516])                                            |Unfinished|
 //! |ext_metadata         |The IMAP METADATA Extension ([RFC 5464])                                               |Unfinished|
+//! |ext_annotate         |IMAP ANNOTATE Extension ([RFC 5257]; only the ANNOTATION search key)                   |Unfinished|
+//! |ext_uidplus          |IMAP4 UIDPLUS extension ([RFC 4315]; only the response codes)                          |Unfinished|
+//! |ext_namespace        |IMAP4 Namespace ([RFC 2342])                                                           |Unfinished|
+//! |ext_esearch          |IMAP4 Extension for Returning SEARCH Results in Extended Format ([RFC 4731])           |Unfinished|
+//! |ext_list_extended    |IMAP4 LIST Command Extensions ([RFC 5258])                                             |Unfinished|
+//! |ext_special_use      |IMAP LIST Extension for Special-Use Mailboxes ([RFC 6154])                             |Unfinished|
+//! |ext_multiappend      |IMAP MULTIAPPEND Extension ([RFC 3502])                                                |Unfinished|
+//! |ext_acl              |IMAP4 Access Control List (ACL) Extension ([RFC 4314])                                 |Unfinished|
+//! |ext_within           |IMAP4 WITHIN Search Extension ([RFC 5032])                                             |Unfinished|
+//! |ext_searchres        |IMAP Extension for Referencing the Last SEARCH Result ([RFC 5182])                     |Unfinished|
+//! |ext_objectid         |IMAP Extension for Object Identifiers ([RFC 8474])                                     |Unfinished|
+//! |ext_savedate         |IMAP "SAVEDATE" Extension ([RFC 8514])                                                 |Unfinished|
+//! |ext_unauthenticate   |IMAP UNAUTHENTICATE Extension for Connection Reuse ([RFC 8437])                        |Unfinished|
+//! |ext_preview          |IMAP4 Extension for Returning a Preview ([RFC 8970])                                   |Unfinished|
+//! |ext_partial          |IMAP PARTIAL Extension for Paged SEARCH and FETCH ([RFC 9394])                         |Unfinished|
+//! |ext_status_size      |IMAP4 Extension for Returning STATUS=SIZE ([RFC 8438]) and APPENDLIMIT ([RFC 7889])    |Unfinished|
+//! |ext_utf8             |IMAP Support for UTF-8 ([RFC 6855]; only UTF8=ACCEPT and the APPEND data extension)   |Unfinished|
+//! |ext_list_status      |IMAP4 Extension for Returning STATUS Information in Extended LIST ([RFC 5819])         |Unfinished|
 //! |starttls             |IMAP4rev1 ([RFC 3501]; section 6.2.1)                                                  |          |
 //!
 //! STARTTLS is not an IMAP extension but feature-gated because it [should be avoided](https://nostarttls.secvuln.info/).
@@ -132,10 +156,12 @@
 //!
 //! | Feature          | Description                                                    | Enabled by default |
 //! |------------------|----------------------------------------------------------------|--------------------|
+//! | std              | Provide all types (see below). Implied by all features except `unvalidated` and `quirk_atom_char_relaxed`. | Yes |
 //! | arbitrary        | Derive `Arbitrary` implementations.                            | No                 |
 //! | bounded-static   | Derive `ToStatic/IntoStatic` implementations.                  | No                 |
 //! | serde            | Derive `serde`s `Serialize` and `Deserialize` implementations. | No                 |
 //! | unvalidated      | Unlock `unvalidated` constructors.                             | No                 |
+//! | quirk_atom_char_relaxed | Tolerate `{` in `unvalidated` atoms and tags (see imap-codec's quirks). | No |
 //!
 //! When using `arbitrary`, all types defined in imap-types implement the [Arbitrary] trait to ease testing.
 //! This is used, for example, to generate instances during fuzz-testing.
@@ -145,27 +171,50 @@
 //! When the `serde` feature is used, all types implement [Serde](https://serde.rs/)'s [Serialize](https://docs.serde.rs/serde/trait.Serialize.html) and
 //! [Deserialize](https://docs.serde.rs/serde/trait.Deserialize.html) traits. (Try running `cargo run --example serde_json`.)
 //!
+//! Without `std`, imap-types is `no_std` (but requires `alloc`) and only provides the [`core`] types, e.g., [`Atom`](core::Atom),
+//! [`Tag`](core::Tag), [`IString`](core::IString), or [`NString`](core::NString), together with their validation.
+//!
 //! [Arbitrary]: https://docs.rs/arbitrary/1.0.1/arbitrary/trait.Arbitrary.html
 //! [parse_command]: https://github.com/duesee/imap-codec/blob/main/imap-codec/examples/parse_command.rs
 //! [RFC 2088]: https://datatracker.ietf.org/doc/html/rfc2088
 //! [RFC 2177]: https://datatracker.ietf.org/doc/html/rfc2177
 //! [RFC 2193]: https://datatracker.ietf.org/doc/html/rfc2193
 //! [RFC 2221]: https://datatracker.ietf.org/doc/html/rfc2221
+//! [RFC 2342]: https://datatracker.ietf.org/doc/html/rfc2342
 //! [RFC 2971]: https://datatracker.ietf.org/doc/html/rfc2971
 //! [RFC 3501]: https://datatracker.ietf.org/doc/html/rfc3501
+//! [RFC 3502]: https://datatracker.ietf.org/doc/html/rfc3502
 //! [RFC 3516]: https://datatracker.ietf.org/doc/html/rfc3516
 //! [RFC 3691]: https://datatracker.ietf.org/doc/html/rfc3691
+//! [RFC 4314]: https://datatracker.ietf.org/doc/html/rfc4314
+//! [RFC 4315]: https://datatracker.ietf.org/doc/html/rfc4315
+//! [RFC 4731]: https://datatracker.ietf.org/doc/html/rfc4731
 //! [RFC 4959]: https://datatracker.ietf.org/doc/html/rfc4959
 //! [RFC 4978]: https://datatracker.ietf.org/doc/html/rfc4978
+//! [RFC 5032]: https://datatracker.ietf.org/doc/html/rfc5032
 //! [RFC 5161]: https://datatracker.ietf.org/doc/html/rfc5161
+//! [RFC 5182]: https://datatracker.ietf.org/doc/html/rfc5182
 //! [RFC 5256]: https://datatracker.ietf.org/doc/html/rfc5256
+//! [RFC 5257]: https://datatracker.ietf.org/doc/html/rfc5257
+//! [RFC 5258]: https://datatracker.ietf.org/doc/html/rfc5258
 //! [RFC 5464]: https://datatracker.ietf.org/doc/html/rfc5464
+//! [RFC 5819]: https://datatracker.ietf.org/doc/html/rfc5819
 //! [RFC 5957]: https://datatracker.ietf.org/doc/html/rfc5957
+//! [RFC 6154]: https://datatracker.ietf.org/doc/html/rfc6154
 //! [RFC 6851]: https://datatracker.ietf.org/doc/html/rfc6851
+//! [RFC 6855]: https://datatracker.ietf.org/doc/html/rfc6855
 //! [RFC 7162]: https://datatracker.ietf.org/doc/html/rfc7162
 //! [RFC 7888]: https://datatracker.ietf.org/doc/html/rfc7888
+//! [RFC 7889]: https://datatracker.ietf.org/doc/html/rfc7889
+//! [RFC 8437]: https://datatracker.ietf.org/doc/html/rfc8437
+//! [RFC 8438]: https://datatracker.ietf.org/doc/html/rfc8438
+//! [RFC 8474]: https://datatracker.ietf.org/doc/html/rfc8474
+//! [RFC 8514]: https://datatracker.ietf.org/doc/html/rfc8514
+//! [RFC 8970]: https://datatracker.ietf.org/doc/html/rfc8970
 //! [RFC 9208]: https://datatracker.ietf.org/doc/html/rfc9208
+//! [RFC 9394]: https://datatracker.ietf.org/doc/html/rfc9394
 
+#![cfg_attr(not(feature = "std"), no_std)]
 #![forbid(unsafe_code)]
 #![deny(missing_debug_implementations)]
 // TODO(#313)
@@ -174,27 +223,46 @@
 
 // Test examples from imap-types' README.
 #[doc = include_str!("../README.md")]
-#[cfg(doctest)]
+#[cfg(all(doctest, feature = "std"))]
 pub struct ReadmeDoctests;
 
+extern crate alloc;
+
 #[cfg(feature = "arbitrary")]
 mod arbitrary;
+#[cfg(feature = "std")]
 pub mod auth;
+#[cfg(feature = "std")]
 pub mod body;
+#[cfg(feature = "std")]
 pub mod command;
 pub mod core;
+#[cfg(feature = "std")]
 pub mod datetime;
+#[cfg(feature = "std")]
 pub mod envelope;
 pub mod error;
+#[cfg(feature = "std")]
 pub mod extensions;
+#[cfg(feature = "std")]
 pub mod fetch;
+#[cfg(feature = "std")]
 pub mod flag;
+#[cfg(feature = "std")]
 pub mod mailbox;
+#[cfg(all(test, not(feature = "std")))]
+mod no_std;
+#[cfg(feature = "std")]
 pub mod response;
+#[cfg(feature = "std")]
 pub mod search;
+#[cfg(feature = "std")]
 pub mod secret;
+#[cfg(feature = "std")]
 pub mod sequence;
+#[cfg(feature = "std")]
 pub mod state;
+#[cfg(feature = "std")]
 pub mod status;
 pub mod utils;
 
@@ -12,7 +12,7 @@ use serde::{Deserialize, Serialize};
 use crate::{
     core::{impl_try_from, AString, IString},
     error::{ValidationError, ValidationErrorKind},
-    mailbox::error::MailboxOtherError,
+    mailbox::error::{MailboxDecodeError, MailboxOtherError},
     utils::indicators::is_list_char,
 };
 
@@ -39,6 +39,15 @@ impl<'a> ListCharString<'a> {
         Ok(())
     }
 
+    pub fn inner(&self) -> &str {
+        self.0.as_ref()
+    }
+
+    /// Whether the pattern contains a wildcard, i.e., `*` or `%`.
+    pub fn contains_wildcard(&self) -> bool {
+        self.0.contains(['*', '%'])
+    }
+
     /// Constructs a list char string without validation.
     ///
     /// # Warning: IMAP conformance
@@ -189,12 +198,61 @@ impl_try_from!(AString<'a>, 'a, String, Mailbox<'a>);
 impl<'a> From<AString<'a>> for Mailbox<'a> {
     fn from(value: AString<'a>) -> Self {
         match from_utf8(value.as_ref()) {
-            Ok(value) if value.to_ascii_lowercase() == "inbox" => Self::Inbox,
+            Ok(value) if value.eq_ignore_ascii_case("inbox") => Self::Inbox,
             _ => Self::Other(MailboxOther::try_from(value).unwrap()),
         }
     }
 }
 
+impl<'a> Mailbox<'a> {
+    /// Decode the mailbox name from modified UTF-7 (see RFC 3501, section 5.1.3).
+    pub fn decode_utf7(&self) -> Result<String, MailboxDecodeError> {
+        match self {
+            Self::Inbox => Ok(String::from("INBOX")),
+            Self::Other(other) => other.decode_utf7(),
+        }
+    }
+
+    /// Whether `self` and `other` name the same mailbox in a hierarchy with the given `delimiter`.
+    ///
+    /// Trailing delimiters are ignored, i.e., `INBOX/` is the same as `INBOX`. INBOX is compared
+    /// case-insensitively, also as the first hierarchy level, i.e., `inbox/Sent` is the same as
+    /// `INBOX/Sent`. All other names are compared byte-wise because RFC 3501 takes no position on
+    /// case-sensitivity.
+    pub fn same_as(&self, other: &Mailbox, delimiter: char) -> bool {
+        fn normalize<'m>(mailbox: &'m Mailbox, delimiter: &str) -> (bool, &'m [u8]) {
+            let name = match mailbox {
+                Mailbox::Inbox => b"INBOX".as_ref(),
+                Mailbox::Other(other) => other.as_ref(),
+            };
+
+            let mut name = name;
+            while let Some(stripped) = name.strip_suffix(delimiter.as_bytes()) {
+                name = stripped;
+            }
+
+            // Split off the first hierarchy level if it is INBOX.
+            match name.get(..5) {
+                Some(first) if first.eq_ignore_ascii_case(b"INBOX") => {
+                    let rest = &name[5..];
+
+                    if rest.is_empty() || rest.starts_with(delimiter.as_bytes()) {
+                        (true, rest)
+                    } else {
+                        (false, name)
+                    }
+                }
+                _ => (false, name),
+            }
+        }
+
+        let mut buffer = [0; 4];
+        let delimiter = delimiter.encode_utf8(&mut buffer);
+
+        normalize(self, delimiter) == normalize(other, delimiter)
+    }
+}
+
 // We do not implement `AsRef<...>` for `Mailbox` because we want to enforce that a consumer
 // `match`es on `Mailbox::Inbox`/`Mailbox::Other`.
 
@@ -205,17 +263,22 @@ pub struct MailboxOther<'a>(pub(crate) AString<'a>);
 
 impl<'a> MailboxOther<'a> {
     pub fn validate(value: impl AsRef<[u8]>) -> Result<(), MailboxOtherError> {
-        if value.as_ref().to_ascii_lowercase() == b"inbox" {
+        if value.as_ref().eq_ignore_ascii_case(b"inbox") {
             return Err(MailboxOtherError::Reserved);
         }
 
         Ok(())
     }
 
-    pub fn inner(&self) -> &AString {
+    pub fn inner(&self) -> &AString<'_> {
         &self.0
     }
 
+    /// Decode the mailbox name from modified UTF-7 (see RFC 3501, section 5.1.3).
+    pub fn decode_utf7(&self) -> Result<String, MailboxDecodeError> {
+        decode_utf7(self.0.as_ref())
+    }
+
     /// Constructs a mailbox without validation.
     ///
     /// # Warning: IMAP conformance
@@ -272,12 +335,99 @@ impl<'a> AsRef<[u8]> for MailboxOther<'a> {
     }
 }
 
+fn decode_utf7(value: &[u8]) -> Result<String, MailboxDecodeError> {
+    fn base64_value(byte: u8) -> Option<u32> {
+        match byte {
+            b'A'..=b'Z' => Some((byte - b'A') as u32),
+            b'a'..=b'z' => Some((byte - b'a') as u32 + 26),
+            b'0'..=b'9' => Some((byte - b'0') as u32 + 52),
+            b'+' => Some(62),
+            b',' => Some(63),
+            _ => None,
+        }
+    }
+
+    let mut out = String::with_capacity(value.len());
+    let mut at = 0;
+
+    while at < value.len() {
+        match value[at] {
+            b'&' => {
+                let start = at;
+                at += 1;
+
+                if value.get(at) == Some(&b'-') {
+                    out.push('&');
+                    at += 1;
+                    continue;
+                }
+
+                let mut bits = 0u32;
+                let mut bit_count = 0;
+                let mut units = Vec::new();
+
+                loop {
+                    match value.get(at) {
+                        None => return Err(MailboxDecodeError::Unterminated { at: start }),
+                        Some(b'-') => {
+                            at += 1;
+                            break;
+                        }
+                        Some(&byte) => {
+                            let sextet = base64_value(byte)
+                                .ok_or(MailboxDecodeError::UnexpectedByte { byte, at })?;
+
+                            bits = (bits << 6) | sextet;
+                            bit_count += 6;
+
+                            if bit_count >= 16 {
+                                bit_count -= 16;
+                                units.push((bits >> bit_count) as u16);
+                                bits &= (1 << bit_count) - 1;
+                            }
+
+                            at += 1;
+                        }
+                    }
+                }
+
+                // Remaining bits must be zero padding of less than one base64 character.
+                if bit_count >= 6 || bits != 0 {
+                    return Err(MailboxDecodeError::InvalidEncoding { at: start });
+                }
+
+                for c in char::decode_utf16(units) {
+                    out.push(c.map_err(|_| MailboxDecodeError::InvalidEncoding { at: start })?);
+                }
+            }
+            byte @ 0x20..=0x7e => {
+                out.push(byte as char);
+                at += 1;
+            }
+            byte => return Err(MailboxDecodeError::UnexpectedByte { byte, at }),
+        }
+    }
+
+    Ok(out)
+}
+
 /// Error-related types.
 pub mod error {
     use thiserror::Error;
 
     use crate::error::ValidationError;
 
+    /// Error during decoding of a modified UTF-7 mailbox name.
+    #[derive(Clone, Debug, Eq, Error, Hash, Ord, PartialEq, PartialOrd)]
+    pub enum MailboxDecodeError {
+        #[error("Unexpected byte `{byte:#04x}` at {at}")]
+        UnexpectedByte { byte: u8, at: usize },
+        #[error("Unterminated shift sequence starting at {at}")]
+        Unterminated { at: usize },
+        #[error("Invalid encoding in shift sequence starting at {at}")]
+        InvalidEncoding { at: usize },
+    }
+
     #[derive(Clone, Debug, Eq, Error, Hash, Ord, PartialEq, PartialOrd)]
     pub enum MailboxOtherError {
         #[error(transparent)]
@@ -320,6 +470,84 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_mailbox_same_as() {
+        let tests = [
+            ("INBOX", "INBOX/", '/', true),
+            ("inbox", "INBOX", '/', true),
+            ("Inbox/", "iNbOx", '/', true),
+            ("inbox/Sent", "INBOX/Sent/", '/', true),
+            ("INBOX.Sent", "inbox.Sent..", '.', true),
+            ("Archive/2024/", "Archive/2024", '/', true),
+            ("Archive", "Archive", '.', true),
+            // Only INBOX is case-insensitive ...
+            ("Sent", "sent", '/', false),
+            ("INBOX/Sent", "INBOX/sent", '/', false),
+            // ... and only as the first hierarchy level.
+            ("INBOXES", "inboxes", '/', false),
+            ("Archive/INBOX", "Archive/inbox", '/', false),
+            // The delimiter matters.
+            ("INBOX/", "INBOX", '.', false),
+            ("INBOX", "INBOX.Sent", '.', false),
+        ];
+
+        for (left, right, delimiter, expected) in tests {
+            let left = Mailbox::try_from(left).unwrap();
+            let right = Mailbox::try_from(right).unwrap();
+
+            assert_eq!(
+                left.same_as(&right, delimiter),
+                expected,
+                "{left:?} {right:?}"
+            );
+            assert_eq!(
+                right.same_as(&left, delimiter),
+                expected,
+                "{right:?} {left:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_decode_utf7() {
+        let tests = [
+            ("Drafts", "Drafts"),
+            ("Entw&APw-rfe", "Entwürfe"),
+            ("&ZeVnLIqe-", "日本語"),
+            ("Tom &- Jerry", "Tom & Jerry"),
+        ];
+
+        for (test, expected) in tests {
+            let got = Mailbox::try_from(test).unwrap().decode_utf7().unwrap();
+            assert_eq!(expected, got);
+        }
+
+        assert_eq!(Mailbox::Inbox.decode_utf7().unwrap(), "INBOX");
+    }
+
+    #[test]
+    fn test_decode_utf7_failing() {
+        let tests = [
+            ("Entw&APw", MailboxDecodeError::Unterminated { at: 4 }),
+            ("&ZeVnLIqe", MailboxDecodeError::Unterminated { at: 0 }),
+            (
+                "A&AP!-",
+                MailboxDecodeError::UnexpectedByte { byte: b'!', at: 4 },
+            ),
+            ("&APx-", MailboxDecodeError::InvalidEncoding { at: 0 }),
+            ("&2D0-", MailboxDecodeError::InvalidEncoding { at: 0 }),
+            (
+                "ü",
+                MailboxDecodeError::UnexpectedByte { byte: 0xc3, at: 0 },
+            ),
+        ];
+
+        for (test, expected) in tests {
+            let got = Mailbox::try_from(test).unwrap().decode_utf7();
+            assert_eq!(Err(expected), got);
+        }
+    }
+
     #[test]
     fn test_conversion_mailbox_failing() {
         let tests = ["\x00", "A\x00", "\x00A"];
@@ -329,4 +557,32 @@ mod tests {
             assert!(Mailbox::try_from(String::from(test)).is_err());
         }
     }
+
+    #[test]
+    fn test_list_char_string() {
+        let tests = [
+            ("*", true),
+            ("%", true),
+            ("INBOX.*", true),
+            ("Archive/%", true),
+            ("INBOX", false),
+            ("a]b", false),
+        ];
+
+        for (test, expected) in tests {
+            let lcs = ListCharString::try_from(test).unwrap();
+            assert_eq!(lcs.inner(), test);
+            assert_eq!(lcs.contains_wildcard(), expected);
+        }
+    }
+
+    #[test]
+    fn test_list_char_string_failing() {
+        let tests = ["", " ", "a b", "(", "\"", "\\", "{", "\x00", "ä"];
+
+        for test in tests {
+            assert!(ListCharString::try_from(test).is_err());
+            assert!(ListCharString::try_from(String::from(test)).is_err());
+        }
+    }
 }
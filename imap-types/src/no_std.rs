@@ -0,0 +1,42 @@
+//! Tests of the core types without `std`.
+//!
+//! Run with `cargo test -p imap-types --no-default-features --lib`.
+
+use alloc::{borrow::ToOwned, string::ToString, vec};
+
+use crate::{
+    core::{error::TagError, AString, Atom, IString, NString, Quoted, Tag, Vec1},
+    error::ValidationError,
+};
+
+#[test]
+fn test_core_types() {
+    assert_eq!(Atom::try_from("NOOP").unwrap().as_ref(), "NOOP");
+    assert!(Atom::try_from("").is_err());
+    assert!(Atom::try_from("A B").is_err());
+
+    assert_eq!(Tag::try_from("A1".to_owned()).unwrap().as_ref(), "A1");
+    assert_eq!(Tag::validate(b"A+"), Err(TagError::Plus { at: 1 }));
+
+    assert!(matches!(IString::try_from("alice"), Ok(IString::Quoted(_))));
+    assert!(matches!(IString::try_from("\r\n"), Ok(IString::Literal(_))));
+    assert!(matches!(AString::try_from("alice"), Ok(AString::Atom(_))));
+    assert_eq!(
+        NString::try_from("alice").unwrap().0.unwrap().as_ref(),
+        b"alice"
+    );
+    assert!(Quoted::try_from("\r").is_err());
+
+    assert!(Vec1::<u8>::try_from(vec![1, 2, 3]).is_ok());
+    assert!(Vec1::<u8>::try_from(vec![]).is_err());
+}
+
+#[test]
+fn test_errors_display() {
+    let error: ValidationError = Atom::try_from("").unwrap_err();
+    assert_eq!(error.to_string(), "Validation failed: Must not be empty");
+    assert_eq!(
+        TagError::Space { at: 2 }.to_string(),
+        "Must not contain SP (at index 2)"
+    );
+}
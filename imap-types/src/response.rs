@@ -1,5 +1,7 @@
 //! # 7. Server Responses
 
+#[cfg(feature = "ext_condstore_qresync")]
+use std::num::NonZeroU64;
 use std::{
     borrow::Cow,
     fmt::{Debug, Display, Formatter},
@@ -14,28 +16,48 @@ use bounded_static::ToStatic;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "ext_acl")]
+use crate::core::AString;
 #[cfg(feature = "ext_id")]
 use crate::core::{IString, NString};
+#[cfg(feature = "ext_acl")]
+use crate::extensions::acl::{AclEntry, AclRights};
+#[cfg(feature = "ext_utf8")]
+use crate::extensions::enable::Utf8Kind;
+#[cfg(feature = "ext_esearch")]
+use crate::extensions::esearch::ESearchReturn;
 #[cfg(feature = "ext_metadata")]
 use crate::extensions::metadata::{MetadataCode, MetadataResponse};
+#[cfg(feature = "ext_namespace")]
+use crate::extensions::namespace::NamespaceDescriptor;
+#[cfg(feature = "ext_objectid")]
+use crate::extensions::objectid::ObjectId;
 #[cfg(feature = "ext_sort_thread")]
 use crate::extensions::sort::SortAlgorithm;
 #[cfg(feature = "ext_sort_thread")]
 use crate::extensions::thread::{Thread, ThreadingAlgorithm};
+#[cfg(any(feature = "ext_login_referrals", feature = "ext_mailbox_referrals"))]
+use crate::extensions::url::ImapUrl;
+#[cfg(any(feature = "ext_condstore_qresync", feature = "ext_uidplus"))]
+use crate::sequence::SequenceSet;
 use crate::{
     auth::AuthMechanism,
-    core::{impl_try_from, AString, Atom, Charset, QuotedChar, Tag, Text, Vec1},
-    error::ValidationError,
+    command::Command,
+    core::{impl_try_from, Atom, Charset, QuotedChar, Tag, Text, Vec1, VecN},
+    error::{ValidationError, ValidationErrorKind},
     extensions::{
         compress::CompressionAlgorithm,
         enable::CapabilityEnable,
-        quota::{QuotaGet, Resource},
+        quota::{QuotaGet, QuotaRoot, Resource},
     },
-    fetch::MessageDataItem,
-    flag::{Flag, FlagNameAttribute, FlagPerm},
+    fetch::{MessageDataItem, MessageDataItemName, ParsedMessage},
+    flag::{Flag, FlagFetch, FlagNameAttribute, FlagPerm},
     mailbox::Mailbox,
-    response::error::{ContinueError, FetchError},
+    response::error::{
+        ContinueBase64Error, ContinueError, FetchError, FetchResponseError, StatusError,
+    },
     status::StatusDataItem,
+    utils::indicators::is_text_char,
 };
 
 /// Greeting.
@@ -87,6 +109,14 @@ impl<'a> Greeting<'a> {
             text: text.try_into()?,
         })
     }
+
+    /// Returns `true` when the server rejected the connection, i.e., sent a BYE greeting.
+    ///
+    /// Note: A client must not send any command and should expect the server to close the
+    /// connection.
+    pub fn is_rejection(&self) -> bool {
+        self.kind == GreetingKind::Bye
+    }
 }
 
 #[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
@@ -131,6 +161,48 @@ pub enum Response<'a> {
     Status(Status<'a>),
 }
 
+impl<'a> Response<'a> {
+    /// Returns `true` when this is an untagged `OK` or `NO` status response.
+    ///
+    /// Servers send these, e.g., as progress messages during long-running commands. They do not
+    /// indicate the completion of a command, i.e., a client must keep waiting for the tagged status
+    /// response.
+    pub fn is_informational(&self) -> bool {
+        matches!(
+            self,
+            Self::Status(Status::Untagged(StatusBody {
+                kind: StatusKind::Ok | StatusKind::No,
+                ..
+            }))
+        )
+    }
+}
+
+#[cfg(feature = "bounded-static")]
+impl<'a> Response<'a> {
+    /// Convert into an owned response.
+    ///
+    /// This is a shortcut for [`IntoBoundedStatic::into_static`](bounded_static::IntoBoundedStatic::into_static)
+    /// that doesn't require the trait to be in scope.
+    ///
+    /// ```
+    /// use imap_types::response::{Response, Status};
+    ///
+    /// let text = String::from("Hello");
+    /// let response: Response<'static> =
+    ///     Response::Status(Status::ok(None, None, text.as_str()).unwrap()).into_static();
+    /// drop(text);
+    ///
+    /// assert_eq!(
+    ///     response,
+    ///     Response::Status(Status::ok(None, None, "Hello").unwrap())
+    /// );
+    /// ```
+    pub fn into_static(self) -> Response<'static> {
+        bounded_static::IntoBoundedStatic::into_static(self)
+    }
+}
+
 /// Status response.
 #[cfg_attr(feature = "bounded-static", derive(ToStatic))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -225,6 +297,9 @@ pub struct Bye<'a> {
 }
 
 impl<'a> Status<'a> {
+    /// Construct a status response.
+    ///
+    /// Note: The `text` must not be empty (see [`Text`]). Thus, `A1 OK \r\n` can't be constructed.
     pub fn new<T>(
         tag: Option<Tag<'a>>,
         kind: StatusKind,
@@ -270,6 +345,24 @@ impl<'a> Status<'a> {
         Self::new(tag, StatusKind::Bad, code, text)
     }
 
+    /// Construct a tagged OK response with a CAPABILITY response code.
+    ///
+    /// Useful after LOGIN, AUTHENTICATE, or STARTTLS to advertise the new capability set, e.g.,
+    /// `A1 OK [CAPABILITY IMAP4rev1 ...] Logged in`.
+    pub fn ok_with_capabilities<C, T>(
+        tag: Tag<'a>,
+        capabilities: C,
+        text: T,
+    ) -> Result<Self, StatusError<C::Error, T::Error>>
+    where
+        C: TryInto<Vec1<Capability<'a>>>,
+        T: TryInto<Text<'a>>,
+    {
+        let code = Code::capability(capabilities).map_err(StatusError::Capabilities)?;
+
+        Self::ok(Some(tag), Some(code), text).map_err(StatusError::Text)
+    }
+
     pub fn bye<T>(code: Option<Code<'a>>, text: T) -> Result<Self, T::Error>
     where
         T: TryInto<Text<'a>>,
@@ -282,14 +375,14 @@ impl<'a> Status<'a> {
 
     // ---------------------------------------------------------------------------------------------
 
-    pub fn tag(&self) -> Option<&Tag> {
+    pub fn tag(&self) -> Option<&Tag<'_>> {
         match self {
             Self::Tagged(Tagged { tag, .. }) => Some(tag),
             _ => None,
         }
     }
 
-    pub fn code(&self) -> Option<&Code> {
+    pub fn code(&self) -> Option<&Code<'_>> {
         match self {
             Self::Untagged(StatusBody { code, .. })
             | Self::Tagged(Tagged {
@@ -300,7 +393,7 @@ impl<'a> Status<'a> {
         }
     }
 
-    pub fn text(&self) -> &Text {
+    pub fn text(&self) -> &Text<'_> {
         match self {
             Self::Untagged(StatusBody { text, .. })
             | Self::Tagged(Tagged {
@@ -310,6 +403,26 @@ impl<'a> Status<'a> {
             | Self::Bye(Bye { text, .. }) => text,
         }
     }
+
+    /// Returns `true` when this status has a `PARSE` response code.
+    ///
+    /// Servers use it to signal that the [RFC-2822] or [MIME-IMB] headers of a message couldn't
+    /// be parsed, e.g., during FETCH.
+    pub fn is_parse_error(&self) -> bool {
+        matches!(self.code(), Some(Code::Parse))
+    }
+
+    /// Returns the human-readable text of a `PARSE` status, i.e., the context of the parse error.
+    ///
+    /// `PARSE` has no arguments. Servers may describe which message (part) failed to parse in
+    /// the text, e.g., `* NO [PARSE] Unexpected characters in address`.
+    pub fn parse_error_context(&self) -> Option<&Text<'_>> {
+        if self.is_parse_error() {
+            Some(self.text())
+        } else {
+            None
+        }
+    }
 }
 
 /// ## 7.2 - 7.4 Server and Mailbox Status; Mailbox Size; Message Status
@@ -434,6 +547,17 @@ pub enum Data<'a> {
     /// delimited by a space.
     Search(Vec<NonZeroU32>),
 
+    #[cfg(feature = "ext_esearch")]
+    /// ESEARCH response
+    ESearch {
+        /// Tag of the SEARCH command this response belongs to (`(TAG "...")`.)
+        tag: Option<Tag<'a>>,
+        /// Results are UIDs.
+        uid: bool,
+        /// Result data, e.g., `MIN 1`, `COUNT 15`, ...
+        returns: Vec<ESearchReturn>,
+    },
+
     #[cfg(feature = "ext_sort_thread")]
     Sort(Vec<NonZeroU32>),
 
@@ -556,7 +680,7 @@ pub enum Data<'a> {
 
     Quota {
         /// Quota root.
-        root: AString<'a>,
+        root: QuotaRoot<'a>,
         /// List of quotas.
         quotas: Vec1<QuotaGet<'a>>,
     },
@@ -565,7 +689,7 @@ pub enum Data<'a> {
         /// Mailbox name.
         mailbox: Mailbox<'a>,
         /// List of quota roots.
-        roots: Vec<AString<'a>>,
+        roots: Vec<QuotaRoot<'a>>,
     },
 
     #[cfg(feature = "ext_id")]
@@ -575,12 +699,69 @@ pub enum Data<'a> {
         parameters: Option<Vec<(IString<'a>, NString<'a>)>>,
     },
 
+    #[cfg(feature = "ext_namespace")]
+    /// NAMESPACE response
+    Namespace {
+        /// Personal namespaces (`NIL` when empty.)
+        personal: Vec<NamespaceDescriptor<'a>>,
+        /// Other users' namespaces (`NIL` when empty.)
+        other: Vec<NamespaceDescriptor<'a>>,
+        /// Shared namespaces (`NIL` when empty.)
+        shared: Vec<NamespaceDescriptor<'a>>,
+    },
+
     #[cfg(feature = "ext_metadata")]
     /// Metadata response
     Metadata {
         mailbox: Mailbox<'a>,
         items: MetadataResponse<'a>,
     },
+
+    /// ACL response (RFC 4314).
+    ///
+    /// ```imap
+    /// S: * ACL INBOX Fred rwipsldexta
+    /// ```
+    #[cfg(feature = "ext_acl")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext_acl")))]
+    Acl {
+        /// Mailbox.
+        mailbox: Mailbox<'a>,
+        /// Identifiers and their rights.
+        entries: Vec<AclEntry<'a>>,
+    },
+
+    /// LISTRIGHTS response (RFC 4314).
+    ///
+    /// ```imap
+    /// S: * LISTRIGHTS ~/Mail/saved smith la r swicdkxte
+    /// ```
+    #[cfg(feature = "ext_acl")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext_acl")))]
+    ListRights {
+        /// Mailbox.
+        mailbox: Mailbox<'a>,
+        /// Identifier, e.g., a user or group name.
+        identifier: AString<'a>,
+        /// Rights that are always granted to the identifier.
+        required: AclRights<'a>,
+        /// Rights that may be granted to the identifier (each group is granted as a whole).
+        optional: Vec<AclRights<'a>>,
+    },
+
+    /// MYRIGHTS response (RFC 4314).
+    ///
+    /// ```imap
+    /// S: * MYRIGHTS INBOX rwiptsldaex
+    /// ```
+    #[cfg(feature = "ext_acl")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext_acl")))]
+    MyRights {
+        /// Mailbox.
+        mailbox: Mailbox<'a>,
+        /// Rights of the current user.
+        rights: AclRights<'a>,
+    },
 }
 
 impl<'a> Data<'a> {
@@ -630,6 +811,224 @@ impl<'a> Data<'a> {
 
         Ok(Self::Fetch { seq, items })
     }
+
+    /// Assemble a FETCH response for the requested message data items.
+    ///
+    /// Supported are `BODY`, `BODYSTRUCTURE`, `ENVELOPE`, `FLAGS`, `INTERNALDATE`, `RFC822.SIZE`,
+    /// and `UID`. Use [`Macro::expand`](crate::fetch::Macro::expand) to answer a macro.
+    ///
+    /// Note: Items that require the message content, e.g., `BODY[...]`, are not supported.
+    pub fn fetch_response<'b>(
+        seq: NonZeroU32,
+        items_requested: &[MessageDataItemName<'b>],
+        message: &ParsedMessage<'a>,
+    ) -> Result<Self, FetchResponseError<'b>> {
+        let items = items_requested
+            .iter()
+            .map(|item| {
+                Ok(match item {
+                    MessageDataItemName::Body => {
+                        MessageDataItem::Body(message.body_structure.without_extension_data())
+                    }
+                    MessageDataItemName::BodyStructure => {
+                        MessageDataItem::BodyStructure(message.body_structure.clone())
+                    }
+                    MessageDataItemName::Envelope => {
+                        MessageDataItem::Envelope(message.envelope.clone())
+                    }
+                    MessageDataItemName::Flags => MessageDataItem::Flags(message.flags.clone()),
+                    MessageDataItemName::InternalDate => {
+                        MessageDataItem::InternalDate(message.internal_date.clone())
+                    }
+                    MessageDataItemName::Rfc822Size => MessageDataItem::Rfc822Size(message.size),
+                    MessageDataItemName::Uid => MessageDataItem::Uid(message.uid),
+                    unsupported => {
+                        return Err(FetchResponseError::Unsupported(unsupported.clone()))
+                    }
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self::Fetch {
+            seq,
+            items: Vec1::try_from(items).map_err(|_| FetchResponseError::Empty)?,
+        })
+    }
+
+    /// Return the STATUS data items, or an empty slice when this is not a STATUS response.
+    fn status_items(&self) -> &[StatusDataItem] {
+        match self {
+            Self::Status { items, .. } => items.as_ref(),
+            _ => &[],
+        }
+    }
+
+    /// Return the number of messages (`MESSAGES`) of a STATUS response.
+    pub fn messages(&self) -> Option<u32> {
+        self.status_items().iter().find_map(|item| match item {
+            StatusDataItem::Messages(value) => Some(*value),
+            _ => None,
+        })
+    }
+
+    /// Return the number of recent messages (`RECENT`) of a STATUS response.
+    pub fn recent(&self) -> Option<u32> {
+        self.status_items().iter().find_map(|item| match item {
+            StatusDataItem::Recent(value) => Some(*value),
+            _ => None,
+        })
+    }
+
+    /// Return the next unique identifier (`UIDNEXT`) of a STATUS response.
+    pub fn uid_next(&self) -> Option<NonZeroU32> {
+        self.status_items().iter().find_map(|item| match item {
+            StatusDataItem::UidNext(value) => Some(*value),
+            _ => None,
+        })
+    }
+
+    /// Return the unique identifier validity value (`UIDVALIDITY`) of a STATUS response.
+    pub fn uid_validity(&self) -> Option<NonZeroU32> {
+        self.status_items().iter().find_map(|item| match item {
+            StatusDataItem::UidValidity(value) => Some(*value),
+            _ => None,
+        })
+    }
+
+    /// Return the number of unseen messages (`UNSEEN`) of a STATUS response.
+    pub fn unseen(&self) -> Option<u32> {
+        self.status_items().iter().find_map(|item| match item {
+            StatusDataItem::Unseen(value) => Some(*value),
+            _ => None,
+        })
+    }
+
+    /// Return the number of deleted messages (`DELETED`) of a STATUS response.
+    pub fn deleted(&self) -> Option<u32> {
+        self.status_items().iter().find_map(|item| match item {
+            StatusDataItem::Deleted(value) => Some(*value),
+            _ => None,
+        })
+    }
+
+    /// Return the reclaimable storage (`DELETED-STORAGE`) of a STATUS response.
+    pub fn deleted_storage(&self) -> Option<u64> {
+        self.status_items().iter().find_map(|item| match item {
+            StatusDataItem::DeletedStorage(value) => Some(*value),
+            _ => None,
+        })
+    }
+
+    /// Return the highest mod-sequence value (`HIGHESTMODSEQ`) of a STATUS response.
+    #[cfg(feature = "ext_condstore_qresync")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext_condstore_qresync")))]
+    pub fn highest_modseq(&self) -> Option<NonZeroU64> {
+        self.status_items().iter().find_map(|item| match item {
+            StatusDataItem::HighestModSeq(value) => Some(*value),
+            _ => None,
+        })
+    }
+
+    /// Return the mailbox size (`SIZE`) of a STATUS response.
+    #[cfg(feature = "ext_status_size")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext_status_size")))]
+    pub fn size(&self) -> Option<u64> {
+        self.status_items().iter().find_map(|item| match item {
+            StatusDataItem::Size(value) => Some(*value),
+            _ => None,
+        })
+    }
+
+    /// Return the append limit (`APPENDLIMIT`) of a STATUS response.
+    ///
+    /// `Some(None)` means that the server reported `APPENDLIMIT NIL`, i.e., there is no limit.
+    #[cfg(feature = "ext_status_size")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext_status_size")))]
+    pub fn append_limit(&self) -> Option<Option<u32>> {
+        self.status_items().iter().find_map(|item| match item {
+            StatusDataItem::AppendLimit(value) => Some(*value),
+            _ => None,
+        })
+    }
+
+    /// Return a copy with the items of a STATUS response in canonical order.
+    ///
+    /// The canonical order is `MESSAGES`, `RECENT`, `UIDNEXT`, `UIDVALIDITY`, `UNSEEN`, `DELETED`,
+    /// and `DELETED-STORAGE` (followed by `HIGHESTMODSEQ`, `MAILBOXID`, `SIZE`, and `APPENDLIMIT`). This is useful to compare or cache STATUS responses regardless of
+    /// the order used by the server. Other responses are returned unchanged.
+    pub fn canonicalize_status(&self) -> Self {
+        let mut data = self.clone();
+
+        if let Self::Status { items, .. } = &mut data {
+            items.to_mut().sort_by_key(StatusDataItem::canonical_rank);
+        }
+
+        data
+    }
+
+    /// Compare with `other` regardless of the order of FETCH and STATUS items.
+    ///
+    /// Servers may return the items of a FETCH or STATUS response in any order. Two such responses
+    /// are considered equal when they contain the same items (including duplicates). Nested lists,
+    /// e.g., the flags in `FLAGS (...)`, must still be in the same order. Other responses are
+    /// compared with `==`.
+    pub fn eq_unordered(&self, other: &Self) -> bool {
+        match (self, other) {
+            (
+                Self::Fetch { seq, items },
+                Self::Fetch {
+                    seq: other_seq,
+                    items: other_items,
+                },
+            ) => seq == other_seq && eq_unordered(items.as_ref(), other_items.as_ref()),
+            (
+                Self::Status { mailbox, items },
+                Self::Status {
+                    mailbox: other_mailbox,
+                    items: other_items,
+                },
+            ) => mailbox == other_mailbox && eq_unordered(items, other_items),
+            _ => self == other,
+        }
+    }
+
+    /// Whether this data uses `RECENT` or `\Recent`.
+    ///
+    /// IMAP4rev2 ([RFC 9051](https://datatracker.ietf.org/doc/html/rfc9051)) removed the `RECENT`
+    /// response, the `RECENT` status item, and the `\Recent` flag. imap-codec still parses them
+    /// (as required by IMAP4rev1), but this method can be used to detect and drop them.
+    pub fn uses_recent(&self) -> bool {
+        match self {
+            Self::Recent(_) => true,
+            Self::Status { items, .. } => items
+                .iter()
+                .any(|item| matches!(item, StatusDataItem::Recent(_))),
+            Self::Fetch { items, .. } => items.as_ref().iter().any(|item| match item {
+                MessageDataItem::Flags(flags) => flags.contains(&FlagFetch::Recent),
+                _ => false,
+            }),
+            _ => false,
+        }
+    }
+}
+
+/// Compare two slices as multisets.
+fn eq_unordered<T: PartialEq>(left: &[T], right: &[T]) -> bool {
+    if left.len() != right.len() {
+        return false;
+    }
+
+    let mut matched = vec![false; right.len()];
+
+    left.iter().all(
+        |item| match (0..right.len()).find(|&i| !matched[i] && right[i] == *item) {
+            Some(i) => {
+                matched[i] = true;
+                true
+            }
+            None => false,
+        },
+    )
 }
 
 /// ## 7.5. Server Responses - Command Continuation Request
@@ -678,6 +1077,29 @@ impl<'a> CommandContinuationRequest<'a> {
     {
         Self::Base64(data.into())
     }
+
+    /// Create a continuation request from a base64-encoded challenge, e.g., from a SASL library.
+    ///
+    /// The challenge may be wrapped over multiple lines (with `\r\n` or `\n`). The lines are
+    /// concatenated before decoding, so a line doesn't need to end on a 4-character boundary.
+    /// The decoded challenge may contain arbitrary bytes and is always sent as a single line.
+    pub fn from_base64(data: &[u8]) -> Result<Self, ContinueBase64Error> {
+        let mut compact = Vec::with_capacity(data.len());
+
+        for (at, byte) in data.iter().copied().enumerate() {
+            match byte {
+                b'\r' | b'\n' => {}
+                b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'+' | b'/' | b'=' => compact.push(byte),
+                _ => return Err(ContinueBase64Error::UnexpectedByte { byte, at }),
+            }
+        }
+
+        let decoded = _base64
+            .decode(compact)
+            .map_err(|_| ContinueBase64Error::InvalidLength)?;
+
+        Ok(Self::Base64(Cow::Owned(decoded)))
+    }
 }
 
 #[cfg_attr(feature = "bounded-static", derive(ToStatic))]
@@ -694,6 +1116,7 @@ impl<'a> CommandContinuationRequestBasic<'a> {
     /// Note: To avoid ambiguities in the IMAP standard, this constructor ensures that:
     /// * iff `code` is `None`, `text` must not start with `[`.
     /// * iff `code` is `None`, `text` must *not* be valid according to base64.
+    ///
     /// Otherwise, we could send a `Continue::Basic` that is interpreted as `Continue::Base64`.
     pub fn new<T>(code: Option<Code<'a>>, text: T) -> Result<Self, ContinueError<T::Error>>
     where
@@ -823,14 +1246,17 @@ pub enum Code<'a> {
     /// message without the \Seen flag set.
     Unseen(NonZeroU32),
 
-    /// IMAP4 Login Referrals (RFC 2221)
-    // TODO(misuse): the imap url is more complicated than that...
+    /// IMAP4 Login Referrals (RFC 2221) and IMAP4 Mailbox Referrals (RFC 2193)
+    ///
+    /// ```abnf
+    /// referral_response_code = "[" "REFERRAL" 1*(SP <url>) "]"
+    /// ```
     #[cfg(any(feature = "ext_mailbox_referrals", feature = "ext_login_referrals"))]
     #[cfg_attr(
         docsrs,
         doc(cfg(any(feature = "ext_mailbox_referrals", feature = "ext_login_referrals")))
     )]
-    Referral(Cow<'a, str>),
+    Referral(Vec1<ImapUrl<'a>>),
 
     CompressionActive,
 
@@ -849,6 +1275,64 @@ pub enum Code<'a> {
     /// Server does not know how to decode the section's CTE.
     UnknownCte,
 
+    /// `APPENDUID` (RFC 4315)
+    ///
+    /// Followed by the UIDVALIDITY of the destination mailbox and the UID(s) assigned to the
+    /// appended message(s).
+    #[cfg(feature = "ext_uidplus")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext_uidplus")))]
+    AppendUid {
+        uid_validity: NonZeroU32,
+        uids: SequenceSet,
+    },
+
+    /// `COPYUID` (RFC 4315)
+    ///
+    /// Followed by the UIDVALIDITY of the destination mailbox, the UIDs of the copied messages
+    /// in the source mailbox, and the UIDs assigned in the destination mailbox (in the same order).
+    #[cfg(feature = "ext_uidplus")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext_uidplus")))]
+    CopyUid {
+        uid_validity: NonZeroU32,
+        source: SequenceSet,
+        destination: SequenceSet,
+    },
+
+    /// `HIGHESTMODSEQ` (RFC 7162)
+    ///
+    /// Followed by the highest mod-sequence value of all messages in the mailbox.
+    #[cfg(feature = "ext_condstore_qresync")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext_condstore_qresync")))]
+    HighestModSeq(NonZeroU64),
+
+    /// `NOMODSEQ` (RFC 7162)
+    ///
+    /// The selected mailbox doesn't support the persistent storage of mod-sequences.
+    #[cfg(feature = "ext_condstore_qresync")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext_condstore_qresync")))]
+    NoModSeq,
+
+    /// `MODIFIED` (RFC 7162)
+    ///
+    /// Followed by the messages that failed the `UNCHANGEDSINCE` test of a STORE command.
+    #[cfg(feature = "ext_condstore_qresync")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext_condstore_qresync")))]
+    Modified(SequenceSet),
+
+    /// `UIDNOTSTICKY` (RFC 4315)
+    ///
+    /// The selected mailbox does not support persistent UIDs.
+    #[cfg(feature = "ext_uidplus")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext_uidplus")))]
+    UidNotSticky,
+
+    /// `MAILBOXID` (RFC 8474)
+    ///
+    /// Followed by the object identifier of a newly created mailbox.
+    #[cfg(feature = "ext_objectid")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext_objectid")))]
+    MailboxId(ObjectId),
+
     /// Additional response codes defined by particular client or server
     /// implementations SHOULD be prefixed with an "X" until they are
     /// added to a revision of this protocol.  Client implementations
@@ -862,7 +1346,26 @@ pub enum Code<'a> {
     ///
     /// Note: We use this as a fallback for everything that was not recognized as
     ///       `Code`. This includes, e.g., variants with missing parameters, etc.
+    ///       Exception: `UIDNEXT`, `UIDVALIDITY`, and `UNSEEN` with a zero or malformed
+    ///       number are rejected by imap-codec (`ResponseDecodeError::InvalidCode`).
+    ///       Unknown codes that follow the `atom [SP 1*<any TEXT-CHAR except "]">]`
+    ///       structure are parsed into [`Code::OtherParsed`] instead.
     Other(CodeOther<'a>),
+
+    /// Additional (unknown) response code that follows the generic structure, e.g., `[XVENDOR foo]`.
+    ///
+    /// ```abnf
+    /// atom [SP 1*<any TEXT-CHAR except "]">]
+    /// ```
+    ///
+    /// Note: The `name` should not be a code known to [`Code`]. Otherwise, it will be
+    ///       interpreted as the known code when parsed again. A known code with malformed
+    ///       arguments, e.g., `[PERMANENTFLAGS]`, is parsed into [`Code::Other`].
+    OtherParsed {
+        #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::arbitrary::arbitrary_code_name))]
+        name: Atom<'a>,
+        value: Option<CodeOtherValue<'a>>,
+    },
 }
 
 impl<'a> Code<'a> {
@@ -892,6 +1395,25 @@ impl<'a> Code<'a> {
     pub fn unseen(uidnext: u32) -> Result<Self, TryFromIntError> {
         Ok(Self::Unseen(NonZeroU32::try_from(uidnext)?))
     }
+
+    /// Return a copy with `IMAP4rev1` moved to the front of a CAPABILITY code.
+    ///
+    /// Some clients expect `IMAP4rev1` to be listed first. The order of the remaining capabilities
+    /// is kept. Other codes are returned unchanged.
+    ///
+    /// Note: The capability list can't be empty (see [`Code::capability`]).
+    pub fn canonicalize_capability(&self) -> Self {
+        match self {
+            Self::Capability(caps) => {
+                let mut caps = caps.clone().into_inner();
+                // Note: `sort_by_key` is stable.
+                caps.sort_by_key(|cap| *cap != Capability::Imap4Rev1);
+
+                Self::Capability(VecN(caps))
+            }
+            _ => self.clone(),
+        }
+    }
 }
 
 /// An (unknown) code.
@@ -931,9 +1453,9 @@ impl<'a> CodeOther<'a> {
     /// untrusted data.
     #[cfg(feature = "unvalidated")]
     #[cfg_attr(docsrs, doc(cfg(feature = "unvalidated")))]
-    pub fn unvalidated<D: 'a>(data: D) -> Self
+    pub fn unvalidated<D>(data: D) -> Self
     where
-        D: Into<Cow<'a, [u8]>>,
+        D: Into<Cow<'a, [u8]>> + 'a,
     {
         Self(data.into())
     }
@@ -943,6 +1465,117 @@ impl<'a> CodeOther<'a> {
     }
 }
 
+/// Argument of an (unknown) code, i.e., `1*<any TEXT-CHAR except "]">`.
+#[cfg_attr(feature = "bounded-static", derive(ToStatic))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct CodeOtherValue<'a>(Cow<'a, str>);
+
+// We want a slightly more dense `Debug` implementation.
+impl<'a> Debug for CodeOtherValue<'a> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "CodeOtherValue({:?})", self.0)
+    }
+}
+
+impl<'a> CodeOtherValue<'a> {
+    pub fn validate(value: impl AsRef<[u8]>) -> Result<(), ValidationError> {
+        let value = value.as_ref();
+
+        if value.is_empty() {
+            return Err(ValidationError::new(ValidationErrorKind::Empty));
+        }
+
+        if let Some(at) = value.iter().position(|b| !is_text_char(*b) || *b == b']') {
+            return Err(ValidationError::new(ValidationErrorKind::InvalidByteAt {
+                byte: value[at],
+                at,
+            }));
+        };
+
+        Ok(())
+    }
+
+    pub fn inner(&self) -> &str {
+        self.0.as_ref()
+    }
+
+    pub fn into_inner(self) -> Cow<'a, str> {
+        self.0
+    }
+
+    /// Constructs a code value without validation.
+    ///
+    /// # Warning: IMAP conformance
+    ///
+    /// The caller must ensure that `inner` is valid according to [`Self::validate`]. Failing to do
+    /// so may create invalid/unparsable IMAP messages, or even produce unintended protocol flows.
+    /// Do not call this constructor with untrusted data.
+    ///
+    /// Note: This method will `panic!` on wrong input in debug builds.
+    #[cfg(feature = "unvalidated")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "unvalidated")))]
+    pub fn unvalidated<C>(inner: C) -> Self
+    where
+        C: Into<Cow<'a, str>>,
+    {
+        let inner = inner.into();
+
+        #[cfg(debug_assertions)]
+        Self::validate(inner.as_bytes()).unwrap();
+
+        Self(inner)
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for CodeOtherValue<'a> {
+    type Error = ValidationError;
+
+    fn try_from(value: &'a [u8]) -> Result<Self, Self::Error> {
+        Self::validate(value)?;
+
+        // Safety: `unwrap` can't panic due to `validate`.
+        Ok(Self(Cow::Borrowed(std::str::from_utf8(value).unwrap())))
+    }
+}
+
+impl<'a> TryFrom<Vec<u8>> for CodeOtherValue<'a> {
+    type Error = ValidationError;
+
+    fn try_from(value: Vec<u8>) -> Result<Self, Self::Error> {
+        Self::validate(&value)?;
+
+        // Safety: `unwrap` can't panic due to `validate`.
+        Ok(Self(Cow::Owned(String::from_utf8(value).unwrap())))
+    }
+}
+
+impl<'a> TryFrom<&'a str> for CodeOtherValue<'a> {
+    type Error = ValidationError;
+
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        Self::validate(value)?;
+
+        Ok(Self(Cow::Borrowed(value)))
+    }
+}
+
+impl<'a> TryFrom<String> for CodeOtherValue<'a> {
+    type Error = ValidationError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::validate(&value)?;
+
+        Ok(Self(Cow::Owned(value)))
+    }
+}
+
+impl<'a> AsRef<str> for CodeOtherValue<'a> {
+    fn as_ref(&self) -> &str {
+        self.0.as_ref()
+    }
+}
+
 #[cfg_attr(feature = "bounded-static", derive(ToStatic))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -988,6 +1621,27 @@ pub enum Capability<'a> {
     Id,
     /// See RFC 3691.
     Unselect,
+    #[cfg(feature = "ext_namespace")]
+    /// See RFC 2342.
+    Namespace,
+    #[cfg(feature = "ext_esearch")]
+    /// See RFC 4731.
+    ESearch,
+    #[cfg(feature = "ext_list_extended")]
+    /// See RFC 5258.
+    ListExtended,
+    #[cfg(feature = "ext_list_status")]
+    /// See RFC 5819.
+    ListStatus,
+    #[cfg(feature = "ext_special_use")]
+    /// See RFC 6154.
+    SpecialUse,
+    #[cfg(feature = "ext_special_use")]
+    /// See RFC 6154.
+    CreateSpecialUse,
+    #[cfg(feature = "ext_multiappend")]
+    /// See RFC 3502.
+    MultiAppend,
     #[cfg(feature = "ext_sort_thread")]
     Sort(Option<SortAlgorithm<'a>>),
     #[cfg(feature = "ext_sort_thread")]
@@ -1001,6 +1655,38 @@ pub enum Capability<'a> {
     #[cfg(feature = "ext_binary")]
     /// IMAP4 Binary Content Extension
     Binary,
+    #[cfg(feature = "ext_acl")]
+    /// See RFC 4314.
+    Acl,
+    #[cfg(feature = "ext_within")]
+    /// See RFC 5032.
+    Within,
+    #[cfg(feature = "ext_objectid")]
+    /// See RFC 8474.
+    ObjectId,
+    #[cfg(feature = "ext_savedate")]
+    /// See RFC 8514.
+    SaveDate,
+    #[cfg(feature = "ext_unauthenticate")]
+    /// See RFC 8437.
+    Unauthenticate,
+    #[cfg(feature = "ext_preview")]
+    /// See RFC 8970.
+    Preview,
+    #[cfg(feature = "ext_partial")]
+    /// See RFC 9394.
+    Partial,
+    #[cfg(feature = "ext_status_size")]
+    /// See RFC 8438.
+    StatusSize,
+    #[cfg(feature = "ext_utf8")]
+    /// See RFC 6855.
+    Utf8(Utf8Kind),
+    #[cfg(feature = "ext_status_size")]
+    /// See RFC 7889.
+    ///
+    /// `None` means that the limit differs per mailbox (see the `APPENDLIMIT` status data item).
+    AppendLimit(Option<u32>),
     /// Other/Unknown
     Other(CapabilityOther<'a>),
 }
@@ -1031,6 +1717,20 @@ impl<'a> Display for Capability<'a> {
             #[cfg(feature = "ext_id")]
             Self::Id => write!(f, "ID"),
             Self::Unselect => write!(f, "UNSELECT"),
+            #[cfg(feature = "ext_namespace")]
+            Self::Namespace => write!(f, "NAMESPACE"),
+            #[cfg(feature = "ext_esearch")]
+            Self::ESearch => write!(f, "ESEARCH"),
+            #[cfg(feature = "ext_list_extended")]
+            Self::ListExtended => write!(f, "LIST-EXTENDED"),
+            #[cfg(feature = "ext_list_status")]
+            Self::ListStatus => write!(f, "LIST-STATUS"),
+            #[cfg(feature = "ext_special_use")]
+            Self::SpecialUse => write!(f, "SPECIAL-USE"),
+            #[cfg(feature = "ext_special_use")]
+            Self::CreateSpecialUse => write!(f, "CREATE-SPECIAL-USE"),
+            #[cfg(feature = "ext_multiappend")]
+            Self::MultiAppend => write!(f, "MULTIAPPEND"),
             #[cfg(feature = "ext_sort_thread")]
             Self::Sort(None) => write!(f, "SORT"),
             #[cfg(feature = "ext_sort_thread")]
@@ -1043,6 +1743,28 @@ impl<'a> Display for Capability<'a> {
             Self::MetadataServer => write!(f, "METADATA-SERVER"),
             #[cfg(feature = "ext_binary")]
             Self::Binary => write!(f, "BINARY"),
+            #[cfg(feature = "ext_acl")]
+            Self::Acl => write!(f, "ACL"),
+            #[cfg(feature = "ext_within")]
+            Self::Within => write!(f, "WITHIN"),
+            #[cfg(feature = "ext_objectid")]
+            Self::ObjectId => write!(f, "OBJECTID"),
+            #[cfg(feature = "ext_savedate")]
+            Self::SaveDate => write!(f, "SAVEDATE"),
+            #[cfg(feature = "ext_unauthenticate")]
+            Self::Unauthenticate => write!(f, "UNAUTHENTICATE"),
+            #[cfg(feature = "ext_preview")]
+            Self::Preview => write!(f, "PREVIEW"),
+            #[cfg(feature = "ext_partial")]
+            Self::Partial => write!(f, "PARTIAL"),
+            #[cfg(feature = "ext_utf8")]
+            Self::Utf8(kind) => write!(f, "UTF8={}", kind),
+            #[cfg(feature = "ext_status_size")]
+            Self::StatusSize => write!(f, "STATUS=SIZE"),
+            #[cfg(feature = "ext_status_size")]
+            Self::AppendLimit(None) => write!(f, "APPENDLIMIT"),
+            #[cfg(feature = "ext_status_size")]
+            Self::AppendLimit(Some(limit)) => write!(f, "APPENDLIMIT={}", limit),
             Self::Other(other) => write!(f, "{}", other.0),
         }
     }
@@ -1100,6 +1822,20 @@ impl<'a> From<Atom<'a>> for Capability<'a> {
             "move" => Self::Move,
             #[cfg(feature = "ext_id")]
             "id" => Self::Id,
+            #[cfg(feature = "ext_namespace")]
+            "namespace" => Self::Namespace,
+            #[cfg(feature = "ext_esearch")]
+            "esearch" => Self::ESearch,
+            #[cfg(feature = "ext_list_extended")]
+            "list-extended" => Self::ListExtended,
+            #[cfg(feature = "ext_list_status")]
+            "list-status" => Self::ListStatus,
+            #[cfg(feature = "ext_special_use")]
+            "special-use" => Self::SpecialUse,
+            #[cfg(feature = "ext_special_use")]
+            "create-special-use" => Self::CreateSpecialUse,
+            #[cfg(feature = "ext_multiappend")]
+            "multiappend" => Self::MultiAppend,
             #[cfg(feature = "ext_sort_thread")]
             "sort" => Self::Sort(None),
             #[cfg(feature = "ext_metadata")]
@@ -1108,6 +1844,28 @@ impl<'a> From<Atom<'a>> for Capability<'a> {
             "metadata-server" => Self::MetadataServer,
             #[cfg(feature = "ext_binary")]
             "binary" => Self::Binary,
+            #[cfg(feature = "ext_acl")]
+            "acl" => Self::Acl,
+            #[cfg(feature = "ext_within")]
+            "within" => Self::Within,
+            #[cfg(feature = "ext_objectid")]
+            "objectid" => Self::ObjectId,
+            #[cfg(feature = "ext_savedate")]
+            "savedate" => Self::SaveDate,
+            #[cfg(feature = "ext_unauthenticate")]
+            "unauthenticate" => Self::Unauthenticate,
+            #[cfg(feature = "ext_preview")]
+            "preview" => Self::Preview,
+            #[cfg(feature = "ext_partial")]
+            "partial" => Self::Partial,
+            #[cfg(feature = "ext_utf8")]
+            "utf8=accept" => Self::Utf8(Utf8Kind::Accept),
+            #[cfg(feature = "ext_utf8")]
+            "utf8=only" => Self::Utf8(Utf8Kind::Only),
+            #[cfg(feature = "ext_status_size")]
+            "status=size" => Self::StatusSize,
+            #[cfg(feature = "ext_status_size")]
+            "appendlimit" => Self::AppendLimit(None),
             "unselect" => Self::Unselect,
             _ => {
                 // TODO(efficiency)
@@ -1141,6 +1899,12 @@ impl<'a> From<Atom<'a>> for Capability<'a> {
                                 return Self::Sort(Some(SortAlgorithm::from(atom)));
                             }
                         }
+                        #[cfg(feature = "ext_status_size")]
+                        "appendlimit" => {
+                            if let Ok(limit) = right.parse::<u32>() {
+                                return Self::AppendLimit(Some(limit));
+                            }
+                        }
                         #[cfg(feature = "ext_sort_thread")]
                         "thread" => {
                             if let Ok(atom) = Atom::try_from(right) {
@@ -1157,6 +1921,110 @@ impl<'a> From<Atom<'a>> for Capability<'a> {
     }
 }
 
+/// A deduplicated set of capabilities, e.g., to maintain a single capability view.
+///
+/// Capabilities are announced in the greeting (see [`Code::Capability`]), in a CAPABILITY
+/// response (see [`Data::Capability`]), or in a tagged OK response. Use [`Capabilities::merge`]
+/// to combine them, or [`Capabilities::replace`] when a later announcement is authoritative,
+/// e.g., after STARTTLS or LOGIN.
+#[cfg_attr(feature = "bounded-static", derive(ToStatic))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Capabilities<'a>(Vec<Capability<'a>>);
+
+impl<'a> Capabilities<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Extract the capabilities from a greeting (if any).
+    pub fn from_greeting(greeting: &Greeting<'a>) -> Option<Self> {
+        match &greeting.code {
+            Some(Code::Capability(caps)) => Some(caps.as_ref().iter().cloned().collect()),
+            _ => None,
+        }
+    }
+
+    /// Merge `other` into this set.
+    ///
+    /// Capabilities are compared case-insensitively. For duplicates, the capability from `other`
+    /// is kept.
+    pub fn merge<I>(&mut self, other: I)
+    where
+        I: IntoIterator<Item = Capability<'a>>,
+    {
+        for capability in other {
+            self.0
+                .retain(|known| !capability_eq_ignore_ascii_case(known, &capability));
+            self.0.push(capability);
+        }
+    }
+
+    /// Replace this set with a later announcement.
+    ///
+    /// Capabilities that are not announced again are removed. Duplicates in `other` are handled
+    /// as in [`Capabilities::merge`].
+    pub fn replace<I>(&mut self, other: I)
+    where
+        I: IntoIterator<Item = Capability<'a>>,
+    {
+        self.0.clear();
+        self.merge(other);
+    }
+
+    /// Returns `true` when the set contains the capability (compared case-insensitively).
+    pub fn contains(&self, capability: &Capability) -> bool {
+        self.0
+            .iter()
+            .any(|known| capability_eq_ignore_ascii_case(known, capability))
+    }
+
+    pub fn as_slice(&self) -> &[Capability<'a>] {
+        &self.0
+    }
+
+    pub fn into_inner(self) -> Vec<Capability<'a>> {
+        self.0
+    }
+}
+
+/// Compare capabilities, ignoring the case of names that are not known to imap-types.
+///
+/// Note: Known names are normalized during construction, so only the `Other` variants need care.
+fn capability_eq_ignore_ascii_case(a: &Capability, b: &Capability) -> bool {
+    match (a, b) {
+        (Capability::Auth(a), Capability::Auth(b)) => a.as_ref().eq_ignore_ascii_case(b.as_ref()),
+        #[cfg(feature = "ext_sort_thread")]
+        (
+            Capability::Sort(Some(SortAlgorithm::Other(a))),
+            Capability::Sort(Some(SortAlgorithm::Other(b))),
+        ) => a.as_ref().eq_ignore_ascii_case(b.as_ref()),
+        #[cfg(feature = "ext_sort_thread")]
+        (
+            Capability::Thread(ThreadingAlgorithm::Other(a)),
+            Capability::Thread(ThreadingAlgorithm::Other(b)),
+        ) => a.as_ref().eq_ignore_ascii_case(b.as_ref()),
+        (Capability::Other(a), Capability::Other(b)) => {
+            a.0.as_ref().eq_ignore_ascii_case(b.0.as_ref())
+        }
+        _ => a == b,
+    }
+}
+
+impl<'a> FromIterator<Capability<'a>> for Capabilities<'a> {
+    fn from_iter<T: IntoIterator<Item = Capability<'a>>>(iter: T) -> Self {
+        let mut capabilities = Self::new();
+        capabilities.merge(iter);
+        capabilities
+    }
+}
+
+impl<'a> From<Vec1<Capability<'a>>> for Capabilities<'a> {
+    fn from(value: Vec1<Capability<'a>>) -> Self {
+        value.into_iter().collect()
+    }
+}
+
 /// An (unknown) capability.
 ///
 /// It's guaranteed that this type can't represent any capability from [`Capability`].
@@ -1165,10 +2033,155 @@ impl<'a> From<Atom<'a>> for Capability<'a> {
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct CapabilityOther<'a>(Atom<'a>);
 
+/// Correlates responses to commands by their tag.
+///
+/// Commands are [`register`](Self::register)ed when they are sent. Untagged data is then
+/// attributed to the oldest pending command that expects this kind of data, e.g., `* SEARCH` to
+/// a `SEARCH` command, or `* 1 FETCH` to a `FETCH` or `STORE` command. When the tagged status
+/// response of a command arrives, its data is dispatched as a [`Routed::Completed`] bundle.
+///
+/// Data that isn't expected by any pending command, e.g., an unsolicited `* 3 EXISTS`, is returned
+/// as [`Routed::Unsolicited`].
+///
+/// Note: IMAP doesn't associate untagged data with a specific command. When two pipelined
+/// commands expect the same kind of data (which clients should avoid), it is attributed to the
+/// command that was sent first.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ResponseRouter<'a> {
+    pending: Vec<PendingCommand<'a>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PendingCommand<'a> {
+    tag: Tag<'a>,
+    name: &'static str,
+    data: Vec<Data<'a>>,
+}
+
+impl<'a> ResponseRouter<'a> {
+    /// Create a router without pending commands.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a sent command so that its responses can be correlated.
+    pub fn register(&mut self, command: &Command<'a>) {
+        self.pending.push(PendingCommand {
+            tag: command.tag.clone(),
+            name: command.body.name(),
+            data: Vec::new(),
+        });
+    }
+
+    /// Feed a response into the router.
+    pub fn feed(&mut self, response: Response<'a>) -> Routed<'a> {
+        match response {
+            Response::Data(data) => {
+                match self
+                    .pending
+                    .iter_mut()
+                    .find(|pending| expects(pending.name, &data))
+                {
+                    Some(pending) => {
+                        pending.data.push(data);
+                        Routed::Buffered
+                    }
+                    None => Routed::Unsolicited(data),
+                }
+            }
+            Response::Status(Status::Tagged(Tagged { tag, body })) => {
+                let data = match self.pending.iter().position(|pending| pending.tag == tag) {
+                    Some(index) => self.pending.remove(index).data,
+                    None => Vec::new(),
+                };
+
+                Routed::Completed {
+                    tag,
+                    data,
+                    status: body,
+                }
+            }
+            other => Routed::Other(other),
+        }
+    }
+
+    /// Return the buffered, not yet dispatched, data of the command tagged `tag`.
+    pub fn pending(&self, tag: &Tag) -> &[Data<'a>] {
+        self.pending
+            .iter()
+            .find(|pending| pending.tag == *tag)
+            .map(|pending| pending.data.as_slice())
+            .unwrap_or_default()
+    }
+}
+
+/// Returns `true` when the command named `name` expects `data` as (part of) its response.
+fn expects(name: &str, data: &Data) -> bool {
+    match data {
+        Data::Capability(_) => name == "CAPABILITY",
+        Data::List { .. } => name == "LIST",
+        Data::Lsub { .. } => name == "LSUB",
+        Data::Status { .. } => matches!(name, "STATUS" | "LIST"),
+        Data::Search(_) => name == "SEARCH",
+        #[cfg(feature = "ext_esearch")]
+        Data::ESearch { .. } => matches!(name, "SEARCH" | "SORT"),
+        #[cfg(feature = "ext_sort_thread")]
+        Data::Sort(_) => name == "SORT",
+        #[cfg(feature = "ext_sort_thread")]
+        Data::Thread(_) => name == "THREAD",
+        Data::Flags(_) | Data::Exists(_) | Data::Recent(_) => {
+            matches!(name, "SELECT" | "EXAMINE")
+        }
+        Data::Expunge(_) => matches!(name, "EXPUNGE" | "MOVE"),
+        Data::Fetch { .. } => matches!(name, "FETCH" | "STORE"),
+        Data::Enabled { .. } => name == "ENABLE",
+        Data::Quota { .. } => matches!(name, "GETQUOTA" | "GETQUOTAROOT" | "SETQUOTA"),
+        Data::QuotaRoot { .. } => name == "GETQUOTAROOT",
+        #[cfg(feature = "ext_id")]
+        Data::Id { .. } => name == "ID",
+        #[cfg(feature = "ext_namespace")]
+        Data::Namespace { .. } => name == "NAMESPACE",
+        #[cfg(feature = "ext_metadata")]
+        Data::Metadata { .. } => name == "GETMETADATA",
+        #[cfg(feature = "ext_acl")]
+        Data::Acl { .. } => name == "GETACL",
+        #[cfg(feature = "ext_acl")]
+        Data::ListRights { .. } => name == "LISTRIGHTS",
+        #[cfg(feature = "ext_acl")]
+        Data::MyRights { .. } => name == "MYRIGHTS",
+    }
+}
+
+/// Result of [`ResponseRouter::feed`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Routed<'a> {
+    /// The response was buffered for a pending command.
+    Buffered,
+
+    /// A command was completed.
+    Completed {
+        /// Tag of the completed command.
+        tag: Tag<'a>,
+        /// Data received for the command.
+        data: Vec<Data<'a>>,
+        /// Tagged status response.
+        status: StatusBody<'a>,
+    },
+
+    /// The data isn't expected by any pending command, e.g., an unsolicited `* 3 EXISTS`.
+    Unsolicited(Data<'a>),
+
+    /// The response is not subject to routing, e.g., a command continuation request, an untagged
+    /// status response, or a BYE response.
+    Other(Response<'a>),
+}
+
 /// Error-related types.
 pub mod error {
     use thiserror::Error;
 
+    use crate::fetch::MessageDataItemName;
+
     #[derive(Clone, Debug, Eq, Error, Hash, Ord, PartialEq, PartialOrd)]
     pub enum ContinueError<T> {
         #[error("invalid text")]
@@ -1177,6 +2190,15 @@ pub mod error {
         Ambiguity,
     }
 
+    /// Error during decoding of a base64-encoded challenge.
+    #[derive(Clone, Debug, Eq, Error, Hash, Ord, PartialEq, PartialOrd)]
+    pub enum ContinueBase64Error {
+        #[error("Unexpected byte `{byte:#04x}` at {at}")]
+        UnexpectedByte { byte: u8, at: usize },
+        #[error("Invalid length or padding")]
+        InvalidLength,
+    }
+
     #[derive(Clone, Debug, Eq, Error, Hash, Ord, PartialEq, PartialOrd)]
     pub enum FetchError<S, I> {
         #[error("Invalid sequence or UID: {0:?}")]
@@ -1184,6 +2206,22 @@ pub mod error {
         #[error("Invalid items: {0:?}")]
         InvalidItems(I),
     }
+
+    #[derive(Clone, Debug, Eq, Error, Hash, Ord, PartialEq, PartialOrd)]
+    pub enum StatusError<C, T> {
+        #[error("Invalid capabilities: {0:?}")]
+        Capabilities(C),
+        #[error("Invalid text: {0:?}")]
+        Text(T),
+    }
+
+    #[derive(Clone, Debug, Eq, Error, Hash, PartialEq)]
+    pub enum FetchResponseError<'a> {
+        #[error("Unsupported message data item: {0:?}")]
+        Unsupported(MessageDataItemName<'a>),
+        #[error("No message data items requested")]
+        Empty,
+    }
 }
 
 #[cfg(test)]
@@ -1208,4 +2246,522 @@ mod tests {
             assert!(test.is_err());
         }
     }
+
+    #[test]
+    fn test_continue_from_base64() {
+        // A 3 KiB challenge containing every byte value, wrapped at 76 characters.
+        let challenge: Vec<u8> = (0..=255u8).cycle().take(3 * 1024).collect();
+        let encoded = _base64.encode(&challenge);
+        let wrapped = encoded
+            .as_bytes()
+            .chunks(76)
+            .collect::<Vec<_>>()
+            .join(b"\r\n".as_ref());
+
+        let expected = CommandContinuationRequest::base64(challenge);
+        assert_eq!(
+            CommandContinuationRequest::from_base64(encoded.as_bytes()),
+            Ok(expected.clone())
+        );
+        assert_eq!(
+            CommandContinuationRequest::from_base64(&wrapped),
+            Ok(expected)
+        );
+
+        // Lines don't need to end on a 4-character boundary.
+        assert_eq!(
+            CommandContinuationRequest::from_base64(b"YW\nJj\nZA=\n="),
+            Ok(CommandContinuationRequest::base64(b"abcd".as_ref()))
+        );
+        assert_eq!(
+            CommandContinuationRequest::from_base64(b""),
+            Ok(CommandContinuationRequest::base64(b"".as_ref()))
+        );
+    }
+
+    #[test]
+    fn test_continue_from_base64_failing() {
+        assert_eq!(
+            CommandContinuationRequest::from_base64(b"YWJj\r\nZA ="),
+            Err(ContinueBase64Error::UnexpectedByte { byte: b' ', at: 8 })
+        );
+        assert_eq!(
+            CommandContinuationRequest::from_base64(b"YWJjZ"),
+            Err(ContinueBase64Error::InvalidLength)
+        );
+    }
+
+    #[test]
+    fn test_status_ok_with_capabilities() {
+        let got = Status::ok_with_capabilities(
+            Tag::try_from("A1").unwrap(),
+            vec![Capability::Imap4Rev1, Capability::Idle],
+            "Logged in",
+        )
+        .unwrap();
+
+        assert_eq!(got.tag(), Some(&Tag::try_from("A1").unwrap()));
+        assert_eq!(
+            got.code(),
+            Some(&Code::Capability(
+                Vec1::try_from(vec![Capability::Imap4Rev1, Capability::Idle]).unwrap()
+            ))
+        );
+
+        assert!(matches!(
+            Status::ok_with_capabilities(Tag::try_from("A1").unwrap(), vec![], "Logged in"),
+            Err(StatusError::Capabilities(_))
+        ));
+        assert!(matches!(
+            Status::ok_with_capabilities(
+                Tag::try_from("A1").unwrap(),
+                vec![Capability::Imap4Rev1],
+                ""
+            ),
+            Err(StatusError::Text(_))
+        ));
+    }
+
+    #[test]
+    fn test_status_empty_text_failing() {
+        let tag = || Some(Tag::try_from("A1").unwrap());
+
+        assert!(Status::ok(tag(), None, "").is_err());
+        assert!(Status::no(tag(), None, "").is_err());
+        assert!(Status::bad(tag(), None, "").is_err());
+        assert!(Status::ok(None, Some(Code::Alert), "").is_err());
+        assert!(Status::bye(None, "").is_err());
+        assert!(Status::ok(tag(), None, " ").is_ok());
+    }
+
+    #[test]
+    fn test_status_accessors() {
+        let data = Data::Status {
+            mailbox: Mailbox::Inbox,
+            items: vec![
+                StatusDataItem::Messages(42),
+                StatusDataItem::UidNext(NonZeroU32::new(1337).unwrap()),
+            ]
+            .into(),
+        };
+
+        assert_eq!(data.messages(), Some(42));
+        assert_eq!(data.uid_next(), NonZeroU32::new(1337));
+        assert_eq!(data.unseen(), None);
+        assert_eq!(data.uid_validity(), None);
+        assert_eq!(data.recent(), None);
+
+        assert_eq!(Data::Exists(42).messages(), None);
+    }
+
+    #[cfg(feature = "ext_condstore_qresync")]
+    #[test]
+    fn test_status_accessor_highest_modseq() {
+        let data = Data::Status {
+            mailbox: Mailbox::Inbox,
+            items: vec![
+                StatusDataItem::Messages(42),
+                StatusDataItem::HighestModSeq(NonZeroU64::new(7011231777).unwrap()),
+            ]
+            .into(),
+        };
+
+        assert_eq!(data.highest_modseq(), NonZeroU64::new(7011231777));
+        assert_eq!(data.messages(), Some(42));
+
+        let data = Data::Status {
+            mailbox: Mailbox::Inbox,
+            items: vec![StatusDataItem::Messages(42)].into(),
+        };
+
+        assert_eq!(data.highest_modseq(), None);
+        assert_eq!(Data::Exists(42).highest_modseq(), None);
+    }
+
+    #[test]
+    fn test_capabilities_merge() {
+        let greeting = Greeting::ok(
+            Some(Code::Capability(
+                Vec1::try_from(vec![
+                    Capability::Imap4Rev1,
+                    Capability::try_from("AUTH=PLAIN").unwrap(),
+                    Capability::try_from("XFOO").unwrap(),
+                ])
+                .unwrap(),
+            )),
+            "Hello",
+        )
+        .unwrap();
+
+        let mut capabilities = Capabilities::from_greeting(&greeting).unwrap();
+
+        let data = Data::capability(vec![
+            Capability::Imap4Rev1,
+            Capability::Idle,
+            Capability::try_from("xfoo").unwrap(),
+            Capability::try_from("auth=x-custom").unwrap(),
+        ])
+        .unwrap();
+
+        if let Data::Capability(caps) = data {
+            capabilities.merge(caps);
+        }
+
+        // `AUTH=PLAIN` is kept, duplicates are taken from the later announcement.
+        assert_eq!(
+            capabilities.as_slice(),
+            [
+                Capability::Auth(AuthMechanism::Plain),
+                Capability::Imap4Rev1,
+                Capability::Idle,
+                Capability::try_from("xfoo").unwrap(),
+                Capability::try_from("auth=x-custom").unwrap(),
+            ]
+        );
+        assert!(capabilities.contains(&Capability::try_from("XFOO").unwrap()));
+        assert!(capabilities.contains(&Capability::try_from("AUTH=x-Custom").unwrap()));
+        assert!(!capabilities.contains(&Capability::Move));
+    }
+
+    #[test]
+    fn test_capabilities_replace() {
+        let greeting = Greeting::ok(
+            Some(Code::Capability(
+                Vec1::try_from(vec![
+                    Capability::Imap4Rev1,
+                    Capability::try_from("AUTH=PLAIN").unwrap(),
+                    Capability::try_from("XFOO").unwrap(),
+                ])
+                .unwrap(),
+            )),
+            "Hello",
+        )
+        .unwrap();
+
+        let mut capabilities = Capabilities::from_greeting(&greeting).unwrap();
+        assert!(Capabilities::from_greeting(&Greeting::ok(None, "Hello").unwrap()).is_none());
+
+        let data = Data::capability(vec![
+            Capability::Imap4Rev1,
+            Capability::Idle,
+            Capability::try_from("xfoo").unwrap(),
+        ])
+        .unwrap();
+
+        if let Data::Capability(caps) = data {
+            capabilities.replace(caps);
+        }
+
+        // `AUTH=PLAIN` was not announced again.
+        assert_eq!(
+            capabilities.as_slice(),
+            [
+                Capability::Imap4Rev1,
+                Capability::Idle,
+                Capability::try_from("xfoo").unwrap(),
+            ]
+        );
+        assert!(capabilities.contains(&Capability::try_from("XFOO").unwrap()));
+        assert!(!capabilities.contains(&Capability::Auth(AuthMechanism::Plain)));
+        assert!(!capabilities.contains(&Capability::Move));
+
+        // Duplicates are removed.
+        let capabilities = Capabilities::from_iter([
+            Capability::try_from("XFOO").unwrap(),
+            Capability::Idle,
+            Capability::try_from("xfoo").unwrap(),
+        ]);
+        assert_eq!(
+            capabilities.as_slice(),
+            [Capability::Idle, Capability::try_from("xfoo").unwrap()]
+        );
+    }
+
+    #[test]
+    fn test_canonicalize_status() {
+        let data = Data::Status {
+            mailbox: Mailbox::Inbox,
+            items: vec![
+                StatusDataItem::Unseen(3),
+                StatusDataItem::UidNext(NonZeroU32::new(10).unwrap()),
+                StatusDataItem::Messages(42),
+                StatusDataItem::Recent(1),
+            ]
+            .into(),
+        };
+
+        let expected = Data::Status {
+            mailbox: Mailbox::Inbox,
+            items: vec![
+                StatusDataItem::Messages(42),
+                StatusDataItem::Recent(1),
+                StatusDataItem::UidNext(NonZeroU32::new(10).unwrap()),
+                StatusDataItem::Unseen(3),
+            ]
+            .into(),
+        };
+
+        assert_eq!(data.canonicalize_status(), expected);
+        assert_eq!(expected.canonicalize_status(), expected);
+        // Raw order is kept.
+        assert_ne!(data, expected);
+
+        assert_eq!(Data::Exists(1).canonicalize_status(), Data::Exists(1));
+    }
+
+    #[test]
+    fn test_eq_unordered() {
+        let fetch = |items: Vec<MessageDataItem<'static>>| Data::Fetch {
+            seq: NonZeroU32::new(1).unwrap(),
+            items: Vec1::try_from(items).unwrap(),
+        };
+
+        let flags = MessageDataItem::Flags(vec![FlagFetch::Flag(Flag::Seen)]);
+        let uid = MessageDataItem::Uid(NonZeroU32::new(42).unwrap());
+        let size = MessageDataItem::Rfc822Size(1337);
+
+        let data = fetch(vec![flags.clone(), uid.clone(), size.clone()]);
+        let reordered = fetch(vec![size.clone(), flags.clone(), uid.clone()]);
+
+        assert_ne!(data, reordered);
+        assert!(data.eq_unordered(&reordered));
+        assert!(reordered.eq_unordered(&data));
+
+        // Different sequence number.
+        let other_seq = Data::Fetch {
+            seq: NonZeroU32::new(2).unwrap(),
+            items: Vec1::try_from(vec![flags.clone(), uid.clone(), size.clone()]).unwrap(),
+        };
+        assert!(!data.eq_unordered(&other_seq));
+
+        // Missing, changed, or duplicated items.
+        assert!(!data.eq_unordered(&fetch(vec![flags.clone(), uid.clone()])));
+        assert!(!data.eq_unordered(&fetch(vec![
+            flags.clone(),
+            uid.clone(),
+            MessageDataItem::Rfc822Size(1),
+        ])));
+        assert!(!fetch(vec![flags.clone(), flags.clone(), uid.clone()])
+            .eq_unordered(&fetch(vec![flags.clone(), uid.clone(), uid.clone()])));
+
+        let status = Data::Status {
+            mailbox: Mailbox::Inbox,
+            items: vec![StatusDataItem::Messages(42), StatusDataItem::Unseen(3)].into(),
+        };
+        let reordered = Data::Status {
+            mailbox: Mailbox::Inbox,
+            items: vec![StatusDataItem::Unseen(3), StatusDataItem::Messages(42)].into(),
+        };
+        assert!(status.eq_unordered(&reordered));
+        assert!(!status.eq_unordered(&data));
+
+        assert!(Data::Exists(1).eq_unordered(&Data::Exists(1)));
+        assert!(!Data::Exists(1).eq_unordered(&Data::Exists(2)));
+    }
+
+    #[test]
+    fn test_canonicalize_capability() {
+        let code = Code::capability(vec![
+            Capability::Idle,
+            Capability::Imap4Rev1,
+            Capability::Enable,
+        ])
+        .unwrap();
+        let expected = Code::capability(vec![
+            Capability::Imap4Rev1,
+            Capability::Idle,
+            Capability::Enable,
+        ])
+        .unwrap();
+
+        assert_eq!(code.canonicalize_capability(), expected);
+        assert_eq!(expected.canonicalize_capability(), expected);
+        assert_eq!(Code::Alert.canonicalize_capability(), Code::Alert);
+
+        assert!(Code::capability(vec![]).is_err());
+    }
+
+    #[test]
+    fn test_response_router() {
+        use crate::{command::CommandBody, search::SearchKey};
+
+        let mut router = ResponseRouter::new();
+
+        let a1 = Tag::try_from("A1").unwrap();
+        let a2 = Tag::try_from("A2").unwrap();
+
+        // Pipeline a FETCH and a SEARCH.
+        router.register(
+            &Command::new(
+                a1.clone(),
+                CommandBody::fetch("1:2", vec![MessageDataItemName::Flags], false).unwrap(),
+            )
+            .unwrap(),
+        );
+        router.register(
+            &Command::new(
+                a2.clone(),
+                CommandBody::search(None, Vec1::from(SearchKey::All), false),
+            )
+            .unwrap(),
+        );
+
+        let fetch = |seq| Data::fetch(seq, vec![MessageDataItem::Flags(vec![])]).unwrap();
+        let ok = |tag: &Tag<'static>| {
+            Response::Status(Status::ok(Some(tag.clone()), None, "...").unwrap())
+        };
+        let body = StatusBody {
+            kind: StatusKind::Ok,
+            code: None,
+            text: Text::try_from("...").unwrap(),
+        };
+
+        let tests = [
+            (Response::Data(fetch(1)), Routed::Buffered),
+            (
+                Response::Data(Data::Search(vec![NonZeroU32::new(2).unwrap()])),
+                Routed::Buffered,
+            ),
+            (
+                Response::Data(Data::Exists(3)),
+                Routed::Unsolicited(Data::Exists(3)),
+            ),
+            (
+                Response::Status(Status::ok(None, None, "hello").unwrap()),
+                Routed::Other(Response::Status(Status::ok(None, None, "hello").unwrap())),
+            ),
+            (Response::Data(fetch(2)), Routed::Buffered),
+            // A2 completes first, but only gets its own data.
+            (
+                ok(&a2),
+                Routed::Completed {
+                    tag: a2.clone(),
+                    data: vec![Data::Search(vec![NonZeroU32::new(2).unwrap()])],
+                    status: body.clone(),
+                },
+            ),
+            (
+                ok(&a1),
+                Routed::Completed {
+                    tag: a1.clone(),
+                    data: vec![fetch(1), fetch(2)],
+                    status: body.clone(),
+                },
+            ),
+            // Unknown tags complete without data.
+            (
+                ok(&Tag::try_from("A3").unwrap()),
+                Routed::Completed {
+                    tag: Tag::try_from("A3").unwrap(),
+                    data: vec![],
+                    status: body,
+                },
+            ),
+        ];
+
+        for (response, expected) in tests {
+            assert_eq!(router.feed(response), expected);
+        }
+
+        assert!(router.pending(&a1).is_empty());
+        assert_eq!(router, ResponseRouter::new());
+    }
+
+    #[test]
+    fn test_fetch_response() {
+        use chrono::DateTime as ChronoDateTime;
+
+        use crate::{
+            body::{BasicFields, Body, BodyStructure, SinglePartExtensionData, SpecificFields},
+            core::{IString, NString},
+            datetime::DateTime,
+            envelope::Envelope,
+            fetch::Macro,
+            flag::FlagFetch,
+        };
+
+        let body_structure = BodyStructure::Single {
+            body: Body {
+                basic: BasicFields {
+                    parameter_list: vec![],
+                    id: NString(None),
+                    description: NString(None),
+                    content_transfer_encoding: IString::try_from("7bit").unwrap(),
+                    size: 13,
+                },
+                specific: SpecificFields::Text {
+                    subtype: IString::try_from("plain").unwrap(),
+                    number_of_lines: 1,
+                },
+            },
+            extension_data: Some(SinglePartExtensionData {
+                md5: NString(None),
+                tail: None,
+            }),
+        };
+
+        let envelope = Envelope {
+            date: NString(None),
+            subject: NString::try_from("Hello").unwrap(),
+            from: vec![],
+            sender: vec![],
+            reply_to: vec![],
+            to: vec![],
+            cc: vec![],
+            bcc: vec![],
+            in_reply_to: NString(None),
+            message_id: NString(None),
+        };
+
+        let internal_date = DateTime::try_from(
+            ChronoDateTime::parse_from_rfc2822("Tue, 1 Jul 2003 10:52:37 +0200").unwrap(),
+        )
+        .unwrap();
+
+        let message = ParsedMessage {
+            uid: NonZeroU32::new(42).unwrap(),
+            flags: vec![FlagFetch::Flag(Flag::Seen)],
+            internal_date: internal_date.clone(),
+            size: 13,
+            envelope: envelope.clone(),
+            body_structure: body_structure.clone(),
+        };
+
+        let got =
+            Data::fetch_response(NonZeroU32::new(1).unwrap(), &Macro::Full.expand(), &message)
+                .unwrap();
+
+        let expected = Data::fetch(
+            1,
+            vec![
+                MessageDataItem::Flags(vec![FlagFetch::Flag(Flag::Seen)]),
+                MessageDataItem::InternalDate(internal_date),
+                MessageDataItem::Rfc822Size(13),
+                MessageDataItem::Envelope(envelope),
+                MessageDataItem::Body(BodyStructure::Single {
+                    body: match body_structure {
+                        BodyStructure::Single { body, .. } => body,
+                        BodyStructure::Multi { .. } => unreachable!(),
+                    },
+                    extension_data: None,
+                }),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(got, expected);
+
+        assert_eq!(
+            Data::fetch_response(
+                NonZeroU32::new(1).unwrap(),
+                &[MessageDataItemName::Rfc822],
+                &message
+            ),
+            Err(FetchResponseError::Unsupported(MessageDataItemName::Rfc822))
+        );
+        assert_eq!(
+            Data::fetch_response(NonZeroU32::new(1).unwrap(), &[], &message),
+            Err(FetchResponseError::Empty)
+        );
+    }
 }
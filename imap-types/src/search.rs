@@ -1,14 +1,21 @@
 //! Search-related types.
 
+#[cfg(feature = "ext_within")]
+use std::num::NonZeroU32;
+
 #[cfg(feature = "bounded-static")]
 use bounded_static::ToStatic;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "ext_annotate")]
+use crate::{core::NString, mailbox::ListMailbox};
 use crate::{
     core::{AString, Atom, Vec1},
     datetime::NaiveDate,
-    sequence::SequenceSet,
+    flag::Flag,
+    search::error::SearchKeyError,
+    sequence::{SeqOrUid, Sequence, SequenceSet},
 };
 
 /// The defined search keys.
@@ -160,13 +167,325 @@ pub enum SearchKey<'a> {
 
     /// Messages that do not have the \Seen flag set.
     Unseen,
+
+    /// Messages that have an annotation entry/attribute matching the specified value (RFC 5257).
+    ///
+    /// ```abnf
+    /// search-annotation = "ANNOTATION" SP entry-match SP attrib-match SP value
+    ///
+    /// entry-match       = list-mailbox
+    ///
+    /// attrib-match      = astring
+    /// ```
+    ///
+    /// Note: Wildcards ("*" and "%") are only carried as-is and interpreted by the server.
+    /// Unquoted wildcards are only possible in `entry`. In `attribute`, they require a quoted
+    /// string or literal.
+    #[cfg(feature = "ext_annotate")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext_annotate")))]
+    Annotation {
+        /// Slash-separated path to the entry, e.g., `/comment`.
+        entry: ListMailbox<'a>,
+        /// Dot-separated attribute name, e.g., `value.priv`.
+        attribute: AString<'a>,
+        /// Value to search for.
+        value: NString<'a>,
+    },
+
+    /// Messages whose internal date is older than the specified interval in seconds (RFC 5032).
+    #[cfg(feature = "ext_within")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext_within")))]
+    Older(NonZeroU32),
+
+    /// Messages whose internal date is within the specified interval in seconds (RFC 5032).
+    #[cfg(feature = "ext_within")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext_within")))]
+    Younger(NonZeroU32),
+
+    /// Messages in mailboxes that support save dates (RFC 8514).
+    #[cfg(feature = "ext_savedate")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext_savedate")))]
+    SaveDateSupported,
+
+    /// Messages whose save date (disregarding time and timezone) is earlier than the
+    /// specified date (RFC 8514).
+    #[cfg(feature = "ext_savedate")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext_savedate")))]
+    SavedBefore(NaiveDate),
+
+    /// Messages whose save date (disregarding time and timezone) is within the specified
+    /// date (RFC 8514).
+    #[cfg(feature = "ext_savedate")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext_savedate")))]
+    SavedOn(NaiveDate),
+
+    /// Messages whose save date (disregarding time and timezone) is within or later than the
+    /// specified date (RFC 8514).
+    #[cfg(feature = "ext_savedate")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext_savedate")))]
+    SavedSince(NaiveDate),
 }
 
 impl<'a> SearchKey<'a> {
+    /// Same as [`SearchKey::uids`].
+    #[deprecated(note = "Use `SearchKey::uids` instead.")]
     pub fn uid<S>(sequence_set: S) -> Self
+    where
+        S: Into<SequenceSet>,
+    {
+        Self::uids(sequence_set)
+    }
+
+    /// Messages with unique identifiers in the given set, i.e., `UID <sequence-set>`.
+    ///
+    /// Note: Use [`SearchKey::seqs`] for message sequence numbers.
+    pub fn uids<S>(sequence_set: S) -> Self
     where
         S: Into<SequenceSet>,
     {
         Self::Uid(sequence_set.into())
     }
+
+    /// Messages with message sequence numbers in the given set, i.e., `<sequence-set>`.
+    ///
+    /// Note: Use [`SearchKey::uids`] for unique identifiers.
+    pub fn seqs<S>(sequence_set: S) -> Self
+    where
+        S: Into<SequenceSet>,
+    {
+        Self::SequenceSet(sequence_set.into())
+    }
+
+    /// Messages with the specified keyword flag set, i.e., `KEYWORD <flag>`.
+    ///
+    /// Only [`Flag::Keyword`] is accepted. System flags have dedicated search keys, e.g., use
+    /// [`SearchKey::Seen`] instead of `KEYWORD \Seen`.
+    pub fn keyword(flag: Flag<'a>) -> Result<Self, SearchKeyError> {
+        Ok(Self::Keyword(Self::keyword_atom(flag, false)?))
+    }
+
+    /// Messages that do not have the specified keyword flag set, i.e., `UNKEYWORD <flag>`.
+    ///
+    /// Only [`Flag::Keyword`] is accepted. System flags have dedicated search keys, e.g., use
+    /// [`SearchKey::Unseen`] instead of `UNKEYWORD \Seen`.
+    pub fn unkeyword(flag: Flag<'a>) -> Result<Self, SearchKeyError> {
+        Ok(Self::Unkeyword(Self::keyword_atom(flag, true)?))
+    }
+
+    fn keyword_atom(flag: Flag<'a>, negated: bool) -> Result<Atom<'a>, SearchKeyError> {
+        let (positive, negative) = match flag {
+            Flag::Keyword(atom) => return Ok(atom),
+            Flag::Answered => ("Answered", "Unanswered"),
+            Flag::Deleted => ("Deleted", "Undeleted"),
+            Flag::Draft => ("Draft", "Undraft"),
+            Flag::Flagged => ("Flagged", "Unflagged"),
+            Flag::Seen => ("Seen", "Unseen"),
+            Flag::Extension(_) => return Err(SearchKeyError::NotAKeyword(flag.to_string())),
+        };
+
+        Err(SearchKeyError::SystemFlag {
+            flag: flag.to_string(),
+            key: if negated { negative } else { positive },
+        })
+    }
+
+    /// Estimate the fraction of messages in a mailbox matched by this search key.
+    ///
+    /// The result is in `0.0..=1.0`. Lower values are more selective, e.g., `UID 5` has a much
+    /// lower value than `ALL` (`1.0`). Servers may use this to decide in which order to evaluate
+    /// search keys.
+    ///
+    /// Note: This is a heuristic over the search key alone. It doesn't know the mailbox, so the
+    /// value of a flag or date key is a (rough) guess.
+    pub fn estimated_selectivity(&self) -> f32 {
+        let selectivity = match self {
+            Self::And(keys) => keys
+                .as_ref()
+                .iter()
+                .map(Self::estimated_selectivity)
+                .product(),
+            Self::Or(left, right) => {
+                let left = left.estimated_selectivity();
+                let right = right.estimated_selectivity();
+
+                left + right - left * right
+            }
+            Self::Not(key) => 1.0 - key.estimated_selectivity(),
+            Self::SequenceSet(sequence_set) | Self::Uid(sequence_set) => {
+                sequence_set_selectivity(sequence_set)
+            }
+            Self::All => 1.0,
+            #[cfg(feature = "ext_savedate")]
+            Self::SaveDateSupported => 1.0,
+            // Flags (complementary keys, e.g., `SEEN` and `UNSEEN`, sum up to 1.0)
+            Self::Seen | Self::Unanswered => 0.8,
+            Self::Unseen | Self::Answered => 0.2,
+            Self::Undeleted | Self::Undraft | Self::Unflagged | Self::Old => 0.95,
+            Self::Deleted | Self::Draft | Self::Flagged | Self::Recent | Self::New => 0.05,
+            Self::Keyword(_) => 0.1,
+            Self::Unkeyword(_) => 0.9,
+            // Dates
+            Self::Before(_) | Self::SentBefore(_) | Self::Since(_) | Self::SentSince(_) => 0.5,
+            Self::On(_) | Self::SentOn(_) => 0.01,
+            #[cfg(feature = "ext_savedate")]
+            Self::SavedBefore(_) | Self::SavedSince(_) => 0.5,
+            #[cfg(feature = "ext_savedate")]
+            Self::SavedOn(_) => 0.01,
+            #[cfg(feature = "ext_within")]
+            Self::Older(_) | Self::Younger(_) => 0.5,
+            // Size
+            Self::Larger(_) | Self::Smaller(_) => 0.5,
+            // Content
+            Self::Bcc(_)
+            | Self::Cc(_)
+            | Self::From(_)
+            | Self::Subject(_)
+            | Self::To(_)
+            | Self::Body(_)
+            | Self::Text(_) => 0.05,
+            Self::Header(..) => 0.1,
+            #[cfg(feature = "ext_annotate")]
+            Self::Annotation { .. } => 0.1,
+        };
+
+        selectivity.clamp(0.0, 1.0)
+    }
+}
+
+/// Assumed number of messages in a mailbox to turn a number of messages into a fraction.
+const ASSUMED_MAILBOX_SIZE: f32 = 1000.0;
+
+fn sequence_set_selectivity(sequence_set: &SequenceSet) -> f32 {
+    sequence_set
+        .0
+        .as_ref()
+        .iter()
+        .map(|sequence| match sequence {
+            Sequence::Single(_) => 1.0 / ASSUMED_MAILBOX_SIZE,
+            Sequence::Range(SeqOrUid::Value(from), SeqOrUid::Value(to)) => {
+                (from.get().abs_diff(to.get()) as f32 + 1.0) / ASSUMED_MAILBOX_SIZE
+            }
+            // Open ranges, e.g., `5:*`, depend on the mailbox.
+            Sequence::Range(_, _) => 0.5,
+            #[cfg(feature = "ext_searchres")]
+            Sequence::SavedResult => 0.5,
+        })
+        .sum()
+}
+
+/// Error-related types.
+pub mod error {
+    use thiserror::Error;
+
+    #[derive(Clone, Debug, Eq, Error, Hash, Ord, PartialEq, PartialOrd)]
+    pub enum SearchKeyError {
+        #[error("`{flag}` is a system flag: Please use `SearchKey::{key}` instead")]
+        SystemFlag { flag: String, key: &'static str },
+        #[error("`{0}` is not a keyword")]
+        NotAKeyword(String),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimated_selectivity() {
+        let all = SearchKey::All.estimated_selectivity();
+        let uid = SearchKey::uids(SequenceSet::try_from(5).unwrap()).estimated_selectivity();
+
+        assert_eq!(all, 1.0);
+        assert!(uid < all);
+        assert!(uid < SearchKey::Seen.estimated_selectivity());
+        assert!(
+            uid < SearchKey::uids(SequenceSet::try_from(1..=100).unwrap()).estimated_selectivity()
+        );
+
+        // Negation inverts the selectivity ...
+        assert_eq!(
+            SearchKey::Not(Box::new(SearchKey::All)).estimated_selectivity(),
+            0.0
+        );
+        // ... AND narrows it down ...
+        let and = SearchKey::And(
+            Vec1::try_from(vec![
+                SearchKey::Unseen,
+                SearchKey::uids(SequenceSet::try_from(5).unwrap()),
+            ])
+            .unwrap(),
+        );
+        assert!(and.estimated_selectivity() <= uid);
+        // ... and OR widens it.
+        let or = SearchKey::Or(
+            Box::new(SearchKey::Unseen),
+            Box::new(SearchKey::uids(SequenceSet::try_from(5).unwrap())),
+        );
+        assert!(or.estimated_selectivity() >= SearchKey::Unseen.estimated_selectivity());
+
+        // The result is always a fraction.
+        assert_eq!(
+            SearchKey::seqs(SequenceSet::try_from("1:100000").unwrap()).estimated_selectivity(),
+            1.0
+        );
+    }
+
+    #[test]
+    fn test_estimated_selectivity_complementary() {
+        let keyword = Atom::try_from("Important").unwrap();
+
+        let tests = [
+            (SearchKey::Answered, SearchKey::Unanswered),
+            (SearchKey::Deleted, SearchKey::Undeleted),
+            (SearchKey::Draft, SearchKey::Undraft),
+            (SearchKey::Flagged, SearchKey::Unflagged),
+            (SearchKey::Recent, SearchKey::Old),
+            (SearchKey::Seen, SearchKey::Unseen),
+            (
+                SearchKey::Keyword(keyword.clone()),
+                SearchKey::Unkeyword(keyword),
+            ),
+        ];
+
+        for (key, complement) in tests {
+            let sum = key.estimated_selectivity() + complement.estimated_selectivity();
+            assert!(
+                (sum - 1.0).abs() < f32::EPSILON,
+                "{key:?} + {complement:?} = {sum}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_keyword() {
+        let atom = Atom::try_from("Important").unwrap();
+
+        assert_eq!(
+            SearchKey::keyword(Flag::Keyword(atom.clone())),
+            Ok(SearchKey::Keyword(atom.clone()))
+        );
+        assert_eq!(
+            SearchKey::unkeyword(Flag::Keyword(atom.clone())),
+            Ok(SearchKey::Unkeyword(atom))
+        );
+
+        assert_eq!(
+            SearchKey::keyword(Flag::Seen),
+            Err(SearchKeyError::SystemFlag {
+                flag: "\\Seen".into(),
+                key: "Seen"
+            })
+        );
+        assert_eq!(
+            SearchKey::unkeyword(Flag::Seen),
+            Err(SearchKeyError::SystemFlag {
+                flag: "\\Seen".into(),
+                key: "Unseen"
+            })
+        );
+        assert!(matches!(
+            SearchKey::keyword(Flag::system(Atom::try_from("Foo").unwrap())),
+            Err(SearchKeyError::NotAKeyword(_))
+        ));
+    }
 }
@@ -18,6 +18,7 @@ use serde::{Deserialize, Serialize};
 use crate::{
     core::Vec1,
     error::{ValidationError, ValidationErrorKind},
+    sequence::error::SequenceSetError,
 };
 
 pub const ONE: NonZeroU32 = match NonZeroU32::new(1) {
@@ -102,26 +103,76 @@ impl TryFrom<Vec<NonZeroU32>> for SequenceSet {
 }
 
 impl TryFrom<&str> for SequenceSet {
-    type Error = ValidationError;
+    type Error = SequenceSetError;
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
         value.parse()
     }
 }
 
+/// Parse a sequence set as used in IMAP, e.g., `1,3:5,8:*`.
+///
+/// Sequences are kept as written. In particular, an inverted range such as `5:1` is accepted
+/// and kept as-is because IMAP defines it to be the same as `1:5`. Also, `*:*` is kept as a range
+/// (of the largest number in use). Because leading zeros and whitespace are rejected, encoding a
+/// parsed sequence set yields the input again.
 impl FromStr for SequenceSet {
-    type Err = ValidationError;
+    type Err = SequenceSetError;
 
     fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if value.is_empty() {
+            return Err(SequenceSetError::Empty);
+        }
+
         let mut results = vec![];
+        let mut at = 0;
 
-        for seq in value.split(',') {
-            results.push(Sequence::try_from(seq)?);
+        for sequence in value.split(',') {
+            results.push(parse_sequence(sequence, at)?);
+            at += sequence.len() + 1;
         }
 
-        Ok(SequenceSet(Vec1::try_from(results).map_err(|_| {
-            ValidationError::new(ValidationErrorKind::Empty)
-        })?))
+        // Safety: `unwrap` can't panic because `split` yields at least one item.
+        Ok(SequenceSet(Vec1::try_from(results).unwrap()))
+    }
+}
+
+/// Parse a single sequence starting at byte offset `at` (see [`SequenceSet::from_str`]).
+fn parse_sequence(value: &str, at: usize) -> Result<Sequence, SequenceSetError> {
+    #[cfg(feature = "ext_searchres")]
+    if value == "$" {
+        return Ok(Sequence::SavedResult);
+    }
+
+    let mut bounds = value.split(':');
+
+    // Safety: `split` yields at least one item.
+    let start = bounds.next().unwrap();
+
+    match (bounds.next(), bounds.next()) {
+        (None, _) => Ok(Sequence::Single(parse_seq_or_uid(start, at)?)),
+        (Some(end), None) => Ok(Sequence::Range(
+            parse_seq_or_uid(start, at)?,
+            parse_seq_or_uid(end, at + start.len() + 1)?,
+        )),
+        (Some(end), Some(_)) => Err(SequenceSetError::Invalid {
+            at: at + start.len() + 1 + end.len(),
+        }),
+    }
+}
+
+/// Parse a sequence number, UID, or `*` starting at byte offset `at`.
+fn parse_seq_or_uid(value: &str, at: usize) -> Result<SeqOrUid, SequenceSetError> {
+    match value {
+        "*" => Ok(SeqOrUid::Asterisk),
+        "0" => Err(SequenceSetError::ZeroNotAllowed { at }),
+        // Note: Rust's `parse::<NonZeroU32>` accepts leading zeros and a leading `+`.
+        _ if value.starts_with('0') || !value.bytes().all(|b| b.is_ascii_digit()) => {
+            Err(SequenceSetError::Invalid { at })
+        }
+        _ => NonZeroU32::from_str(value)
+            .map(SeqOrUid::Value)
+            .map_err(|_| SequenceSetError::Invalid { at }),
     }
 }
 
@@ -132,6 +183,13 @@ impl FromStr for SequenceSet {
 pub enum Sequence {
     Single(SeqOrUid),
     Range(SeqOrUid, SeqOrUid),
+    /// The saved result of the last SEARCH command, i.e., `$` (RFC 5182).
+    ///
+    /// Note: The referenced messages are only known to the server. Thus, `$` is skipped when
+    /// iterating over a [`SequenceSet`].
+    #[cfg(feature = "ext_searchres")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext_searchres")))]
+    SavedResult,
 }
 
 impl From<SeqOrUid> for Sequence {
@@ -158,6 +216,11 @@ impl FromStr for Sequence {
     type Err = ValidationError;
 
     fn from_str(value: &str) -> Result<Self, Self::Err> {
+        #[cfg(feature = "ext_searchres")]
+        if value == "$" {
+            return Ok(Sequence::SavedResult);
+        }
+
         match value.split(':').count() {
             0 => Err(ValidationError::new(ValidationErrorKind::Empty)),
             1 => Ok(Sequence::Single(SeqOrUid::try_from(value)?)),
@@ -182,6 +245,10 @@ impl FromStr for Sequence {
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Copy)]
 pub enum SeqOrUid {
+    /// A sequence number or UID.
+    ///
+    /// Note: Zero is not a valid sequence number or UID. Conversions from numbers and strings
+    /// reject `0` with a "must not be zero" validation error.
     Value(NonZeroU32),
     Asterisk,
 }
@@ -228,10 +295,12 @@ macro_rules! impl_try_from_num {
             type Error = ValidationError;
 
             fn try_from(value: $num) -> Result<Self, Self::Error> {
-                if let Ok(value) = u32::try_from(value) {
-                    if let Ok(value) = NonZeroU32::try_from(value) {
-                        return Ok(Self::Value(value));
-                    }
+                if value == 0 {
+                    return Err(ValidationError::new(ValidationErrorKind::ZeroNotAllowed));
+                }
+
+                if let Some(value) = u32::try_from(value).ok().and_then(NonZeroU32::new) {
+                    return Ok(Self::Value(value));
                 }
 
                 Err(ValidationError::new(ValidationErrorKind::Invalid))
@@ -265,6 +334,8 @@ impl FromStr for SeqOrUid {
     fn from_str(value: &str) -> Result<Self, Self::Err> {
         if value == "*" {
             Ok(SeqOrUid::Asterisk)
+        } else if value == "0" {
+            Err(ValidationError::new(ValidationErrorKind::ZeroNotAllowed))
         } else {
             // This is to align parsing here with the IMAP grammar:
             // Rust's `parse::<NonZeroU32>` function accepts numbers that start with 0.
@@ -500,6 +571,156 @@ impl<'a> SequenceSet {
             active_range: None,
         }
     }
+
+    /// Return the lone number if the sequence set consists of a single number, e.g., `5` or `5:5`.
+    ///
+    /// Note: A sequence set is never empty. `*` is not considered a single value because its
+    /// meaning depends on the mailbox.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::num::NonZeroU32;
+    ///
+    /// use imap_types::sequence::SequenceSet;
+    ///
+    /// assert_eq!(
+    ///     SequenceSet::try_from("5").unwrap().is_single(),
+    ///     NonZeroU32::new(5)
+    /// );
+    /// assert_eq!(SequenceSet::try_from("1:5").unwrap().is_single(), None);
+    /// ```
+    pub fn is_single(&self) -> Option<NonZeroU32> {
+        match self.0.as_ref() {
+            [Sequence::Single(SeqOrUid::Value(value))] => Some(*value),
+            [Sequence::Range(SeqOrUid::Value(start), SeqOrUid::Value(end))] if start == end => {
+                Some(*start)
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether `n` is part of the sequence set (with `*` resolved to `largest`).
+    ///
+    /// Note: `$` never contains a number because only the server knows which messages it references.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::num::NonZeroU32;
+    ///
+    /// use imap_types::sequence::SequenceSet;
+    ///
+    /// let seq = SequenceSet::try_from("1:3,*").unwrap();
+    /// let largest = NonZeroU32::new(10).unwrap();
+    ///
+    /// assert!(seq.contains(NonZeroU32::new(2).unwrap(), largest));
+    /// assert!(!seq.contains(NonZeroU32::new(5).unwrap(), largest));
+    /// assert!(seq.contains(NonZeroU32::new(10).unwrap(), largest));
+    /// ```
+    pub fn contains(&self, n: NonZeroU32, largest: NonZeroU32) -> bool {
+        self.0.as_ref().iter().any(|seq| match seq {
+            Sequence::Single(a) => a.expand(largest) == n,
+            Sequence::Range(a, b) => {
+                let a = a.expand(largest);
+                let b = b.expand(largest);
+
+                a.min(b) <= n && n <= a.max(b)
+            }
+            #[cfg(feature = "ext_searchres")]
+            Sequence::SavedResult => false,
+        })
+    }
+
+    /// Return the union of both sequence sets (with `*` resolved to `largest`).
+    ///
+    /// The result is normalized, i.e., it consists of sorted, non-overlapping, and non-adjacent
+    /// sequences. `$` is kept as-is when used in either sequence set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::num::NonZeroU32;
+    ///
+    /// use imap_types::sequence::SequenceSet;
+    ///
+    /// let a = SequenceSet::try_from("7,1:3").unwrap();
+    /// let b = SequenceSet::try_from("2:5,*").unwrap();
+    /// let largest = NonZeroU32::new(10).unwrap();
+    ///
+    /// assert_eq!(
+    ///     a.union(&b, largest),
+    ///     SequenceSet::try_from("1:5,7,10").unwrap()
+    /// );
+    /// ```
+    pub fn union(&self, other: &SequenceSet, largest: NonZeroU32) -> SequenceSet {
+        let mut ranges = simplify(self.clone(), largest, true);
+        ranges.extend(simplify(other.clone(), largest, true));
+
+        #[cfg_attr(not(feature = "ext_searchres"), allow(unused_mut))]
+        let mut sequences = normalize(cleanup(ranges));
+
+        #[cfg(feature = "ext_searchres")]
+        if self.0.as_ref().contains(&Sequence::SavedResult)
+            || other.0.as_ref().contains(&Sequence::SavedResult)
+        {
+            sequences.push(Sequence::SavedResult);
+        }
+
+        // Note: Every sequence set contributes at least one sequence (or `$`).
+        SequenceSet(Vec1::try_from(sequences).unwrap())
+    }
+
+    /// Return the intersection of both sequence sets (with `*` resolved to `largest`).
+    ///
+    /// The result is normalized (see [`SequenceSet::union`]) or `None` when there are no common
+    /// numbers. `$` is ignored because only the server knows which messages it references.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::num::NonZeroU32;
+    ///
+    /// use imap_types::sequence::SequenceSet;
+    ///
+    /// let a = SequenceSet::try_from("1:5,8:*").unwrap();
+    /// let b = SequenceSet::try_from("4:9").unwrap();
+    /// let largest = NonZeroU32::new(10).unwrap();
+    ///
+    /// assert_eq!(
+    ///     a.intersection(&b, largest),
+    ///     Some(SequenceSet::try_from("4:5,8:9").unwrap())
+    /// );
+    /// assert_eq!(
+    ///     a.intersection(&SequenceSet::try_from("6:7").unwrap(), largest),
+    ///     None
+    /// );
+    /// ```
+    pub fn intersection(&self, other: &SequenceSet, largest: NonZeroU32) -> Option<SequenceSet> {
+        let left = cleanup(simplify(self.clone(), largest, true));
+        let right = cleanup(simplify(other.clone(), largest, true));
+
+        let mut ranges = VecDeque::new();
+        let (mut left, mut right) = (left.iter().peekable(), right.iter().peekable());
+
+        while let (Some(&&(a, b)), Some(&&(c, d))) = (left.peek(), right.peek()) {
+            let start = max(a, c);
+            let end = b.min(d);
+
+            if start <= end {
+                ranges.push_back((start, end));
+            }
+
+            // Advance the range that ends first.
+            if b <= d {
+                left.next();
+            } else {
+                right.next();
+            }
+        }
+
+        Vec1::try_from(normalize(ranges)).ok().map(SequenceSet)
+    }
 }
 
 impl SeqOrUid {
@@ -564,27 +785,34 @@ impl Iterator for Sorting {
 // * Use u32 instead of NonZeroU32 (for internal purposes)
 // * Expand Single(a) to (a, a)
 // * Sort Range(a, b) so that a <= b
+// * Skip SavedResult
+#[cfg_attr(not(feature = "ext_searchres"), allow(clippy::unnecessary_filter_map))]
 fn simplify(sequence_set: SequenceSet, largest: NonZeroU32, sort: bool) -> VecDeque<(u32, u32)> {
     sequence_set
         .0
          .0
         .into_iter()
-        .map(|seq| match seq {
-            Sequence::Single(a) => (u32::from(a.expand(largest)), u32::from(a.expand(largest))),
+        .filter_map(|seq| match seq {
+            Sequence::Single(a) => {
+                Some((u32::from(a.expand(largest)), u32::from(a.expand(largest))))
+            }
             Sequence::Range(a, b) => {
                 let a = u32::from(a.expand(largest));
                 let b = u32::from(b.expand(largest));
 
                 if sort {
                     if a <= b {
-                        (a, b)
+                        Some((a, b))
                     } else {
-                        (b, a)
+                        Some((b, a))
                     }
                 } else {
-                    (a, b)
+                    Some((a, b))
                 }
             }
+            // Note: Only the server knows which messages are referenced by `$`.
+            #[cfg(feature = "ext_searchres")]
+            Sequence::SavedResult => None,
         })
         .collect()
 }
@@ -597,7 +825,11 @@ fn cleanup(remaining: VecDeque<(u32, u32)>) -> VecDeque<(u32, u32)> {
     };
 
     let mut stack = VecDeque::new();
-    stack.push_back(remaining.pop_front().unwrap());
+    match remaining.pop_front() {
+        Some(first) => stack.push_back(first),
+        // Note: Happens when the sequence set only consists of `$`.
+        None => return stack,
+    }
 
     for (x, y) in remaining.into_iter() {
         let last = stack.back_mut().unwrap();
@@ -612,6 +844,48 @@ fn cleanup(remaining: VecDeque<(u32, u32)>) -> VecDeque<(u32, u32)> {
     stack
 }
 
+// Convert cleaned up ranges back into sequences.
+fn normalize(ranges: VecDeque<(u32, u32)>) -> Vec<Sequence> {
+    ranges
+        .into_iter()
+        .map(|(a, b)| {
+            // We know here that `a >= 1` and `b >= 1`.
+            let a = SeqOrUid::Value(NonZeroU32::new(a).unwrap());
+            let b = SeqOrUid::Value(NonZeroU32::new(b).unwrap());
+
+            if a == b {
+                Sequence::Single(a)
+            } else {
+                Sequence::Range(a, b)
+            }
+        })
+        .collect()
+}
+
+/// Error-related types.
+pub mod error {
+    #[cfg(feature = "bounded-static")]
+    use bounded_static::ToStatic;
+    use thiserror::Error;
+
+    /// Error during parsing of a sequence set.
+    ///
+    /// `at` is the byte offset of the offending number (or of the extra `:`).
+    #[cfg_attr(feature = "bounded-static", derive(ToStatic))]
+    #[derive(Clone, Debug, Eq, Error, Hash, Ord, PartialEq, PartialOrd)]
+    pub enum SequenceSetError {
+        #[error("Sequence set must not be empty")]
+        Empty,
+        #[error("Sequence number or UID must not be zero (at {at})")]
+        ZeroNotAllowed { at: usize },
+        #[error("Invalid sequence at {at}")]
+        Invalid { at: usize },
+        /// The sequence set has more than `max` sequences (see imap-codec's `CommandCodec`).
+        #[error("Sequence set must not have more than {max} sequences")]
+        TooLarge { max: usize },
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::num::NonZeroU32;
@@ -629,7 +903,23 @@ mod tests {
         );
         assert_eq!(
             SequenceSet::try_from(0),
-            Err(ValidationError::new(ValidationErrorKind::Invalid))
+            Err(ValidationError::new(ValidationErrorKind::ZeroNotAllowed))
+        );
+    }
+
+    #[test]
+    fn test_creation_of_sequence_from_str_zero() {
+        for (test, at) in [("0", 0), ("1:0", 2), ("0:*", 0), ("1,0", 2)] {
+            assert_eq!(
+                SequenceSet::try_from(test),
+                Err(SequenceSetError::ZeroNotAllowed { at }),
+                "{test}"
+            );
+        }
+
+        assert_eq!(
+            SequenceSet::try_from("01"),
+            Err(SequenceSetError::Invalid { at: 0 })
         );
     }
 
@@ -806,6 +1096,43 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_creation_of_sequence_set_from_str_error_position() {
+        let tests = [
+            ("", SequenceSetError::Empty),
+            ("1,", SequenceSetError::Invalid { at: 2 }),
+            (",1", SequenceSetError::Invalid { at: 0 }),
+            ("1,2:x", SequenceSetError::Invalid { at: 4 }),
+            ("1,2:3:4", SequenceSetError::Invalid { at: 5 }),
+            ("1,+2", SequenceSetError::Invalid { at: 2 }),
+            ("1,2,5:0", SequenceSetError::ZeroNotAllowed { at: 6 }),
+            ("4294967296", SequenceSetError::Invalid { at: 0 }),
+        ];
+
+        for (test, expected) in tests {
+            assert_eq!(SequenceSet::from_str(test), Err(expected), "{test}");
+        }
+    }
+
+    #[test]
+    fn test_creation_of_sequence_set_from_str_ranges() {
+        // Inverted ranges are kept as written.
+        assert_eq!(
+            SequenceSet::from_str("5:1"),
+            Ok(SequenceSet::from(Sequence::Range(
+                SeqOrUid::Value(NonZeroU32::new(5).unwrap()),
+                SeqOrUid::Value(NonZeroU32::new(1).unwrap()),
+            )))
+        );
+        assert_eq!(
+            SequenceSet::from_str("*:*"),
+            Ok(SequenceSet::from(Sequence::Range(
+                SeqOrUid::Asterisk,
+                SeqOrUid::Asterisk
+            )))
+        );
+    }
+
     #[test]
     fn test_iteration_over_some_sequence_sets() {
         let tests = vec![
@@ -834,6 +1161,23 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_is_single() {
+        let tests = [
+            ("5", NonZeroU32::new(5)),
+            ("5:5", NonZeroU32::new(5)),
+            ("1:5", None),
+            ("1,2", None),
+            ("5,5", None),
+            ("*", None),
+            ("*:*", None),
+        ];
+
+        for (test, expected) in tests {
+            assert_eq!(SequenceSet::try_from(test).unwrap().is_single(), expected);
+        }
+    }
+
     /// See https://github.com/duesee/imap-codec/issues/411
     #[test]
     fn test_issue_411() {
@@ -861,6 +1205,89 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_iter_overlapping() {
+        let largest = NonZeroU32::new(10).unwrap();
+
+        let tests = [
+            ("1:5,3:8", (1..=8).collect::<Vec<u32>>()),
+            ("3:8,1:5", (1..=8).collect()),
+            ("8:3,5:1,2", (1..=8).collect()),
+            ("1:3,4:6", (1..=6).collect()),
+            ("9:*,*,7", vec![7, 9, 10]),
+        ];
+
+        for (test, expected) in tests {
+            let got: Vec<u32> = SequenceSet::try_from(test)
+                .unwrap()
+                .iter(largest)
+                .map(u32::from)
+                .collect();
+            assert_eq!(expected, got, "{test}");
+        }
+    }
+
+    #[test]
+    fn test_contains() {
+        let seq = SequenceSet::try_from("1:3,7:5,*").unwrap();
+        let largest = NonZeroU32::new(10).unwrap();
+
+        for n in 1..=12 {
+            let expected = [1, 2, 3, 5, 6, 7, 10].contains(&n);
+            let got = seq.contains(NonZeroU32::new(n).unwrap(), largest);
+            assert_eq!(expected, got, "{n}");
+        }
+
+        // `*` follows `largest`.
+        assert!(seq.contains(NonZeroU32::new(12).unwrap(), NonZeroU32::new(12).unwrap()));
+    }
+
+    #[test]
+    fn test_union() {
+        let largest = NonZeroU32::new(10).unwrap();
+
+        let tests = [
+            ("1:5", "3:8", "1:8"),
+            ("1", "2", "1:2"),
+            ("1,3", "5", "1,3,5"),
+            ("5,1", "1,5", "1,5"),
+            ("*", "8:9", "8:10"),
+            ("1:*", "4", "1:10"),
+        ];
+
+        for (left, right, expected) in tests {
+            let left = SequenceSet::try_from(left).unwrap();
+            let right = SequenceSet::try_from(right).unwrap();
+            let expected = SequenceSet::try_from(expected).unwrap();
+
+            assert_eq!(left.union(&right, largest), expected);
+            assert_eq!(right.union(&left, largest), expected);
+        }
+    }
+
+    #[test]
+    fn test_intersection() {
+        let largest = NonZeroU32::new(10).unwrap();
+
+        let tests = [
+            ("1:5", "3:8", Some("3:5")),
+            ("1:5,7:9", "4:8", Some("4:5,7:8")),
+            ("1:*", "*", Some("10")),
+            ("2,4,6", "1:10", Some("2,4,6")),
+            ("1:3", "4:6", None),
+            ("1,3", "2", None),
+        ];
+
+        for (left, right, expected) in tests {
+            let left = SequenceSet::try_from(left).unwrap();
+            let right = SequenceSet::try_from(right).unwrap();
+            let expected = expected.map(|expected| SequenceSet::try_from(expected).unwrap());
+
+            assert_eq!(left.intersection(&right, largest), expected);
+            assert_eq!(right.intersection(&left, largest), expected);
+        }
+    }
+
     #[test]
     fn test_clean() {
         let tests = vec![
@@ -49,13 +49,22 @@
 //! (6) CLOSE command, or failed SELECT or EXAMINE command
 //! (7) LOGOUT command, server shutdown, or connection closed
 //! ```
+//!
+//! With `ext_unauthenticate` (RFC 8437), a successful UNAUTHENTICATE command additionally
+//! transitions from Authenticated or Selected back to Not Authenticated. See [`State::unauthenticate`].
+//!
+//! [`State::check_command`] tells whether a command is permitted in the current state.
 
 #[cfg(feature = "bounded-static")]
 use bounded_static::ToStatic;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use crate::{core::Tag, mailbox::Mailbox};
+use crate::{
+    command::CommandBody, core::Tag, mailbox::Mailbox, response::Code, state::error::StateError,
+};
+#[cfg(feature = "starttls")]
+use crate::{response::Capability, state::error::AuthenticationError};
 
 /// State of the IMAP4rev1 connection.
 #[cfg_attr(feature = "bounded-static", derive(ToStatic))]
@@ -75,7 +84,7 @@ pub enum State<'a> {
 
     /// A mailbox has been selected to access.
     /// This state is entered when a mailbox has been successfully selected.
-    Selected(Mailbox<'a>),
+    Selected(Mailbox<'a>, AccessMode),
 
     /// The connection is being terminated.
     /// This state can be entered as a result of a client request (via the LOGOUT command) or by unilateral action on the part of either the client or server.
@@ -90,7 +99,239 @@ pub enum State<'a> {
 
     IdleAuthenticated(Tag<'a>),
 
-    IdleSelected(Tag<'a>, Mailbox<'a>),
+    IdleSelected(Tag<'a>, Mailbox<'a>, AccessMode),
+}
+
+impl<'a> State<'a> {
+    /// Returns `true` when a mailbox is selected read-only.
+    ///
+    /// Commands that modify the mailbox, e.g., STORE, should not be issued in this case.
+    pub fn is_read_only(&self) -> bool {
+        matches!(
+            self,
+            Self::Selected(_, AccessMode::ReadOnly)
+                | Self::IdleSelected(_, _, AccessMode::ReadOnly)
+        )
+    }
+
+    /// Records the access mode indicated by a `READ-ONLY` or `READ-WRITE` code.
+    ///
+    /// This should be used with the code of the tagged OK response to SELECT or EXAMINE, but
+    /// also with later responses, because the access mode may change while a mailbox is selected.
+    ///
+    /// Returns `true` when the access mode was recorded, i.e., when a mailbox is selected and
+    /// `code` is [`Code::ReadOnly`] or [`Code::ReadWrite`].
+    pub fn update_access_mode(&mut self, code: &Code) -> bool {
+        match (self, AccessMode::from_code(code)) {
+            (Self::Selected(_, access_mode) | Self::IdleSelected(_, _, access_mode), Some(new)) => {
+                *access_mode = new;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Checks whether `command` is permitted in the current state.
+    ///
+    /// * CAPABILITY, NOOP, LOGOUT (and ID) are permitted in any state.
+    /// * STARTTLS, AUTHENTICATE, and LOGIN are only permitted in the Not Authenticated state.
+    /// * CHECK, CLOSE, EXPUNGE, SEARCH, FETCH, STORE, COPY, MOVE, UNSELECT (and SORT, THREAD) are
+    ///   only permitted in the Selected state.
+    /// * ENABLE is only permitted in the Authenticated state (RFC 5161).
+    /// * All other commands are permitted in the Authenticated and Selected state.
+    ///
+    /// No command is permitted before the greeting, after LOGOUT, or during IDLE.
+    pub fn check_command(&self, command: &CommandBody) -> Result<(), StateError> {
+        let (authenticated, selected) = match self {
+            Self::NotAuthenticated => (false, false),
+            Self::Authenticated => (true, false),
+            Self::Selected(..) => (true, true),
+            Self::Greeting | Self::Logout | Self::IdleAuthenticated(_) | Self::IdleSelected(..) => {
+                return Err(StateError::NoCommandPermitted);
+            }
+        };
+
+        match command {
+            CommandBody::Capability | CommandBody::Noop | CommandBody::Logout => Ok(()),
+            #[cfg(feature = "ext_id")]
+            CommandBody::Id { .. } => Ok(()),
+            #[cfg(feature = "starttls")]
+            CommandBody::StartTLS if authenticated => Err(StateError::MustBeNotAuthenticated),
+            CommandBody::Authenticate { .. } | CommandBody::Login { .. } if authenticated => {
+                Err(StateError::MustBeNotAuthenticated)
+            }
+            #[cfg(feature = "starttls")]
+            CommandBody::StartTLS => Ok(()),
+            CommandBody::Authenticate { .. } | CommandBody::Login { .. } => Ok(()),
+            CommandBody::Check
+            | CommandBody::Close
+            | CommandBody::Expunge
+            | CommandBody::Search { .. }
+            | CommandBody::Fetch { .. }
+            | CommandBody::Store { .. }
+            | CommandBody::Copy { .. }
+            | CommandBody::Move { .. }
+            | CommandBody::Unselect
+                if !selected =>
+            {
+                Err(StateError::MustBeSelected)
+            }
+            #[cfg(feature = "ext_sort_thread")]
+            CommandBody::Sort { .. } | CommandBody::Thread { .. } if !selected => {
+                Err(StateError::MustBeSelected)
+            }
+            #[cfg(feature = "ext_esearch")]
+            CommandBody::SearchExtended { .. } if !selected => Err(StateError::MustBeSelected),
+            #[cfg(feature = "ext_condstore_qresync")]
+            CommandBody::FetchWithModifiers { .. } | CommandBody::StoreWithModifiers { .. }
+                if !selected =>
+            {
+                Err(StateError::MustBeSelected)
+            }
+            CommandBody::Enable { .. } if !authenticated || selected => {
+                Err(StateError::MustBeAuthenticatedNotSelected)
+            }
+            _ if !authenticated => Err(StateError::MustBeAuthenticated),
+            _ => Ok(()),
+        }
+    }
+
+    /// Records a successful CLOSE command.
+    ///
+    /// CLOSE transitions from the Selected state back to the Authenticated state. Returns `true`
+    /// when messages marked as `\Deleted` were implicitly expunged, i.e., when the mailbox was
+    /// selected read-write. A mailbox selected read-only, e.g., via EXAMINE, is not expunged.
+    ///
+    /// Note: CHECK doesn't change the state and is equivalent to NOOP in IMAP4rev2.
+    pub fn close(&mut self) -> Result<bool, StateError> {
+        match self {
+            Self::Selected(_, access_mode) => {
+                let expunged = *access_mode == AccessMode::ReadWrite;
+                *self = Self::Authenticated;
+                Ok(expunged)
+            }
+            _ => Err(StateError::MustBeSelected),
+        }
+    }
+
+    /// Records a successful UNAUTHENTICATE command (RFC 8437).
+    ///
+    /// UNAUTHENTICATE is only permitted in the Authenticated and Selected state. In this case,
+    /// the state is reset to [`State::NotAuthenticated`] and `true` is returned. Otherwise, the
+    /// state is left unchanged and `false` is returned.
+    #[cfg(feature = "ext_unauthenticate")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext_unauthenticate")))]
+    pub fn unauthenticate(&mut self) -> bool {
+        match self {
+            Self::Authenticated | Self::Selected(..) => {
+                *self = Self::NotAuthenticated;
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Access mode of a selected mailbox.
+#[cfg_attr(feature = "bounded-static", derive(ToStatic))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum AccessMode {
+    /// Mailbox was selected via EXAMINE or the server sent `READ-ONLY`.
+    ReadOnly,
+    /// Mailbox was selected via SELECT or the server sent `READ-WRITE`.
+    ReadWrite,
+}
+
+impl AccessMode {
+    /// Returns the access mode requested by a SELECT or EXAMINE command.
+    ///
+    /// Note: The server may still select a mailbox read-only in response to SELECT.
+    /// Use [`State::update_access_mode`] to record the access mode announced by the server.
+    pub fn from_command(command: &CommandBody) -> Option<Self> {
+        match command {
+            CommandBody::Select { .. } => Some(Self::ReadWrite),
+            CommandBody::Examine { .. } => Some(Self::ReadOnly),
+            _ => None,
+        }
+    }
+
+    /// Returns the access mode indicated by a `READ-ONLY` or `READ-WRITE` code.
+    pub fn from_code(code: &Code) -> Option<Self> {
+        match code {
+            Code::ReadOnly => Some(Self::ReadOnly),
+            Code::ReadWrite => Some(Self::ReadWrite),
+            _ => None,
+        }
+    }
+}
+
+/// Checks whether LOGIN or AUTHENTICATE is currently permitted.
+///
+/// `capabilities` are the capabilities advertised by the server and `tls` tells whether TLS is
+/// active, e.g., after a successful STARTTLS. Note that the capabilities must be requested again
+/// after STARTTLS because the server may advertise different ones.
+///
+/// * LOGIN is rejected when the server advertises `LOGINDISABLED`.
+/// * AUTHENTICATE is rejected when the server does not advertise the mechanism via `AUTH=`.
+///
+/// When the server advertises `STARTTLS` and TLS is not active, [`AuthenticationError::StartTlsRequired`]
+/// is returned instead. All other commands are permitted.
+#[cfg(feature = "starttls")]
+#[cfg_attr(docsrs, doc(cfg(feature = "starttls")))]
+pub fn check_authentication(
+    capabilities: &[Capability],
+    tls: bool,
+    command: &CommandBody,
+) -> Result<(), AuthenticationError> {
+    let permitted = match command {
+        CommandBody::Login { .. } => !capabilities.contains(&Capability::LoginDisabled),
+        CommandBody::Authenticate { mechanism, .. } => capabilities
+            .iter()
+            .any(|capability| matches!(capability, Capability::Auth(other) if other == mechanism)),
+        _ => return Ok(()),
+    };
+
+    if permitted {
+        Ok(())
+    } else if !tls && capabilities.contains(&Capability::StartTls) {
+        Err(AuthenticationError::StartTlsRequired)
+    } else if let CommandBody::Login { .. } = command {
+        Err(AuthenticationError::LoginDisabled)
+    } else {
+        Err(AuthenticationError::MechanismNotAdvertised)
+    }
+}
+
+/// Error-related types.
+pub mod error {
+    use thiserror::Error;
+
+    #[derive(Clone, Debug, Eq, Error, Hash, Ord, PartialEq, PartialOrd)]
+    pub enum StateError {
+        #[error("Command is only permitted in the Not Authenticated state")]
+        MustBeNotAuthenticated,
+        #[error("Command is only permitted in the Authenticated or Selected state")]
+        MustBeAuthenticated,
+        #[error("Command is only permitted in the Authenticated state")]
+        MustBeAuthenticatedNotSelected,
+        #[error("Command is only permitted in the Selected state")]
+        MustBeSelected,
+        #[error("No command is permitted in this state")]
+        NoCommandPermitted,
+    }
+
+    #[cfg(feature = "starttls")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "starttls")))]
+    #[derive(Clone, Debug, Eq, Error, Hash, Ord, PartialEq, PartialOrd)]
+    pub enum AuthenticationError {
+        #[error("Authentication is not permitted before STARTTLS")]
+        StartTlsRequired,
+        #[error("LOGIN is disabled")]
+        LoginDisabled,
+        #[error("Authentication mechanism is not advertised")]
+        MechanismNotAdvertised,
+    }
 }
 
 #[cfg(test)]
@@ -99,7 +340,11 @@ mod tests {
     use bounded_static::{IntoBoundedStatic, ToBoundedStatic};
 
     use super::*;
-    use crate::{core::Tag, mailbox::Mailbox};
+    use crate::{
+        core::Tag,
+        extensions::enable::{CapabilityEnable, Utf8Kind},
+        mailbox::Mailbox,
+    };
 
     #[test]
     fn test_conversion() {
@@ -107,10 +352,14 @@ mod tests {
             State::Greeting,
             State::NotAuthenticated,
             State::Authenticated,
-            State::Selected(Mailbox::Inbox),
+            State::Selected(Mailbox::Inbox, AccessMode::ReadWrite),
             State::Logout,
             State::IdleAuthenticated(Tag::try_from("A").unwrap()),
-            State::IdleSelected(Tag::try_from("A").unwrap(), Mailbox::Inbox),
+            State::IdleSelected(
+                Tag::try_from("A").unwrap(),
+                Mailbox::Inbox,
+                AccessMode::ReadOnly,
+            ),
         ];
 
         for _test in tests {
@@ -124,4 +373,206 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_access_mode() {
+        let examine = CommandBody::examine("INBOX").unwrap();
+        let select = CommandBody::select("INBOX").unwrap();
+
+        assert_eq!(
+            AccessMode::from_command(&examine),
+            Some(AccessMode::ReadOnly)
+        );
+        assert_eq!(
+            AccessMode::from_command(&select),
+            Some(AccessMode::ReadWrite)
+        );
+        assert_eq!(AccessMode::from_command(&CommandBody::Noop), None);
+
+        let mut state = State::Selected(Mailbox::Inbox, AccessMode::from_command(&select).unwrap());
+        assert!(!state.is_read_only());
+
+        assert!(state.update_access_mode(&Code::ReadOnly));
+        assert!(state.is_read_only());
+
+        assert!(!state.update_access_mode(&Code::Alert));
+        assert!(state.is_read_only());
+
+        assert!(state.update_access_mode(&Code::ReadWrite));
+        assert!(!state.is_read_only());
+
+        let mut state = State::IdleSelected(
+            Tag::try_from("A").unwrap(),
+            Mailbox::Inbox,
+            AccessMode::ReadWrite,
+        );
+        assert!(state.update_access_mode(&Code::ReadOnly));
+        assert!(state.is_read_only());
+
+        let mut state = State::Authenticated;
+        assert!(!state.update_access_mode(&Code::ReadOnly));
+        assert!(!state.is_read_only());
+    }
+
+    #[test]
+    fn test_check_command() {
+        let login = CommandBody::login("alice", "password").unwrap();
+        let select = CommandBody::select("INBOX").unwrap();
+        let enable = CommandBody::enable(vec![CapabilityEnable::Utf8(Utf8Kind::Accept)]).unwrap();
+
+        let state = State::NotAuthenticated;
+        assert_eq!(state.check_command(&CommandBody::Noop), Ok(()));
+        assert_eq!(state.check_command(&login), Ok(()));
+        assert_eq!(
+            state.check_command(&enable),
+            Err(StateError::MustBeAuthenticatedNotSelected)
+        );
+        assert_eq!(
+            state.check_command(&select),
+            Err(StateError::MustBeAuthenticated)
+        );
+        assert_eq!(
+            state.check_command(&CommandBody::Check),
+            Err(StateError::MustBeSelected)
+        );
+
+        let state = State::Authenticated;
+        assert_eq!(state.check_command(&CommandBody::Capability), Ok(()));
+        assert_eq!(state.check_command(&enable), Ok(()));
+        assert_eq!(
+            state.check_command(&login),
+            Err(StateError::MustBeNotAuthenticated)
+        );
+        assert_eq!(state.check_command(&select), Ok(()));
+        for command in [CommandBody::Check, CommandBody::Close, CommandBody::Expunge] {
+            assert_eq!(
+                state.check_command(&command),
+                Err(StateError::MustBeSelected)
+            );
+        }
+
+        let state = State::Selected(Mailbox::Inbox, AccessMode::ReadOnly);
+        assert_eq!(state.check_command(&select), Ok(()));
+        assert_eq!(
+            state.check_command(&enable),
+            Err(StateError::MustBeAuthenticatedNotSelected)
+        );
+        for command in [CommandBody::Check, CommandBody::Close, CommandBody::Expunge] {
+            assert_eq!(state.check_command(&command), Ok(()));
+        }
+
+        for state in [
+            State::Greeting,
+            State::Logout,
+            State::IdleAuthenticated(Tag::try_from("A").unwrap()),
+        ] {
+            assert_eq!(
+                state.check_command(&CommandBody::Noop),
+                Err(StateError::NoCommandPermitted)
+            );
+        }
+    }
+
+    #[test]
+    fn test_close() {
+        // CLOSE from Authenticated is rejected.
+        let mut state = State::Authenticated;
+        assert_eq!(state.close(), Err(StateError::MustBeSelected));
+        assert_eq!(state, State::Authenticated);
+
+        // SELECT -> CLOSE expunges.
+        let mut state = State::Selected(Mailbox::Inbox, AccessMode::ReadWrite);
+        assert_eq!(state.check_command(&CommandBody::Close), Ok(()));
+        assert_eq!(state.close(), Ok(true));
+        assert_eq!(state, State::Authenticated);
+
+        // EXAMINE -> CLOSE does not expunge.
+        let mut state = State::Selected(Mailbox::Inbox, AccessMode::ReadOnly);
+        assert_eq!(state.close(), Ok(false));
+        assert_eq!(state, State::Authenticated);
+    }
+
+    #[cfg(feature = "ext_unauthenticate")]
+    #[test]
+    fn test_unauthenticate() {
+        let mut state = State::Authenticated;
+        assert!(state.unauthenticate());
+        assert_eq!(state, State::NotAuthenticated);
+
+        let mut state = State::Selected(Mailbox::Inbox, AccessMode::ReadWrite);
+        assert!(state.unauthenticate());
+        assert_eq!(state, State::NotAuthenticated);
+
+        let tests = [
+            State::Greeting,
+            State::NotAuthenticated,
+            State::Logout,
+            State::IdleAuthenticated(Tag::try_from("A").unwrap()),
+            State::IdleSelected(
+                Tag::try_from("A").unwrap(),
+                Mailbox::Inbox,
+                AccessMode::ReadOnly,
+            ),
+        ];
+
+        for test in tests {
+            let mut state = test.clone();
+            assert!(!state.unauthenticate());
+            assert_eq!(state, test);
+        }
+    }
+
+    #[cfg(feature = "starttls")]
+    #[test]
+    fn test_check_authentication() {
+        use crate::auth::AuthMechanism;
+
+        let login = CommandBody::login("alice", "password").unwrap();
+        let authenticate = CommandBody::authenticate(AuthMechanism::Plain);
+
+        // Before STARTTLS
+        let capabilities = vec![
+            Capability::Imap4Rev1,
+            Capability::StartTls,
+            Capability::LoginDisabled,
+        ];
+        assert_eq!(
+            check_authentication(&capabilities, false, &login),
+            Err(AuthenticationError::StartTlsRequired)
+        );
+        assert_eq!(
+            check_authentication(&capabilities, false, &authenticate),
+            Err(AuthenticationError::StartTlsRequired)
+        );
+        assert_eq!(
+            check_authentication(&capabilities, false, &CommandBody::StartTLS),
+            Ok(())
+        );
+
+        // After STARTTLS
+        let capabilities = vec![
+            Capability::Imap4Rev1,
+            Capability::Auth(AuthMechanism::Plain),
+        ];
+        assert_eq!(check_authentication(&capabilities, true, &login), Ok(()));
+        assert_eq!(
+            check_authentication(&capabilities, true, &authenticate),
+            Ok(())
+        );
+        assert_eq!(
+            check_authentication(
+                &capabilities,
+                true,
+                &CommandBody::authenticate(AuthMechanism::Login)
+            ),
+            Err(AuthenticationError::MechanismNotAdvertised)
+        );
+
+        // LOGINDISABLED without STARTTLS
+        let capabilities = vec![Capability::Imap4Rev1, Capability::LoginDisabled];
+        assert_eq!(
+            check_authentication(&capabilities, true, &login),
+            Err(AuthenticationError::LoginDisabled)
+        );
+    }
 }
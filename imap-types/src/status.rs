@@ -1,4 +1,6 @@
 use std::num::NonZeroU32;
+#[cfg(feature = "ext_condstore_qresync")]
+use std::num::NonZeroU64;
 
 #[cfg(feature = "arbitrary")]
 use arbitrary::Arbitrary;
@@ -7,6 +9,9 @@ use bounded_static::ToStatic;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "ext_objectid")]
+use crate::extensions::objectid::ObjectId;
+
 /// Status data item name used to request a status data item.
 #[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
 #[cfg_attr(feature = "bounded-static", derive(ToStatic))]
@@ -38,6 +43,21 @@ pub enum StatusDataItemName {
     #[cfg(feature = "ext_condstore_qresync")]
     #[cfg_attr(docsrs, doc(cfg(feature = "ext_condstore_qresync")))]
     HighestModSeq,
+
+    /// The object identifier of the mailbox (RFC 8474).
+    #[cfg(feature = "ext_objectid")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext_objectid")))]
+    MailboxId,
+
+    /// The total size of the mailbox in octets (RFC 8438).
+    #[cfg(feature = "ext_status_size")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext_status_size")))]
+    Size,
+
+    /// The maximum size of a message that can be appended to the mailbox (RFC 7889).
+    #[cfg(feature = "ext_status_size")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext_status_size")))]
+    AppendLimit,
 }
 
 /// Status data item.
@@ -69,4 +89,49 @@ pub enum StatusDataItem {
 
     /// The amount of storage space that can be reclaimed by performing EXPUNGE on the mailbox.
     DeletedStorage(u64),
+
+    /// The highest mod-sequence value of all messages in the mailbox (RFC 7162).
+    #[cfg(feature = "ext_condstore_qresync")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext_condstore_qresync")))]
+    HighestModSeq(NonZeroU64),
+
+    /// The object identifier of the mailbox (RFC 8474).
+    #[cfg(feature = "ext_objectid")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext_objectid")))]
+    MailboxId(ObjectId),
+
+    /// The total size of the mailbox in octets (RFC 8438).
+    #[cfg(feature = "ext_status_size")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext_status_size")))]
+    Size(u64),
+
+    /// The maximum size of a message that can be appended to the mailbox (RFC 7889).
+    ///
+    /// `None` (`NIL`) means that there is no limit.
+    #[cfg(feature = "ext_status_size")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ext_status_size")))]
+    AppendLimit(Option<u32>),
+}
+
+impl StatusDataItem {
+    /// Position of this item in canonical order (as listed in RFC 3501 and RFC 9051).
+    pub(crate) fn canonical_rank(&self) -> u8 {
+        match self {
+            Self::Messages(_) => 0,
+            Self::Recent(_) => 1,
+            Self::UidNext(_) => 2,
+            Self::UidValidity(_) => 3,
+            Self::Unseen(_) => 4,
+            Self::Deleted(_) => 5,
+            Self::DeletedStorage(_) => 6,
+            #[cfg(feature = "ext_condstore_qresync")]
+            Self::HighestModSeq(_) => 7,
+            #[cfg(feature = "ext_objectid")]
+            Self::MailboxId(_) => 8,
+            #[cfg(feature = "ext_status_size")]
+            Self::Size(_) => 9,
+            #[cfg(feature = "ext_status_size")]
+            Self::AppendLimit(_) => 10,
+        }
+    }
 }
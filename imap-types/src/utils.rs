@@ -1,6 +1,6 @@
 //! Functions that may come in handy.
 
-use std::borrow::Cow;
+use alloc::{borrow::Cow, format, string::String, vec::Vec};
 
 /// Converts bytes into a ready-to-be-printed form.
 pub fn escape_byte_string<B>(bytes: B) -> String
@@ -65,6 +65,26 @@ pub mod indicators {
         is_char(b) && !is_atom_specials(b)
     }
 
+    /// Relaxed `ATOM-CHAR` that additionally allows "{"
+    ///
+    /// Used by imap-codec's parser for `atom` and `tag` when the `quirk_atom_char_relaxed` feature is enabled.
+    /// Note: This is not IMAP conformant and must only be used to cope with non-conforming implementations.
+    pub fn is_atom_char_relaxed(b: u8) -> bool {
+        is_atom_char(b) || b == b'{'
+    }
+
+    /// `<any ASTRING-CHAR except "+">` (used in `tag`)
+    pub fn is_tag_char(b: u8) -> bool {
+        is_astring_char(b) && b != b'+'
+    }
+
+    /// Relaxed tag char that additionally allows "{"
+    ///
+    /// See [`is_atom_char_relaxed`].
+    pub fn is_tag_char_relaxed(b: u8) -> bool {
+        is_tag_char(b) || b == b'{'
+    }
+
     /// `atom-specials = "(" / ")" / "{" / SP / CTL / list-wildcards / quoted-specials / resp-specials`
     pub fn is_atom_specials(i: u8) -> bool {
         match i {
@@ -107,9 +127,31 @@ pub mod indicators {
     pub fn is_list_char(i: u8) -> bool {
         is_atom_char(i) || is_list_wildcards(i) || is_resp_specials(i)
     }
+
+    /// `unreserved / sub-delims` (used in `reg-name` of an IMAP URL host)
+    pub fn is_reg_name_char(byte: u8) -> bool {
+        byte.is_ascii_alphanumeric()
+            || matches!(
+                byte,
+                b'-' | b'.'
+                    | b'_'
+                    | b'~'
+                    | b'!'
+                    | b'$'
+                    | b'&'
+                    | b'\''
+                    | b'('
+                    | b')'
+                    | b'*'
+                    | b'+'
+                    | b','
+                    | b';'
+                    | b'='
+            )
+    }
 }
 
-pub fn escape_quoted(unescaped: &str) -> Cow<str> {
+pub fn escape_quoted(unescaped: &str) -> Cow<'_, str> {
     let mut escaped = Cow::Borrowed(unescaped);
 
     if escaped.contains('\\') {
@@ -123,7 +165,7 @@ pub fn escape_quoted(unescaped: &str) -> Cow<str> {
     escaped
 }
 
-pub fn unescape_quoted(escaped: &str) -> Cow<str> {
+pub fn unescape_quoted(escaped: &str) -> Cow<'_, str> {
     let mut unescaped = Cow::Borrowed(escaped);
 
     if unescaped.contains("\\\\") {
@@ -139,6 +181,8 @@ pub fn unescape_quoted(escaped: &str) -> Cow<str> {
 
 #[cfg(test)]
 mod tests {
+    use alloc::string::ToString;
+
     use super::*;
 
     #[test]